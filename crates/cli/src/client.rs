@@ -42,6 +42,11 @@ pub fn poll_interval() -> Duration {
     parse_duration_ms("OJ_POLL_INTERVAL_MS").unwrap_or(Duration::from_millis(50))
 }
 
+/// Polling interval for following a pipeline log
+pub fn follow_interval() -> Duration {
+    parse_duration_ms("OJ_FOLLOW_INTERVAL_MS").unwrap_or(Duration::from_millis(500))
+}
+
 /// Client errors
 #[derive(Debug, Error)]
 pub enum ClientError {
@@ -237,6 +242,46 @@ impl DaemonClient {
         }
     }
 
+    /// Query the phase history of a pipeline
+    pub async fn get_pipeline_history(
+        &self,
+        id: &str,
+    ) -> Result<Option<Vec<oj_daemon::PhaseEntry>>, ClientError> {
+        match self
+            .send(Request::Query {
+                query: Query::PipelineHistory { id: id.to_string() },
+            })
+            .await?
+        {
+            Response::PipelineHistory { history } => Ok(history),
+            Response::Error { message } => Err(ClientError::Rejected(message)),
+            _ => Err(ClientError::UnexpectedResponse),
+        }
+    }
+
+    /// Read a pipeline's log from byte `offset` on
+    pub async fn get_pipeline_logs(
+        &self,
+        id: &str,
+        phase: Option<&str>,
+        offset: u64,
+    ) -> Result<Option<oj_daemon::LogChunk>, ClientError> {
+        match self
+            .send(Request::Query {
+                query: Query::PipelineLogs {
+                    id: id.to_string(),
+                    phase: phase.map(str::to_string),
+                    offset,
+                },
+            })
+            .await?
+        {
+            Response::PipelineLogs { logs } => Ok(logs),
+            Response::Error { message } => Err(ClientError::Rejected(message)),
+            _ => Err(ClientError::UnexpectedResponse),
+        }
+    }
+
     /// Get daemon status
     pub async fn status(&self) -> Result<(u64, usize, usize), ClientError> {
         match self.send(Request::Status).await? {
@@ -287,6 +332,20 @@ impl DaemonClient {
         }
     }
 
+    /// Query for runbook crons
+    pub async fn list_crons(&self) -> Result<Vec<oj_daemon::CronSummary>, ClientError> {
+        match self
+            .send(Request::Query {
+                query: Query::ListCrons,
+            })
+            .await?
+        {
+            Response::Crons { crons } => Ok(crons),
+            Response::Error { message } => Err(ClientError::Rejected(message)),
+            _ => Err(ClientError::UnexpectedResponse),
+        }
+    }
+
     /// Send input to a session
     pub async fn session_send(&self, id: &str, input: &str) -> Result<(), ClientError> {
         match self
@@ -0,0 +1,33 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! `oj cron` - Cron management commands
+
+use clap::{Args, Subcommand};
+
+#[derive(Args)]
+pub struct CronArgs {
+    #[command(subcommand)]
+    pub command: CronCommand,
+}
+
+#[derive(Subcommand)]
+pub enum CronCommand {
+    /// List crons
+    List,
+    /// Enable a cron
+    Enable {
+        /// Cron name
+        name: String,
+    },
+    /// Disable a cron
+    Disable {
+        /// Cron name
+        name: String,
+    },
+    /// Run a cron once now
+    Run {
+        /// Cron name
+        name: String,
+    },
+}
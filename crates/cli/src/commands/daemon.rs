@@ -107,7 +107,7 @@ async fn status(project_root: &Path) -> Result<()> {
         .await
         .unwrap_or_else(|_| "unknown".to_string());
 
-    let uptime_str = format_uptime(uptime);
+    let uptime_str = super::format_duration(uptime);
     println!("Status: running");
     println!("Version: {}", version);
     println!("Uptime: {}", uptime_str);
@@ -141,20 +141,6 @@ async fn logs(project_root: &Path, lines: usize, follow: bool) -> Result<()> {
     Ok(())
 }
 
-fn format_uptime(secs: u64) -> String {
-    let hours = secs / 3600;
-    let mins = (secs % 3600) / 60;
-    let secs = secs % 60;
-
-    if hours > 0 {
-        format!("{}h {}m {}s", hours, mins, secs)
-    } else if mins > 0 {
-        format!("{}m {}s", mins, secs)
-    } else {
-        format!("{}s", secs)
-    }
-}
-
 fn find_ojd_binary() -> Result<PathBuf> {
     // First check if we're running from cargo (development)
     if let Ok(manifest_dir) = std::env::var("CARGO_MANIFEST_DIR") {
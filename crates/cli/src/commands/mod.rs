@@ -3,6 +3,7 @@
 
 //! CLI command implementations
 
+pub mod cron;
 pub mod daemon;
 pub mod done;
 pub mod emit;
@@ -10,3 +11,43 @@ pub mod pipeline;
 pub mod run;
 pub mod session;
 pub mod worker;
+
+use oj_core::Clock;
+
+/// Format a number of seconds as e.g. `1h 2m 3s`
+pub fn format_duration(secs: u64) -> String {
+    let hours = secs / 3600;
+    let mins = (secs % 3600) / 60;
+    let secs = secs % 60;
+
+    if hours > 0 {
+        format!("{}h {}m {}s", hours, mins, secs)
+    } else if mins > 0 {
+        format!("{}m {}s", mins, secs)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// Format a Unix epoch milliseconds timestamp with how long ago it was
+///
+/// Timestamps of 0 come from state recorded before timestamps were kept.
+pub fn format_timestamp(epoch_ms: u64) -> String {
+    if epoch_ms == 0 {
+        return "unknown".to_string();
+    }
+    let ago = oj_core::SystemClock.epoch_ms().saturating_sub(epoch_ms) / 1000;
+    format!(
+        "{} ({} ago)",
+        oj_core::clock::format_epoch_ms(epoch_ms),
+        format_duration(ago)
+    )
+}
+
+/// How long ago a Unix epoch milliseconds timestamp was, or `-` if unknown
+pub fn format_age(epoch_ms: u64) -> String {
+    if epoch_ms == 0 {
+        return "-".to_string();
+    }
+    format_duration(oj_core::SystemClock.epoch_ms().saturating_sub(epoch_ms) / 1000)
+}
@@ -3,7 +3,10 @@
 
 //! `oj pipeline` - Pipeline management commands
 
+use crate::client::{self, ClientError, DaemonClient};
 use clap::{Args, Subcommand};
+use oj_core::Clock;
+use oj_daemon::{PhaseEntry, PipelineChild};
 
 #[derive(Args)]
 pub struct PipelineArgs {
@@ -19,6 +22,20 @@ pub enum PipelineCommand {
     Show {
         /// Pipeline ID or name
         id: String,
+        /// Also show every phase the pipeline has been through
+        #[arg(long)]
+        history: bool,
+    },
+    /// Show a pipeline's log: shell output, phase changes and agent activity
+    Logs {
+        /// Pipeline ID or name
+        id: String,
+        /// Only show lines written during this phase
+        #[arg(long)]
+        phase: Option<String>,
+        /// Keep printing new lines until the pipeline finishes
+        #[arg(short, long)]
+        follow: bool,
     },
     /// Resume monitoring for an escalated pipeline
     Resume {
@@ -34,3 +51,85 @@ pub enum PipelineCommand {
         error: Option<String>,
     },
 }
+
+/// Print child pipelines as a tree, indenting each level by two more spaces
+pub fn print_children(children: &[PipelineChild], depth: usize) {
+    for child in children {
+        println!(
+            "{:indent$}{} {} [{}] {} ({}), from phase {}",
+            "",
+            child.id,
+            child.name,
+            child.kind,
+            child.phase,
+            child.phase_status,
+            child.parent_phase,
+            indent = depth * 2
+        );
+        print_children(&child.children, depth + 1);
+    }
+}
+
+/// Print a pipeline's phase history as a table, oldest first
+pub fn print_history(history: &[PhaseEntry]) {
+    println!("  History:");
+    if history.is_empty() {
+        println!("    (none recorded)");
+        return;
+    }
+    println!(
+        "    {:<15} {:<8} {:<10} {:<24} {:<12} RESULT",
+        "PHASE", "TRIGGER", "STATUS", "STARTED", "DURATION"
+    );
+    let now = oj_core::SystemClock.epoch_ms();
+    for entry in history {
+        let started = if entry.started_at_ms == 0 {
+            "unknown".to_string()
+        } else {
+            oj_core::clock::format_epoch_ms(entry.started_at_ms)
+        };
+        let duration = match entry.ended_at_ms {
+            _ if entry.started_at_ms == 0 => "-".to_string(),
+            Some(ended) => super::format_duration(ended.saturating_sub(entry.started_at_ms) / 1000),
+            None => format!(
+                "{}+",
+                super::format_duration(now.saturating_sub(entry.started_at_ms) / 1000)
+            ),
+        };
+        let result = match (entry.exit_code, &entry.error) {
+            (Some(code), Some(error)) => format!("exit {}: {}", code, error),
+            (Some(code), None) => format!("exit {}", code),
+            (None, Some(error)) => error.clone(),
+            (None, None) => String::new(),
+        };
+        println!(
+            "    {:<15} {:<8} {:<10} {:<24} {:<12} {}",
+            entry.phase, entry.trigger, entry.status, started, duration, result
+        );
+    }
+}
+
+/// Print a pipeline's log, optionally following it until the pipeline finishes
+///
+/// Returns false if there is no such pipeline.
+pub async fn print_logs(
+    client: &DaemonClient,
+    id: &str,
+    phase: Option<&str>,
+    follow: bool,
+) -> Result<bool, ClientError> {
+    let mut offset = 0;
+    loop {
+        let Some(chunk) = client.get_pipeline_logs(id, phase, offset).await? else {
+            return Ok(false);
+        };
+        for line in &chunk.lines {
+            println!("{}", line);
+        }
+        if !follow || chunk.finished {
+            return Ok(true);
+        }
+        offset = chunk.offset;
+        tokio::time::sleep(client::follow_interval()).await;
+    }
+}
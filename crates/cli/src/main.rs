@@ -1,6 +1,11 @@
 // SPDX-License-Identifier: BUSL-1.1
 // Copyright (c) 2026 Alfred Jean LLC
 
+// Allow panic!/unwrap/expect in test code
+#![cfg_attr(test, allow(clippy::panic))]
+#![cfg_attr(test, allow(clippy::unwrap_used))]
+#![cfg_attr(test, allow(clippy::expect_used))]
+
 //! oj - Otter Jobs CLI
 
 mod client;
@@ -8,7 +13,7 @@ mod commands;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
-use commands::{daemon, done, emit, pipeline, run, session, worker};
+use commands::{cron, daemon, done, emit, pipeline, run, session, worker};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
@@ -40,6 +45,8 @@ enum Commands {
     Pipeline(pipeline::PipelineArgs),
     /// Session management
     Session(session::SessionArgs),
+    /// Cron management
+    Cron(cron::CronArgs),
     /// Emit an event
     Emit(emit::EmitArgs),
     /// Signal agent completion
@@ -141,18 +148,29 @@ async fn main() -> Result<()> {
                         }
                     }
                 }
-                PipelineCommand::Show { id } => {
+                PipelineCommand::Show { id, history } => {
                     if let Some(p) = client.get_pipeline(&id).await? {
                         println!("Pipeline: {}", p.id);
                         println!("  Name: {}", p.name);
                         println!("  Kind: {}", p.kind);
                         println!("  Phase: {} ({})", p.phase, p.phase_status);
+                        if p.phase_started_at_ms != 0 {
+                            println!(
+                                "  Phase Started: {}",
+                                commands::format_timestamp(p.phase_started_at_ms)
+                            );
+                        }
+                        println!("  Created: {}", commands::format_timestamp(p.created_at_ms));
+                        println!("  Updated: {}", commands::format_timestamp(p.updated_at_ms));
                         if let Some(ws) = &p.workspace_path {
                             println!("  Workspace: {}", ws.display());
                         }
                         if let Some(session) = &p.session_id {
                             println!("  Session: {}", session);
                         }
+                        if let Some(parent) = &p.parent {
+                            println!("  Parent: {} (phase {})", parent.id, parent.phase);
+                        }
                         if let Some(error) = &p.error {
                             println!("  Error: {}", error);
                         }
@@ -162,10 +180,32 @@ async fn main() -> Result<()> {
                                 println!("    {}: {}", k, v);
                             }
                         }
+                        if !p.outputs.is_empty() {
+                            println!("  Outputs:");
+                            for (k, v) in &p.outputs {
+                                println!("    {}: {}", k, v);
+                            }
+                        }
+                        if !p.children.is_empty() {
+                            println!("  Children:");
+                            commands::pipeline::print_children(&p.children, 2);
+                        }
+                        if history {
+                            let entries = client.get_pipeline_history(&p.id).await?;
+                            commands::pipeline::print_history(&entries.unwrap_or_default());
+                        }
                     } else {
                         println!("Pipeline not found: {}", id);
                     }
                 }
+                PipelineCommand::Logs { id, phase, follow } => {
+                    let found =
+                        commands::pipeline::print_logs(&client, &id, phase.as_deref(), follow)
+                            .await?;
+                    if !found {
+                        println!("Pipeline not found: {}", id);
+                    }
+                }
                 PipelineCommand::Resume { id } => {
                     client.pipeline_resume(&id).await?;
                     println!("Resumed monitoring for pipeline {}", id);
@@ -187,11 +227,12 @@ async fn main() -> Result<()> {
                     if sessions.is_empty() {
                         println!("No sessions");
                     } else {
-                        println!("{:<20} PIPELINE", "SESSION");
+                        println!("{:<20} {:<12} PIPELINE", "SESSION", "AGE");
                         for s in sessions {
                             println!(
-                                "{:<20} {}",
+                                "{:<20} {:<12} {}",
                                 s.id,
+                                commands::format_age(s.created_at_ms),
                                 s.pipeline_id.unwrap_or_else(|| "-".to_string())
                             );
                         }
@@ -207,8 +248,72 @@ async fn main() -> Result<()> {
             }
         }
 
-        Commands::Worker(_) => {
-            anyhow::bail!("Worker commands not yet supported")
+        Commands::Worker(args) => {
+            use commands::worker::WorkerCommand;
+
+            match args.command {
+                WorkerCommand::Start { name } => {
+                    client
+                        .send_event(Event::WorkerStart {
+                            worker: name.clone(),
+                        })
+                        .await?;
+                    println!("Started worker {}", name);
+                }
+                WorkerCommand::Stop { name } => {
+                    client
+                        .send_event(Event::WorkerStop {
+                            worker: name.clone(),
+                        })
+                        .await?;
+                    println!("Stopped worker {}", name);
+                }
+                WorkerCommand::Wake { name } => {
+                    client
+                        .send_event(Event::WorkerWake {
+                            worker: name.clone(),
+                        })
+                        .await?;
+                    println!("Woke worker {}", name);
+                }
+            }
+        }
+
+        Commands::Cron(args) => {
+            use commands::cron::CronCommand;
+
+            match args.command {
+                CronCommand::List => {
+                    let crons = client.list_crons().await?;
+                    if crons.is_empty() {
+                        println!("No crons");
+                    } else {
+                        println!("{:<20} {:<10} SCHEDULE", "CRON", "STATUS");
+                        for c in crons {
+                            let status = if c.enabled { "enabled" } else { "disabled" };
+                            println!("{:<20} {:<10} {}", c.name, status, c.schedule);
+                        }
+                    }
+                }
+                CronCommand::Enable { name } => {
+                    client
+                        .send_event(Event::CronEnable { cron: name.clone() })
+                        .await?;
+                    println!("Enabled cron {}", name);
+                }
+                CronCommand::Disable { name } => {
+                    client
+                        .send_event(Event::CronDisable { cron: name.clone() })
+                        .await?;
+                    println!("Disabled cron {}", name);
+                }
+                CronCommand::Run { name } => {
+                    client
+                        .send_event(Event::CronRun { cron: name.clone() })
+                        .await?;
+                    println!("Ran cron {}", name);
+                }
+            }
         }
 
         Commands::Daemon(_) => unreachable!(),
@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Named action history
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// How often a named action has fired at one target, and when it last did
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActionRecord {
+    pub attempts: u32,
+    #[serde(with = "crate::clock::instant_serde")]
+    pub last_fired: Instant,
+}
+
+impl ActionRecord {
+    /// Record the first firing
+    pub fn new(now: Instant) -> Self {
+        Self {
+            attempts: 1,
+            last_fired: now,
+        }
+    }
+
+    /// Record another firing
+    pub fn fire(&mut self, now: Instant) {
+        self.attempts += 1;
+        self.last_fired = now;
+    }
+
+    /// Whether `cooldown` has not yet passed since the last firing
+    pub fn cooling_down(&self, cooldown: Duration, now: Instant) -> bool {
+        now.saturating_duration_since(self.last_fired) < cooldown
+    }
+}
+
+#[cfg(test)]
+#[path = "action_tests.rs"]
+mod tests;
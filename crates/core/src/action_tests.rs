@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+
+#[test]
+fn fire_counts_attempts() {
+    let start = Instant::now();
+    let mut record = ActionRecord::new(start);
+    record.fire(start + Duration::from_secs(5));
+    assert_eq!(record.attempts, 2);
+    assert_eq!(record.last_fired, start + Duration::from_secs(5));
+}
+
+#[test]
+fn cooldown_runs_from_last_firing() {
+    let start = Instant::now();
+    let record = ActionRecord::new(start);
+    let cooldown = Duration::from_secs(30);
+    assert!(record.cooling_down(cooldown, start + Duration::from_secs(29)));
+    assert!(!record.cooling_down(cooldown, start + Duration::from_secs(30)));
+}
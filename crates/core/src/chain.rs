@@ -0,0 +1,46 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Progress through response chains
+
+use serde::{Deserialize, Serialize};
+
+/// Position in a chain of steps that each allow a bounded number of attempts,
+/// such as `["nudge:2", "restart", "escalate"]`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainProgress {
+    /// Index of the step last taken
+    pub step: usize,
+    /// Times that step has been taken
+    pub attempts: u32,
+}
+
+impl ChainProgress {
+    /// The position after taking one more step
+    ///
+    /// `limits[i]` is how many times step `i` may be taken before moving on.
+    /// Returns `None` once every step is used up.
+    pub fn advance(current: Option<Self>, limits: &[u32]) -> Option<Self> {
+        let mut next = match current {
+            None => Self {
+                step: 0,
+                attempts: 1,
+            },
+            Some(progress) => Self {
+                step: progress.step,
+                attempts: progress.attempts + 1,
+            },
+        };
+        while next.attempts > *limits.get(next.step)? {
+            next = Self {
+                step: next.step + 1,
+                attempts: 1,
+            };
+        }
+        Some(next)
+    }
+}
+
+#[cfg(test)]
+#[path = "chain_tests.rs"]
+mod tests;
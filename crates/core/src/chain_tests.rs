@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+
+fn walk(limits: &[u32]) -> Vec<(usize, u32)> {
+    let mut positions = Vec::new();
+    let mut current = None;
+    while let Some(next) = ChainProgress::advance(current, limits) {
+        positions.push((next.step, next.attempts));
+        current = Some(next);
+    }
+    positions
+}
+
+#[test]
+fn walks_each_step_up_to_its_limit() {
+    assert_eq!(walk(&[1, 2, 1]), vec![(0, 1), (1, 1), (1, 2), (2, 1)]);
+}
+
+#[test]
+fn skips_steps_with_no_attempts() {
+    assert_eq!(walk(&[0, 1]), vec![(1, 1)]);
+}
+
+#[test]
+fn empty_chain_has_no_steps() {
+    assert_eq!(ChainProgress::advance(None, &[]), None);
+}
@@ -3,12 +3,17 @@
 
 //! Clock abstraction for testable time handling
 
-use std::sync::{Arc, Mutex};
-use std::time::{Duration, Instant};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 /// A clock that provides the current time
 pub trait Clock: Clone + Send + Sync {
+    /// Monotonic time, for measuring durations within this process
     fn now(&self) -> Instant;
+
+    /// Wall-clock time as Unix epoch milliseconds, for timestamps that are
+    /// persisted or shown to users
+    fn epoch_ms(&self) -> u64;
 }
 
 /// Real system clock
@@ -19,18 +24,61 @@ impl Clock for SystemClock {
     fn now(&self) -> Instant {
         Instant::now()
     }
+
+    fn epoch_ms(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis() as u64)
+    }
+}
+
+/// A clock stopped at a recorded wall-clock time
+///
+/// Used to replay operations as of when they were written, so that times
+/// derived from them (heartbeats, creation times) survive a restart.
+#[derive(Clone, Copy, Debug)]
+pub struct FixedClock {
+    instant: Instant,
+    epoch_ms: u64,
+}
+
+impl FixedClock {
+    pub fn at_epoch_ms(epoch_ms: u64) -> Self {
+        Self {
+            instant: from_unix_millis(epoch_ms),
+            epoch_ms,
+        }
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> Instant {
+        self.instant
+    }
+
+    fn epoch_ms(&self) -> u64 {
+        self.epoch_ms
+    }
 }
 
 /// Fake clock for testing with controllable time
+///
+/// Wall-clock time starts at 2026-01-01 00:00:00 UTC and advances along with
+/// the monotonic time.
 #[derive(Clone)]
 pub struct FakeClock {
     current: Arc<Mutex<Instant>>,
+    epoch_ms: Arc<Mutex<u64>>,
 }
 
 impl FakeClock {
+    /// Wall-clock time a new fake clock starts at
+    pub const START_EPOCH_MS: u64 = 1_767_225_600_000;
+
     pub fn new() -> Self {
         Self {
             current: Arc::new(Mutex::new(Instant::now())),
+            epoch_ms: Arc::new(Mutex::new(Self::START_EPOCH_MS)),
         }
     }
 
@@ -38,6 +86,13 @@ impl FakeClock {
     pub fn advance(&self, duration: Duration) {
         let mut current = self.current.lock().unwrap_or_else(|e| e.into_inner());
         *current += duration;
+        let mut epoch_ms = self.epoch_ms.lock().unwrap_or_else(|e| e.into_inner());
+        *epoch_ms += duration.as_millis() as u64;
+    }
+
+    /// Set the wall-clock time, in Unix epoch milliseconds
+    pub fn set_epoch_ms(&self, epoch_ms: u64) {
+        *self.epoch_ms.lock().unwrap_or_else(|e| e.into_inner()) = epoch_ms;
     }
 
     /// Set the clock to a specific instant
@@ -57,6 +112,104 @@ impl Clock for FakeClock {
     fn now(&self) -> Instant {
         *self.current.lock().unwrap_or_else(|e| e.into_inner())
     }
+
+    fn epoch_ms(&self) -> u64 {
+        *self.epoch_ms.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Format Unix epoch milliseconds as `YYYY-MM-DD HH:MM:SS UTC`
+pub fn format_epoch_ms(epoch_ms: u64) -> String {
+    let secs = epoch_ms / 1000;
+    let (year, month, day) = civil_from_days(secs / 86_400);
+    let secs_of_day = secs % 86_400;
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60
+    )
+}
+
+/// Convert days since the Unix epoch to a (year, month, day) date
+pub fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Serde support for `Instant` fields, stored as Unix epoch milliseconds
+///
+/// An `Instant` only has meaning inside the process that took it, so it is
+/// written as the wall-clock time it corresponds to and read back relative
+/// to the current `Instant`. Time elapsed since it survives a restart.
+pub mod instant_serde {
+    use super::{from_unix_millis, to_unix_millis};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::time::Instant;
+
+    pub fn serialize<S: Serializer>(instant: &Instant, s: S) -> Result<S::Ok, S::Error> {
+        to_unix_millis(*instant).serialize(s)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Instant, D::Error> {
+        Ok(from_unix_millis(u64::deserialize(d)?))
+    }
+}
+
+fn to_unix_millis(instant: Instant) -> u64 {
+    let now = Instant::now();
+    let wall = if instant <= now {
+        SystemTime::now().checked_sub(now - instant)
+    } else {
+        SystemTime::now().checked_add(instant - now)
+    };
+    wall.and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+fn from_unix_millis(millis: u64) -> Instant {
+    let wall = UNIX_EPOCH + Duration::from_millis(millis);
+    let now = Instant::now();
+    match SystemTime::now().duration_since(wall) {
+        // Older than the monotonic clock can go: as old as it gets, so the
+        // time elapsed since is not lost
+        Ok(elapsed) => now
+            .checked_sub(elapsed)
+            .unwrap_or_else(|| earliest_instant(now)),
+        Err(e) => now.checked_add(e.duration()).unwrap_or(now),
+    }
+}
+
+/// The earliest `Instant` there is, usually around when the machine booted
+fn earliest_instant(now: Instant) -> Instant {
+    static EARLIEST: OnceLock<Instant> = OnceLock::new();
+    *EARLIEST.get_or_init(|| {
+        let mut earliest = now;
+        let mut step = Duration::from_millis(1);
+        // Take ever larger steps back until one fails, then ever smaller ones
+        while let Some(instant) = earliest.checked_sub(step) {
+            earliest = instant;
+            step = step.saturating_mul(2);
+        }
+        while step > Duration::from_millis(1) {
+            step /= 2;
+            if let Some(instant) = earliest.checked_sub(step) {
+                earliest = instant;
+            }
+        }
+        earliest
+    })
 }
 
 #[cfg(test)]
@@ -30,3 +30,70 @@ fn fake_clock_is_cloneable_and_shared() {
     let t2 = clock1.now();
     assert!(t2.duration_since(t1) >= Duration::from_secs(30));
 }
+
+#[test]
+fn fake_clock_wall_time_advances_and_can_be_set() {
+    let clock = FakeClock::new();
+    assert_eq!(clock.epoch_ms(), FakeClock::START_EPOCH_MS);
+    clock.advance(Duration::from_secs(90));
+    assert_eq!(clock.epoch_ms(), FakeClock::START_EPOCH_MS + 90_000);
+    clock.set_epoch_ms(1_000);
+    assert_eq!(clock.epoch_ms(), 1_000);
+}
+
+#[test]
+fn fixed_clock_maps_wall_time_to_instant() {
+    let clock = FixedClock::at_epoch_ms(SystemClock.epoch_ms() - 60_000);
+    let elapsed = clock.now().elapsed();
+    assert!(elapsed >= Duration::from_millis(59_950), "{:?}", elapsed);
+    assert!(elapsed < Duration::from_millis(60_100), "{:?}", elapsed);
+}
+
+#[test]
+fn civil_dates_from_days() {
+    assert_eq!(civil_from_days(0), (1970, 1, 1));
+    assert_eq!(civil_from_days(20_454), (2026, 1, 1));
+    // Leap day
+    assert_eq!(civil_from_days(19_782), (2024, 2, 29));
+}
+
+#[test]
+fn formats_epoch_ms_as_utc() {
+    assert_eq!(format_epoch_ms(0), "1970-01-01 00:00:00 UTC");
+    assert_eq!(
+        format_epoch_ms(FakeClock::START_EPOCH_MS + 3_723_500),
+        "2026-01-01 01:02:03 UTC"
+    );
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Stamped {
+    #[serde(with = "instant_serde")]
+    at: Instant,
+}
+
+#[test]
+fn instant_serde_keeps_elapsed_time() {
+    let now = Instant::now();
+    for at in [now - Duration::from_secs(90), now + Duration::from_secs(30)] {
+        let json = serde_json::to_string(&Stamped { at }).unwrap();
+        let parsed: Stamped = serde_json::from_str(&json).unwrap();
+        let drift = if parsed.at > at {
+            parsed.at - at
+        } else {
+            at - parsed.at
+        };
+        assert!(drift < Duration::from_millis(50), "drift {:?}", drift);
+    }
+}
+
+#[test]
+fn instants_before_the_monotonic_clock_saturate() {
+    let earliest = earliest_instant(Instant::now());
+    assert!(earliest.checked_sub(Duration::from_millis(1)).is_none());
+
+    // Long before this machine booted: not mistaken for now
+    let at = from_unix_millis(1_000);
+    assert!(at <= Instant::now() - Duration::from_millis(1));
+    assert!(at >= earliest);
+}
@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Cron state
+
+use serde::{Deserialize, Serialize};
+
+/// Persisted state of a runbook cron
+///
+/// Crons are disabled until enabled with `oj cron enable`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cron {
+    pub name: String,
+    pub enabled: bool,
+}
+
+impl Cron {
+    /// Create a disabled cron
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            enabled: false,
+        }
+    }
+}
@@ -50,7 +50,10 @@ pub enum Effect {
     /// Persist an operation to storage
     Persist { operation: Operation },
 
-    /// Execute a shell command
+    /// Run a phase's shell command in the background
+    ///
+    /// Completion is reported with `ShellCompleted`, or `ShellTimedOut` if
+    /// the command is killed for running past its timeout.
     Shell {
         /// Pipeline this belongs to
         pipeline_id: String,
@@ -62,6 +65,32 @@ pub enum Effect {
         cwd: PathBuf,
         /// Environment variables
         env: HashMap<String, String>,
+        /// How long the command may run
+        #[serde(default, with = "duration_serde::option")]
+        timeout: Option<Duration>,
+        /// Pipeline log file the command's output is appended to
+        log_path: PathBuf,
+        /// Output variables set to the command's trimmed stdout
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        stdout_vars: Vec<String>,
+    },
+
+    /// Kill a pipeline's running shell command, if it has one
+    ///
+    /// No completion event is produced for a cancelled command.
+    CancelShell { pipeline_id: String },
+
+    /// Run a fire-and-forget shell hook in the background
+    ///
+    /// Unlike `Shell`, the event loop does not wait for the command and no
+    /// completion event is produced.
+    Hook {
+        /// Hook name, for logging (e.g. `build.on_phase`)
+        name: String,
+        /// Command to execute (already interpolated)
+        command: String,
+        /// Working directory
+        cwd: PathBuf,
     },
 
     /// Send a desktop notification
@@ -85,7 +114,9 @@ impl crate::traced::TracedEffect for Effect {
             Effect::SetTimer { .. } => "set_timer",
             Effect::CancelTimer { .. } => "cancel_timer",
             Effect::Persist { .. } => "persist",
+            Effect::CancelShell { .. } => "cancel_shell",
             Effect::Shell { .. } => "shell",
+            Effect::Hook { .. } => "hook",
             Effect::Notify { .. } => "notify",
         }
     }
@@ -125,12 +156,23 @@ impl crate::traced::TracedEffect for Effect {
                 pipeline_id,
                 phase,
                 cwd,
+                timeout,
                 ..
-            } => vec![
-                ("pipeline_id", pipeline_id.clone()),
-                ("phase", phase.clone()),
-                ("cwd", cwd.display().to_string()),
-            ],
+            } => {
+                let mut fields = vec![
+                    ("pipeline_id", pipeline_id.clone()),
+                    ("phase", phase.clone()),
+                    ("cwd", cwd.display().to_string()),
+                ];
+                if let Some(timeout) = timeout {
+                    fields.push(("timeout_ms", timeout.as_millis().to_string()));
+                }
+                fields
+            }
+            Effect::CancelShell { pipeline_id } => vec![("pipeline_id", pipeline_id.clone())],
+            Effect::Hook { name, cwd, .. } => {
+                vec![("hook", name.clone()), ("cwd", cwd.display().to_string())]
+            }
             Effect::Notify { title, .. } => vec![("title", title.clone())],
         }
     }
@@ -148,6 +190,23 @@ mod duration_serde {
         let millis = u64::deserialize(d)?;
         Ok(Duration::from_millis(millis))
     }
+
+    pub mod option {
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+        use std::time::Duration;
+
+        pub fn serialize<S: Serializer>(
+            duration: &Option<Duration>,
+            s: S,
+        ) -> Result<S::Ok, S::Error> {
+            duration.map(|d| d.as_millis()).serialize(s)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<Duration>, D::Error> {
+            let millis = Option::<u64>::deserialize(d)?;
+            Ok(millis.map(Duration::from_millis))
+        }
+    }
 }
 
 #[cfg(test)]
@@ -27,6 +27,17 @@ fn effect_serialization_roundtrip() {
             env: [("KEY".to_string(), "value".to_string())]
                 .into_iter()
                 .collect(),
+            timeout: Some(Duration::from_secs(600)),
+            log_path: PathBuf::from("/tmp/logs/pipe-1.log"),
+            stdout_vars: vec!["sha".to_string()],
+        },
+        Effect::CancelShell {
+            pipeline_id: "pipe-1".to_string(),
+        },
+        Effect::Hook {
+            name: "build.on_phase".to_string(),
+            command: "echo build -> plan".to_string(),
+            cwd: PathBuf::from("/tmp"),
         },
     ];
 
@@ -15,9 +15,24 @@ pub enum Event {
         args: HashMap<String, String>,
     },
 
+    /// Worker start request
+    WorkerStart { worker: String },
+
+    /// Worker stop request
+    WorkerStop { worker: String },
+
     /// Worker wake signal
     WorkerWake { worker: String },
 
+    /// Cron enable request
+    CronEnable { cron: String },
+
+    /// Cron disable request
+    CronDisable { cron: String },
+
+    /// Run a cron once, now
+    CronRun { cron: String },
+
     /// Session started successfully
     SessionStarted { session_id: String },
 
@@ -41,8 +56,14 @@ pub enum Event {
         pipeline_id: String,
         phase: String,
         exit_code: i32,
+        /// Variables the command published for later phases
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        outputs: HashMap<String, String>,
     },
 
+    /// Shell command was killed for running past its phase timeout
+    ShellTimedOut { pipeline_id: String, phase: String },
+
     /// Custom event for extensibility
     Custom {
         name: String,
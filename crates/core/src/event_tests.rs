@@ -12,6 +12,9 @@ fn event_serialization_roundtrip() {
                 .into_iter()
                 .collect(),
         },
+        Event::WorkerStart {
+            worker: "builds".to_string(),
+        },
         Event::WorkerWake {
             worker: "builds".to_string(),
         },
@@ -25,6 +28,13 @@ fn event_serialization_roundtrip() {
             pipeline_id: "pipe-1".to_string(),
             phase: "init".to_string(),
             exit_code: 0,
+            outputs: [("sha".to_string(), "abc123".to_string())]
+                .into_iter()
+                .collect(),
+        },
+        Event::ShellTimedOut {
+            pipeline_id: "pipe-1".to_string(),
+            phase: "test".to_string(),
         },
     ];
 
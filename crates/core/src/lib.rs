@@ -8,20 +8,34 @@
 
 //! oj-core: Core library for the Otter Jobs (oj) CLI tool
 
+pub mod action;
+pub mod chain;
 pub mod clock;
+pub mod cron;
 pub mod effect;
 pub mod event;
 pub mod id;
+pub mod lock;
 pub mod operation;
 pub mod pipeline;
+pub mod queue;
+pub mod semaphore;
 pub mod traced;
 pub mod worker;
 
-pub use clock::{Clock, FakeClock, SystemClock};
+pub use action::ActionRecord;
+pub use chain::ChainProgress;
+pub use clock::{Clock, FakeClock, FixedClock, SystemClock};
+pub use cron::Cron;
 pub use effect::Effect;
 pub use event::Event;
 pub use id::{IdGen, SequentialIdGen, UuidIdGen};
+pub use lock::{AcquireResult, Lock, LockConfig, LockState, ReleaseResult};
 pub use operation::Operation;
-pub use pipeline::{PhaseStatus, Pipeline};
+pub use pipeline::{
+    PhaseRecord, PhaseStatus, PhaseTrigger, Pipeline, PipelineParent, StrategyState,
+};
+pub use queue::{ItemState, Queue, QueueConfig, QueueItem, QueueOrder};
+pub use semaphore::{Semaphore, SemaphoreConfig, SemaphoreHolder, SemaphoreResult};
 pub use traced::TracedEffect;
 pub use worker::{Worker, WorkerStatus};
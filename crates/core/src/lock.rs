@@ -0,0 +1,190 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Lock state machine for exclusive access to a resource
+
+use crate::clock::Clock;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Lock configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockConfig {
+    /// How long a holder may go without heartbeating before the lock is stale
+    pub heartbeat_timeout: Duration,
+    /// Maximum time a single holder may keep the lock
+    pub max_hold_duration: Option<Duration>,
+}
+
+impl Default for LockConfig {
+    fn default() -> Self {
+        Self {
+            heartbeat_timeout: Duration::from_secs(30 * 60),
+            max_hold_duration: None,
+        }
+    }
+}
+
+/// Current state of a lock
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LockState {
+    Free,
+    Held {
+        holder: String,
+        #[serde(with = "crate::clock::instant_serde")]
+        acquired_at: Instant,
+        #[serde(with = "crate::clock::instant_serde")]
+        last_heartbeat: Instant,
+    },
+}
+
+/// Result of trying to acquire a lock
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AcquireResult {
+    /// The lock was free and is now held
+    Acquired,
+    /// The caller already held the lock; heartbeat refreshed
+    Extended,
+    /// The previous holder was stale; lock taken over
+    Reclaimed { previous: String },
+    /// Another holder has the lock
+    Busy { holder: String },
+}
+
+impl AcquireResult {
+    /// Whether the caller holds the lock after this result
+    pub fn is_held(&self) -> bool {
+        !matches!(self, AcquireResult::Busy { .. })
+    }
+}
+
+/// Result of releasing a lock
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReleaseResult {
+    Released,
+    NotOwner { holder: String },
+    AlreadyFree,
+}
+
+/// An exclusive lock with stale detection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lock {
+    pub name: String,
+    pub state: LockState,
+    pub config: LockConfig,
+}
+
+impl Lock {
+    /// Create a new free lock
+    pub fn new(name: String, config: LockConfig) -> Self {
+        Self {
+            name,
+            state: LockState::Free,
+            config,
+        }
+    }
+
+    /// Try to acquire the lock for `holder`
+    pub fn acquire(&mut self, holder: &str, clock: &impl Clock) -> AcquireResult {
+        let current = match self.holder() {
+            None => {
+                self.grant(holder, clock.now());
+                return AcquireResult::Acquired;
+            }
+            Some(current) => current.to_string(),
+        };
+
+        if current == holder {
+            self.heartbeat(holder, clock);
+            AcquireResult::Extended
+        } else if self.is_stale(clock) {
+            self.grant(holder, clock.now());
+            AcquireResult::Reclaimed { previous: current }
+        } else {
+            AcquireResult::Busy { holder: current }
+        }
+    }
+
+    /// Release the lock held by `holder`
+    pub fn release(&mut self, holder: &str) -> ReleaseResult {
+        match &self.state {
+            LockState::Free => ReleaseResult::AlreadyFree,
+            LockState::Held {
+                holder: current, ..
+            } if current == holder => {
+                self.state = LockState::Free;
+                ReleaseResult::Released
+            }
+            LockState::Held {
+                holder: current, ..
+            } => ReleaseResult::NotOwner {
+                holder: current.clone(),
+            },
+        }
+    }
+
+    /// Refresh the heartbeat if `holder` owns the lock
+    ///
+    /// Returns false if the lock is free or held by someone else.
+    pub fn heartbeat(&mut self, holder: &str, clock: &impl Clock) -> bool {
+        match &mut self.state {
+            LockState::Held {
+                holder: current,
+                last_heartbeat,
+                ..
+            } if current == holder => {
+                *last_heartbeat = clock.now();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Mark the lock as held by `holder` as of `now`, replacing any holder
+    ///
+    /// Used when replaying an acquisition that was already decided.
+    pub fn grant(&mut self, holder: &str, now: Instant) {
+        self.state = LockState::Held {
+            holder: holder.to_string(),
+            acquired_at: now,
+            last_heartbeat: now,
+        };
+    }
+
+    /// Check whether the current holder has stopped heartbeating
+    pub fn is_stale(&self, clock: &impl Clock) -> bool {
+        let LockState::Held {
+            acquired_at,
+            last_heartbeat,
+            ..
+        } = &self.state
+        else {
+            return false;
+        };
+
+        let now = clock.now();
+        if now.saturating_duration_since(*last_heartbeat) > self.config.heartbeat_timeout {
+            return true;
+        }
+        self.config
+            .max_hold_duration
+            .is_some_and(|max| now.saturating_duration_since(*acquired_at) > max)
+    }
+
+    /// Get the current holder, if any
+    pub fn holder(&self) -> Option<&str> {
+        match &self.state {
+            LockState::Free => None,
+            LockState::Held { holder, .. } => Some(holder),
+        }
+    }
+
+    /// Check if the lock is held by `holder`
+    pub fn is_held_by(&self, holder: &str) -> bool {
+        self.holder() == Some(holder)
+    }
+}
+
+#[cfg(test)]
+#[path = "lock_tests.rs"]
+mod tests;
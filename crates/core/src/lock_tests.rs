@@ -0,0 +1,115 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+use crate::FakeClock;
+
+fn test_lock() -> Lock {
+    Lock::new(
+        "main_branch".to_string(),
+        LockConfig {
+            heartbeat_timeout: Duration::from_secs(60),
+            max_hold_duration: None,
+        },
+    )
+}
+
+#[test]
+fn acquire_free_lock() {
+    let clock = FakeClock::new();
+    let mut lock = test_lock();
+
+    assert_eq!(lock.acquire("pipe-1", &clock), AcquireResult::Acquired);
+    assert!(lock.is_held_by("pipe-1"));
+}
+
+#[test]
+fn acquire_by_same_holder_extends() {
+    let clock = FakeClock::new();
+    let mut lock = test_lock();
+    lock.acquire("pipe-1", &clock);
+
+    clock.advance(Duration::from_secs(50));
+    assert_eq!(lock.acquire("pipe-1", &clock), AcquireResult::Extended);
+
+    // Heartbeat was refreshed, so the lock is not stale 50s later
+    clock.advance(Duration::from_secs(50));
+    assert!(!lock.is_stale(&clock));
+}
+
+#[test]
+fn acquire_held_lock_is_busy() {
+    let clock = FakeClock::new();
+    let mut lock = test_lock();
+    lock.acquire("pipe-1", &clock);
+
+    assert_eq!(
+        lock.acquire("pipe-2", &clock),
+        AcquireResult::Busy {
+            holder: "pipe-1".to_string()
+        }
+    );
+    assert!(lock.is_held_by("pipe-1"));
+}
+
+#[test]
+fn stale_lock_is_reclaimed() {
+    let clock = FakeClock::new();
+    let mut lock = test_lock();
+    lock.acquire("pipe-1", &clock);
+
+    clock.advance(Duration::from_secs(61));
+    assert!(lock.is_stale(&clock));
+    assert_eq!(
+        lock.acquire("pipe-2", &clock),
+        AcquireResult::Reclaimed {
+            previous: "pipe-1".to_string()
+        }
+    );
+    assert!(lock.is_held_by("pipe-2"));
+}
+
+#[test]
+fn heartbeat_keeps_lock_fresh() {
+    let clock = FakeClock::new();
+    let mut lock = test_lock();
+    lock.acquire("pipe-1", &clock);
+
+    clock.advance(Duration::from_secs(45));
+    assert!(lock.heartbeat("pipe-1", &clock));
+    clock.advance(Duration::from_secs(45));
+    assert!(!lock.is_stale(&clock));
+
+    assert!(!lock.heartbeat("pipe-2", &clock));
+}
+
+#[test]
+fn max_hold_duration_makes_lock_stale() {
+    let clock = FakeClock::new();
+    let mut lock = test_lock();
+    lock.config.max_hold_duration = Some(Duration::from_secs(90));
+    lock.acquire("pipe-1", &clock);
+
+    clock.advance(Duration::from_secs(50));
+    lock.heartbeat("pipe-1", &clock);
+    clock.advance(Duration::from_secs(50));
+    assert!(lock.is_stale(&clock));
+}
+
+#[test]
+fn release_results() {
+    let clock = FakeClock::new();
+    let mut lock = test_lock();
+
+    assert_eq!(lock.release("pipe-1"), ReleaseResult::AlreadyFree);
+
+    lock.acquire("pipe-1", &clock);
+    assert_eq!(
+        lock.release("pipe-2"),
+        ReleaseResult::NotOwner {
+            holder: "pipe-1".to_string()
+        }
+    );
+    assert_eq!(lock.release("pipe-1"), ReleaseResult::Released);
+    assert_eq!(lock.state, LockState::Free);
+}
@@ -3,7 +3,7 @@
 
 //! Operations for the write-ahead log
 
-use crate::PhaseStatus;
+use crate::{ChainProgress, PhaseStatus, PhaseTrigger, PipelineParent};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -20,10 +20,25 @@ pub enum Operation {
         /// Initial phase name from runbook (defaults to "init" for legacy WAL compat)
         #[serde(default = "default_init_phase")]
         initial_phase: String,
+        /// Set when another pipeline's phase runs this one
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        parent: Option<PipelineParent>,
     },
 
     /// Transition a pipeline to a new phase
-    PipelineTransition { id: String, phase: String },
+    PipelineTransition {
+        id: String,
+        phase: String,
+        /// Why the phase was entered (legacy entries default to `next`)
+        #[serde(default)]
+        trigger: PhaseTrigger,
+        /// Why the previous phase failed, if it did
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+        /// Values the previous phase published for the phases after it
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        outputs: HashMap<String, String>,
+    },
 
     /// Update the status of the current phase
     PhaseStatusUpdate {
@@ -31,6 +46,10 @@ pub enum Operation {
         status: PhaseStatus,
     },
 
+    /// Record the exit code of the shell command or session running a
+    /// pipeline's current phase
+    PhaseExited { pipeline_id: String, exit_code: i32 },
+
     /// Delete a pipeline
     PipelineDelete { id: String },
 
@@ -49,6 +68,150 @@ pub enum Operation {
 
     /// Delete a workspace record
     WorkspaceDelete { id: String },
+
+    /// Acquire (or reclaim) a lock for a holder
+    LockAcquire { name: String, holder: String },
+
+    /// Release a lock held by a holder
+    LockRelease { name: String, holder: String },
+
+    /// Refresh the heartbeat of a held lock
+    LockHeartbeat { name: String, holder: String },
+
+    /// Acquire `slots` of a semaphore for a holder
+    SemaphoreAcquire {
+        name: String,
+        holder: String,
+        slots: u32,
+    },
+
+    /// Release all slots of a semaphore held by a holder
+    SemaphoreRelease { name: String, holder: String },
+
+    /// Refresh the heartbeat of a semaphore holder
+    SemaphoreHeartbeat { name: String, holder: String },
+
+    /// Record the strategy attempt a pipeline phase is running
+    StrategyAttempt {
+        pipeline_id: String,
+        phase: String,
+        attempt: usize,
+        checkpoint: Option<String>,
+    },
+
+    /// Record how far an agent trigger has got through its recovery chain
+    RecoveryStep {
+        pipeline_id: String,
+        trigger: String,
+        progress: ChainProgress,
+    },
+
+    /// Start a pipeline's recovery chains over, e.g. once its agent makes progress
+    RecoveryReset { pipeline_id: String },
+
+    /// Add an item to a queue
+    QueuePush {
+        name: String,
+        item_id: String,
+        data: HashMap<String, String>,
+        priority: i64,
+    },
+
+    /// Claim a pending queue item for a holder
+    QueueClaim {
+        name: String,
+        item_id: String,
+        holder: String,
+    },
+
+    /// Restart the visibility timeout of a holder's claim on a queue item
+    QueueExtend {
+        name: String,
+        item_id: String,
+        holder: String,
+    },
+
+    /// Remove a queue item that was processed successfully
+    QueueComplete { name: String, item_id: String },
+
+    /// Return a claimed queue item to pending after a failed attempt
+    QueueFail {
+        name: String,
+        item_id: String,
+        reason: String,
+    },
+
+    /// Return a claimed queue item to pending without counting the attempt
+    QueueRelease { name: String, item_id: String },
+
+    /// Move an exhausted queue item to the dead letter queue
+    QueueDead {
+        name: String,
+        item_id: String,
+        reason: String,
+    },
+
+    /// Drop a queue item, e.g. an exhausted item or one past dead letter retention
+    QueueRemove { name: String, item_id: String },
+
+    /// Mark a worker as running
+    WorkerStart { name: String },
+
+    /// Mark a worker as stopped
+    WorkerStop { name: String },
+
+    /// Record that a worker started a pipeline for a queue item
+    WorkerAssign {
+        name: String,
+        pipeline_id: String,
+        item_id: String,
+    },
+
+    /// Record that a worker's pipeline finished
+    WorkerRelease { name: String, pipeline_id: String },
+
+    /// Enable a cron
+    CronEnable { name: String },
+
+    /// Disable a cron
+    CronDisable { name: String },
+
+    /// Record how far a monitored item has got through the response chain
+    MonitorRespond {
+        name: String,
+        item_id: String,
+        progress: ChainProgress,
+    },
+
+    /// Forget a monitored item's response progress once it stops matching
+    MonitorReset { name: String, item_id: String },
+
+    /// Record that a named action fired at a target
+    ActionFire { name: String, target: String },
+
+    /// Forget every named action fired at a target
+    ActionReset { target: String },
+
+    /// Marks that a snapshot covers every entry up to `sequence`
+    SnapshotTaken { sequence: u64 },
+}
+
+impl Operation {
+    /// The pipeline whose record this operation changes, if any
+    pub fn pipeline_id(&self) -> Option<&str> {
+        match self {
+            Operation::PipelineTransition { id, .. } | Operation::WorkspaceCreate { id, .. } => {
+                Some(id)
+            }
+            Operation::PhaseStatusUpdate { pipeline_id, .. }
+            | Operation::PhaseExited { pipeline_id, .. }
+            | Operation::SessionCreate { pipeline_id, .. }
+            | Operation::StrategyAttempt { pipeline_id, .. }
+            | Operation::RecoveryStep { pipeline_id, .. }
+            | Operation::RecoveryReset { pipeline_id } => Some(pipeline_id),
+            _ => None,
+        }
+    }
 }
 
 /// Default phase for legacy WAL entries without initial_phase
@@ -19,6 +19,24 @@ fn legacy_wal_without_initial_phase() {
     }
 }
 
+#[test]
+fn legacy_transition_defaults_to_next() {
+    let legacy_json = r#"{"PipelineTransition":{"id":"pipe-1","phase":"plan"}}"#;
+
+    let op: Operation = serde_json::from_str(legacy_json).unwrap();
+
+    assert_eq!(
+        op,
+        Operation::PipelineTransition {
+            id: "pipe-1".to_string(),
+            phase: "plan".to_string(),
+            trigger: PhaseTrigger::Next,
+            error: None,
+            outputs: HashMap::new(),
+        }
+    );
+}
+
 #[test]
 fn operation_serialization_roundtrip() {
     let ops = vec![
@@ -30,16 +48,34 @@ fn operation_serialization_roundtrip() {
                 .into_iter()
                 .collect(),
             initial_phase: "init".to_string(),
+            parent: None,
         },
         Operation::PipelineTransition {
             id: "pipe-1".to_string(),
             phase: "plan".to_string(),
+            trigger: PhaseTrigger::OnFail,
+            error: Some("tests failed".to_string()),
+            outputs: HashMap::new(),
+        },
+        Operation::PhaseExited {
+            pipeline_id: "pipe-1".to_string(),
+            exit_code: 2,
         },
         Operation::WorkspaceCreate {
             id: "ws-1".to_string(),
             path: PathBuf::from("/tmp/worktree"),
             branch: "feature/test".to_string(),
         },
+        Operation::LockAcquire {
+            name: "main_branch".to_string(),
+            holder: "pipe-1".to_string(),
+        },
+        Operation::SemaphoreAcquire {
+            name: "agents".to_string(),
+            holder: "pipe-1".to_string(),
+            slots: 2,
+        },
+        Operation::SnapshotTaken { sequence: 42 },
     ];
 
     for op in ops {
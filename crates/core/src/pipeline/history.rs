@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Record of the phases a pipeline has been through
+
+use super::phase::PhaseStatus;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// How a pipeline came to enter a phase
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PhaseTrigger {
+    /// First phase of a new pipeline
+    Start,
+    /// The previous phase completed
+    #[default]
+    Next,
+    /// The previous phase failed and routed here through its `on_fail`
+    OnFail,
+    /// The previous phase failed with no `on_fail` to route to
+    Failed,
+}
+
+impl fmt::Display for PhaseTrigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PhaseTrigger::Start => "start",
+            PhaseTrigger::Next => "next",
+            PhaseTrigger::OnFail => "on_fail",
+            PhaseTrigger::Failed => "failed",
+        })
+    }
+}
+
+/// One visit of a pipeline to a phase
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PhaseRecord {
+    pub phase: String,
+    /// Latest status while the phase runs, final status once it has ended
+    pub status: PhaseStatus,
+    pub trigger: PhaseTrigger,
+    /// Unix epoch milliseconds
+    pub started_at_ms: u64,
+    /// Unix epoch milliseconds, `None` while the phase is current
+    pub ended_at_ms: Option<u64>,
+    /// Exit code of the shell command or agent session that ran the phase
+    pub exit_code: Option<i32>,
+    /// Why the phase failed
+    pub error: Option<String>,
+}
+
+impl PhaseRecord {
+    pub fn new(phase: String, trigger: PhaseTrigger, started_at_ms: u64) -> Self {
+        Self {
+            phase,
+            status: PhaseStatus::Pending,
+            trigger,
+            started_at_ms,
+            ended_at_ms: None,
+            exit_code: None,
+            error: None,
+        }
+    }
+
+    /// Close the record, as failed if there is an error
+    pub fn end(&mut self, ended_at_ms: u64, error: Option<&str>) {
+        self.ended_at_ms = Some(ended_at_ms);
+        if let Some(error) = error {
+            self.status = PhaseStatus::Failed;
+            self.error = Some(error.to_string());
+        } else if self.status != PhaseStatus::Failed {
+            self.status = PhaseStatus::Completed;
+        }
+    }
+}
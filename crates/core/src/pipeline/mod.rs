@@ -3,8 +3,10 @@
 
 //! Pipeline state machine
 
+mod history;
 mod phase;
 mod state;
 
+pub use history::{PhaseRecord, PhaseTrigger};
 pub use phase::PhaseStatus;
-pub use state::Pipeline;
+pub use state::{Pipeline, PipelineParent, StrategyState};
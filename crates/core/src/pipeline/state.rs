@@ -3,7 +3,9 @@
 
 //! Pipeline state machine
 
+use super::history::{PhaseRecord, PhaseTrigger};
 use super::phase::PhaseStatus;
+use crate::chain::ChainProgress;
 use crate::clock::Clock;
 use crate::effect::Effect;
 use crate::event::Event;
@@ -11,7 +13,7 @@ use crate::operation::Operation;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
-use std::time::Instant;
+use std::time::Duration;
 
 /// A pipeline instance
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,13 +25,54 @@ pub struct Pipeline {
     pub phase: String,
     pub phase_status: PhaseStatus,
     pub inputs: HashMap<String, String>,
+    /// Values published by finished phases, available to later phases
+    #[serde(default)]
+    pub outputs: HashMap<String, String>,
     pub workspace_path: Option<PathBuf>,
     pub session_id: Option<String>,
-    #[serde(skip, default = "Instant::now")]
-    pub created_at: Instant,
-    #[serde(skip, default = "Instant::now")]
-    pub phase_started_at: Instant,
+    /// When the pipeline was created, in Unix epoch milliseconds
+    #[serde(default)]
+    pub created_at_ms: u64,
+    /// When the pipeline last changed, in Unix epoch milliseconds
+    #[serde(default)]
+    pub updated_at_ms: u64,
+    /// When the current phase started, in Unix epoch milliseconds
+    #[serde(default)]
+    pub phase_started_at_ms: u64,
     pub error: Option<String>,
+    /// Progress through the current phase's strategy, if it runs one
+    #[serde(default)]
+    pub strategy: Option<StrategyState>,
+    /// Progress through the current phase's agent recovery chains, keyed by
+    /// trigger (`idle`, `exit` or `error`)
+    #[serde(default)]
+    pub recovery: HashMap<String, ChainProgress>,
+    /// Every phase visited, oldest first; the last entry is the current phase
+    #[serde(default)]
+    pub history: Vec<PhaseRecord>,
+    /// The pipeline whose phase started this one, for child pipelines
+    #[serde(default)]
+    pub parent: Option<PipelineParent>,
+}
+
+/// The phase of another pipeline that runs a child pipeline
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PipelineParent {
+    /// Parent pipeline ID
+    pub id: String,
+    /// Parent phase waiting on the child
+    pub phase: String,
+}
+
+/// Position within a strategy's fallback chain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StrategyState {
+    /// Phase running the strategy
+    pub phase: String,
+    /// Index of the attempt in progress
+    pub attempt: usize,
+    /// Checkpoint captured before the first attempt, used by rollbacks
+    pub checkpoint: Option<String>,
 }
 
 impl Pipeline {
@@ -42,7 +85,12 @@ impl Pipeline {
         initial_phase: String,
         clock: &impl Clock,
     ) -> Self {
-        let now = clock.now();
+        let now = clock.epoch_ms();
+        let history = vec![PhaseRecord::new(
+            initial_phase.clone(),
+            PhaseTrigger::Start,
+            now,
+        )];
         Self {
             id,
             name,
@@ -50,11 +98,61 @@ impl Pipeline {
             phase: initial_phase,
             phase_status: PhaseStatus::Pending,
             inputs,
+            outputs: HashMap::new(),
             workspace_path: None,
             session_id: None,
-            created_at: now,
-            phase_started_at: now,
+            created_at_ms: now,
+            updated_at_ms: now,
+            phase_started_at_ms: now,
             error: None,
+            strategy: None,
+            recovery: HashMap::new(),
+            history,
+            parent: None,
+        }
+    }
+
+    /// Move to a new phase, closing the current one in the history
+    ///
+    /// `error` is why the current phase failed, if it did, and `outputs` are
+    /// the values it published, which replace earlier values of the same name.
+    pub fn enter_phase(
+        &mut self,
+        phase: &str,
+        trigger: PhaseTrigger,
+        error: Option<&str>,
+        outputs: &HashMap<String, String>,
+        epoch_ms: u64,
+    ) {
+        if let Some(current) = self.history.last_mut().filter(|r| r.ended_at_ms.is_none()) {
+            current.end(epoch_ms, error);
+        }
+        self.history
+            .push(PhaseRecord::new(phase.to_string(), trigger, epoch_ms));
+        if let Some(error) = error {
+            self.error = Some(error.to_string());
+        }
+        self.outputs
+            .extend(outputs.iter().map(|(k, v)| (k.clone(), v.clone())));
+        self.phase = phase.to_string();
+        self.phase_status = PhaseStatus::Pending;
+        self.phase_started_at_ms = epoch_ms;
+        self.strategy = None;
+        self.recovery.clear();
+    }
+
+    /// Update the status of the current phase
+    pub fn set_phase_status(&mut self, status: PhaseStatus) {
+        self.phase_status = status;
+        if let Some(current) = self.history.last_mut() {
+            current.status = status;
+        }
+    }
+
+    /// Record the exit code of whatever ran the current phase
+    pub fn record_exit(&mut self, exit_code: i32) {
+        if let Some(current) = self.history.last_mut() {
+            current.exit_code = Some(exit_code);
         }
     }
 
@@ -62,14 +160,16 @@ impl Pipeline {
     ///
     /// Note: Phase transitions (determining the next phase) are handled by the runtime
     /// using the runbook definition. This method only handles status updates and failures.
-    pub fn transition(&self, event: &Event, _clock: &impl Clock) -> (Pipeline, Vec<Effect>) {
+    pub fn transition(&self, event: &Event, clock: &impl Clock) -> (Pipeline, Vec<Effect>) {
         let mut pipeline = self.clone();
         let mut effects = Vec::new();
+        let now = clock.epoch_ms();
 
         match event {
             Event::SessionStarted { session_id } => {
                 if pipeline.session_id.as_ref() == Some(session_id) {
                     pipeline.phase_status = PhaseStatus::Running;
+                    pipeline.updated_at_ms = now;
                 }
             }
 
@@ -78,6 +178,7 @@ impl Pipeline {
                 exit_code,
             } => {
                 if pipeline.session_id.as_ref() == Some(session_id) {
+                    pipeline.updated_at_ms = now;
                     if *exit_code == 0 {
                         // Success - mark phase as completed
                         // The runtime will determine the next phase from the runbook
@@ -86,6 +187,7 @@ impl Pipeline {
                     } else {
                         // Failure
                         pipeline.phase = "failed".to_string();
+                        pipeline.phase_started_at_ms = now;
                         pipeline.phase_status = PhaseStatus::Failed;
                         pipeline.error = Some(format!("exit code: {}", exit_code));
 
@@ -93,6 +195,9 @@ impl Pipeline {
                             operation: Operation::PipelineTransition {
                                 id: pipeline.id.clone(),
                                 phase: "failed".to_string(),
+                                trigger: PhaseTrigger::Failed,
+                                error: pipeline.error.clone(),
+                                outputs: HashMap::new(),
                             },
                         });
                     }
@@ -103,12 +208,15 @@ impl Pipeline {
                 if &pipeline.id == pipeline_id {
                     // Mark phase as completed, runtime handles transition
                     pipeline.phase_status = PhaseStatus::Completed;
+                    pipeline.updated_at_ms = now;
                 }
             }
 
             Event::AgentError { pipeline_id, error } => {
                 if &pipeline.id == pipeline_id {
                     pipeline.phase = "failed".to_string();
+                    pipeline.phase_started_at_ms = now;
+                    pipeline.updated_at_ms = now;
                     pipeline.phase_status = PhaseStatus::Failed;
                     pipeline.error = Some(error.clone());
 
@@ -116,6 +224,9 @@ impl Pipeline {
                         operation: Operation::PipelineTransition {
                             id: pipeline.id.clone(),
                             phase: "failed".to_string(),
+                            trigger: PhaseTrigger::Failed,
+                            error: Some(error.clone()),
+                            outputs: HashMap::new(),
                         },
                     });
                 }
@@ -126,15 +237,31 @@ impl Pipeline {
 
             // These events are handled elsewhere (by the runtime)
             Event::CommandInvoked { .. }
+            | Event::WorkerStart { .. }
+            | Event::WorkerStop { .. }
             | Event::WorkerWake { .. }
+            | Event::CronEnable { .. }
+            | Event::CronDisable { .. }
+            | Event::CronRun { .. }
             | Event::SessionOutput { .. }
             | Event::ShellCompleted { .. }
+            | Event::ShellTimedOut { .. }
             | Event::Custom { .. } => {}
         }
 
         (pipeline, effects)
     }
 
+    /// How long the pipeline has existed at `now_ms`
+    pub fn age(&self, now_ms: u64) -> Duration {
+        Duration::from_millis(now_ms.saturating_sub(self.created_at_ms))
+    }
+
+    /// How long the current phase has been running at `now_ms`
+    pub fn phase_elapsed(&self, now_ms: u64) -> Duration {
+        Duration::from_millis(now_ms.saturating_sub(self.phase_started_at_ms))
+    }
+
     /// Check if the pipeline is in a terminal state
     pub fn is_terminal(&self) -> bool {
         self.phase == "done" || self.phase == "failed"
@@ -97,3 +97,31 @@ fn pipeline_session_exit_failure() {
     assert!(new_pipeline.error.is_some());
     assert_eq!(effects.len(), 1); // Persist
 }
+
+#[test]
+fn pipeline_failure_restarts_phase_clock() {
+    let clock = FakeClock::new();
+    let pipeline = Pipeline::new(
+        "pipe-1".to_string(),
+        "test".to_string(),
+        "build".to_string(),
+        HashMap::new(),
+        "init".to_string(),
+        &clock,
+    );
+    assert_eq!(pipeline.created_at_ms, FakeClock::START_EPOCH_MS);
+
+    clock.advance(std::time::Duration::from_secs(120));
+    let event = Event::AgentError {
+        pipeline_id: "pipe-1".to_string(),
+        error: "stuck".to_string(),
+    };
+    let (failed, _) = pipeline.transition(&event, &clock);
+
+    let now = clock.epoch_ms();
+    assert_eq!(failed.created_at_ms, FakeClock::START_EPOCH_MS);
+    assert_eq!(failed.updated_at_ms, now);
+    assert_eq!(failed.phase_started_at_ms, now);
+    assert_eq!(failed.age(now).as_secs(), 120);
+    assert_eq!(failed.phase_elapsed(now).as_secs(), 0);
+}
@@ -0,0 +1,307 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Queue state machine for durable work items
+
+use crate::clock::Clock;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+/// Order in which pending items are claimed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QueueOrder {
+    /// Lowest `priority` value first, oldest first among equals
+    #[default]
+    Priority,
+    /// Oldest first
+    CreatedAt,
+}
+
+/// Queue configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueueConfig {
+    /// How long a claimed item stays hidden before it is handed out again
+    pub visibility_timeout: Duration,
+    /// Failed attempts allowed after the first before the item is exhausted
+    pub max_retries: u32,
+    /// How long exhausted items are kept in the dead letter queue;
+    /// `None` discards them instead
+    pub dead_letter: Option<Duration>,
+    /// Claim order
+    pub order: QueueOrder,
+}
+
+impl Default for QueueConfig {
+    fn default() -> Self {
+        Self {
+            visibility_timeout: Duration::from_secs(30 * 60),
+            max_retries: 0,
+            dead_letter: None,
+            order: QueueOrder::default(),
+        }
+    }
+}
+
+/// Where an item is in its lifecycle
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ItemState {
+    /// Waiting to be claimed
+    Pending,
+    /// Hidden from other claimants while `holder` works on it
+    Claimed {
+        holder: String,
+        #[serde(with = "crate::clock::instant_serde")]
+        claimed_at: Instant,
+    },
+    /// Exhausted and parked in the dead letter queue
+    Dead {
+        #[serde(with = "crate::clock::instant_serde")]
+        since: Instant,
+    },
+}
+
+/// A work item
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueueItem {
+    pub id: String,
+    pub data: HashMap<String, String>,
+    pub priority: i64,
+    #[serde(with = "crate::clock::instant_serde")]
+    pub created_at: Instant,
+    /// Number of times the item has been claimed
+    pub attempts: u32,
+    pub state: ItemState,
+    /// Reason for the most recent failure
+    pub error: Option<String>,
+}
+
+impl QueueItem {
+    /// Check if the item is waiting to be claimed
+    pub fn is_pending(&self) -> bool {
+        self.state == ItemState::Pending
+    }
+
+    /// Check if the item is in the dead letter queue
+    pub fn is_dead(&self) -> bool {
+        matches!(self.state, ItemState::Dead { .. })
+    }
+
+    /// Get the current holder, if claimed
+    pub fn holder(&self) -> Option<&str> {
+        match &self.state {
+            ItemState::Claimed { holder, .. } => Some(holder),
+            _ => None,
+        }
+    }
+}
+
+/// A queue of work items, including its dead letter items
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Queue {
+    pub name: String,
+    /// Items in push order
+    pub items: Vec<QueueItem>,
+    /// IDs of items that were completed, dropped or purged, so sources
+    /// re-emitting them don't get them run again
+    #[serde(default)]
+    pub finished: HashSet<String>,
+    pub config: QueueConfig,
+}
+
+impl Queue {
+    /// Create an empty queue
+    pub fn new(name: String, config: QueueConfig) -> Self {
+        Self {
+            name,
+            items: Vec::new(),
+            finished: HashSet::new(),
+            config,
+        }
+    }
+
+    /// Add an item as of `now`
+    ///
+    /// Returns false if an item with the same ID is already in the queue or
+    /// has finished.
+    pub fn push(
+        &mut self,
+        id: &str,
+        data: HashMap<String, String>,
+        priority: i64,
+        now: Instant,
+    ) -> bool {
+        if self.get(id).is_some() || self.finished.contains(id) {
+            return false;
+        }
+        self.items.push(QueueItem {
+            id: id.to_string(),
+            data,
+            priority,
+            created_at: now,
+            attempts: 0,
+            state: ItemState::Pending,
+            error: None,
+        });
+        true
+    }
+
+    /// Get the pending item that should be claimed next
+    pub fn next_pending(&self) -> Option<&QueueItem> {
+        let pending = self.items.iter().filter(|item| item.is_pending());
+        match self.config.order {
+            QueueOrder::Priority => pending.min_by_key(|item| (item.priority, item.created_at)),
+            QueueOrder::CreatedAt => pending.min_by_key(|item| item.created_at),
+        }
+    }
+
+    /// Claim a pending item for `holder` as of `now`, counting an attempt
+    pub fn claim(&mut self, id: &str, holder: &str, now: Instant) -> bool {
+        let Some(item) = self.get_mut(id).filter(|item| item.is_pending()) else {
+            return false;
+        };
+        item.attempts += 1;
+        item.state = ItemState::Claimed {
+            holder: holder.to_string(),
+            claimed_at: now,
+        };
+        true
+    }
+
+    /// Restart the visibility timeout of `holder`'s claim as of `now`
+    pub fn extend(&mut self, id: &str, holder: &str, now: Instant) -> bool {
+        let Some(item) = self
+            .get_mut(id)
+            .filter(|item| item.holder() == Some(holder))
+        else {
+            return false;
+        };
+        item.state = ItemState::Claimed {
+            holder: holder.to_string(),
+            claimed_at: now,
+        };
+        true
+    }
+
+    /// Remove a claimed item that was processed successfully
+    pub fn complete(&mut self, id: &str) -> bool {
+        if self.get(id).and_then(|item| item.holder()).is_none() {
+            return false;
+        }
+        self.remove(id)
+    }
+
+    /// Return a claimed item to pending after a failed attempt
+    pub fn requeue(&mut self, id: &str, error: &str) -> bool {
+        let Some(item) = self.get_mut(id).filter(|item| item.holder().is_some()) else {
+            return false;
+        };
+        item.state = ItemState::Pending;
+        item.error = Some(error.to_string());
+        true
+    }
+
+    /// Return a claimed item to pending without counting the attempt
+    pub fn release(&mut self, id: &str) -> bool {
+        let Some(item) = self.get_mut(id).filter(|item| item.holder().is_some()) else {
+            return false;
+        };
+        item.state = ItemState::Pending;
+        item.attempts = item.attempts.saturating_sub(1);
+        true
+    }
+
+    /// Move an item to the dead letter queue as of `now`
+    pub fn bury(&mut self, id: &str, error: &str, now: Instant) -> bool {
+        let Some(item) = self.get_mut(id) else {
+            return false;
+        };
+        item.state = ItemState::Dead { since: now };
+        item.error = Some(error.to_string());
+        true
+    }
+
+    /// Remove an item regardless of its state, marking its ID finished
+    pub fn remove(&mut self, id: &str) -> bool {
+        let before = self.items.len();
+        self.items.retain(|item| item.id != id);
+        if self.items.len() == before {
+            return false;
+        }
+        self.finished.insert(id.to_string());
+        true
+    }
+
+    /// Check whether an item has used up its retries
+    pub fn is_exhausted(&self, id: &str) -> bool {
+        self.get(id)
+            .is_some_and(|item| item.attempts > self.config.max_retries)
+    }
+
+    /// IDs of claimed items whose visibility timeout has passed
+    pub fn expired(&self, clock: &impl Clock) -> Vec<String> {
+        let now = clock.now();
+        self.items
+            .iter()
+            .filter(|item| match &item.state {
+                ItemState::Claimed { claimed_at, .. } => {
+                    now.saturating_duration_since(*claimed_at) >= self.config.visibility_timeout
+                }
+                _ => false,
+            })
+            .map(|item| item.id.clone())
+            .collect()
+    }
+
+    /// IDs of dead items past their retention
+    pub fn purgeable(&self, clock: &impl Clock) -> Vec<String> {
+        let now = clock.now();
+        let retention = self.config.dead_letter.unwrap_or_default();
+        self.items
+            .iter()
+            .filter(|item| match &item.state {
+                ItemState::Dead { since } => now.saturating_duration_since(*since) >= retention,
+                _ => false,
+            })
+            .map(|item| item.id.clone())
+            .collect()
+    }
+
+    /// The next time a claim expires or a dead item becomes purgeable
+    pub fn next_deadline(&self) -> Option<Instant> {
+        let retention = self.config.dead_letter.unwrap_or_default();
+        self.items
+            .iter()
+            .filter_map(|item| match &item.state {
+                ItemState::Pending => None,
+                ItemState::Claimed { claimed_at, .. } => {
+                    Some(*claimed_at + self.config.visibility_timeout)
+                }
+                ItemState::Dead { since } => Some(*since + retention),
+            })
+            .min()
+    }
+
+    /// Get an item by ID
+    pub fn get(&self, id: &str) -> Option<&QueueItem> {
+        self.items.iter().find(|item| item.id == id)
+    }
+
+    fn get_mut(&mut self, id: &str) -> Option<&mut QueueItem> {
+        self.items.iter_mut().find(|item| item.id == id)
+    }
+
+    /// Number of items waiting to be claimed
+    pub fn pending_count(&self) -> usize {
+        self.items.iter().filter(|item| item.is_pending()).count()
+    }
+
+    /// Items in the dead letter queue
+    pub fn dead(&self) -> impl Iterator<Item = &QueueItem> {
+        self.items.iter().filter(|item| item.is_dead())
+    }
+}
+
+#[cfg(test)]
+#[path = "queue_tests.rs"]
+mod tests;
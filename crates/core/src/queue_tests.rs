@@ -0,0 +1,168 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+use crate::FakeClock;
+
+fn test_queue(order: QueueOrder) -> Queue {
+    Queue::new(
+        "bugs".to_string(),
+        QueueConfig {
+            visibility_timeout: Duration::from_secs(60),
+            max_retries: 1,
+            dead_letter: Some(Duration::from_secs(3600)),
+            order,
+        },
+    )
+}
+
+fn push(queue: &mut Queue, id: &str, priority: i64, clock: &FakeClock) {
+    assert!(queue.push(id, HashMap::new(), priority, clock.now()));
+    clock.advance(Duration::from_secs(1));
+}
+
+#[test]
+fn priority_order_claims_lowest_value_first() {
+    let clock = FakeClock::new();
+    let mut queue = test_queue(QueueOrder::Priority);
+    push(&mut queue, "low", 3, &clock);
+    push(&mut queue, "urgent", 0, &clock);
+    push(&mut queue, "urgent-later", 0, &clock);
+
+    assert_eq!(queue.next_pending().unwrap().id, "urgent");
+    assert!(queue.claim("urgent", "worker-1", clock.now()));
+    assert_eq!(queue.next_pending().unwrap().id, "urgent-later");
+}
+
+#[test]
+fn created_at_order_ignores_priority() {
+    let clock = FakeClock::new();
+    let mut queue = test_queue(QueueOrder::CreatedAt);
+    push(&mut queue, "first", 5, &clock);
+    push(&mut queue, "second", 0, &clock);
+
+    assert_eq!(queue.next_pending().unwrap().id, "first");
+}
+
+#[test]
+fn duplicate_push_is_ignored() {
+    let clock = FakeClock::new();
+    let mut queue = test_queue(QueueOrder::Priority);
+    push(&mut queue, "bug-1", 0, &clock);
+
+    assert!(!queue.push("bug-1", HashMap::new(), 0, clock.now()));
+    assert_eq!(queue.items.len(), 1);
+}
+
+#[test]
+fn claimed_item_is_hidden_until_completed() {
+    let clock = FakeClock::new();
+    let mut queue = test_queue(QueueOrder::Priority);
+    push(&mut queue, "bug-1", 0, &clock);
+
+    assert!(queue.claim("bug-1", "worker-1", clock.now()));
+    assert!(!queue.claim("bug-1", "worker-2", clock.now()));
+    assert!(queue.next_pending().is_none());
+    assert_eq!(queue.get("bug-1").unwrap().holder(), Some("worker-1"));
+
+    assert!(queue.complete("bug-1"));
+    assert!(queue.items.is_empty());
+}
+
+#[test]
+fn failures_exhaust_after_max_retries() {
+    let clock = FakeClock::new();
+    let mut queue = test_queue(QueueOrder::Priority);
+    push(&mut queue, "bug-1", 0, &clock);
+
+    queue.claim("bug-1", "worker-1", clock.now());
+    assert!(!queue.is_exhausted("bug-1"));
+    assert!(queue.requeue("bug-1", "tests failed"));
+    assert_eq!(
+        queue.get("bug-1").unwrap().error.as_deref(),
+        Some("tests failed")
+    );
+
+    queue.claim("bug-1", "worker-1", clock.now());
+    assert!(queue.is_exhausted("bug-1"));
+}
+
+#[test]
+fn release_does_not_count_attempt() {
+    let clock = FakeClock::new();
+    let mut queue = test_queue(QueueOrder::Priority);
+    push(&mut queue, "bug-1", 0, &clock);
+
+    queue.claim("bug-1", "worker-1", clock.now());
+    assert!(queue.release("bug-1"));
+    assert_eq!(queue.get("bug-1").unwrap().attempts, 0);
+    assert!(queue.get("bug-1").unwrap().is_pending());
+}
+
+#[test]
+fn visibility_timeout_expires_claims() {
+    let clock = FakeClock::new();
+    let mut queue = test_queue(QueueOrder::Priority);
+    push(&mut queue, "bug-1", 0, &clock);
+    queue.claim("bug-1", "worker-1", clock.now());
+    let deadline = queue.next_deadline().unwrap();
+
+    clock.advance(Duration::from_secs(59));
+    assert!(queue.expired(&clock).is_empty());
+
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(clock.now(), deadline);
+    assert_eq!(queue.expired(&clock), vec!["bug-1".to_string()]);
+}
+
+#[test]
+fn extending_a_claim_restarts_its_timeout() {
+    let clock = FakeClock::new();
+    let mut queue = test_queue(QueueOrder::Priority);
+    push(&mut queue, "bug-1", 0, &clock);
+    queue.claim("bug-1", "worker-1", clock.now());
+
+    clock.advance(Duration::from_secs(45));
+    assert!(!queue.extend("bug-1", "worker-2", clock.now()));
+    assert!(queue.extend("bug-1", "worker-1", clock.now()));
+
+    clock.advance(Duration::from_secs(45));
+    assert!(queue.expired(&clock).is_empty());
+    assert_eq!(queue.get("bug-1").unwrap().attempts, 1);
+}
+
+#[test]
+fn dead_items_purged_after_retention() {
+    let clock = FakeClock::new();
+    let mut queue = test_queue(QueueOrder::Priority);
+    push(&mut queue, "bug-1", 0, &clock);
+    queue.claim("bug-1", "worker-1", clock.now());
+
+    assert!(queue.bury("bug-1", "exhausted", clock.now()));
+    assert_eq!(queue.dead().count(), 1);
+    assert!(queue.next_pending().is_none());
+
+    clock.advance(Duration::from_secs(3599));
+    assert!(queue.purgeable(&clock).is_empty());
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(queue.purgeable(&clock), vec!["bug-1".to_string()]);
+}
+
+#[test]
+fn finished_items_are_not_pushed_again() {
+    let clock = FakeClock::new();
+    let mut queue = test_queue(QueueOrder::Priority);
+    push(&mut queue, "bug-1", 0, &clock);
+    push(&mut queue, "bug-2", 0, &clock);
+    queue.claim("bug-1", "worker-1", clock.now());
+    assert!(queue.complete("bug-1"));
+    queue.claim("bug-2", "worker-1", clock.now());
+    assert!(queue.bury("bug-2", "exhausted", clock.now()));
+
+    // Dead items are still in the queue, purged ones are remembered
+    assert!(!queue.push("bug-2", HashMap::new(), 0, clock.now()));
+    assert!(queue.remove("bug-2"));
+    assert!(!queue.push("bug-1", HashMap::new(), 0, clock.now()));
+    assert!(!queue.push("bug-2", HashMap::new(), 0, clock.now()));
+    assert!(queue.items.is_empty());
+}
@@ -0,0 +1,169 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Semaphore state machine for limiting concurrent holders
+
+use crate::clock::Clock;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Semaphore configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SemaphoreConfig {
+    /// Total slots available
+    pub capacity: u32,
+    /// How long a holder may go without heartbeating before its slots are reclaimed
+    pub heartbeat_timeout: Duration,
+}
+
+impl Default for SemaphoreConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 1,
+            heartbeat_timeout: Duration::from_secs(2 * 60 * 60),
+        }
+    }
+}
+
+/// A holder of one or more semaphore slots
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SemaphoreHolder {
+    pub id: String,
+    #[serde(with = "crate::clock::instant_serde")]
+    pub acquired_at: Instant,
+    #[serde(with = "crate::clock::instant_serde")]
+    pub last_heartbeat: Instant,
+    /// Number of slots held
+    pub weight: u32,
+}
+
+/// Result of a semaphore operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SemaphoreResult {
+    /// Slots were granted
+    Acquired,
+    /// The holder already had enough slots; heartbeat refreshed
+    AlreadyHeld,
+    /// Not enough free slots
+    Full { available: u32 },
+    /// The holder's slots were returned
+    Released,
+    /// The holder had no slots to release
+    NotHolder,
+}
+
+/// A counting semaphore with weighted holders and stale reclaim
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Semaphore {
+    pub name: String,
+    pub holders: Vec<SemaphoreHolder>,
+    pub config: SemaphoreConfig,
+}
+
+impl Semaphore {
+    /// Create a new semaphore with no holders
+    pub fn new(name: String, config: SemaphoreConfig) -> Self {
+        Self {
+            name,
+            holders: Vec::new(),
+            config,
+        }
+    }
+
+    /// Try to acquire `weight` slots for `holder`
+    ///
+    /// Re-acquiring refreshes the heartbeat without consuming more slots,
+    /// unless a larger weight is requested.
+    pub fn acquire(&mut self, holder: &str, weight: u32, clock: &impl Clock) -> SemaphoreResult {
+        let now = clock.now();
+        let available = self.available();
+
+        if let Some(existing) = self.holders.iter_mut().find(|h| h.id == holder) {
+            if existing.weight >= weight {
+                existing.last_heartbeat = now;
+                return SemaphoreResult::AlreadyHeld;
+            }
+            let extra = weight - existing.weight;
+            if extra > available {
+                return SemaphoreResult::Full { available };
+            }
+            existing.weight = weight;
+            existing.last_heartbeat = now;
+            return SemaphoreResult::Acquired;
+        }
+
+        if weight > available {
+            return SemaphoreResult::Full { available };
+        }
+        self.grant(holder, weight, now);
+        SemaphoreResult::Acquired
+    }
+
+    /// Release all slots held by `holder`
+    pub fn release(&mut self, holder: &str) -> SemaphoreResult {
+        let before = self.holders.len();
+        self.holders.retain(|h| h.id != holder);
+        if self.holders.len() < before {
+            SemaphoreResult::Released
+        } else {
+            SemaphoreResult::NotHolder
+        }
+    }
+
+    /// Refresh the heartbeat for `holder`
+    ///
+    /// Returns false if `holder` has no slots.
+    pub fn heartbeat(&mut self, holder: &str, clock: &impl Clock) -> bool {
+        match self.holders.iter_mut().find(|h| h.id == holder) {
+            Some(h) => {
+                h.last_heartbeat = clock.now();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove holders that stopped heartbeating, returning their IDs
+    pub fn reclaim_stale(&mut self, clock: &impl Clock) -> Vec<String> {
+        let now = clock.now();
+        let timeout = self.config.heartbeat_timeout;
+        let (stale, fresh): (Vec<_>, Vec<_>) = self
+            .holders
+            .drain(..)
+            .partition(|h| now.saturating_duration_since(h.last_heartbeat) > timeout);
+        self.holders = fresh;
+        stale.into_iter().map(|h| h.id).collect()
+    }
+
+    /// Record `weight` slots held by `holder` as of `now`, without a capacity check
+    ///
+    /// Used when replaying an acquisition that was already decided.
+    pub fn grant(&mut self, holder: &str, weight: u32, now: Instant) {
+        self.holders.retain(|h| h.id != holder);
+        self.holders.push(SemaphoreHolder {
+            id: holder.to_string(),
+            acquired_at: now,
+            last_heartbeat: now,
+            weight,
+        });
+    }
+
+    /// Number of slots currently held
+    pub fn used(&self) -> u32 {
+        self.holders.iter().map(|h| h.weight).sum()
+    }
+
+    /// Number of free slots
+    pub fn available(&self) -> u32 {
+        self.config.capacity.saturating_sub(self.used())
+    }
+
+    /// Check if `holder` holds any slots
+    pub fn is_held_by(&self, holder: &str) -> bool {
+        self.holders.iter().any(|h| h.id == holder)
+    }
+}
+
+#[cfg(test)]
+#[path = "semaphore_tests.rs"]
+mod tests;
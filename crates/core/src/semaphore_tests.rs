@@ -0,0 +1,97 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+use crate::FakeClock;
+
+fn test_semaphore(capacity: u32) -> Semaphore {
+    Semaphore::new(
+        "agents".to_string(),
+        SemaphoreConfig {
+            capacity,
+            heartbeat_timeout: Duration::from_secs(60),
+        },
+    )
+}
+
+#[test]
+fn acquire_until_full() {
+    let clock = FakeClock::new();
+    let mut sem = test_semaphore(2);
+
+    assert_eq!(sem.acquire("pipe-1", 1, &clock), SemaphoreResult::Acquired);
+    assert_eq!(sem.acquire("pipe-2", 1, &clock), SemaphoreResult::Acquired);
+    assert_eq!(
+        sem.acquire("pipe-3", 1, &clock),
+        SemaphoreResult::Full { available: 0 }
+    );
+    assert_eq!(sem.used(), 2);
+}
+
+#[test]
+fn weighted_holders_consume_multiple_slots() {
+    let clock = FakeClock::new();
+    let mut sem = test_semaphore(4);
+
+    assert_eq!(sem.acquire("big", 3, &clock), SemaphoreResult::Acquired);
+    assert_eq!(sem.available(), 1);
+    assert_eq!(
+        sem.acquire("other", 2, &clock),
+        SemaphoreResult::Full { available: 1 }
+    );
+    assert_eq!(sem.acquire("small", 1, &clock), SemaphoreResult::Acquired);
+}
+
+#[test]
+fn reacquire_refreshes_without_consuming() {
+    let clock = FakeClock::new();
+    let mut sem = test_semaphore(2);
+    sem.acquire("pipe-1", 1, &clock);
+
+    clock.advance(Duration::from_secs(50));
+    assert_eq!(
+        sem.acquire("pipe-1", 1, &clock),
+        SemaphoreResult::AlreadyHeld
+    );
+    assert_eq!(sem.used(), 1);
+
+    clock.advance(Duration::from_secs(50));
+    assert!(sem.reclaim_stale(&clock).is_empty());
+}
+
+#[test]
+fn reacquire_with_larger_weight_grows_holding() {
+    let clock = FakeClock::new();
+    let mut sem = test_semaphore(3);
+    sem.acquire("pipe-1", 1, &clock);
+
+    assert_eq!(sem.acquire("pipe-1", 2, &clock), SemaphoreResult::Acquired);
+    assert_eq!(sem.used(), 2);
+    assert_eq!(sem.holders.len(), 1);
+}
+
+#[test]
+fn release_frees_slots() {
+    let clock = FakeClock::new();
+    let mut sem = test_semaphore(1);
+    sem.acquire("pipe-1", 1, &clock);
+
+    assert_eq!(sem.release("pipe-2"), SemaphoreResult::NotHolder);
+    assert_eq!(sem.release("pipe-1"), SemaphoreResult::Released);
+    assert_eq!(sem.available(), 1);
+}
+
+#[test]
+fn stale_holders_are_reclaimed() {
+    let clock = FakeClock::new();
+    let mut sem = test_semaphore(2);
+    sem.acquire("stale", 1, &clock);
+    clock.advance(Duration::from_secs(45));
+    sem.acquire("fresh", 1, &clock);
+
+    clock.advance(Duration::from_secs(30));
+    assert!(sem.heartbeat("fresh", &clock));
+    assert_eq!(sem.reclaim_stale(&clock), vec!["stale".to_string()]);
+    assert!(sem.is_held_by("fresh"));
+    assert_eq!(sem.available(), 1);
+}
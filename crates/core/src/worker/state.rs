@@ -5,6 +5,7 @@
 
 use crate::clock::Clock;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::time::Instant;
 
 /// Worker status
@@ -23,8 +24,10 @@ pub enum WorkerStatus {
 pub struct Worker {
     pub name: String,
     pub status: WorkerStatus,
-    pub current_pipeline: Option<String>,
-    #[serde(skip, default = "Instant::now")]
+    /// Pipelines in flight, keyed by pipeline ID, with the queue item each one handles
+    #[serde(default)]
+    pub pipelines: BTreeMap<String, String>,
+    #[serde(with = "crate::clock::instant_serde", default = "Instant::now")]
     pub last_active: Instant,
 }
 
@@ -34,41 +37,64 @@ impl Worker {
         Self {
             name,
             status: WorkerStatus::Stopped,
-            current_pipeline: None,
+            pipelines: BTreeMap::new(),
             last_active: clock.now(),
         }
     }
 
     /// Start the worker
     pub fn start(&mut self, clock: &impl Clock) {
-        self.status = WorkerStatus::Idle;
+        self.status = if self.pipelines.is_empty() {
+            WorkerStatus::Idle
+        } else {
+            WorkerStatus::Processing
+        };
         self.last_active = clock.now();
     }
 
     /// Stop the worker
+    ///
+    /// Pipelines already in flight keep running and are still tracked, so
+    /// their queue items are settled when they finish.
     pub fn stop(&mut self) {
         self.status = WorkerStatus::Stopped;
-        self.current_pipeline = None;
     }
 
-    /// Begin processing a pipeline
-    pub fn begin_processing(&mut self, pipeline_id: String, clock: &impl Clock) {
-        self.status = WorkerStatus::Processing;
-        self.current_pipeline = Some(pipeline_id);
+    /// Begin processing a pipeline for a queue item
+    pub fn begin_processing(&mut self, pipeline_id: String, item_id: String, clock: &impl Clock) {
+        self.pipelines.insert(pipeline_id, item_id);
+        if self.is_running() {
+            self.status = WorkerStatus::Processing;
+        }
         self.last_active = clock.now();
     }
 
-    /// Finish processing the current pipeline
-    pub fn finish_processing(&mut self, clock: &impl Clock) {
-        self.status = WorkerStatus::Idle;
-        self.current_pipeline = None;
+    /// Finish processing a pipeline, returning the queue item it handled
+    ///
+    /// The worker goes back to Idle once nothing is in flight.
+    pub fn finish_processing(&mut self, pipeline_id: &str, clock: &impl Clock) -> Option<String> {
+        let item_id = self.pipelines.remove(pipeline_id)?;
+        if self.is_running() && self.pipelines.is_empty() {
+            self.status = WorkerStatus::Idle;
+        }
         self.last_active = clock.now();
+        Some(item_id)
+    }
+
+    /// Check if the worker is running (not stopped)
+    pub fn is_running(&self) -> bool {
+        self.status != WorkerStatus::Stopped
     }
 
     /// Check if the worker is available to process work
     pub fn is_available(&self) -> bool {
         self.status == WorkerStatus::Idle
     }
+
+    /// Check if the worker can take on another pipeline under `concurrency`
+    pub fn has_capacity(&self, concurrency: u32) -> bool {
+        self.is_running() && self.pipelines.len() < concurrency as usize
+    }
 }
 
 #[cfg(test)]
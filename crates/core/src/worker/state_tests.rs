@@ -16,16 +16,53 @@ fn worker_lifecycle() {
     assert_eq!(worker.status, WorkerStatus::Idle);
     assert!(worker.is_available());
 
-    worker.begin_processing("pipe-1".to_string(), &clock);
+    worker.begin_processing("pipe-1".to_string(), "bug-1".to_string(), &clock);
     assert_eq!(worker.status, WorkerStatus::Processing);
-    assert_eq!(worker.current_pipeline, Some("pipe-1".to_string()));
+    assert_eq!(worker.pipelines.get("pipe-1"), Some(&"bug-1".to_string()));
     assert!(!worker.is_available());
 
-    worker.finish_processing(&clock);
+    assert_eq!(
+        worker.finish_processing("pipe-1", &clock),
+        Some("bug-1".to_string())
+    );
     assert_eq!(worker.status, WorkerStatus::Idle);
-    assert!(worker.current_pipeline.is_none());
+    assert!(worker.pipelines.is_empty());
     assert!(worker.is_available());
 
     worker.stop();
     assert_eq!(worker.status, WorkerStatus::Stopped);
 }
+
+#[test]
+fn worker_capacity_follows_concurrency() {
+    let clock = FakeClock::new();
+    let mut worker = Worker::new("builds".to_string(), &clock);
+    assert!(!worker.has_capacity(2));
+
+    worker.start(&clock);
+    worker.begin_processing("pipe-1".to_string(), "bug-1".to_string(), &clock);
+    assert!(worker.has_capacity(2));
+    worker.begin_processing("pipe-2".to_string(), "bug-2".to_string(), &clock);
+    assert!(!worker.has_capacity(2));
+
+    // Finishing one pipeline keeps the worker processing the other
+    worker.finish_processing("pipe-1", &clock);
+    assert_eq!(worker.status, WorkerStatus::Processing);
+    assert!(worker.has_capacity(2));
+}
+
+#[test]
+fn stopped_worker_still_tracks_in_flight_pipelines() {
+    let clock = FakeClock::new();
+    let mut worker = Worker::new("builds".to_string(), &clock);
+    worker.start(&clock);
+    worker.begin_processing("pipe-1".to_string(), "bug-1".to_string(), &clock);
+
+    worker.stop();
+    assert!(!worker.has_capacity(2));
+    assert_eq!(
+        worker.finish_processing("pipe-1", &clock),
+        Some("bug-1".to_string())
+    );
+    assert_eq!(worker.status, WorkerStatus::Stopped);
+}
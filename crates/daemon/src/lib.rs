@@ -13,6 +13,6 @@
 pub mod protocol;
 
 pub use protocol::{
-    PipelineDetail, PipelineSummary, Query, Request, Response, SessionSummary, DEFAULT_TIMEOUT,
-    MAX_MESSAGE_SIZE, PROTOCOL_VERSION,
+    CronSummary, LogChunk, PhaseEntry, PipelineChild, PipelineDetail, PipelineSummary, Query,
+    Request, Response, SessionSummary, DEFAULT_TIMEOUT, MAX_MESSAGE_SIZE, PROTOCOL_VERSION,
 };
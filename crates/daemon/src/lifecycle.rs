@@ -13,16 +13,25 @@ use oj_adapters::session::SessionAdapter;
 use oj_adapters::{
     GitAdapter, NoOpNotifyAdapter, TmuxAdapter, TracedRepoAdapter, TracedSessionAdapter,
 };
-use oj_core::{Event, SystemClock, UuidIdGen};
-use oj_engine::{Runtime, RuntimeConfig, RuntimeDeps, Scheduler};
+use oj_core::{Event, Operation, SystemClock, UuidIdGen};
+use oj_engine::{
+    ExecuteError, Runtime, RuntimeConfig, RuntimeDeps, RuntimeError, Scheduler,
+    DEFAULT_COMMAND_TIMEOUT,
+};
 use oj_runbook::{parse_runbook, Runbook};
-use oj_storage::{MaterializedState, Wal};
+use oj_storage::{MaterializedState, SnapshotStore, Wal};
 use sha2::{Digest, Sha256};
 use thiserror::Error;
 use tokio::net::UnixListener;
 use tokio::sync::mpsc;
 use tracing::{info, warn};
 
+/// WAL entries written between snapshots
+const SNAPSHOT_INTERVAL: u64 = 1000;
+
+/// Snapshots kept after compaction
+const SNAPSHOTS_KEPT: usize = 3;
+
 /// Daemon runtime with concrete adapter types (wrapped with tracing)
 pub type DaemonRuntime = Runtime<
     TracedSessionAdapter<TmuxAdapter>,
@@ -47,8 +56,12 @@ pub struct Config {
     pub log_path: PathBuf,
     /// Path to WAL directory
     pub wal_path: PathBuf,
+    /// Path to state snapshot directory
+    pub snapshot_path: PathBuf,
     /// Path to workspaces directory
     pub workspaces_path: PathBuf,
+    /// Path to per-pipeline log directory
+    pub pipeline_logs_path: PathBuf,
 }
 
 impl Config {
@@ -69,7 +82,9 @@ impl Config {
             version_path: state_dir.join("daemon.version"),
             log_path: state_dir.join("daemon.log"),
             wal_path: state_dir.join("wal").join("events.wal"),
+            snapshot_path: state_dir.join("wal").join("snapshots"),
             workspaces_path: state_dir.join("workspaces"),
+            pipeline_logs_path: state_dir.join("logs"),
         })
     }
 }
@@ -85,15 +100,18 @@ pub struct DaemonState {
     pub listener: UnixListener,
     /// Materialized state (shared with runtime)
     pub state: Arc<Mutex<MaterializedState>>,
+    /// Write-ahead log (shared with runtime)
+    wal: Arc<Mutex<Wal>>,
+    /// State snapshots used to compact the WAL
+    snapshots: SnapshotStore,
+    /// WAL sequence of the latest snapshot's `SnapshotTaken` marker
+    snapshot_marker: u64,
     /// Runtime for event processing
     pub runtime: DaemonRuntime,
     /// Scheduler for timers (shared with runtime)
     pub scheduler: Arc<Mutex<Scheduler>>,
     /// Channel for internal events
     pub internal_events: mpsc::Receiver<Event>,
-    // KEEP UNTIL: adapter event emission is implemented
-    #[allow(dead_code)]
-    internal_tx: mpsc::Sender<Event>,
     /// When daemon started
     pub start_time: Instant,
     /// Shutdown requested flag
@@ -106,7 +124,12 @@ impl DaemonState {
     /// Any events produced by the runtime (e.g., ShellCompleted) are fed back
     /// into the event loop iteratively.
     pub async fn process_event(&mut self, event: Event) -> Result<(), LifecycleError> {
-        let mut pending_events = vec![event];
+        self.process_events(vec![event]).await
+    }
+
+    /// Process events produced outside the event loop (e.g., by session checks)
+    pub async fn process_events(&mut self, events: Vec<Event>) -> Result<(), LifecycleError> {
+        let mut pending_events = events;
 
         while let Some(event) = pending_events.pop() {
             let result_events = self
@@ -135,10 +158,44 @@ impl DaemonState {
         Ok(())
     }
 
+    /// Snapshot state and compact the WAL once enough entries have built up
+    pub fn maybe_snapshot(&mut self) {
+        let sequence = self
+            .wal
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .sequence();
+        if sequence >= self.snapshot_marker + SNAPSHOT_INTERVAL {
+            self.snapshot();
+        }
+    }
+
+    /// Snapshot state and compact the WAL
+    ///
+    /// Failures are logged: the WAL alone still holds everything needed.
+    fn snapshot(&mut self) {
+        let mut wal = self.wal.lock().unwrap_or_else(|e| e.into_inner());
+        // Nothing has been written since the last snapshot's marker
+        if wal.sequence() == self.snapshot_marker {
+            return;
+        }
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        match oj_storage::checkpoint(&mut wal, &state, &self.snapshots, SNAPSHOTS_KEPT) {
+            Ok(sequence) => {
+                info!("Snapshot taken at WAL sequence {}", sequence);
+                self.snapshot_marker = wal.sequence();
+            }
+            Err(e) => warn!("Failed to snapshot state: {}", e),
+        }
+    }
+
     /// Shutdown the daemon gracefully
     pub async fn shutdown(&mut self) -> Result<(), LifecycleError> {
         info!("Shutting down daemon...");
 
+        // 0. Snapshot so the next start replays as little as possible
+        self.snapshot();
+
         // 1. Stop accepting connections (listener dropped when DaemonState dropped)
         // Note: we don't drop the listener here to keep accepting until the very end
 
@@ -244,11 +301,26 @@ async fn startup_inner(config: &Config) -> Result<DaemonState, LifecycleError> {
     // 4. Load runbook BEFORE binding socket (fail fast, don't accept connections if invalid)
     let runbook = load_runbook(&config.project_root)?;
 
-    // 5. Load state from WAL
+    // 5. Load state from the latest snapshot plus the WAL entries after it
+    let snapshots = SnapshotStore::new(&config.snapshot_path);
+    let (mut state, snapshot_sequence) = load_snapshot(&snapshots);
     let wal = Wal::open(&config.wal_path)?;
-    let mut state = MaterializedState::default();
-    for op in Wal::replay(&config.wal_path)? {
-        state.apply(&op);
+    if let Some(truncated) = wal.truncated() {
+        warn!(
+            "Truncated torn WAL entry at line {} of {}: {}",
+            truncated.line,
+            config.wal_path.display(),
+            truncated.reason
+        );
+    }
+    let mut snapshot_marker = snapshot_sequence;
+    for record in Wal::replay_after(&config.wal_path, snapshot_sequence)? {
+        if let Operation::SnapshotTaken { sequence } = record.operation {
+            if sequence == snapshot_sequence {
+                snapshot_marker = record.sequence;
+            }
+        }
+        state.apply_at(&record.operation, record.epoch_ms);
     }
 
     info!(
@@ -258,9 +330,6 @@ async fn startup_inner(config: &Config) -> Result<DaemonState, LifecycleError> {
         state.workspaces.len()
     );
 
-    // 6. Reconcile with reality (MVP: log warnings only)
-    reconcile_state(&state, &config.project_root).await;
-
     // 7. Set up adapters (wrapped with tracing for observability)
     let session_adapter = TracedSessionAdapter::new(TmuxAdapter::new());
     let repo_adapter = TracedRepoAdapter::new(GitAdapter::new(config.project_root.clone()));
@@ -279,7 +348,7 @@ async fn startup_inner(config: &Config) -> Result<DaemonState, LifecycleError> {
     // 11. Wrap state and WAL in Arc<Mutex>
     let state = Arc::new(Mutex::new(state));
     let wal = Arc::new(Mutex::new(wal));
-    let scheduler = Arc::new(Mutex::new(Scheduler::new()));
+    let wal_handle = Arc::clone(&wal);
 
     // 12. Create runtime
     let runtime = Runtime::new(
@@ -289,6 +358,7 @@ async fn startup_inner(config: &Config) -> Result<DaemonState, LifecycleError> {
             notify: notify_adapter,
             wal,
             state: Arc::clone(&state),
+            events: Some(internal_tx),
         },
         runbook,
         SystemClock,
@@ -296,9 +366,26 @@ async fn startup_inner(config: &Config) -> Result<DaemonState, LifecycleError> {
         RuntimeConfig {
             project_root: config.project_root.clone(),
             worktree_root: config.workspaces_path.clone(),
+            log_root: config.pipeline_logs_path.clone(),
+            command_timeout: DEFAULT_COMMAND_TIMEOUT,
         },
     );
 
+    // Timers set by effects live in the runtime's scheduler
+    let scheduler = runtime.scheduler();
+
+    // Poll queue sources on the first tick
+    startup_step("start queue sources", runtime.start_queue_sources().await)?;
+
+    // Enabled crons keep their schedule across restarts
+    startup_step("start crons", runtime.start_crons().await)?;
+
+    // Workers that were running before the restart pick up where they left off
+    startup_step("resume workers", runtime.resume_workers().await)?;
+
+    // Reconcile pipelines, sessions and workspaces with what survived the restart
+    startup_step("resume pipelines", runtime.resume_pipelines().await)?;
+
     info!(
         "Daemon started for project: {}",
         config.project_root.display()
@@ -309,15 +396,52 @@ async fn startup_inner(config: &Config) -> Result<DaemonState, LifecycleError> {
         lock_file,
         listener,
         state,
+        wal: wal_handle,
+        snapshots,
+        snapshot_marker,
         runtime,
         scheduler,
         internal_events,
-        internal_tx,
         start_time: Instant::now(),
         shutdown_requested: false,
     })
 }
 
+/// Load the newest readable snapshot, falling back to older ones
+///
+/// Returns empty state at sequence 0 when there is none.
+fn load_snapshot(snapshots: &SnapshotStore) -> (MaterializedState, u64) {
+    let sequences = match snapshots.sequences() {
+        Ok(sequences) => sequences,
+        Err(e) => {
+            warn!("Failed to list snapshots: {}", e);
+            return (MaterializedState::default(), 0);
+        }
+    };
+    for sequence in sequences.into_iter().rev() {
+        match snapshots.load(sequence) {
+            Ok(snapshot) => {
+                info!("Loaded snapshot at WAL sequence {}", sequence);
+                return (snapshot.state, snapshot.sequence);
+            }
+            Err(e) => warn!("Skipping unreadable snapshot {}: {}", sequence, e),
+        }
+    }
+    (MaterializedState::default(), 0)
+}
+
+/// Log a failed startup step and carry on, unless the WAL could not be written
+fn startup_step(step: &str, result: Result<(), RuntimeError>) -> Result<(), LifecycleError> {
+    match result {
+        Ok(()) => Ok(()),
+        Err(RuntimeError::Execute(ExecuteError::Storage(e))) => Err(LifecycleError::Wal(e)),
+        Err(e) => {
+            warn!("Failed to {}: {}", step, e);
+            Ok(())
+        }
+    }
+}
+
 /// Clean up resources on startup failure
 fn cleanup_on_failure(config: &Config) {
     // Remove socket if we created it
@@ -364,36 +488,6 @@ fn load_runbook(project_root: &Path) -> Result<Runbook, LifecycleError> {
     Ok(parse_runbook(&combined_content)?)
 }
 
-/// Reconcile persisted state with actual world state
-async fn reconcile_state(state: &MaterializedState, _project_root: &Path) {
-    // MVP: Just log warnings about state that may need attention
-
-    // Check for in-progress pipelines
-    let in_progress: Vec<_> = state
-        .pipelines
-        .values()
-        .filter(|p| !p.is_terminal())
-        .collect();
-
-    if !in_progress.is_empty() {
-        warn!(
-            "Found {} in-progress pipelines from previous session (manual intervention may be needed)",
-            in_progress.len()
-        );
-        for p in &in_progress {
-            warn!("  - {} ({}): {}", p.id, p.name, p.phase);
-        }
-    }
-
-    // Check for orphaned sessions
-    if !state.sessions.is_empty() {
-        warn!(
-            "Found {} sessions from previous session (may be stale)",
-            state.sessions.len()
-        );
-    }
-}
-
 /// Get the state directory for oj
 fn state_dir() -> Result<PathBuf, LifecycleError> {
     // Use XDG_STATE_HOME or default to ~/.local/state
@@ -101,31 +101,41 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 if let Err(e) = daemon.check_heartbeats().await {
                     error!("Error checking heartbeats: {}", e);
                 }
+                daemon.maybe_snapshot();
 
                 // Periodic session checks (every 10 seconds)
                 if last_session_check.elapsed() >= session_check_interval {
                     last_session_check = Instant::now();
 
                     let session_events = {
-                        let state = daemon.state.lock().unwrap_or_else(|e| e.into_inner());
+                        // Snapshot state so the lock isn't held across tmux calls
+                        let state = daemon.state.lock().unwrap_or_else(|e| e.into_inner()).clone();
                         let sessions = oj_adapters::TmuxAdapter::new();
                         check_sessions(&sessions, &state).await
                     };
 
                     for event in session_events {
-                        match event {
+                        let (pipeline_id, result) = match event {
                             SessionEvent::TmuxExited { pipeline_id } => {
                                 // tmux died - session is gone
-                                if let Err(e) = daemon.runtime.handle_tmux_exited(&pipeline_id).await {
-                                    error!("Error handling tmux exit for {}: {}", pipeline_id, e);
-                                }
+                                let result = daemon.runtime.handle_tmux_exited(&pipeline_id).await;
+                                (pipeline_id, result)
                             }
                             SessionEvent::ClaudeExited { pipeline_id } => {
                                 // claude exited - trigger on_exit
-                                if let Err(e) = daemon.runtime.handle_claude_exited(&pipeline_id).await {
-                                    error!("Error handling claude exit for {}: {}", pipeline_id, e);
+                                let result = daemon.runtime.handle_claude_exited(&pipeline_id).await;
+                                (pipeline_id, result)
+                            }
+                        };
+                        match result {
+                            Ok(result_events) => {
+                                if let Err(e) = daemon.process_events(result_events).await {
+                                    error!("Error processing session exit for {}: {}", pipeline_id, e);
                                 }
                             }
+                            Err(e) => {
+                                error!("Error handling session exit for {}: {}", pipeline_id, e);
+                            }
                         }
                     }
                 }
@@ -8,7 +8,7 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-use oj_core::Event;
+use oj_core::{Event, PipelineParent};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use thiserror::Error;
 
@@ -49,8 +49,20 @@ pub enum Request {
 #[serde(tag = "type")]
 pub enum Query {
     ListPipelines,
-    GetPipeline { id: String },
+    GetPipeline {
+        id: String,
+    },
+    PipelineHistory {
+        id: String,
+    },
+    /// Log lines written since byte `offset`, optionally only those of one phase
+    PipelineLogs {
+        id: String,
+        phase: Option<String>,
+        offset: u64,
+    },
     ListSessions,
+    ListCrons,
 }
 
 /// Response from daemon to CLI
@@ -80,9 +92,18 @@ pub enum Response {
         pipeline: Option<Box<PipelineDetail>>,
     },
 
+    /// Phase history of a pipeline, oldest first
+    PipelineHistory { history: Option<Vec<PhaseEntry>> },
+
+    /// Part of a pipeline's log
+    PipelineLogs { logs: Option<LogChunk> },
+
     /// List of sessions
     Sessions { sessions: Vec<SessionSummary> },
 
+    /// List of runbook crons
+    Crons { crons: Vec<CronSummary> },
+
     /// Daemon status
     Status {
         uptime_secs: u64,
@@ -113,9 +134,61 @@ pub struct PipelineDetail {
     pub phase: String,
     pub phase_status: String,
     pub inputs: HashMap<String, String>,
+    /// Values published by finished phases
+    #[serde(default)]
+    pub outputs: HashMap<String, String>,
     pub workspace_path: Option<PathBuf>,
     pub session_id: Option<String>,
     pub error: Option<String>,
+    /// Unix epoch milliseconds, or 0 if unknown
+    pub created_at_ms: u64,
+    pub updated_at_ms: u64,
+    pub phase_started_at_ms: u64,
+    /// The pipeline and phase that started this one, for child pipelines
+    #[serde(default)]
+    pub parent: Option<PipelineParent>,
+    /// Pipelines started by this one's phases, oldest first
+    #[serde(default)]
+    pub children: Vec<PipelineChild>,
+}
+
+/// A child pipeline, with its own children
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PipelineChild {
+    pub id: String,
+    pub name: String,
+    pub kind: String,
+    pub phase: String,
+    pub phase_status: String,
+    /// Phase of the parent that started it
+    pub parent_phase: String,
+    #[serde(default)]
+    pub children: Vec<PipelineChild>,
+}
+
+/// One phase visited by a pipeline
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PhaseEntry {
+    pub phase: String,
+    pub status: String,
+    /// How the phase was entered (`start`, `next`, `on_fail` or `failed`)
+    pub trigger: String,
+    /// Unix epoch milliseconds
+    pub started_at_ms: u64,
+    /// Unix epoch milliseconds, `None` for the current phase
+    pub ended_at_ms: Option<u64>,
+    pub exit_code: Option<i32>,
+    pub error: Option<String>,
+}
+
+/// Lines read from a pipeline's log
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LogChunk {
+    pub lines: Vec<String>,
+    /// Byte offset to read the next chunk from
+    pub offset: u64,
+    /// Whether the pipeline has finished, so nothing more will be written
+    pub finished: bool,
 }
 
 /// Summary of a session for listing
@@ -123,6 +196,16 @@ pub struct PipelineDetail {
 pub struct SessionSummary {
     pub id: String,
     pub pipeline_id: Option<String>,
+    /// Unix epoch milliseconds, or 0 if unknown
+    pub created_at_ms: u64,
+}
+
+/// Summary of a cron for listing
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CronSummary {
+    pub name: String,
+    pub schedule: String,
+    pub enabled: bool,
 }
 
 /// Protocol errors
@@ -51,6 +51,88 @@ fn encode_decode_query() {
     assert_eq!(request, decoded);
 }
 
+#[test]
+fn encode_decode_pipeline_history() {
+    let response = Response::PipelineHistory {
+        history: Some(vec![PhaseEntry {
+            phase: "test".to_string(),
+            status: "Failed".to_string(),
+            trigger: "start".to_string(),
+            started_at_ms: 1_000,
+            ended_at_ms: Some(2_000),
+            exit_code: Some(1),
+            error: Some("shell exited with code 1".to_string()),
+        }]),
+    };
+
+    let encoded = encode(&response).expect("encode failed");
+    let decoded: Response = decode(&encoded).expect("decode failed");
+
+    assert_eq!(response, decoded);
+}
+
+#[test]
+fn encode_decode_pipeline_tree() {
+    let child = |id: &str, children| PipelineChild {
+        id: id.to_string(),
+        name: id.to_string(),
+        kind: "build".to_string(),
+        phase: "done".to_string(),
+        phase_status: "Completed".to_string(),
+        parent_phase: "build".to_string(),
+        children,
+    };
+    let response = Response::Pipeline {
+        pipeline: Some(Box::new(PipelineDetail {
+            id: "pipe-1".to_string(),
+            name: "release".to_string(),
+            kind: "release".to_string(),
+            phase: "build".to_string(),
+            phase_status: "Running".to_string(),
+            inputs: HashMap::new(),
+            outputs: HashMap::new(),
+            workspace_path: None,
+            session_id: None,
+            error: None,
+            created_at_ms: 1_000,
+            updated_at_ms: 2_000,
+            phase_started_at_ms: 1_500,
+            parent: None,
+            children: vec![child("pipe-2", vec![child("pipe-3", vec![])])],
+        })),
+    };
+
+    let encoded = encode(&response).expect("encode failed");
+    let decoded: Response = decode(&encoded).expect("decode failed");
+
+    assert_eq!(response, decoded);
+}
+
+#[test]
+fn encode_decode_pipeline_logs() {
+    let request = Request::Query {
+        query: Query::PipelineLogs {
+            id: "pipe-123".to_string(),
+            phase: Some("build".to_string()),
+            offset: 128,
+        },
+    };
+    let response = Response::PipelineLogs {
+        logs: Some(LogChunk {
+            lines: vec!["2026-01-01 00:00:00 UTC [build] compiling".to_string()],
+            offset: 170,
+            finished: false,
+        }),
+    };
+
+    let decoded: Request =
+        decode(&encode(&request).expect("encode failed")).expect("decode failed");
+    assert_eq!(request, decoded);
+    let decoded: Response =
+        decode(&encode(&response).expect("encode failed")).expect("decode failed");
+    assert_eq!(response, decoded);
+}
+
 #[test]
 fn encode_returns_json_without_length_prefix() {
     let response = Response::Ok;
@@ -3,13 +3,15 @@
 
 //! Socket server and connection handling.
 
+use oj_engine::pipeline_log;
+use oj_storage::MaterializedState;
 use tokio::net::UnixStream;
 use tracing::{debug, error};
 
 use crate::lifecycle::DaemonState;
 use crate::protocol::{
-    self, PipelineDetail, PipelineSummary, Query, Request, Response, SessionSummary,
-    DEFAULT_TIMEOUT, PROTOCOL_VERSION,
+    self, CronSummary, LogChunk, PhaseEntry, PipelineChild, PipelineDetail, PipelineSummary, Query,
+    Request, Response, SessionSummary, DEFAULT_TIMEOUT, PROTOCOL_VERSION,
 };
 
 /// Handle a single client connection
@@ -169,6 +171,27 @@ async fn handle_request(daemon: &mut DaemonState, request: Request) -> Response
     }
 }
 
+/// The pipelines started by a pipeline's phases, and theirs in turn
+fn child_tree(state: &MaterializedState, pipeline_id: &str) -> Vec<PipelineChild> {
+    state
+        .child_pipelines(pipeline_id)
+        .into_iter()
+        .map(|child| PipelineChild {
+            id: child.id.clone(),
+            name: child.name.clone(),
+            kind: child.kind.clone(),
+            phase: child.phase.clone(),
+            phase_status: format!("{:?}", child.phase_status),
+            parent_phase: child
+                .parent
+                .as_ref()
+                .map(|parent| parent.phase.clone())
+                .unwrap_or_default(),
+            children: child_tree(state, &child.id),
+        })
+        .collect()
+}
+
 /// Handle query requests
 fn handle_query(daemon: &DaemonState, query: Query) -> Response {
     let state = daemon.state.lock().unwrap_or_else(|e| e.into_inner());
@@ -198,14 +221,63 @@ fn handle_query(daemon: &DaemonState, query: Query) -> Response {
                     phase: p.phase.clone(),
                     phase_status: format!("{:?}", p.phase_status),
                     inputs: p.inputs.clone(),
+                    outputs: p.outputs.clone(),
                     workspace_path: p.workspace_path.clone(),
                     session_id: p.session_id.clone(),
                     error: p.error.clone(),
+                    created_at_ms: p.created_at_ms,
+                    updated_at_ms: p.updated_at_ms,
+                    phase_started_at_ms: p.phase_started_at_ms,
+                    parent: p.parent.clone(),
+                    children: child_tree(&state, &p.id),
                 })
             });
             Response::Pipeline { pipeline }
         }
 
+        Query::PipelineHistory { id } => {
+            let history = state.get_pipeline(&id).map(|p| {
+                p.history
+                    .iter()
+                    .map(|record| PhaseEntry {
+                        phase: record.phase.clone(),
+                        status: format!("{:?}", record.status),
+                        trigger: record.trigger.to_string(),
+                        started_at_ms: record.started_at_ms,
+                        ended_at_ms: record.ended_at_ms,
+                        exit_code: record.exit_code,
+                        error: record.error.clone(),
+                    })
+                    .collect()
+            });
+            Response::PipelineHistory { history }
+        }
+
+        Query::PipelineLogs { id, phase, offset } => {
+            let Some((pipeline_id, finished)) = state
+                .get_pipeline(&id)
+                .map(|p| (p.id.clone(), p.is_terminal()))
+            else {
+                return Response::PipelineLogs { logs: None };
+            };
+            // Logs are read from disk; the state is not needed for that
+            drop(state);
+
+            let path = pipeline_log::log_path(&daemon.config.pipeline_logs_path, &pipeline_id);
+            match pipeline_log::read_from(&path, offset, phase.as_deref()) {
+                Ok((lines, offset)) => Response::PipelineLogs {
+                    logs: Some(LogChunk {
+                        lines,
+                        offset,
+                        finished,
+                    }),
+                },
+                Err(e) => Response::Error {
+                    message: format!("cannot read {}: {}", path.display(), e),
+                },
+            }
+        }
+
         Query::ListSessions => {
             let sessions = state
                 .sessions
@@ -213,10 +285,27 @@ fn handle_query(daemon: &DaemonState, query: Query) -> Response {
                 .map(|s| SessionSummary {
                     id: s.id.clone(),
                     pipeline_id: Some(s.pipeline_id.clone()),
+                    created_at_ms: s.created_at_ms,
                 })
                 .collect();
             Response::Sessions { sessions }
         }
+
+        Query::ListCrons => {
+            let mut crons: Vec<_> = daemon
+                .runtime
+                .runbook()
+                .crons
+                .values()
+                .map(|def| CronSummary {
+                    name: def.name.clone(),
+                    schedule: def.schedule.to_string(),
+                    enabled: state.crons.get(&def.name).is_some_and(|c| c.enabled),
+                })
+                .collect();
+            crons.sort_by(|a, b| a.name.cmp(&b.name));
+            Response::Crons { crons }
+        }
     }
 }
 
@@ -0,0 +1,49 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Named action rate limiting
+
+use oj_core::{ActionRecord, Effect, Operation};
+use oj_runbook::ActionDef;
+use std::time::Instant;
+
+/// Whether a named action may fire at a target
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Readiness {
+    Ready,
+    /// Fired too recently; try again later
+    CoolingDown,
+    /// Fired `max_attempts` times already; move on to something else
+    Exhausted,
+}
+
+/// Check an action's attempts and cooldown against its history at a target
+pub fn readiness(def: &ActionDef, record: Option<&ActionRecord>, now: Instant) -> Readiness {
+    let Some(record) = record else {
+        return Readiness::Ready;
+    };
+    if def.max_attempts.is_some_and(|max| record.attempts >= max) {
+        return Readiness::Exhausted;
+    }
+    if def
+        .cooldown
+        .is_some_and(|cooldown| record.cooling_down(cooldown, now))
+    {
+        return Readiness::CoolingDown;
+    }
+    Readiness::Ready
+}
+
+/// Build the effect recording that an action fired at a target
+pub fn fire_effect(name: &str, target: &str) -> Effect {
+    Effect::Persist {
+        operation: Operation::ActionFire {
+            name: name.to_string(),
+            target: target.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+#[path = "action_tests.rs"]
+mod tests;
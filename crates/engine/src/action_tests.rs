@@ -0,0 +1,57 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+use std::time::Duration;
+
+fn restart() -> ActionDef {
+    ActionDef {
+        name: "restart".to_string(),
+        run: "oj pipeline resume {name}".to_string(),
+        cooldown: Some(Duration::from_secs(300)),
+        max_attempts: Some(2),
+    }
+}
+
+#[test]
+fn first_firing_is_ready() {
+    assert_eq!(
+        readiness(&restart(), None, Instant::now()),
+        Readiness::Ready
+    );
+}
+
+#[test]
+fn cooldown_holds_until_elapsed() {
+    let start = Instant::now();
+    let record = ActionRecord::new(start);
+    assert_eq!(
+        readiness(&restart(), Some(&record), start + Duration::from_secs(60)),
+        Readiness::CoolingDown
+    );
+    assert_eq!(
+        readiness(&restart(), Some(&record), start + Duration::from_secs(300)),
+        Readiness::Ready
+    );
+}
+
+#[test]
+fn max_attempts_exhausts_regardless_of_cooldown() {
+    let start = Instant::now();
+    let mut record = ActionRecord::new(start);
+    record.fire(start);
+    let later = start + Duration::from_secs(3600);
+    assert_eq!(
+        readiness(&restart(), Some(&record), later),
+        Readiness::Exhausted
+    );
+
+    let unlimited = ActionDef {
+        max_attempts: None,
+        ..restart()
+    };
+    assert_eq!(
+        readiness(&unlimited, Some(&record), later),
+        Readiness::Ready
+    );
+}
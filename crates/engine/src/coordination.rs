@@ -0,0 +1,271 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Lock and semaphore effects for phases that require coordination.
+//!
+//! The runtime decides acquisition outcomes against materialized state and
+//! these helpers turn the outcome into effects, so the WAL records decisions
+//! rather than re-deciding them on replay.
+
+use oj_core::{AcquireResult, Effect, Event, Operation, SemaphoreResult};
+use oj_runbook::{LockDef, SemaphoreDef};
+
+/// Timer that refreshes a held lock's heartbeat
+pub fn lock_heartbeat_timer(pipeline_id: &str) -> String {
+    format!("lock:{}:heartbeat", pipeline_id)
+}
+
+/// Timer that retries a blocked lock acquisition (to pick up stale reclaims)
+pub fn lock_retry_timer(pipeline_id: &str) -> String {
+    format!("lock:{}:retry", pipeline_id)
+}
+
+/// Build effects for the outcome of a lock acquisition attempt
+pub fn lock_acquire_effects(
+    def: &LockDef,
+    pipeline_id: &str,
+    result: &AcquireResult,
+) -> Vec<Effect> {
+    let operation = match result {
+        AcquireResult::Busy { holder } => {
+            return vec![
+                Effect::Emit {
+                    event: Event::Custom {
+                        name: "lock:waiting".to_string(),
+                        data: serde_json::json!({
+                            "lock": def.name,
+                            "pipeline_id": pipeline_id,
+                            "holder": holder,
+                        }),
+                    },
+                },
+                Effect::SetTimer {
+                    id: lock_retry_timer(pipeline_id),
+                    duration: def.heartbeat,
+                },
+            ];
+        }
+        AcquireResult::Extended => Operation::LockHeartbeat {
+            name: def.name.clone(),
+            holder: pipeline_id.to_string(),
+        },
+        AcquireResult::Acquired | AcquireResult::Reclaimed { .. } => Operation::LockAcquire {
+            name: def.name.clone(),
+            holder: pipeline_id.to_string(),
+        },
+    };
+
+    let mut effects = vec![
+        Effect::Persist { operation },
+        Effect::CancelTimer {
+            id: lock_retry_timer(pipeline_id),
+        },
+        Effect::SetTimer {
+            id: lock_heartbeat_timer(pipeline_id),
+            duration: def.heartbeat,
+        },
+    ];
+
+    let mut data = serde_json::json!({
+        "lock": def.name,
+        "pipeline_id": pipeline_id,
+    });
+    let name = match result {
+        AcquireResult::Reclaimed { previous } => {
+            data["previous"] = serde_json::json!(previous);
+            "lock:reclaimed"
+        }
+        _ => "lock:acquired",
+    };
+    effects.push(Effect::Emit {
+        event: Event::Custom {
+            name: name.to_string(),
+            data,
+        },
+    });
+
+    effects
+}
+
+/// Build effects to refresh a held lock's heartbeat and schedule the next one
+pub fn lock_heartbeat_effects(def: &LockDef, pipeline_id: &str) -> Vec<Effect> {
+    vec![
+        Effect::Persist {
+            operation: Operation::LockHeartbeat {
+                name: def.name.clone(),
+                holder: pipeline_id.to_string(),
+            },
+        },
+        Effect::SetTimer {
+            id: lock_heartbeat_timer(pipeline_id),
+            duration: def.heartbeat,
+        },
+    ]
+}
+
+/// Build effects to release a lock held by a pipeline
+pub fn lock_release_effects(lock_name: &str, pipeline_id: &str) -> Vec<Effect> {
+    vec![
+        Effect::Persist {
+            operation: Operation::LockRelease {
+                name: lock_name.to_string(),
+                holder: pipeline_id.to_string(),
+            },
+        },
+        Effect::CancelTimer {
+            id: lock_heartbeat_timer(pipeline_id),
+        },
+        Effect::Emit {
+            event: Event::Custom {
+                name: "lock:released".to_string(),
+                data: serde_json::json!({
+                    "lock": lock_name,
+                    "pipeline_id": pipeline_id,
+                }),
+            },
+        },
+    ]
+}
+
+/// Timer that refreshes a held semaphore slot's heartbeat
+pub fn semaphore_heartbeat_timer(pipeline_id: &str) -> String {
+    format!("semaphore:{}:heartbeat", pipeline_id)
+}
+
+/// Timer that retries a blocked semaphore acquisition
+pub fn semaphore_retry_timer(pipeline_id: &str) -> String {
+    format!("semaphore:{}:retry", pipeline_id)
+}
+
+/// Build effects for the outcome of a semaphore acquisition attempt
+pub fn semaphore_acquire_effects(
+    def: &SemaphoreDef,
+    pipeline_id: &str,
+    slots: u32,
+    result: &SemaphoreResult,
+) -> Vec<Effect> {
+    let operation = match result {
+        SemaphoreResult::Full { available } => {
+            return vec![
+                Effect::Emit {
+                    event: Event::Custom {
+                        name: "semaphore:waiting".to_string(),
+                        data: serde_json::json!({
+                            "semaphore": def.name,
+                            "pipeline_id": pipeline_id,
+                            "available": available,
+                        }),
+                    },
+                },
+                Effect::SetTimer {
+                    id: semaphore_retry_timer(pipeline_id),
+                    duration: def.slot_heartbeat,
+                },
+            ];
+        }
+        SemaphoreResult::Acquired => Operation::SemaphoreAcquire {
+            name: def.name.clone(),
+            holder: pipeline_id.to_string(),
+            slots,
+        },
+        SemaphoreResult::AlreadyHeld => Operation::SemaphoreHeartbeat {
+            name: def.name.clone(),
+            holder: pipeline_id.to_string(),
+        },
+        SemaphoreResult::Released | SemaphoreResult::NotHolder => return vec![],
+    };
+
+    let mut effects = vec![
+        Effect::Persist { operation },
+        Effect::CancelTimer {
+            id: semaphore_retry_timer(pipeline_id),
+        },
+        Effect::SetTimer {
+            id: semaphore_heartbeat_timer(pipeline_id),
+            duration: def.slot_heartbeat,
+        },
+    ];
+    if *result == SemaphoreResult::Acquired {
+        effects.push(Effect::Emit {
+            event: Event::Custom {
+                name: "semaphore:acquired".to_string(),
+                data: serde_json::json!({
+                    "semaphore": def.name,
+                    "pipeline_id": pipeline_id,
+                    "slots": slots,
+                }),
+            },
+        });
+    }
+    effects
+}
+
+/// Build effects to drop holders whose slot heartbeats went stale
+pub fn semaphore_reclaim_effects(semaphore_name: &str, holders: &[String]) -> Vec<Effect> {
+    holders
+        .iter()
+        .flat_map(|holder| {
+            [
+                Effect::Persist {
+                    operation: Operation::SemaphoreRelease {
+                        name: semaphore_name.to_string(),
+                        holder: holder.clone(),
+                    },
+                },
+                Effect::Emit {
+                    event: Event::Custom {
+                        name: "semaphore:reclaimed".to_string(),
+                        data: serde_json::json!({
+                            "semaphore": semaphore_name,
+                            "previous": holder,
+                        }),
+                    },
+                },
+            ]
+        })
+        .collect()
+}
+
+/// Build effects to refresh a held slot's heartbeat and schedule the next one
+pub fn semaphore_heartbeat_effects(def: &SemaphoreDef, pipeline_id: &str) -> Vec<Effect> {
+    vec![
+        Effect::Persist {
+            operation: Operation::SemaphoreHeartbeat {
+                name: def.name.clone(),
+                holder: pipeline_id.to_string(),
+            },
+        },
+        Effect::SetTimer {
+            id: semaphore_heartbeat_timer(pipeline_id),
+            duration: def.slot_heartbeat,
+        },
+    ]
+}
+
+/// Build effects to release the slots held by a pipeline
+pub fn semaphore_release_effects(semaphore_name: &str, pipeline_id: &str) -> Vec<Effect> {
+    vec![
+        Effect::Persist {
+            operation: Operation::SemaphoreRelease {
+                name: semaphore_name.to_string(),
+                holder: pipeline_id.to_string(),
+            },
+        },
+        Effect::CancelTimer {
+            id: semaphore_heartbeat_timer(pipeline_id),
+        },
+        Effect::Emit {
+            event: Event::Custom {
+                name: "semaphore:released".to_string(),
+                data: serde_json::json!({
+                    "semaphore": semaphore_name,
+                    "pipeline_id": pipeline_id,
+                }),
+            },
+        },
+    ]
+}
+
+#[cfg(test)]
+#[path = "coordination_tests.rs"]
+mod tests;
@@ -0,0 +1,121 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+
+#[test]
+fn acquired_lock_persists_and_starts_heartbeat() {
+    let def = LockDef::new("main_branch");
+    let effects = lock_acquire_effects(&def, "pipe-1", &AcquireResult::Acquired);
+
+    assert!(effects.contains(&Effect::Persist {
+        operation: Operation::LockAcquire {
+            name: "main_branch".to_string(),
+            holder: "pipe-1".to_string(),
+        },
+    }));
+    assert!(effects.contains(&Effect::SetTimer {
+        id: "lock:pipe-1:heartbeat".to_string(),
+        duration: def.heartbeat,
+    }));
+}
+
+#[test]
+fn busy_lock_schedules_retry_without_persisting() {
+    let def = LockDef::new("main_branch");
+    let effects = lock_acquire_effects(
+        &def,
+        "pipe-2",
+        &AcquireResult::Busy {
+            holder: "pipe-1".to_string(),
+        },
+    );
+
+    assert!(!effects.iter().any(|e| matches!(e, Effect::Persist { .. })));
+    assert!(effects.contains(&Effect::SetTimer {
+        id: "lock:pipe-2:retry".to_string(),
+        duration: def.heartbeat,
+    }));
+}
+
+#[test]
+fn reclaimed_lock_reports_previous_holder() {
+    let def = LockDef::new("main_branch");
+    let effects = lock_acquire_effects(
+        &def,
+        "pipe-2",
+        &AcquireResult::Reclaimed {
+            previous: "pipe-1".to_string(),
+        },
+    );
+
+    let reclaimed = effects.iter().find_map(|e| match e {
+        Effect::Emit {
+            event: Event::Custom { name, data },
+        } if name == "lock:reclaimed" => Some(data.clone()),
+        _ => None,
+    });
+    assert_eq!(reclaimed.unwrap()["previous"], "pipe-1");
+}
+
+#[test]
+fn release_persists_and_cancels_heartbeat() {
+    let effects = lock_release_effects("main_branch", "pipe-1");
+
+    assert!(effects.contains(&Effect::Persist {
+        operation: Operation::LockRelease {
+            name: "main_branch".to_string(),
+            holder: "pipe-1".to_string(),
+        },
+    }));
+    assert!(effects.contains(&Effect::CancelTimer {
+        id: "lock:pipe-1:heartbeat".to_string(),
+    }));
+}
+
+#[test]
+fn acquired_slot_persists_weight() {
+    let def = SemaphoreDef::new("agents");
+    let effects = semaphore_acquire_effects(&def, "pipe-1", 2, &SemaphoreResult::Acquired);
+
+    assert!(effects.contains(&Effect::Persist {
+        operation: Operation::SemaphoreAcquire {
+            name: "agents".to_string(),
+            holder: "pipe-1".to_string(),
+            slots: 2,
+        },
+    }));
+    assert!(effects.contains(&Effect::SetTimer {
+        id: "semaphore:pipe-1:heartbeat".to_string(),
+        duration: def.slot_heartbeat,
+    }));
+}
+
+#[test]
+fn full_semaphore_schedules_retry_without_persisting() {
+    let def = SemaphoreDef::new("agents");
+    let effects =
+        semaphore_acquire_effects(&def, "pipe-2", 1, &SemaphoreResult::Full { available: 0 });
+
+    assert!(!effects.iter().any(|e| matches!(e, Effect::Persist { .. })));
+    assert!(effects.contains(&Effect::SetTimer {
+        id: "semaphore:pipe-2:retry".to_string(),
+        duration: def.slot_heartbeat,
+    }));
+}
+
+#[test]
+fn reclaim_releases_each_stale_holder() {
+    let effects =
+        semaphore_reclaim_effects("agents", &["pipe-1".to_string(), "pipe-2".to_string()]);
+    let released: Vec<_> = effects
+        .iter()
+        .filter_map(|e| match e {
+            Effect::Persist {
+                operation: Operation::SemaphoreRelease { holder, .. },
+            } => Some(holder.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(released, vec!["pipe-1", "pipe-2"]);
+}
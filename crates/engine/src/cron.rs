@@ -0,0 +1,36 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Cron scheduling
+
+use oj_core::Effect;
+use oj_runbook::{CronDef, CronSchedule};
+use std::time::Duration;
+
+/// Timer that fires a cron's next run
+pub fn cron_tick_timer(name: &str) -> String {
+    format!("cron:{}:tick", name)
+}
+
+/// Time from `now` (since the Unix epoch) until the cron should next run
+pub fn next_delay(def: &CronDef, now: Duration) -> Option<Duration> {
+    match &def.schedule {
+        CronSchedule::Interval(interval) => Some(*interval),
+        CronSchedule::Expression(expr) => {
+            let next = expr.next_after(now.as_secs())?;
+            Some(Duration::from_secs(next).saturating_sub(now))
+        }
+    }
+}
+
+/// Build the effect that schedules a cron's next run
+pub fn schedule_effect(def: &CronDef, now: Duration) -> Option<Effect> {
+    next_delay(def, now).map(|duration| Effect::SetTimer {
+        id: cron_tick_timer(&def.name),
+        duration,
+    })
+}
+
+#[cfg(test)]
+#[path = "cron_tests.rs"]
+mod tests;
@@ -0,0 +1,44 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+use oj_runbook::CronExpr;
+
+fn cron_def(schedule: CronSchedule) -> CronDef {
+    CronDef {
+        name: "janitor".to_string(),
+        schedule,
+        monitors: vec![],
+        run: None,
+    }
+}
+
+#[test]
+fn interval_waits_full_interval() {
+    let def = cron_def(CronSchedule::Interval(Duration::from_secs(30)));
+    assert_eq!(
+        next_delay(&def, Duration::from_millis(1_500)),
+        Some(Duration::from_secs(30))
+    );
+}
+
+#[test]
+fn expression_waits_until_next_match() {
+    let def = cron_def(CronSchedule::Expression(
+        CronExpr::parse("*/5 * * * *").unwrap(),
+    ));
+    // 00:01:30.5 on day one waits until 00:05:00
+    let now = Duration::from_millis(90_500);
+    assert_eq!(next_delay(&def, now), Some(Duration::from_millis(209_500)));
+
+    let effect = schedule_effect(&def, now).unwrap();
+    assert!(matches!(effect, Effect::SetTimer { id, .. } if id == "cron:janitor:tick"));
+}
+
+#[test]
+fn impossible_expression_is_never_scheduled() {
+    let def = cron_def(CronSchedule::Expression(
+        CronExpr::parse("0 0 30 2 *").unwrap(),
+    ));
+    assert!(schedule_effect(&def, Duration::ZERO).is_none());
+}
@@ -19,6 +19,20 @@ pub enum RuntimeError {
     PipelineDefNotFound(String),
     #[error("agent not found: {0}")]
     AgentNotFound(String),
+    #[error("guard not found: {0}")]
+    GuardNotFound(String),
+    #[error("strategy not found: {0}")]
+    StrategyNotFound(String),
+    #[error("queue not found: {0}")]
+    QueueNotFound(String),
+    #[error("worker not found: {0}")]
+    WorkerNotFound(String),
+    #[error("cron not found: {0}")]
+    CronNotFound(String),
+    #[error("monitor not found: {0}")]
+    MonitorNotFound(String),
+    #[error("action not found: {0}")]
+    ActionNotFound(String),
     #[error("prompt error for agent {agent}: {message}")]
     PromptError { agent: String, message: String },
     #[error("invalid run directive for {context}: {directive}")]
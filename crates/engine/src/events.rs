@@ -9,14 +9,14 @@
 use crate::error::RuntimeError;
 use crate::Executor;
 use oj_adapters::{NotifyAdapter, RepoAdapter, SessionAdapter};
-use oj_core::{Effect, Event, Operation, PhaseStatus, Pipeline};
+use oj_core::{Clock, Effect, Event, Operation, PhaseStatus, Pipeline};
 use std::time::Duration;
 
 /// Handle custom events from the daemon.
 ///
 /// Custom events provide an extension point for controlling sessions and pipelines.
-pub async fn handle_custom_event<S, R, N>(
-    executor: &Executor<S, R, N>,
+pub async fn handle_custom_event<S, R, N, C>(
+    executor: &Executor<S, R, N, C>,
     name: &str,
     data: &serde_json::Value,
     get_pipeline: impl Fn(&str) -> Option<Pipeline>,
@@ -25,6 +25,7 @@ where
     S: SessionAdapter,
     R: RepoAdapter,
     N: NotifyAdapter,
+    C: Clock,
 {
     match name {
         "session:send" => {
@@ -75,8 +76,8 @@ where
             Ok(vec![])
         }
         _ => {
-            // Unknown custom events are informational
-            tracing::debug!(name, "ignoring unknown custom event");
+            // Other custom events are left to runbook [[on]] rules and wake-ups
+            tracing::debug!(name, "no built-in handler for custom event");
             Ok(vec![])
         }
     }
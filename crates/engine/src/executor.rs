@@ -3,13 +3,19 @@
 
 //! Effect executor
 
+use crate::shell::{self, ShellJob};
 use crate::{RuntimeDeps, Scheduler};
 use oj_adapters::{NotifyAdapter, RepoAdapter, SessionAdapter};
-use oj_core::{Effect, Event};
+use oj_core::{Clock, Effect, Event, Operation};
 use oj_storage::{MaterializedState, Wal};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use thiserror::Error;
+use tokio::sync::{mpsc, oneshot};
 
 /// Errors that can occur during effect execution
 #[derive(Debug, Error)]
@@ -26,27 +32,47 @@ pub enum ExecuteError {
     WorkspaceNotFound(String),
     #[error("shell execution error: {0}")]
     Shell(String),
+    #[error("{0} timed out after {1:?}")]
+    ShellTimeout(String, Duration),
+}
+
+/// How long a command the runtime waits on may run, unless configured otherwise
+pub const DEFAULT_COMMAND_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// A shell command running in the background for a pipeline
+struct RunningShell {
+    /// Tells the shell apart from a later one started for the same pipeline
+    id: u64,
+    cancel: oneshot::Sender<()>,
 }
 
 /// Executes effects using the configured adapters
-pub struct Executor<S, R, N> {
+pub struct Executor<S, R, N, C> {
     sessions: S,
     repos: R,
     notify: N,
     wal: Arc<Mutex<Wal>>,
     state: Arc<Mutex<MaterializedState>>,
     scheduler: Arc<Mutex<Scheduler>>,
-    clock: oj_core::SystemClock,
+    events: Option<mpsc::Sender<Event>>,
+    /// Background shell commands, keyed by pipeline ID
+    shells: Arc<Mutex<HashMap<String, RunningShell>>>,
+    next_shell_id: AtomicU64,
+    /// Limit on commands the runtime runs to completion: checks, captures and hooks
+    command_timeout: Duration,
+    /// Times timers and persisted operations
+    clock: C,
 }
 
-impl<S, R, N> Executor<S, R, N>
+impl<S, R, N, C> Executor<S, R, N, C>
 where
     S: SessionAdapter,
     R: RepoAdapter,
     N: NotifyAdapter,
+    C: Clock,
 {
     /// Create a new executor
-    pub fn new(deps: RuntimeDeps<S, R, N>, scheduler: Arc<Mutex<Scheduler>>) -> Self {
+    pub fn new(deps: RuntimeDeps<S, R, N>, scheduler: Arc<Mutex<Scheduler>>, clock: C) -> Self {
         Self {
             sessions: deps.sessions,
             repos: deps.repos,
@@ -54,10 +80,20 @@ where
             wal: deps.wal,
             state: deps.state,
             scheduler,
-            clock: oj_core::SystemClock,
+            events: deps.events,
+            shells: Arc::new(Mutex::new(HashMap::new())),
+            next_shell_id: AtomicU64::new(0),
+            command_timeout: DEFAULT_COMMAND_TIMEOUT,
+            clock,
         }
     }
 
+    /// Set how long `capture`, `exit_code` and hooks run before being killed
+    pub fn with_command_timeout(mut self, timeout: Duration) -> Self {
+        self.command_timeout = timeout;
+        self
+    }
+
     /// Execute a single effect with tracing
     ///
     /// Returns an optional event that should be fed back into the event loop.
@@ -98,7 +134,8 @@ where
                 let message = format!("{:?}", event);
                 eprintln!("[EVENT] {}", message);
                 self.notify.send("events", &message).await?;
-                Ok(None)
+                // Feed it back so subscribers (e.g. guard wake_on) can react
+                Ok(Some(event))
             }
 
             Effect::Spawn {
@@ -107,23 +144,30 @@ where
                 env,
                 cwd,
             } => {
-                let workspace_path = {
-                    let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
-                    state
-                        .workspaces
-                        .get(&workspace_id)
-                        .map(|w| w.path.clone())
-                        .ok_or_else(|| ExecuteError::WorkspaceNotFound(workspace_id.clone()))?
-                };
-
                 // Use cwd override if provided, otherwise default to workspace path
-                let effective_cwd = cwd.unwrap_or(workspace_path);
+                let effective_cwd = match cwd {
+                    Some(cwd) => cwd,
+                    None => {
+                        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+                        state
+                            .workspaces
+                            .get(&workspace_id)
+                            .map(|w| w.path.clone())
+                            .ok_or_else(|| ExecuteError::WorkspaceNotFound(workspace_id.clone()))?
+                    }
+                };
 
                 // TracedSessionAdapter handles logging and precondition validation
-                self.sessions
+                let session_id = self
+                    .sessions
                     .spawn(&workspace_id, &effective_cwd, &command, &env)
                     .await?;
 
+                // Record the adapter's id so the session can be found again after a restart
+                self.persist(&Operation::SessionCreate {
+                    id: session_id,
+                    pipeline_id: workspace_id,
+                })?;
                 Ok(None)
             }
 
@@ -149,7 +193,7 @@ where
             }
 
             Effect::SetTimer { id, duration } => {
-                let now = oj_core::Clock::now(&self.clock);
+                let now = self.clock.now();
                 self.scheduler
                     .lock()
                     .unwrap_or_else(|e| e.into_inner())
@@ -166,14 +210,7 @@ where
             }
 
             Effect::Persist { operation } => {
-                {
-                    let mut wal = self.wal.lock().unwrap_or_else(|e| e.into_inner());
-                    wal.append(&operation)?;
-                }
-                {
-                    let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
-                    state.apply(&operation);
-                }
+                self.persist(&operation)?;
                 Ok(None)
             }
 
@@ -183,42 +220,63 @@ where
                 command,
                 cwd,
                 env,
+                timeout,
+                log_path,
+                stdout_vars,
             } => {
-                let output = tokio::process::Command::new("sh")
-                    .arg("-c")
-                    .arg(&command)
-                    .current_dir(&cwd)
-                    .envs(&env)
-                    .output()
-                    .await
-                    .map_err(|e| ExecuteError::Shell(e.to_string()))?;
-
-                let exit_code = output.status.code().unwrap_or(-1);
-
-                // Log output
-                if !output.stdout.is_empty() {
-                    tracing::info!(
-                        pipeline_id,
-                        phase,
-                        stdout = %String::from_utf8_lossy(&output.stdout),
-                        "shell stdout"
-                    );
+                let job = ShellJob {
+                    pipeline_id,
+                    phase,
+                    command,
+                    cwd,
+                    env,
+                    timeout,
+                    log_path,
+                    stdout_vars,
+                };
+                match &self.events {
+                    Some(events) => {
+                        self.spawn_shell(job, events.clone());
+                        Ok(None)
+                    }
+                    // Without an event loop to report back to, wait for the command here
+                    None => {
+                        let (_cancel, cancelled) = oneshot::channel();
+                        Ok(shell::run(job, cancelled).await)
+                    }
                 }
-                if !output.stderr.is_empty() {
-                    tracing::warn!(
-                        pipeline_id,
-                        phase,
-                        stderr = %String::from_utf8_lossy(&output.stderr),
-                        "shell stderr"
-                    );
+            }
+
+            Effect::CancelShell { pipeline_id } => {
+                let running = self
+                    .shells
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .remove(&pipeline_id);
+                if let Some(running) = running {
+                    // The command may have just finished on its own
+                    let _ = running.cancel.send(());
                 }
+                Ok(None)
+            }
 
-                // Return event to feed back into loop
-                Ok(Some(Event::ShellCompleted {
-                    pipeline_id,
-                    phase,
-                    exit_code,
-                }))
+            Effect::Hook { name, command, cwd } => {
+                let timeout = self.command_timeout;
+                tokio::spawn(async move {
+                    match run_command(&command, &cwd, timeout).await {
+                        Ok(output) if output.status.success() => {
+                            tracing::info!(hook = %name, "hook finished");
+                        }
+                        Ok(output) => tracing::warn!(
+                            hook = %name,
+                            exit_code = output.status.code().unwrap_or(-1),
+                            stderr = %String::from_utf8_lossy(&output.stderr),
+                            "hook failed"
+                        ),
+                        Err(e) => tracing::warn!(hook = %name, error = %e, "hook failed"),
+                    }
+                });
+                Ok(None)
             }
 
             Effect::Notify { title, message } => {
@@ -257,6 +315,92 @@ where
         Ok(result_events)
     }
 
+    /// Run a shell command and capture its trimmed stdout
+    ///
+    /// Used for values the runtime needs back, such as strategy checkpoints.
+    pub async fn capture(&self, command: &str, cwd: &Path) -> Result<String, ExecuteError> {
+        let output = run_command(command, cwd, self.command_timeout).await?;
+
+        if !output.status.success() {
+            return Err(ExecuteError::Shell(format!(
+                "{} exited with code {}",
+                command,
+                output.status.code().unwrap_or(-1)
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Run a shell command as a background task that reports back on `events`
+    ///
+    /// A pipeline runs one command at a time, so any command still running
+    /// for the pipeline is cancelled.
+    fn spawn_shell(&self, job: ShellJob, events: mpsc::Sender<Event>) {
+        let (cancel, cancelled) = oneshot::channel();
+        let id = self.next_shell_id.fetch_add(1, Ordering::Relaxed);
+        let pipeline_id = job.pipeline_id.clone();
+        let job_pipeline_id = pipeline_id.clone();
+        let shells = Arc::clone(&self.shells);
+
+        // Hold the lock until the shell is registered, so it cannot finish
+        // and deregister before then
+        let mut shells_guard = self.shells.lock().unwrap_or_else(|e| e.into_inner());
+        tokio::spawn(async move {
+            let event = shell::run(job, cancelled).await;
+            {
+                let mut shells_guard = shells.lock().unwrap_or_else(|e| e.into_inner());
+                if shells_guard.get(&pipeline_id).is_some_and(|s| s.id == id) {
+                    shells_guard.remove(&pipeline_id);
+                }
+            }
+            if let Some(event) = event {
+                if events.send(event).await.is_err() {
+                    tracing::warn!(pipeline_id, "event loop closed, dropping shell result");
+                }
+            }
+        });
+        let previous = shells_guard.insert(job_pipeline_id, RunningShell { id, cancel });
+        if let Some(previous) = previous {
+            let _ = previous.cancel.send(());
+        }
+    }
+
+    /// Run a shell command to completion and return its exit code
+    ///
+    /// Used for checks the runtime needs answered straight away, such as
+    /// guard conditions and strategy rollbacks.
+    pub async fn exit_code(&self, command: &str, cwd: &Path) -> Result<i32, ExecuteError> {
+        let output = run_command(command, cwd, self.command_timeout).await?;
+        Ok(output.status.code().unwrap_or(-1))
+    }
+
+    /// Capture the last `lines` lines a session has printed
+    pub async fn session_output(
+        &self,
+        session_id: &str,
+        lines: u32,
+    ) -> Result<String, ExecuteError> {
+        Ok(self.sessions.capture_output(session_id, lines).await?)
+    }
+
+    /// Whether a session is still running
+    pub async fn session_alive(&self, session_id: &str) -> Result<bool, ExecuteError> {
+        Ok(self.sessions.is_alive(session_id).await?)
+    }
+
+    /// Append an operation to the WAL and apply it to state
+    fn persist(&self, operation: &Operation) -> Result<(), ExecuteError> {
+        // Recorded with the operation so a replay sees the same timestamps
+        let epoch_ms = self.clock.epoch_ms();
+        {
+            let mut wal = self.wal.lock().unwrap_or_else(|e| e.into_inner());
+            wal.append_at(operation, epoch_ms)?;
+        }
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.apply_with(operation, &self.clock);
+        Ok(())
+    }
+
     /// Get a reference to the state
     pub fn state(&self) -> Arc<Mutex<MaterializedState>> {
         Arc::clone(&self.state)
@@ -273,6 +417,38 @@ where
     }
 }
 
+/// Run a shell command to completion, killing it once `timeout` passes
+///
+/// The command runs in its own process group, so whatever it started in the
+/// background is killed with it.
+async fn run_command(
+    command: &str,
+    cwd: &Path,
+    timeout: Duration,
+) -> Result<std::process::Output, ExecuteError> {
+    let child = tokio::process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .current_dir(cwd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .process_group(0)
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| ExecuteError::Shell(e.to_string()))?;
+    let pid = child.id();
+    match tokio::time::timeout(timeout, child.wait_with_output()).await {
+        Ok(output) => output.map_err(|e| ExecuteError::Shell(e.to_string())),
+        Err(_) => {
+            if let Some(pid) = pid {
+                shell::kill_process_group(pid).await;
+            }
+            Err(ExecuteError::ShellTimeout(command.to_string(), timeout))
+        }
+    }
+}
+
 #[cfg(test)]
 #[path = "executor_tests.rs"]
 mod tests;
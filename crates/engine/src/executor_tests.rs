@@ -4,11 +4,18 @@
 use super::*;
 use crate::RuntimeDeps;
 use oj_adapters::{FakeNotifyAdapter, FakeRepoAdapter, FakeSessionAdapter};
-use oj_core::Operation;
+use oj_core::{FakeClock, Operation};
 use std::collections::HashMap;
+use std::time::Duration;
 use tempfile::tempdir;
 
-async fn setup() -> Executor<FakeSessionAdapter, FakeRepoAdapter, FakeNotifyAdapter> {
+async fn setup() -> Executor<FakeSessionAdapter, FakeRepoAdapter, FakeNotifyAdapter, FakeClock> {
+    setup_with_events(None).await
+}
+
+async fn setup_with_events(
+    events: Option<mpsc::Sender<Event>>,
+) -> Executor<FakeSessionAdapter, FakeRepoAdapter, FakeNotifyAdapter, FakeClock> {
     let dir = tempdir().unwrap();
     let wal = Wal::open(&dir.path().join("test.wal")).unwrap();
 
@@ -19,8 +26,10 @@ async fn setup() -> Executor<FakeSessionAdapter, FakeRepoAdapter, FakeNotifyAdap
             notify: FakeNotifyAdapter::new(),
             wal: Arc::new(Mutex::new(wal)),
             state: Arc::new(Mutex::new(MaterializedState::default())),
+            events,
         },
         Arc::new(Mutex::new(Scheduler::new())),
+        FakeClock::new(),
     )
 }
 
@@ -36,6 +45,7 @@ async fn executor_persist_effect() {
                 name: "test".to_string(),
                 inputs: HashMap::new(),
                 initial_phase: "init".to_string(),
+                parent: None,
             },
         })
         .await
@@ -74,6 +84,9 @@ async fn shell_effect_runs_command() {
             command: "echo hello".to_string(),
             cwd: std::path::PathBuf::from("/tmp"),
             env: HashMap::new(),
+            timeout: None,
+            log_path: std::env::temp_dir().join("oj-executor-test.log"),
+            stdout_vars: Vec::new(),
         })
         .await
         .unwrap();
@@ -95,6 +108,9 @@ async fn shell_failure_returns_nonzero() {
             command: "exit 1".to_string(),
             cwd: std::path::PathBuf::from("/tmp"),
             env: HashMap::new(),
+            timeout: None,
+            log_path: std::env::temp_dir().join("oj-executor-test.log"),
+            stdout_vars: Vec::new(),
         })
         .await
         .unwrap();
@@ -104,3 +120,132 @@ async fn shell_failure_returns_nonzero() {
         Some(Event::ShellCompleted { exit_code: 1, .. })
     ));
 }
+
+fn background_shell(command: &str, log_path: std::path::PathBuf) -> Effect {
+    Effect::Shell {
+        pipeline_id: "pipe-1".to_string(),
+        phase: "build".to_string(),
+        command: command.to_string(),
+        cwd: std::path::PathBuf::from("/tmp"),
+        env: HashMap::new(),
+        timeout: None,
+        log_path,
+        stdout_vars: Vec::new(),
+    }
+}
+
+#[tokio::test]
+async fn background_shell_reports_on_event_channel() {
+    let dir = tempdir().unwrap();
+    let (tx, mut rx) = mpsc::channel(8);
+    let executor = setup_with_events(Some(tx)).await;
+
+    let log_path = dir.path().join("pipe-1.log");
+    let event = executor
+        .execute(background_shell("echo streamed", log_path.clone()))
+        .await
+        .unwrap();
+    assert!(event.is_none());
+
+    let event = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .unwrap();
+    assert!(matches!(
+        event,
+        Some(Event::ShellCompleted { exit_code: 0, .. })
+    ));
+    let log = std::fs::read_to_string(&log_path).unwrap();
+    assert!(log.contains("[build] streamed"));
+}
+
+#[tokio::test]
+async fn cancel_shell_stops_background_command() {
+    let dir = tempdir().unwrap();
+    let (tx, mut rx) = mpsc::channel(8);
+    let executor = setup_with_events(Some(tx)).await;
+
+    executor
+        .execute(background_shell("sleep 30", dir.path().join("pipe-1.log")))
+        .await
+        .unwrap();
+    executor
+        .execute(Effect::CancelShell {
+            pipeline_id: "pipe-1".to_string(),
+        })
+        .await
+        .unwrap();
+
+    // A cancelled command reports nothing, and drops the executor's sender clone
+    drop(executor);
+    let event = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .unwrap();
+    assert!(event.is_none());
+}
+
+#[tokio::test]
+async fn capture_returns_trimmed_stdout() {
+    let executor = setup().await;
+
+    let output = executor
+        .capture("echo ' abc123 '", std::path::Path::new("/tmp"))
+        .await
+        .unwrap();
+    assert_eq!(output, "abc123");
+
+    let err = executor
+        .capture("exit 2", std::path::Path::new("/tmp"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ExecuteError::Shell(_)));
+}
+
+#[tokio::test]
+async fn commands_are_killed_after_the_timeout() {
+    let executor = setup()
+        .await
+        .with_command_timeout(Duration::from_millis(100));
+
+    let err = executor
+        .capture("sleep 5", std::path::Path::new("/tmp"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ExecuteError::ShellTimeout(_, _)));
+
+    let err = executor
+        .exit_code("sleep 5", std::path::Path::new("/tmp"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ExecuteError::ShellTimeout(_, _)));
+}
+
+#[tokio::test]
+async fn hooks_are_killed_after_the_timeout() {
+    let dir = tempdir().unwrap();
+    let executor = setup()
+        .await
+        .with_command_timeout(Duration::from_millis(100));
+
+    executor
+        .execute(Effect::Hook {
+            name: "build.on_phase".to_string(),
+            command: "echo $$ > hook.pid; sleep 60".to_string(),
+            cwd: dir.path().to_path_buf(),
+        })
+        .await
+        .unwrap();
+
+    // The hook runs in the background; wait for it to be killed
+    for _ in 0..200 {
+        let stat = std::fs::read_to_string(dir.path().join("hook.pid"))
+            .ok()
+            .map(|pid| {
+                std::fs::read_to_string(format!("/proc/{}/stat", pid.trim())).unwrap_or_default()
+            });
+        if stat.is_some_and(|stat| stat.is_empty() || stat.contains(") Z ")) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(25)).await;
+    }
+    panic!("hook was not killed after its timeout");
+}
@@ -0,0 +1,99 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Guard failure policy and effects.
+//!
+//! Deciding what a failing guard does next is kept free of I/O so the
+//! retry/timeout rules can be tested without running shell conditions.
+
+use oj_core::{Effect, Event, Operation, PhaseStatus, Pipeline};
+use oj_runbook::{GuardAction, GuardDef};
+use std::time::Duration;
+
+/// What to do after a guard condition fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuardFailure {
+    /// Check again after the delay
+    Retry(Duration),
+    /// Wait for one of the guard's `wake_on` events
+    AwaitEvent,
+    /// Out of retries with no timeout left to wait out
+    Exhausted,
+    /// Unsatisfied for longer than the guard's timeout
+    TimedOut(GuardAction),
+}
+
+/// Decide the next step for a guard that has failed `attempts` times,
+/// the first of them `elapsed` ago
+pub fn next_step(def: &GuardDef, attempts: u32, elapsed: Duration) -> GuardFailure {
+    if def.timeout.is_some_and(|timeout| elapsed >= timeout) {
+        return GuardFailure::TimedOut(def.on_timeout);
+    }
+    match (def.retry, def.timeout) {
+        (Some(retry), _) if attempts > retry.max => GuardFailure::Exhausted,
+        (Some(_), _) => GuardFailure::Retry(def.poll_interval()),
+        // Event-driven guards only need a timer to catch the timeout
+        (None, Some(timeout)) if !def.wake_on.is_empty() => {
+            GuardFailure::Retry(timeout.saturating_sub(elapsed))
+        }
+        (None, Some(_)) => GuardFailure::Retry(def.poll_interval()),
+        (None, None) if !def.wake_on.is_empty() => GuardFailure::AwaitEvent,
+        (None, None) => GuardFailure::Exhausted,
+    }
+}
+
+/// Timer that re-checks a failing guard
+pub fn guard_retry_timer(pipeline_id: &str) -> String {
+    format!("guard:{}:retry", pipeline_id)
+}
+
+/// Build effects to wait on a failing guard, re-checking after `delay` if given
+pub fn guard_wait_effects(pipeline_id: &str, guard: &str, delay: Option<Duration>) -> Vec<Effect> {
+    let mut effects = vec![Effect::Emit {
+        event: Event::Custom {
+            name: "guard:waiting".to_string(),
+            data: serde_json::json!({
+                "pipeline_id": pipeline_id,
+                "guard": guard,
+            }),
+        },
+    }];
+    if let Some(delay) = delay {
+        effects.push(Effect::SetTimer {
+            id: guard_retry_timer(pipeline_id),
+            duration: delay,
+        });
+    }
+    effects
+}
+
+/// Build effects to hand a timed-out guard to a human
+pub fn guard_escalate_effects(pipeline: &Pipeline, guard: &str) -> Vec<Effect> {
+    vec![
+        Effect::Emit {
+            event: Event::Custom {
+                name: "pipeline:escalate".to_string(),
+                data: serde_json::json!({
+                    "pipeline_id": pipeline.id,
+                    "pipeline_name": pipeline.name,
+                    "phase": pipeline.phase,
+                    "reason": format!("guard {} timed out", guard),
+                }),
+            },
+        },
+        Effect::Notify {
+            title: format!("Pipeline needs attention: {}", pipeline.name),
+            message: format!("guard {} timed out", guard),
+        },
+        Effect::Persist {
+            operation: Operation::PhaseStatusUpdate {
+                pipeline_id: pipeline.id.clone(),
+                status: PhaseStatus::Waiting,
+            },
+        },
+    ]
+}
+
+#[cfg(test)]
+#[path = "guards_tests.rs"]
+mod tests;
@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+use oj_runbook::RetryConfig;
+
+const SECOND: Duration = Duration::from_secs(1);
+
+#[test]
+fn plain_guard_fails_immediately() {
+    let def = GuardDef::new("check", "true");
+    assert_eq!(next_step(&def, 1, Duration::ZERO), GuardFailure::Exhausted);
+}
+
+#[test]
+fn retries_until_max_then_exhausts() {
+    let mut def = GuardDef::new("tests_pass", "make test");
+    def.retry = Some(RetryConfig {
+        max: 2,
+        interval: 5 * SECOND,
+    });
+
+    assert_eq!(
+        next_step(&def, 1, Duration::ZERO),
+        GuardFailure::Retry(5 * SECOND)
+    );
+    assert_eq!(
+        next_step(&def, 2, 5 * SECOND),
+        GuardFailure::Retry(5 * SECOND)
+    );
+    assert_eq!(next_step(&def, 3, 10 * SECOND), GuardFailure::Exhausted);
+}
+
+#[test]
+fn timeout_polls_then_applies_on_timeout() {
+    let mut def = GuardDef::new("plan_exists", "test -f plan.md");
+    def.timeout = Some(60 * SECOND);
+    def.on_timeout = GuardAction::Escalate;
+
+    assert!(matches!(
+        next_step(&def, 5, 50 * SECOND),
+        GuardFailure::Retry(_)
+    ));
+    assert_eq!(
+        next_step(&def, 6, 60 * SECOND),
+        GuardFailure::TimedOut(GuardAction::Escalate)
+    );
+}
+
+#[test]
+fn timeout_wins_over_remaining_retries() {
+    let mut def = GuardDef::new("slow", "false");
+    def.timeout = Some(10 * SECOND);
+    def.retry = Some(RetryConfig {
+        max: 100,
+        interval: 5 * SECOND,
+    });
+
+    assert_eq!(
+        next_step(&def, 3, 10 * SECOND),
+        GuardFailure::TimedOut(GuardAction::Fail)
+    );
+}
+
+#[test]
+fn wake_on_guard_waits_for_event_instead_of_polling() {
+    let mut def = GuardDef::new("blocker_merged", "false");
+    def.wake_on = vec!["pipeline:{after}:complete".to_string()];
+
+    assert_eq!(next_step(&def, 4, 600 * SECOND), GuardFailure::AwaitEvent);
+}
+
+#[test]
+fn wake_on_guard_with_timeout_rechecks_at_deadline() {
+    let mut def = GuardDef::new("blocker_merged", "false");
+    def.wake_on = vec!["pipeline:{after}:complete".to_string()];
+    def.timeout = Some(60 * SECOND);
+
+    assert_eq!(
+        next_step(&def, 1, 15 * SECOND),
+        GuardFailure::Retry(45 * SECOND)
+    );
+}
@@ -8,19 +8,31 @@
 
 //! Otter Jobs execution engine
 
+mod action;
+mod coordination;
+mod cron;
 mod error;
 mod events;
 mod executor;
+mod guards;
 mod monitor;
 mod phases;
+pub mod pipeline_log;
+mod queue;
+mod resume;
+mod rules;
 mod runtime;
 mod scheduler;
 pub mod session_log;
+mod shell;
 mod spawn;
+mod strategy;
+mod subscriptions;
+mod worker;
 mod workspace;
 
 pub use error::RuntimeError;
-pub use executor::{ExecuteError, Executor};
+pub use executor::{ExecuteError, Executor, DEFAULT_COMMAND_TIMEOUT};
 pub use runtime::{Runtime, RuntimeConfig, RuntimeDeps};
 pub use scheduler::Scheduler;
 pub use workspace::prepare_for_agent;
@@ -4,7 +4,8 @@
 //! Session monitoring for agent pipelines.
 //!
 //! Handles detection of agent state from session logs and triggers
-//! appropriate actions (nudge, recover, escalate, etc.).
+//! appropriate actions (nudge, recover, escalate, etc.). Runbook
+//! `[monitor.*]` response chains reuse the same actions.
 
 use crate::session_log::FailureReason;
 use crate::RuntimeError;
@@ -47,9 +48,18 @@ pub fn get_agent_def<'a>(
         RuntimeError::PipelineNotFound(format!("phase {} not found", pipeline.phase))
     })?;
 
-    // Extract agent name from run directive
-    let agent_name = match &phase_def.run {
-        RunDirective::Agent { agent } => agent,
+    // Extract agent name from run directive, following a strategy to its current attempt
+    let run = match (&phase_def.run, &pipeline.strategy) {
+        (RunDirective::Strategy { strategy }, Some(state)) if state.phase == pipeline.phase => {
+            runbook
+                .get_strategy(strategy)
+                .and_then(|s| s.attempt(state.attempt))
+                .map(|a| &a.run)
+        }
+        (run, _) => Some(run),
+    };
+    let agent_name = match run {
+        Some(RunDirective::Agent { agent }) => agent,
         _ => {
             return Err(RuntimeError::InvalidRunDirective {
                 context: format!("phase {}", pipeline.phase),
@@ -71,7 +81,12 @@ pub fn build_action_effects(
     trigger: &str,
     inputs: &HashMap<String, String>,
 ) -> Result<ActionEffects, RuntimeError> {
-    let action = action_config.action();
+    let Some(action) = action_config.action() else {
+        // Named actions run their own command rather than building effects
+        return Err(RuntimeError::ActionNotFound(
+            action_config.name().unwrap_or_default().to_string(),
+        ));
+    };
     let message = action_config.message();
 
     tracing::info!(
@@ -176,6 +191,34 @@ pub fn build_action_effects(
     }
 }
 
+/// The key that identifies a runbook monitor's source item across checks:
+/// its `id`, `name` or `path` field
+pub fn source_item_key(item: &HashMap<String, String>) -> Option<&str> {
+    ["id", "name", "path"]
+        .iter()
+        .find_map(|field| item.get(*field))
+        .map(String::as_str)
+}
+
+/// Build effects to escalate a monitored item that isn't a pipeline
+pub fn escalate_item_effects(monitor: &str, item_key: &str) -> Vec<Effect> {
+    vec![
+        Effect::Emit {
+            event: oj_core::Event::Custom {
+                name: "monitor:escalate".to_string(),
+                data: serde_json::json!({
+                    "monitor": monitor,
+                    "item": item_key,
+                }),
+            },
+        },
+        Effect::Notify {
+            title: format!("Monitor needs attention: {}", monitor),
+            message: item_key.to_string(),
+        },
+    ]
+}
+
 /// Results from building action effects
 pub enum ActionEffects {
     /// Send nudge message to session
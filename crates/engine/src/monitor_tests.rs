@@ -7,7 +7,6 @@ use super::*;
 use oj_core::{PhaseStatus, Pipeline};
 use oj_runbook::{ActionConfig, AgentAction, AgentDef};
 use std::collections::HashMap;
-use std::time::Instant;
 
 fn test_pipeline() -> Pipeline {
     Pipeline {
@@ -19,9 +18,15 @@ fn test_pipeline() -> Pipeline {
         session_id: Some("sess-1".to_string()),
         workspace_path: Some("/tmp/test".into()),
         inputs: HashMap::new(),
-        created_at: Instant::now(),
-        phase_started_at: Instant::now(),
+        outputs: HashMap::new(),
+        created_at_ms: 0,
+        updated_at_ms: 0,
+        phase_started_at_ms: 0,
         error: None,
+        strategy: None,
+        recovery: HashMap::new(),
+        history: Vec::new(),
+        parent: None,
     }
 }
 
@@ -129,3 +134,26 @@ fn escalate_returns_escalate_effects() {
     let result = build_action_effects(&pipeline, &agent, &config, "idle", &HashMap::new());
     assert!(matches!(result, Ok(ActionEffects::Escalate { .. })));
 }
+
+#[test]
+fn source_item_key_prefers_id() {
+    let item: HashMap<String, String> = [("name", "feat"), ("id", "pipe-1")]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    assert_eq!(source_item_key(&item), Some("pipe-1"));
+
+    let item = [("path".to_string(), "a.log".to_string())].into();
+    assert_eq!(source_item_key(&item), Some("a.log"));
+    assert_eq!(source_item_key(&HashMap::new()), None);
+}
+
+#[test]
+fn escalate_item_emits_and_notifies() {
+    let effects = escalate_item_effects("stale_locks", "lock-1");
+    assert!(matches!(
+        &effects[0],
+        Effect::Emit { event: oj_core::Event::Custom { name, .. } } if name == "monitor:escalate"
+    ));
+    assert!(matches!(&effects[1], Effect::Notify { message, .. } if message == "lock-1"));
+}
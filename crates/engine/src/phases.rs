@@ -5,7 +5,10 @@
 //!
 //! Helpers for building effects that transition pipelines between phases.
 
-use oj_core::{Effect, Event, Operation, PhaseStatus, Pipeline};
+use oj_core::{Effect, Event, Operation, PhaseStatus, PhaseTrigger, Pipeline};
+use oj_runbook::PipelineEvents;
+use std::collections::HashMap;
+use std::path::Path;
 
 /// Build effects to mark a phase as running
 pub fn phase_start_effects(pipeline_id: &str, phase_name: &str) -> Vec<Effect> {
@@ -28,13 +31,21 @@ pub fn phase_start_effects(pipeline_id: &str, phase_name: &str) -> Vec<Effect> {
     ]
 }
 
-/// Build effects to transition to the next phase
-pub fn phase_transition_effects(pipeline: &Pipeline, next_phase: &str) -> Vec<Effect> {
+/// Build effects to transition to the next phase, recording what the finished
+/// phase published
+pub fn phase_transition_effects(
+    pipeline: &Pipeline,
+    next_phase: &str,
+    outputs: HashMap<String, String>,
+) -> Vec<Effect> {
     vec![
         Effect::Persist {
             operation: Operation::PipelineTransition {
                 id: pipeline.id.clone(),
                 phase: next_phase.to_string(),
+                trigger: PhaseTrigger::Next,
+                error: None,
+                outputs,
             },
         },
         Effect::Emit {
@@ -52,10 +63,17 @@ pub fn phase_transition_effects(pipeline: &Pipeline, next_phase: &str) -> Vec<Ef
 /// Build effects to transition to failure phase with error
 pub fn failure_transition_effects(pipeline: &Pipeline, on_fail: &str, error: &str) -> Vec<Effect> {
     vec![
+        // The phase may have been failed while its command was still running
+        Effect::CancelShell {
+            pipeline_id: pipeline.id.clone(),
+        },
         Effect::Persist {
             operation: Operation::PipelineTransition {
                 id: pipeline.id.clone(),
                 phase: on_fail.to_string(),
+                trigger: PhaseTrigger::OnFail,
+                error: Some(error.to_string()),
+                outputs: HashMap::new(),
             },
         },
         Effect::Emit {
@@ -74,10 +92,16 @@ pub fn failure_transition_effects(pipeline: &Pipeline, on_fail: &str, error: &st
 /// Build effects to mark pipeline as failed (terminal)
 pub fn failure_effects(pipeline: &Pipeline, error: &str) -> Vec<Effect> {
     vec![
+        Effect::CancelShell {
+            pipeline_id: pipeline.id.clone(),
+        },
         Effect::Persist {
             operation: Operation::PipelineTransition {
                 id: pipeline.id.clone(),
                 phase: "failed".to_string(),
+                trigger: PhaseTrigger::Failed,
+                error: Some(error.to_string()),
+                outputs: HashMap::new(),
             },
         },
         Effect::Emit {
@@ -104,6 +128,9 @@ pub fn completion_effects(pipeline: &Pipeline) -> Vec<Effect> {
             operation: Operation::PipelineTransition {
                 id: pipeline.id.clone(),
                 phase: "done".to_string(),
+                trigger: PhaseTrigger::Next,
+                error: None,
+                outputs: HashMap::new(),
             },
         });
     }
@@ -126,6 +153,14 @@ pub fn completion_effects(pipeline: &Pipeline) -> Vec<Effect> {
         },
     });
 
+    // Scoped by name so guards can wake on a specific pipeline finishing
+    effects.push(Effect::Emit {
+        event: Event::Custom {
+            name: format!("pipeline:{}:complete", pipeline.name),
+            data: serde_json::json!({ "pipeline_id": pipeline.id }),
+        },
+    });
+
     // Cleanup session if exists
     if let Some(session_id) = &pipeline.session_id {
         effects.push(Effect::Kill {
@@ -140,3 +175,21 @@ pub fn completion_effects(pipeline: &Pipeline) -> Vec<Effect> {
 
     effects
 }
+
+/// Build the effect for a `[pipeline.X.events]` hook, if the runbook defines one
+///
+/// `vars` should already include `phase`, and `error` for failures.
+pub fn hook_effect(
+    pipeline: &Pipeline,
+    events: &PipelineEvents,
+    hook: &str,
+    vars: &HashMap<String, String>,
+    cwd: &Path,
+) -> Option<Effect> {
+    let command = events.get(hook)?;
+    Some(Effect::Hook {
+        name: format!("{}.{}", pipeline.kind, hook),
+        command: oj_runbook::interpolate(command, vars),
+        cwd: cwd.to_path_buf(),
+    })
+}
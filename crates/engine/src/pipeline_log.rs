@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Per-pipeline log files
+
+use oj_core::clock::format_epoch_ms;
+use oj_core::{Clock, SystemClock};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// The log file of a pipeline under `log_root`
+pub fn log_path(log_root: &Path, pipeline_id: &str) -> PathBuf {
+    log_root.join(format!("{}.log", pipeline_id))
+}
+
+/// Timer that snapshots a pipeline agent's output into its log
+pub fn snapshot_timer(pipeline_id: &str) -> String {
+    format!("session:{}:snapshot", pipeline_id)
+}
+
+/// Format one log line: `<time> [<phase>] <text>`
+pub fn format_line(epoch_ms: u64, phase: &str, text: &str) -> String {
+    format!("{} [{}] {}", format_epoch_ms(epoch_ms), phase, text)
+}
+
+/// The phase a log line was written in
+pub fn line_phase(line: &str) -> Option<&str> {
+    let (_, rest) = line.split_once(" [")?;
+    rest.split_once(']').map(|(phase, _)| phase)
+}
+
+/// Read the complete lines of a log written since byte `offset`
+///
+/// Returns the lines, keeping only those of `phase` if given, and the offset
+/// to read from next. A line still being written is left for the next read,
+/// and a log that does not exist yet reads as empty.
+pub fn read_from(path: &Path, offset: u64, phase: Option<&str>) -> io::Result<(Vec<String>, u64)> {
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok((Vec::new(), offset)),
+        Err(e) => return Err(e),
+    };
+    file.seek(SeekFrom::Start(offset))?;
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf)?;
+
+    let complete = buf.iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+    let lines = String::from_utf8_lossy(&buf[..complete])
+        .lines()
+        .filter(|line| phase.is_none() || line_phase(line) == phase)
+        .map(str::to_string)
+        .collect();
+    Ok((lines, offset + complete as u64))
+}
+
+/// Appends lines to a pipeline's log file, stamped with the time and phase
+///
+/// Logging is best effort: a file that cannot be opened or written is
+/// reported once and the pipeline carries on without it.
+pub struct PipelineLog {
+    path: PathBuf,
+    file: Option<File>,
+}
+
+impl PipelineLog {
+    /// Open a pipeline log for appending, creating it if needed
+    pub fn open(path: &Path) -> Self {
+        let file = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| OpenOptions::new().create(true).append(true).open(path));
+        let file = match file {
+            Ok(file) => Some(file),
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "cannot open pipeline log");
+                None
+            }
+        };
+        Self {
+            path: path.to_path_buf(),
+            file,
+        }
+    }
+
+    /// Append `text` to the log, one line per line of text
+    pub fn write(&mut self, phase: &str, text: &str) {
+        let Some(file) = &mut self.file else {
+            return;
+        };
+        let now = SystemClock.epoch_ms();
+        let mut lines = String::new();
+        for line in text.lines() {
+            lines.push_str(&format_line(now, phase, line));
+            lines.push('\n');
+        }
+        if let Err(e) = file.write_all(lines.as_bytes()) {
+            tracing::warn!(path = %self.path.display(), error = %e, "cannot write pipeline log");
+            self.file = None;
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "pipeline_log_tests.rs"]
+mod tests;
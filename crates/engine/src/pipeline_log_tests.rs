@@ -0,0 +1,85 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+use tempfile::tempdir;
+
+#[test]
+fn lines_are_stamped_with_time_and_phase() {
+    assert_eq!(
+        format_line(1_767_225_600_000, "build", "compiling"),
+        "2026-01-01 00:00:00 UTC [build] compiling"
+    );
+}
+
+#[test]
+fn log_appends_each_line() {
+    let dir = tempdir().unwrap();
+    let path = log_path(&dir.path().join("logs"), "pipe-1");
+
+    PipelineLog::open(&path).write("build", "one\ntwo");
+    PipelineLog::open(&path).write("test", "three");
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    let lines: Vec<_> = content.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].ends_with("[build] one"));
+    assert!(lines[1].ends_with("[build] two"));
+    assert!(lines[2].ends_with("[test] three"));
+}
+
+#[test]
+fn unwritable_log_is_ignored() {
+    let dir = tempdir().unwrap();
+    let blocker = dir.path().join("file");
+    std::fs::write(&blocker, "").unwrap();
+
+    // The log directory cannot be created under a regular file
+    let mut log = PipelineLog::open(&log_path(&blocker, "pipe-1"));
+    log.write("build", "lost");
+}
+
+#[test]
+fn line_phase_reads_bracketed_phase() {
+    let line = format_line(1_767_225_600_000, "review", "agent idle [nudge]");
+    assert_eq!(line_phase(&line), Some("review"));
+    assert_eq!(line_phase("no phase here"), None);
+}
+
+#[test]
+fn read_from_resumes_at_offset_and_filters_phase() {
+    let dir = tempdir().unwrap();
+    let path = log_path(dir.path(), "pipe-1");
+    let mut log = PipelineLog::open(&path);
+    log.write("build", "one");
+    log.write("test", "two");
+
+    let (lines, offset) = read_from(&path, 0, Some("test")).unwrap();
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].ends_with("[test] two"));
+
+    log.write("build", "three");
+    let (lines, next) = read_from(&path, offset, None).unwrap();
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].ends_with("[build] three"));
+    assert_eq!(read_from(&path, next, None).unwrap().0.len(), 0);
+}
+
+#[test]
+fn read_from_leaves_partial_line_for_later() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("pipe-1.log");
+    std::fs::write(&path, "done\nhalf").unwrap();
+
+    let (lines, offset) = read_from(&path, 0, None).unwrap();
+    assert_eq!(lines, vec!["done".to_string()]);
+    assert_eq!(offset, 5);
+}
+
+#[test]
+fn read_from_missing_log_is_empty() {
+    let dir = tempdir().unwrap();
+    let (lines, offset) = read_from(&dir.path().join("none.log"), 7, None).unwrap();
+    assert!(lines.is_empty());
+    assert_eq!(offset, 7);
+}
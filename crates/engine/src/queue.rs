@@ -0,0 +1,147 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Queue effects.
+//!
+//! Retry and dead letter decisions are made here against the runbook's queue
+//! configuration and persisted as explicit operations, so WAL replay does not
+//! depend on the configuration the daemon restarts with.
+
+use oj_core::{Clock, Effect, Event, Operation, Queue};
+use std::collections::HashMap;
+
+/// Timer that expires claims and purges dead items of a queue
+pub fn queue_sweep_timer(name: &str) -> String {
+    format!("queue:{}:sweep", name)
+}
+
+/// Timer that polls a queue's source
+pub fn queue_poll_timer(name: &str) -> String {
+    format!("queue:{}:poll", name)
+}
+
+/// Parse the JSON array printed by a queue source into item data
+///
+/// Scalar fields are kept as their string form; nested values as JSON text.
+pub fn parse_source_items(output: &str) -> Result<Vec<HashMap<String, String>>, String> {
+    let items: Vec<serde_json::Map<String, serde_json::Value>> =
+        serde_json::from_str(output).map_err(|e| e.to_string())?;
+    Ok(items
+        .into_iter()
+        .map(|item| {
+            item.into_iter()
+                .filter_map(|(key, value)| {
+                    let value = match value {
+                        serde_json::Value::Null => return None,
+                        serde_json::Value::String(s) => s,
+                        other => other.to_string(),
+                    };
+                    Some((key, value))
+                })
+                .collect()
+        })
+        .collect())
+}
+
+/// Build effects to add an item to a queue
+pub fn push_effects(
+    name: &str,
+    item_id: &str,
+    data: HashMap<String, String>,
+    priority: i64,
+) -> Vec<Effect> {
+    vec![
+        Effect::Persist {
+            operation: Operation::QueuePush {
+                name: name.to_string(),
+                item_id: item_id.to_string(),
+                data,
+                priority,
+            },
+        },
+        Effect::Emit {
+            event: Event::Custom {
+                name: format!("queue:{}:pushed", name),
+                data: serde_json::json!({ "queue": name, "item_id": item_id }),
+            },
+        },
+    ]
+}
+
+/// Build effects to settle a failed attempt on an item
+///
+/// The item goes back to pending until its retries are used up, then to the
+/// dead letter queue or away, depending on the queue's configuration.
+pub fn fail_effects(queue: &Queue, item_id: &str, reason: &str) -> Vec<Effect> {
+    let name = queue.name.clone();
+    let item_id = item_id.to_string();
+    let reason = reason.to_string();
+
+    if !queue.is_exhausted(&item_id) {
+        return vec![Effect::Persist {
+            operation: Operation::QueueFail {
+                name,
+                item_id,
+                reason,
+            },
+        }];
+    }
+
+    let (operation, event) = if queue.config.dead_letter.is_some() {
+        let operation = Operation::QueueDead {
+            name: name.clone(),
+            item_id: item_id.clone(),
+            reason: reason.clone(),
+        };
+        (operation, "dead")
+    } else {
+        let operation = Operation::QueueRemove {
+            name: name.clone(),
+            item_id: item_id.clone(),
+        };
+        (operation, "dropped")
+    };
+    vec![
+        Effect::Persist { operation },
+        Effect::Emit {
+            event: Event::Custom {
+                name: format!("queue:{}:{}", name, event),
+                data: serde_json::json!({
+                    "queue": name,
+                    "item_id": item_id,
+                    "reason": reason,
+                }),
+            },
+        },
+    ]
+}
+
+/// Build effects to expire overdue claims and purge dead items past retention
+pub fn sweep_effects(queue: &Queue, clock: &impl Clock) -> Vec<Effect> {
+    let mut effects = Vec::new();
+    for item_id in queue.expired(clock) {
+        effects.extend(fail_effects(queue, &item_id, "visibility timeout expired"));
+    }
+    for item_id in queue.purgeable(clock) {
+        effects.push(Effect::Persist {
+            operation: Operation::QueueRemove {
+                name: queue.name.clone(),
+                item_id,
+            },
+        });
+    }
+    effects
+}
+
+/// Build the effect that wakes the sweep at the queue's next deadline
+pub fn sweep_timer_effect(queue: &Queue, clock: &impl Clock) -> Option<Effect> {
+    let deadline = queue.next_deadline()?;
+    Some(Effect::SetTimer {
+        id: queue_sweep_timer(&queue.name),
+        duration: deadline.saturating_duration_since(clock.now()),
+    })
+}
+
+#[cfg(test)]
+#[path = "queue_tests.rs"]
+mod tests;
@@ -0,0 +1,113 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+use oj_core::{FakeClock, QueueConfig};
+use std::time::Duration;
+
+fn claimed_queue(max_retries: u32, dead_letter: Option<Duration>, clock: &FakeClock) -> Queue {
+    let mut queue = Queue::new(
+        "bugs".to_string(),
+        QueueConfig {
+            visibility_timeout: Duration::from_secs(60),
+            max_retries,
+            dead_letter,
+            ..QueueConfig::default()
+        },
+    );
+    queue.push("bug-1", HashMap::new(), 0, clock.now());
+    queue.claim("bug-1", "worker-1", clock.now());
+    queue
+}
+
+fn operations(effects: &[Effect]) -> Vec<&Operation> {
+    effects
+        .iter()
+        .filter_map(|e| match e {
+            Effect::Persist { operation } => Some(operation),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn failure_with_retries_left_requeues() {
+    let clock = FakeClock::new();
+    let queue = claimed_queue(1, None, &clock);
+
+    let effects = fail_effects(&queue, "bug-1", "tests failed");
+    assert!(matches!(
+        operations(&effects)[..],
+        [Operation::QueueFail { .. }]
+    ));
+}
+
+#[test]
+fn exhausted_failure_goes_to_dead_letter() {
+    let clock = FakeClock::new();
+    let queue = claimed_queue(0, Some(Duration::from_secs(3600)), &clock);
+
+    let effects = fail_effects(&queue, "bug-1", "tests failed");
+    assert!(matches!(
+        operations(&effects)[..],
+        [Operation::QueueDead { .. }]
+    ));
+    assert!(effects.iter().any(|e| matches!(
+        e,
+        Effect::Emit { event: Event::Custom { name, .. } } if name == "queue:bugs:dead"
+    )));
+}
+
+#[test]
+fn exhausted_failure_without_dead_letter_drops() {
+    let clock = FakeClock::new();
+    let queue = claimed_queue(0, None, &clock);
+
+    let effects = fail_effects(&queue, "bug-1", "tests failed");
+    assert!(matches!(
+        operations(&effects)[..],
+        [Operation::QueueRemove { .. }]
+    ));
+}
+
+#[test]
+fn sweep_expires_overdue_claims() {
+    let clock = FakeClock::new();
+    let queue = claimed_queue(1, None, &clock);
+
+    assert!(sweep_effects(&queue, &clock).is_empty());
+    assert!(matches!(
+        sweep_timer_effect(&queue, &clock),
+        Some(Effect::SetTimer { duration, .. }) if duration == Duration::from_secs(60)
+    ));
+
+    clock.advance(Duration::from_secs(60));
+    let effects = sweep_effects(&queue, &clock);
+    assert!(matches!(
+        operations(&effects)[..],
+        [Operation::QueueFail { reason, .. }] if reason == "visibility timeout expired"
+    ));
+}
+
+#[test]
+fn source_items_flatten_to_strings() {
+    let output = r#"[
+        {"id": "bug-1", "priority": 2, "title": "Crash", "labels": ["bug"], "assignee": null},
+        {"id": 7}
+    ]"#;
+
+    let items = parse_source_items(output).unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0]["id"], "bug-1");
+    assert_eq!(items[0]["priority"], "2");
+    assert_eq!(items[0]["labels"], r#"["bug"]"#);
+    assert!(!items[0].contains_key("assignee"));
+    assert_eq!(items[1]["id"], "7");
+}
+
+#[test]
+fn source_output_must_be_array_of_objects() {
+    assert!(parse_source_items("not json").is_err());
+    assert!(parse_source_items(r#"{"id": "bug-1"}"#).is_err());
+    assert!(parse_source_items("[1, 2]").is_err());
+}
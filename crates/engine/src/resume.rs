@@ -0,0 +1,90 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Deciding how to pick up a pipeline after a daemon restart
+
+use oj_core::{PhaseStatus, Pipeline};
+use oj_runbook::PhaseDef;
+use std::path::Path;
+
+/// Timer that resumes a pipeline after a restart
+pub fn resume_timer(pipeline_id: &str) -> String {
+    format!("pipeline:{}:resume", pipeline_id)
+}
+
+/// What a restarted daemon does with a pipeline it finds in flight
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resume {
+    /// Leave it as it is: terminal, or waiting for a human
+    Leave,
+    /// A guard timed out and escalated to a human: listen for its `wake_on`
+    /// events again
+    AwaitGuard,
+    /// Start the current phase again from the top
+    Start,
+    /// Finish the phase that completed just before the restart
+    Complete,
+    /// Fail the phase, routing to `on_fail` if it has one
+    Fail { error: String },
+    /// The agent is still running: watch it again
+    Monitor,
+    /// The agent's session ended while the daemon was down: apply `on_exit`
+    SessionExited,
+    /// A child pipeline runs the phase: wait for it, or settle the phase if
+    /// it finished while the daemon was down
+    AwaitChild,
+}
+
+/// Decide how to resume a pipeline
+///
+/// `session_alive` is `None` when the pipeline has no recorded session, and
+/// `missing_workspace` is the pipeline's workspace if it has been removed.
+pub fn resume_action(
+    pipeline: &Pipeline,
+    phase_def: Option<&PhaseDef>,
+    session_alive: Option<bool>,
+    missing_workspace: Option<&Path>,
+) -> Resume {
+    if pipeline.is_terminal() {
+        return Resume::Leave;
+    }
+    if let Some(path) = missing_workspace {
+        return Resume::Fail {
+            error: format!("workspace {} no longer exists", path.display()),
+        };
+    }
+    let Some(phase_def) = phase_def else {
+        return Resume::Fail {
+            error: format!("phase {} is no longer in the runbook", pipeline.phase),
+        };
+    };
+
+    match pipeline.phase_status {
+        // Agents waiting for input keep their session and exhausted strategies
+        // their state; anything else waiting was parked by a guard
+        PhaseStatus::Waiting if pipeline.session_id.is_none() && pipeline.strategy.is_none() => {
+            Resume::AwaitGuard
+        }
+        PhaseStatus::Waiting => Resume::Leave,
+        PhaseStatus::Pending => Resume::Start,
+        PhaseStatus::Completed => Resume::Complete,
+        PhaseStatus::Failed => Resume::Fail {
+            error: pipeline
+                .error
+                .clone()
+                .unwrap_or_else(|| "phase failed before restart".to_string()),
+        },
+        PhaseStatus::Running if phase_def.run.is_pipeline() => Resume::AwaitChild,
+        PhaseStatus::Running => match session_alive {
+            Some(true) => Resume::Monitor,
+            Some(false) if phase_def.is_agent() => Resume::SessionExited,
+            // Shell commands died with the daemon; strategies pick up at their
+            // recorded attempt
+            _ => Resume::Start,
+        },
+    }
+}
+
+#[cfg(test)]
+#[path = "resume_tests.rs"]
+mod tests;
@@ -0,0 +1,169 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+use oj_core::FakeClock;
+use oj_runbook::RunDirective;
+use std::collections::{BTreeMap, HashMap};
+
+fn test_pipeline(status: PhaseStatus) -> Pipeline {
+    let mut pipeline = Pipeline::new(
+        "pipe-1".to_string(),
+        "build".to_string(),
+        "feature".to_string(),
+        HashMap::new(),
+        "work".to_string(),
+        &FakeClock::new(),
+    );
+    pipeline.phase_status = status;
+    pipeline
+}
+
+fn test_phase(run: RunDirective) -> PhaseDef {
+    PhaseDef {
+        name: "work".to_string(),
+        run,
+        next: None,
+        next_on_exit: BTreeMap::new(),
+        when: Vec::new(),
+        on_fail: None,
+        lock: None,
+        semaphore: None,
+        slots: 1,
+        pre: Vec::new(),
+        post: Vec::new(),
+        timeout: None,
+        outputs: HashMap::new(),
+    }
+}
+
+fn agent_phase() -> PhaseDef {
+    test_phase(RunDirective::Agent {
+        agent: "worker".to_string(),
+    })
+}
+
+fn shell_phase() -> PhaseDef {
+    test_phase(RunDirective::Shell("make".to_string()))
+}
+
+#[test]
+fn live_agent_is_monitored() {
+    let pipeline = test_pipeline(PhaseStatus::Running);
+    let action = resume_action(&pipeline, Some(&agent_phase()), Some(true), None);
+    assert_eq!(action, Resume::Monitor);
+}
+
+#[test]
+fn dead_agent_session_applies_on_exit() {
+    let pipeline = test_pipeline(PhaseStatus::Running);
+    let action = resume_action(&pipeline, Some(&agent_phase()), Some(false), None);
+    assert_eq!(action, Resume::SessionExited);
+}
+
+#[test]
+fn agent_without_session_is_respawned() {
+    let pipeline = test_pipeline(PhaseStatus::Running);
+    let action = resume_action(&pipeline, Some(&agent_phase()), None, None);
+    assert_eq!(action, Resume::Start);
+}
+
+#[test]
+fn interrupted_shell_is_rerun() {
+    let pipeline = test_pipeline(PhaseStatus::Running);
+    assert_eq!(
+        resume_action(&pipeline, Some(&shell_phase()), None, None),
+        Resume::Start
+    );
+}
+
+#[test]
+fn running_child_pipeline_is_awaited() {
+    let pipeline = test_pipeline(PhaseStatus::Running);
+    let phase = test_phase(RunDirective::Pipeline {
+        pipeline: "build".to_string(),
+        inputs: HashMap::new(),
+    });
+    assert_eq!(
+        resume_action(&pipeline, Some(&phase), None, None),
+        Resume::AwaitChild
+    );
+}
+
+#[test]
+fn settled_phases_finish_their_transition() {
+    let phase = shell_phase();
+    let pending = test_pipeline(PhaseStatus::Pending);
+    assert_eq!(
+        resume_action(&pending, Some(&phase), None, None),
+        Resume::Start
+    );
+    let completed = test_pipeline(PhaseStatus::Completed);
+    assert_eq!(
+        resume_action(&completed, Some(&phase), None, None),
+        Resume::Complete
+    );
+    let mut failed = test_pipeline(PhaseStatus::Failed);
+    failed.error = Some("exit 2".to_string());
+    assert_eq!(
+        resume_action(&failed, Some(&phase), None, None),
+        Resume::Fail {
+            error: "exit 2".to_string()
+        }
+    );
+}
+
+#[test]
+fn escalated_and_terminal_pipelines_are_left_alone() {
+    let mut waiting = test_pipeline(PhaseStatus::Waiting);
+    waiting.session_id = Some("sess-1".to_string());
+    assert_eq!(
+        resume_action(&waiting, Some(&agent_phase()), Some(false), None),
+        Resume::Leave
+    );
+    let mut exhausted = test_pipeline(PhaseStatus::Waiting);
+    exhausted.strategy = Some(oj_core::StrategyState {
+        phase: "work".to_string(),
+        attempt: 1,
+        checkpoint: None,
+    });
+    assert_eq!(
+        resume_action(&exhausted, Some(&shell_phase()), None, None),
+        Resume::Leave
+    );
+    let mut done = test_pipeline(PhaseStatus::Completed);
+    done.phase = "done".to_string();
+    assert_eq!(
+        resume_action(&done, None, None, Some(Path::new("/gone/feature"))),
+        Resume::Leave
+    );
+}
+
+#[test]
+fn pipeline_parked_by_a_guard_awaits_it_again() {
+    let waiting = test_pipeline(PhaseStatus::Waiting);
+    assert_eq!(
+        resume_action(&waiting, Some(&shell_phase()), None, None),
+        Resume::AwaitGuard
+    );
+}
+
+#[test]
+fn missing_workspace_or_phase_fails() {
+    let pipeline = test_pipeline(PhaseStatus::Running);
+    assert_eq!(
+        resume_action(
+            &pipeline,
+            Some(&shell_phase()),
+            None,
+            Some(Path::new("/gone/feature"))
+        ),
+        Resume::Fail {
+            error: "workspace /gone/feature no longer exists".to_string()
+        }
+    );
+    assert!(matches!(
+        resume_action(&pipeline, None, None, None),
+        Resume::Fail { .. }
+    ));
+}
@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! `[[on]]` event rule helpers
+
+use std::collections::HashMap;
+
+/// Flatten custom event data into fields for rule filters and interpolation
+///
+/// Nested objects are reachable by dotted keys (`bug.id`) and also kept
+/// whole as JSON text, as are arrays. Nulls are dropped.
+pub fn event_fields(data: &serde_json::Value) -> HashMap<String, String> {
+    let mut fields = HashMap::new();
+    if let serde_json::Value::Object(map) = data {
+        flatten_into(&mut fields, "", map);
+    }
+    fields
+}
+
+fn flatten_into(
+    fields: &mut HashMap<String, String>,
+    prefix: &str,
+    map: &serde_json::Map<String, serde_json::Value>,
+) {
+    for (key, value) in map {
+        let key = format!("{}{}", prefix, key);
+        match value {
+            serde_json::Value::Null => continue,
+            serde_json::Value::String(s) => {
+                fields.insert(key, s.clone());
+            }
+            serde_json::Value::Object(nested) => {
+                flatten_into(fields, &format!("{}.", key), nested);
+                fields.insert(key, value.to_string());
+            }
+            other => {
+                fields.insert(key, other.to_string());
+            }
+        }
+    }
+}
+
+/// Interpolate a rule's `args`/`data` table, or pass the event's fields through
+pub fn rule_values(
+    values: Option<&HashMap<String, String>>,
+    fields: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    match values {
+        Some(values) => values
+            .iter()
+            .map(|(key, template)| (key.clone(), oj_runbook::interpolate(template, fields)))
+            .collect(),
+        None => fields.clone(),
+    }
+}
+
+#[cfg(test)]
+#[path = "rules_tests.rs"]
+mod tests;
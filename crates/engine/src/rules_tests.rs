@@ -0,0 +1,42 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+
+#[test]
+fn event_fields_flatten_nested_objects() {
+    let fields = event_fields(&serde_json::json!({
+        "branch": "main",
+        "attempt": 2,
+        "ok": true,
+        "skip": null,
+        "labels": ["a", "b"],
+        "bug": { "id": "7", "owner": { "name": "sam" } },
+    }));
+
+    assert_eq!(fields["branch"], "main");
+    assert_eq!(fields["attempt"], "2");
+    assert_eq!(fields["ok"], "true");
+    assert!(!fields.contains_key("skip"));
+    assert_eq!(fields["labels"], r#"["a","b"]"#);
+    assert_eq!(fields["bug.id"], "7");
+    assert_eq!(fields["bug.owner.name"], "sam");
+    assert!(fields["bug"].contains(r#""id":"7""#));
+}
+
+#[test]
+fn event_fields_of_non_object_are_empty() {
+    assert!(event_fields(&serde_json::json!("merge")).is_empty());
+}
+
+#[test]
+fn rule_values_interpolate_or_pass_through() {
+    let fields: HashMap<String, String> =
+        [("id".to_string(), "7".to_string())].into_iter().collect();
+    let values: HashMap<String, String> = [("name".to_string(), "bug-{id}".to_string())]
+        .into_iter()
+        .collect();
+
+    assert_eq!(rule_values(Some(&values), &fields)["name"], "bug-7");
+    assert_eq!(rule_values(None, &fields), fields);
+}
@@ -3,18 +3,38 @@
 
 //! Runtime for the Otter Jobs engine
 
-use crate::monitor::{self, ActionEffects};
 use crate::phases;
-use crate::session_log::{find_session_log, SessionLogWatcher, SessionState};
+use crate::pipeline_log;
+use crate::session_log::SessionLogWatcher;
+use crate::subscriptions::Subscriptions;
 use crate::{error::RuntimeError, Executor, Scheduler};
 use oj_adapters::{NotifyAdapter, RepoAdapter, SessionAdapter};
-use oj_core::{Clock, Effect, Event, IdGen, Operation, PhaseStatus, Pipeline};
-use oj_runbook::Runbook;
+use oj_core::{Clock, Effect, Event, IdGen, Operation, Pipeline};
+use oj_runbook::{ConditionError, OutputSource, PhaseDef, Runbook};
 use oj_storage::{MaterializedState, Wal};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use tokio::sync::mpsc;
+
+mod actions;
+mod agents;
+mod children;
+mod coordination;
+mod cron;
+mod guards;
+mod logs;
+mod monitors;
+mod pipelines;
+mod queue;
+mod resume;
+mod rules;
+mod strategy;
+mod worker;
+
+use coordination::Acquisition;
+use guards::{GuardCheck, GuardStage, GuardWait};
 
 /// Runtime path configuration
 pub struct RuntimeConfig {
@@ -22,6 +42,10 @@ pub struct RuntimeConfig {
     pub project_root: PathBuf,
     /// Directory where worktrees are created
     pub worktree_root: PathBuf,
+    /// Directory holding per-pipeline log files
+    pub log_root: PathBuf,
+    /// Longest a command the runtime waits on (conditions, checks) may run
+    pub command_timeout: Duration,
 }
 
 /// Runtime adapter dependencies
@@ -31,18 +55,32 @@ pub struct RuntimeDeps<S, R, N> {
     pub notify: N,
     pub wal: Arc<Mutex<Wal>>,
     pub state: Arc<Mutex<MaterializedState>>,
+    /// Where background shell phases report back; without it they run inline
+    pub events: Option<mpsc::Sender<Event>>,
 }
 
 /// Runtime that coordinates the system
 pub struct Runtime<S, R, N, C: Clock, I: IdGen> {
-    executor: Executor<S, R, N>,
+    executor: Executor<S, R, N, C>,
     runbook: Runbook,
     clock: C,
     id_gen: I,
     project_root: PathBuf,
     worktree_root: PathBuf,
+    log_root: PathBuf,
     /// Session log watchers, keyed by pipeline ID
     session_watchers: Mutex<HashMap<String, SessionLogWatcher>>,
+    /// Agent output last copied into each pipeline's log, keyed by pipeline ID
+    output_snapshots: Mutex<HashMap<String, String>>,
+    /// Outputs of finished phases waiting to be recorded by the pipeline's
+    /// next transition, keyed by pipeline ID
+    phase_outputs: Mutex<HashMap<String, HashMap<String, String>>>,
+    /// Pipelines waiting on a failing guard, keyed by pipeline ID
+    guard_waits: Mutex<HashMap<String, GuardWait>>,
+    /// Guard `wake_on` subscriptions of waiting pipelines
+    guard_subscriptions: Mutex<Subscriptions>,
+    /// Worker `wake_on` subscriptions of running workers
+    worker_subscriptions: Mutex<Subscriptions>,
 }
 
 impl<S, R, N, C, I> Runtime<S, R, N, C, I>
@@ -62,13 +100,20 @@ where
         config: RuntimeConfig,
     ) -> Self {
         Self {
-            executor: Executor::new(deps, Arc::new(Mutex::new(Scheduler::new()))),
+            executor: Executor::new(deps, Arc::new(Mutex::new(Scheduler::new())), clock.clone())
+                .with_command_timeout(config.command_timeout),
             runbook,
             clock,
             id_gen,
             project_root: config.project_root,
             worktree_root: config.worktree_root,
+            log_root: config.log_root,
             session_watchers: Mutex::new(HashMap::new()),
+            output_snapshots: Mutex::new(HashMap::new()),
+            phase_outputs: Mutex::new(HashMap::new()),
+            guard_waits: Mutex::new(HashMap::new()),
+            guard_subscriptions: Mutex::new(Subscriptions::new()),
+            worker_subscriptions: Mutex::new(Subscriptions::new()),
         }
     }
 
@@ -98,158 +143,60 @@ where
                 pipeline_id,
                 phase,
                 exit_code,
+                outputs,
             } => {
                 result_events.extend(
-                    self.handle_shell_completed(pipeline_id, phase, *exit_code)
+                    self.handle_shell_completed(pipeline_id, phase, *exit_code, outputs)
                         .await?,
                 );
             }
 
+            Event::ShellTimedOut { pipeline_id, phase } => {
+                result_events.extend(self.handle_shell_timed_out(pipeline_id, phase).await?);
+            }
+
             Event::Timer { id } => {
                 result_events.extend(self.handle_timer(id).await?);
             }
 
             Event::Custom { name, data } => {
                 result_events.extend(self.handle_custom_event(name, data).await?);
+                result_events.extend(self.run_event_rules(name, data).await);
+                result_events.extend(self.wake_guards(name).await?);
+                result_events.extend(self.wake_queue_workers(name).await?);
+                result_events.extend(self.wake_subscribed_workers(name));
             }
 
-            _ => {
-                // Other events are informational
+            Event::WorkerStart { worker } => {
+                result_events.extend(self.handle_worker_start(worker).await?);
             }
-        }
 
-        Ok(result_events)
-    }
+            Event::WorkerStop { worker } => {
+                result_events.extend(self.handle_worker_stop(worker).await?);
+            }
 
-    async fn handle_command(
-        &self,
-        command: &str,
-        args: &HashMap<String, String>,
-    ) -> Result<Vec<Event>, RuntimeError> {
-        use oj_runbook::RunDirective;
+            Event::WorkerWake { worker } => {
+                result_events.extend(self.handle_worker_wake(worker).await?);
+            }
 
-        let cmd_def = self
-            .runbook
-            .get_command(command)
-            .ok_or_else(|| RuntimeError::CommandNotFound(command.to_string()))?;
+            Event::CronEnable { cron } => {
+                result_events.extend(self.handle_cron_enable(cron).await?);
+            }
 
-        match &cmd_def.run {
-            RunDirective::Pipeline {
-                pipeline: pipeline_name,
-            } => {
-                let pipeline_def = self
-                    .runbook
-                    .get_pipeline(pipeline_name)
-                    .ok_or_else(|| RuntimeError::PipelineDefNotFound(pipeline_name.to_string()))?;
-
-                let pipeline_id = self.id_gen.next();
-                let name = args
-                    .get("name")
-                    .cloned()
-                    .unwrap_or_else(|| pipeline_id.clone());
-                let workspace_path = self.worktree_root.join(&name);
-                let initial_phase = pipeline_def
-                    .first_phase()
-                    .map(|p| p.name.clone())
-                    .unwrap_or_else(|| "init".to_string());
-
-                let effects = vec![
-                    Effect::Persist {
-                        operation: Operation::WorkspaceCreate {
-                            id: pipeline_id.clone(),
-                            path: workspace_path.clone(),
-                            branch: format!("feature/{}", name),
-                        },
-                    },
-                    Effect::WorktreeAdd {
-                        branch: format!("feature/{}", name),
-                        path: workspace_path.clone(),
-                    },
-                    Effect::Persist {
-                        operation: Operation::PipelineCreate {
-                            id: pipeline_id.clone(),
-                            kind: pipeline_name.to_string(),
-                            name: name.clone(),
-                            inputs: args.clone(),
-                            initial_phase,
-                        },
-                    },
-                    Effect::Emit {
-                        event: Event::Custom {
-                            name: "pipeline:created".to_string(),
-                            data: serde_json::json!({"id": pipeline_id, "name": name, "kind": pipeline_name}),
-                        },
-                    },
-                ];
-
-                let mut result_events = self.executor.execute_all(effects).await?;
-                if let Some(first_phase) = pipeline_def.first_phase() {
-                    result_events.extend(
-                        self.start_phase(&pipeline_id, &first_phase.name, args, &workspace_path)
-                            .await?,
-                    );
-                }
-                Ok(result_events)
+            Event::CronDisable { cron } => {
+                result_events.extend(self.handle_cron_disable(cron).await?);
             }
-            RunDirective::Shell(cmd) => Err(RuntimeError::InvalidRunDirective {
-                context: "command".to_string(),
-                directive: format!("shell ({})", cmd),
-            }),
-            RunDirective::Agent { agent } => Err(RuntimeError::InvalidRunDirective {
-                context: "command".to_string(),
-                directive: format!("agent ({})", agent),
-            }),
-            RunDirective::Strategy { strategy } => Err(RuntimeError::InvalidRunDirective {
-                context: "command".to_string(),
-                directive: format!("strategy ({})", strategy),
-            }),
-        }
-    }
 
-    async fn handle_session_exit(
-        &self,
-        session_id: &str,
-        exit_code: i32,
-    ) -> Result<Vec<Event>, RuntimeError> {
-        let pipeline = {
-            let state = self.executor.state();
-            let guard = state.lock().unwrap_or_else(|e| e.into_inner());
-            guard
-                .pipelines
-                .values()
-                .find(|p| p.session_id.as_ref() == Some(&session_id.to_string()))
-                .cloned()
-        };
-        let Some(pipeline) = pipeline else {
-            return Ok(vec![]);
-        };
+            Event::CronRun { cron } => {
+                result_events.extend(self.handle_cron_run(cron).await?);
+            }
 
-        let event = Event::SessionExited {
-            session_id: session_id.to_string(),
-            exit_code,
-        };
-        let (new_pipeline, effects) = pipeline.transition(&event, &self.clock);
-        let mut events = self.executor.execute_all(effects).await?;
-        if new_pipeline.phase_status == PhaseStatus::Completed {
-            events.extend(self.advance_pipeline(&new_pipeline).await?);
+            _ => {
+                // Other events are informational
+            }
         }
-        Ok(events)
-    }
 
-    async fn handle_agent_event(
-        &self,
-        pipeline_id: &str,
-        event: &Event,
-    ) -> Result<Vec<Event>, RuntimeError> {
-        let pipeline = self
-            .get_pipeline(pipeline_id)
-            .ok_or_else(|| RuntimeError::PipelineNotFound(pipeline_id.to_string()))?;
-
-        match event {
-            Event::AgentDone { .. } => self.advance_pipeline(&pipeline).await,
-            Event::AgentError { error, .. } => self.fail_pipeline(&pipeline, error).await,
-            _ => Ok(vec![]),
-        }
+        Ok(result_events)
     }
 
     /// Handle custom events (delegates to events module)
@@ -258,6 +205,12 @@ where
         name: &str,
         data: &serde_json::Value,
     ) -> Result<Vec<Event>, RuntimeError> {
+        if name == "pipeline:resume" {
+            let pipeline_id = data["pipeline_id"].as_str().unwrap_or_default();
+            if let Some(result_events) = self.resume_guard(pipeline_id).await? {
+                return Ok(result_events);
+            }
+        }
         crate::events::handle_custom_event(&self.executor, name, data, |id| self.get_pipeline(id))
             .await
     }
@@ -272,6 +225,84 @@ where
                 .unwrap_or(id);
             return self.handle_session_monitor(pipeline_id).await;
         }
+
+        // Output snapshot timer: session:<pipeline_id>:snapshot
+        if let Some(pipeline_id) = id
+            .strip_prefix("session:")
+            .and_then(|rest| rest.strip_suffix(":snapshot"))
+        {
+            return self.handle_output_snapshot(pipeline_id).await;
+        }
+
+        // Lock timers: lock:<pipeline_id>:retry and lock:<pipeline_id>:heartbeat
+        if let Some(rest) = id.strip_prefix("lock:") {
+            if let Some(pipeline_id) = rest.strip_suffix(":retry") {
+                return self.handle_coordination_retry(pipeline_id).await;
+            }
+            if let Some(pipeline_id) = rest.strip_suffix(":heartbeat") {
+                return self.handle_lock_heartbeat(pipeline_id).await;
+            }
+        }
+
+        // Guard timers: guard:<pipeline_id>:retry
+        if let Some(pipeline_id) = id
+            .strip_prefix("guard:")
+            .and_then(|rest| rest.strip_suffix(":retry"))
+        {
+            return self.handle_guard_retry(pipeline_id).await;
+        }
+
+        // Queue timers: queue:<name>:sweep and queue:<name>:poll
+        if let Some(rest) = id.strip_prefix("queue:") {
+            if let Some(name) = rest.strip_suffix(":sweep") {
+                return self.handle_queue_sweep(name).await;
+            }
+            if let Some(name) = rest.strip_suffix(":poll") {
+                return self.poll_queue(name).await;
+            }
+        }
+
+        // Cron timers: cron:<name>:tick
+        if let Some(name) = id
+            .strip_prefix("cron:")
+            .and_then(|rest| rest.strip_suffix(":tick"))
+        {
+            return self.handle_cron_tick(name).await;
+        }
+
+        // Worker timers: worker:<name>:wake
+        if let Some(name) = id
+            .strip_prefix("worker:")
+            .and_then(|rest| rest.strip_suffix(":wake"))
+        {
+            return self.handle_worker_wake(name).await;
+        }
+
+        // Strategy timers: strategy:<pipeline_id>:timeout
+        if let Some(pipeline_id) = id
+            .strip_prefix("strategy:")
+            .and_then(|rest| rest.strip_suffix(":timeout"))
+        {
+            return self.handle_strategy_timeout(pipeline_id).await;
+        }
+
+        // Resume timers: pipeline:<pipeline_id>:resume
+        if let Some(pipeline_id) = id
+            .strip_prefix("pipeline:")
+            .and_then(|rest| rest.strip_suffix(":resume"))
+        {
+            return self.handle_pipeline_resume(pipeline_id).await;
+        }
+
+        // Semaphore timers: semaphore:<pipeline_id>:retry and semaphore:<pipeline_id>:heartbeat
+        if let Some(rest) = id.strip_prefix("semaphore:") {
+            if let Some(pipeline_id) = rest.strip_suffix(":retry") {
+                return self.handle_coordination_retry(pipeline_id).await;
+            }
+            if let Some(pipeline_id) = rest.strip_suffix(":heartbeat") {
+                return self.handle_semaphore_heartbeat(pipeline_id).await;
+            }
+        }
         Ok(vec![])
     }
 
@@ -281,6 +312,7 @@ where
         pipeline_id: &str,
         phase: &str,
         exit_code: i32,
+        outputs: &HashMap<String, String>,
     ) -> Result<Vec<Event>, RuntimeError> {
         let pipeline = {
             let state = self.executor.state();
@@ -303,14 +335,65 @@ where
             return Ok(vec![]);
         }
 
-        if exit_code == 0 {
-            self.advance_pipeline(&pipeline).await
+        self.record_phase_exit(pipeline_id, exit_code).await?;
+        // A nonzero exit code the phase routes on is an outcome, not a failure
+        let routed = self
+            .phase_def(&pipeline)
+            .is_some_and(|p| p.routes_exit(exit_code));
+        if exit_code == 0 || routed {
+            if !outputs.is_empty() {
+                self.phase_outputs
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .insert(pipeline_id.to_string(), outputs.clone());
+            }
+            // Re-read so routing sees the exit code just recorded
+            let pipeline = self
+                .get_pipeline(pipeline_id)
+                .ok_or_else(|| RuntimeError::PipelineNotFound(pipeline_id.to_string()))?;
+            self.complete_phase(&pipeline).await
         } else {
-            self.fail_pipeline(&pipeline, &format!("shell exited with code {}", exit_code))
+            self.fail_phase(&pipeline, &format!("shell exited with code {}", exit_code))
                 .await
         }
     }
 
+    /// Fail a phase whose shell command was killed for running too long
+    async fn handle_shell_timed_out(
+        &self,
+        pipeline_id: &str,
+        phase: &str,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let pipeline = self
+            .get_pipeline(pipeline_id)
+            .ok_or_else(|| RuntimeError::PipelineNotFound(pipeline_id.to_string()))?;
+        if pipeline.phase != phase {
+            return Ok(vec![]);
+        }
+
+        let error = match self.phase_def(&pipeline).and_then(|p| p.timeout) {
+            Some(timeout) => format!("shell timed out after {:?}", timeout),
+            None => "shell timed out".to_string(),
+        };
+        self.fail_phase(&pipeline, &error).await
+    }
+
+    /// Record the exit code of the current phase in the pipeline's history
+    async fn record_phase_exit(
+        &self,
+        pipeline_id: &str,
+        exit_code: i32,
+    ) -> Result<(), RuntimeError> {
+        let effect = Effect::Persist {
+            operation: Operation::PhaseExited {
+                pipeline_id: pipeline_id.to_string(),
+                exit_code,
+            },
+        };
+        self.executor.execute(effect).await?;
+        Ok(())
+    }
+
     /// Start a pipeline phase by dispatching based on RunDirective
     async fn start_phase(
         &self,
@@ -341,25 +424,43 @@ where
             RuntimeError::PipelineNotFound(format!("phase {} not found", phase_name))
         })?;
 
+        // Pre-guards gate the phase before it competes for locks or slots
+        match self
+            .check_guards(&pipeline, phase_def, GuardStage::Pre)
+            .await?
+        {
+            GuardCheck::Passed => {}
+            GuardCheck::Waiting => return Ok(vec![]),
+            GuardCheck::Failed(reason) => {
+                return Box::pin(self.fail_pipeline(&pipeline, &reason)).await
+            }
+        }
+
+        // Wait for the phase's lock and semaphore slot; the phase stays Pending until both are free
+        if let Acquisition::Blocked(released) =
+            self.acquire_phase_resources(pipeline_id, phase_def).await?
+        {
+            return Box::pin(self.wake_waiters(released)).await;
+        }
+
         let mut result_events = Vec::new();
 
         // Mark phase as running
         let effects = phases::phase_start_effects(pipeline_id, phase_name);
         result_events.extend(self.executor.execute_all(effects).await?);
+        self.log_pipeline(pipeline_id, phase_name, "phase started");
 
         // Dispatch based on run directive
         match &phase_def.run {
             RunDirective::Shell(cmd) => {
-                // Build template variables
-                let mut vars = inputs.clone();
-                vars.insert("pipeline_id".to_string(), pipeline_id.to_string());
-                vars.insert("name".to_string(), pipeline.name.clone());
-                vars.insert(
-                    "workspace".to_string(),
-                    workspace_path.display().to_string(),
-                );
-
-                let command = oj_runbook::interpolate(cmd, &vars);
+                let command = oj_runbook::interpolate(cmd, &self.template_vars(&pipeline));
+                let mut stdout_vars: Vec<String> = phase_def
+                    .outputs
+                    .iter()
+                    .filter(|(_, source)| **source == OutputSource::Stdout)
+                    .map(|(var, _)| var.clone())
+                    .collect();
+                stdout_vars.sort();
 
                 let effects = vec![Effect::Shell {
                     pipeline_id: pipeline_id.to_string(),
@@ -367,6 +468,9 @@ where
                     command,
                     cwd: workspace_path.to_path_buf(),
                     env: HashMap::new(),
+                    timeout: phase_def.timeout,
+                    log_path: self.log_path(pipeline_id),
+                    stdout_vars,
                 }];
 
                 result_events.extend(self.executor.execute_all(effects).await?);
@@ -376,18 +480,18 @@ where
                 result_events.extend(self.spawn_agent(pipeline_id, agent, inputs).await?);
             }
 
-            RunDirective::Pipeline { pipeline } => {
-                return Err(RuntimeError::InvalidRunDirective {
-                    context: format!("phase {}", phase_name),
-                    directive: format!("nested pipeline ({})", pipeline),
-                });
+            RunDirective::Pipeline {
+                pipeline: child_kind,
+                inputs,
+            } => {
+                result_events.extend(
+                    self.start_child_pipeline(&pipeline, phase_name, child_kind, inputs)
+                        .await?,
+                );
             }
 
             RunDirective::Strategy { strategy } => {
-                return Err(RuntimeError::InvalidRunDirective {
-                    context: format!("phase {}", phase_name),
-                    directive: format!("strategy ({})", strategy),
-                });
+                result_events.extend(self.start_strategy(&pipeline, strategy).await?);
             }
         }
 
@@ -395,7 +499,74 @@ where
     }
 
     /// Advance pipeline to next phase
+    ///
+    /// Post-guards must pass first; a failing one routes to `on_fail`. Then the
+    /// next phase is chosen, the finished phase's lock and semaphore slot are
+    /// released, and the next waiters are woken once this pipeline has moved on.
     async fn advance_pipeline(&self, pipeline: &Pipeline) -> Result<Vec<Event>, RuntimeError> {
+        if let Some(phase_def) = self.phase_def(pipeline).filter(|_| !pipeline.is_terminal()) {
+            match self
+                .check_guards(pipeline, phase_def, GuardStage::Post)
+                .await?
+            {
+                GuardCheck::Passed => {}
+                GuardCheck::Waiting => return Ok(vec![]),
+                GuardCheck::Failed(reason) => return self.fail_pipeline(pipeline, &reason).await,
+            }
+        }
+
+        let next_phase = match self.next_phase_name(pipeline) {
+            Ok(next_phase) => next_phase,
+            Err(e) => {
+                let reason = format!("cannot route from {}: {}", pipeline.phase, e);
+                return self.fail_pipeline(pipeline, &reason).await;
+            }
+        };
+
+        let released = self.release_phase_resources(pipeline).await?;
+        let mut result_events = self.enter_next_phase(pipeline, next_phase).await?;
+        result_events.extend(self.wake_waiters(released).await?);
+        Ok(result_events)
+    }
+
+    /// Choose the phase after the current one, `None` when the pipeline is done
+    ///
+    /// The phase's exit code and `when` routes come first, then `next`, then
+    /// the order of phases in the runbook.
+    fn next_phase_name(&self, pipeline: &Pipeline) -> Result<Option<String>, ConditionError> {
+        let Some(pipeline_def) = self.runbook.get_pipeline(&pipeline.kind) else {
+            return Ok(None);
+        };
+        let Some(phase_def) = pipeline_def.get_phase(&pipeline.phase) else {
+            return Ok(None);
+        };
+
+        // Conditions can test what the finished phase just published
+        let mut published = pipeline.clone();
+        if let Some(outputs) = self
+            .phase_outputs
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&pipeline.id)
+        {
+            published.outputs.extend(outputs.clone());
+        }
+        let exit_code = pipeline.history.last().and_then(|r| r.exit_code);
+
+        match phase_def.route(exit_code, &self.template_vars(&published))? {
+            Some(next) => Ok(Some(next.to_string())),
+            None => Ok(pipeline_def
+                .next_phase(&pipeline.phase)
+                .map(|p| p.name.clone())),
+        }
+    }
+
+    /// Transition to the chosen next phase and start it
+    async fn enter_next_phase(
+        &self,
+        pipeline: &Pipeline,
+        next_phase_name: Option<String>,
+    ) -> Result<Vec<Event>, RuntimeError> {
         // If current phase is terminal (done/failed), complete the pipeline
         // This handles the case where a "done" phase has a run command that just finished
         if pipeline.is_terminal() {
@@ -403,30 +574,25 @@ where
         }
 
         let pipeline_def = self.runbook.get_pipeline(&pipeline.kind);
-        let current_phase_def = pipeline_def
-            .as_ref()
-            .and_then(|p| p.get_phase(&pipeline.phase));
-
-        // Determine next phase: explicit next > sequential order > complete
-        let next_phase_name = if let Some(phase_def) = current_phase_def {
-            if let Some(next) = &phase_def.next {
-                Some(next.clone())
-            } else if let Some(p) = pipeline_def.as_ref() {
-                p.next_phase(&pipeline.phase).map(|pd| pd.name.clone())
-            } else {
-                None
-            }
-        } else {
-            // Phase not found in runbook - no next phase
-            None
-        };
-
+        let outputs = self
+            .phase_outputs
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&pipeline.id)
+            .unwrap_or_default();
         let mut result_events = Vec::new();
 
         match next_phase_name {
             Some(next_phase) => {
-                let effects = phases::phase_transition_effects(pipeline, &next_phase);
+                let effects = phases::phase_transition_effects(pipeline, &next_phase, outputs);
                 result_events.extend(self.executor.execute_all(effects).await?);
+                self.log_pipeline(
+                    &pipeline.id,
+                    &pipeline.phase,
+                    &format!("next: {}", next_phase),
+                );
+                self.run_pipeline_hook(pipeline, "on_phase", &next_phase, None)
+                    .await?;
 
                 let has_phase_def = pipeline_def
                     .as_ref()
@@ -449,8 +615,11 @@ where
                 }
             }
             None => {
-                let effects = phases::phase_transition_effects(pipeline, "done");
+                let effects = phases::phase_transition_effects(pipeline, "done", outputs);
                 result_events.extend(self.executor.execute_all(effects).await?);
+                self.log_pipeline(&pipeline.id, &pipeline.phase, "next: done");
+                self.run_pipeline_hook(pipeline, "on_phase", "done", None)
+                    .await?;
                 result_events.extend(self.complete_pipeline(pipeline).await?);
             }
         }
@@ -458,77 +627,36 @@ where
         Ok(result_events)
     }
 
-    /// Handle pipeline failure
-    async fn fail_pipeline(
-        &self,
-        pipeline: &Pipeline,
-        error: &str,
-    ) -> Result<Vec<Event>, RuntimeError> {
-        let pipeline_def = self.runbook.get_pipeline(&pipeline.kind);
-        let on_fail = pipeline_def
-            .as_ref()
-            .and_then(|p| p.get_phase(&pipeline.phase))
-            .and_then(|p| p.on_fail.as_ref());
-
-        let mut result_events = Vec::new();
-
-        if let Some(on_fail) = on_fail {
-            let effects = phases::failure_transition_effects(pipeline, on_fail, error);
-            result_events.extend(self.executor.execute_all(effects).await?);
-            result_events.extend(
-                self.start_phase(
-                    &pipeline.id,
-                    on_fail,
-                    &pipeline.inputs,
-                    &self.workspace_path(pipeline),
-                )
-                .await?,
-            );
-        } else {
-            let effects = phases::failure_effects(pipeline, error);
-            result_events.extend(self.executor.execute_all(effects).await?);
-        }
-
-        Ok(result_events)
+    /// Variables available to shell commands and guard conditions
+    ///
+    /// Phase outputs take precedence over inputs of the same name.
+    fn template_vars(&self, pipeline: &Pipeline) -> HashMap<String, String> {
+        let mut vars = pipeline.inputs.clone();
+        vars.extend(pipeline.outputs.clone());
+        vars.insert("pipeline_id".to_string(), pipeline.id.clone());
+        vars.insert("name".to_string(), pipeline.name.clone());
+        vars.insert(
+            "workspace".to_string(),
+            self.workspace_path(pipeline).display().to_string(),
+        );
+        vars
     }
 
-    /// Complete a pipeline
-    async fn complete_pipeline(&self, pipeline: &Pipeline) -> Result<Vec<Event>, RuntimeError> {
-        let effects = phases::completion_effects(pipeline);
-        Ok(self.executor.execute_all(effects).await?)
+    /// Get the runbook definition of a pipeline's current phase
+    fn phase_def(&self, pipeline: &Pipeline) -> Option<&PhaseDef> {
+        self.runbook
+            .get_pipeline(&pipeline.kind)
+            .and_then(|p| p.get_phase(&pipeline.phase))
     }
 
-    /// Spawn an agent for a pipeline
-    async fn spawn_agent(
-        &self,
-        pipeline_id: &str,
-        agent_name: &str,
-        inputs: &HashMap<String, String>,
-    ) -> Result<Vec<Event>, RuntimeError> {
-        let agent_def = self
-            .runbook
-            .get_agent(agent_name)
-            .ok_or_else(|| RuntimeError::AgentNotFound(agent_name.to_string()))?;
+    /// Get the runbook this runtime executes
+    pub fn runbook(&self) -> &Runbook {
+        &self.runbook
+    }
 
-        let pipeline = self
-            .get_pipeline(pipeline_id)
-            .ok_or_else(|| RuntimeError::PipelineNotFound(pipeline_id.to_string()))?;
-        let workspace_path = self.workspace_path(&pipeline);
-
-        let mut effects = crate::spawn::build_spawn_effects(
-            agent_def,
-            &pipeline,
-            pipeline_id,
-            agent_name,
-            inputs,
-            &workspace_path,
-            &self.project_root,
-        )?;
-
-        // Start session monitoring after spawn
-        effects.push(self.start_session_monitor(pipeline_id));
-
-        Ok(self.executor.execute_all(effects).await?)
+    /// Get the scheduler that holds this runtime's timers
+    pub fn scheduler(&self) -> Arc<Mutex<Scheduler>> {
+        self.executor.scheduler()
     }
 
     /// Get current pipelines
@@ -545,6 +673,11 @@ where
         state_guard.get_pipeline(id).cloned()
     }
 
+    /// Get the log file of a pipeline
+    fn log_path(&self, pipeline_id: &str) -> PathBuf {
+        pipeline_log::log_path(&self.log_root, pipeline_id)
+    }
+
     /// Get workspace path for a pipeline
     fn workspace_path(&self, pipeline: &Pipeline) -> PathBuf {
         pipeline
@@ -552,186 +685,8 @@ where
             .clone()
             .unwrap_or_else(|| self.worktree_root.join(&pipeline.name))
     }
-
-    /// Start session monitoring for an agent
-    ///
-    /// Sets a timer that will periodically check the session log state.
-    fn start_session_monitor(&self, pipeline_id: &str) -> Effect {
-        Effect::SetTimer {
-            id: format!("session:{}:check", pipeline_id),
-            duration: Duration::from_secs(10),
-        }
-    }
-
-    /// Handle session monitor timer
-    async fn handle_session_monitor(&self, pipeline_id: &str) -> Result<Vec<Event>, RuntimeError> {
-        let pipeline = self
-            .get_pipeline(pipeline_id)
-            .ok_or_else(|| RuntimeError::PipelineNotFound(pipeline_id.to_string()))?;
-
-        if pipeline.is_terminal() {
-            return Ok(vec![]);
-        }
-
-        let agent_def = monitor::get_agent_def(&self.runbook, &pipeline)?.clone();
-        let workspace_path = pipeline
-            .workspace_path
-            .as_ref()
-            .ok_or_else(|| RuntimeError::PipelineNotFound("no workspace".into()))?;
-
-        let session_id = pipeline.session_id.clone().unwrap_or_default();
-        let log_path = match find_session_log(workspace_path, &session_id) {
-            Some(path) => path,
-            None => {
-                self.executor
-                    .execute(self.start_session_monitor(pipeline_id))
-                    .await?;
-                return Ok(vec![]);
-            }
-        };
-
-        let state = {
-            let mut watchers = self
-                .session_watchers
-                .lock()
-                .unwrap_or_else(|e| e.into_inner());
-            let watcher = watchers
-                .entry(pipeline_id.to_string())
-                .or_insert_with(|| SessionLogWatcher::new(log_path));
-            watcher.check_state()
-        };
-
-        match state {
-            SessionState::Working | SessionState::Unknown => {
-                self.executor
-                    .execute(self.start_session_monitor(pipeline_id))
-                    .await?;
-                Ok(vec![])
-            }
-            SessionState::WaitingForInput => {
-                let effects = monitor::build_action_effects(
-                    &pipeline,
-                    &agent_def,
-                    &agent_def.on_idle,
-                    "idle",
-                    &pipeline.inputs,
-                )?;
-                self.execute_action_effects(&pipeline, effects).await
-            }
-            SessionState::Failed(reason) => {
-                let error_msg = monitor::failure_to_message(&reason);
-                let error_type = monitor::failure_to_error_type(&reason);
-                tracing::error!(pipeline_id = %pipeline.id, error = error_msg, "agent error");
-                let action = agent_def.on_error.action_for(error_type.as_ref());
-                let effects = monitor::build_action_effects(
-                    &pipeline,
-                    &agent_def,
-                    &action,
-                    error_msg,
-                    &pipeline.inputs,
-                )?;
-                self.execute_action_effects(&pipeline, effects).await
-            }
-        }
-    }
-
-    /// Handle Claude process exit (on_exit trigger)
-    pub async fn handle_claude_exited(
-        &self,
-        pipeline_id: &str,
-    ) -> Result<Vec<Event>, RuntimeError> {
-        let pipeline = self
-            .get_pipeline(pipeline_id)
-            .ok_or_else(|| RuntimeError::PipelineNotFound(pipeline_id.to_string()))?;
-
-        if pipeline.is_terminal() {
-            return Ok(vec![]);
-        }
-
-        let agent_def = monitor::get_agent_def(&self.runbook, &pipeline)?.clone();
-        tracing::info!(pipeline_id = %pipeline.id, "claude process exited");
-
-        let effects = monitor::build_action_effects(
-            &pipeline,
-            &agent_def,
-            &agent_def.on_exit,
-            "exit",
-            &pipeline.inputs,
-        )?;
-        self.execute_action_effects(&pipeline, effects).await
-    }
-
-    /// Handle tmux session exit (session is gone)
-    pub async fn handle_tmux_exited(&self, pipeline_id: &str) -> Result<Vec<Event>, RuntimeError> {
-        let pipeline = self
-            .get_pipeline(pipeline_id)
-            .ok_or_else(|| RuntimeError::PipelineNotFound(pipeline_id.to_string()))?;
-
-        tracing::error!(pipeline_id = %pipeline.id, "tmux session exited unexpectedly");
-        self.fail_pipeline(&pipeline, "tmux session exited").await
-    }
-
-    async fn execute_action_effects(
-        &self,
-        pipeline: &Pipeline,
-        effects: ActionEffects,
-    ) -> Result<Vec<Event>, RuntimeError> {
-        match effects {
-            ActionEffects::Nudge { mut effects } => {
-                effects.push(self.start_session_monitor(&pipeline.id));
-                Ok(self.executor.execute_all(effects).await?)
-            }
-            ActionEffects::AdvancePipeline => self.advance_pipeline(pipeline).await,
-            ActionEffects::FailPipeline { error } => self.fail_pipeline(pipeline, &error).await,
-            ActionEffects::Restart {
-                kill_session,
-                workspace_path,
-                agent_name,
-                inputs,
-            } => {
-                self.kill_and_respawn(
-                    kill_session,
-                    Some(workspace_path),
-                    &pipeline.id,
-                    &agent_name,
-                    &inputs,
-                )
-                .await
-            }
-            ActionEffects::Recover {
-                kill_session,
-                agent_name,
-                inputs,
-            } => {
-                self.kill_and_respawn(kill_session, None, &pipeline.id, &agent_name, &inputs)
-                    .await
-            }
-            ActionEffects::Escalate { effects } => Ok(self.executor.execute_all(effects).await?),
-        }
-    }
-
-    async fn kill_and_respawn(
-        &self,
-        kill_session: Option<String>,
-        workspace_path: Option<Option<std::path::PathBuf>>,
-        pipeline_id: &str,
-        agent_name: &str,
-        inputs: &HashMap<String, String>,
-    ) -> Result<Vec<Event>, RuntimeError> {
-        if let Some(sid) = kill_session {
-            self.executor
-                .execute(Effect::Kill { session_id: sid })
-                .await?;
-        }
-        if let Some(Some(path)) = workspace_path {
-            self.executor
-                .execute(Effect::WorktreeRemove { path })
-                .await?;
-        }
-        self.spawn_agent(pipeline_id, agent_name, inputs).await
-    }
 }
 
 #[cfg(test)]
-#[path = "runtime_tests.rs"]
+#[path = "runtime_tests/mod.rs"]
 mod tests;
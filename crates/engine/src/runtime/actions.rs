@@ -0,0 +1,262 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Named actions and agent recovery triggers
+
+use super::Runtime;
+use crate::action::{self, Readiness};
+use crate::error::RuntimeError;
+use crate::monitor::{self, ActionEffects};
+use oj_adapters::{NotifyAdapter, RepoAdapter, SessionAdapter};
+use oj_core::{ChainProgress, Clock, Effect, Event, IdGen, Operation, Pipeline};
+use oj_runbook::{ActionConfig, ActionDef, AgentAction, AgentDef};
+use std::collections::HashMap;
+
+impl<S, R, N, C, I> Runtime<S, R, N, C, I>
+where
+    S: SessionAdapter,
+    R: RepoAdapter,
+    N: NotifyAdapter,
+    C: Clock,
+    I: IdGen,
+{
+    /// Run an agent's `on_idle`/`on_exit`/`on_error` action for its pipeline
+    ///
+    /// A chain takes its next step each time the same trigger fires, skipping
+    /// named actions that are used up, and escalates once every step is spent.
+    pub(super) async fn run_agent_action(
+        &self,
+        pipeline: &Pipeline,
+        agent_def: &AgentDef,
+        hook: &str,
+        config: &ActionConfig,
+        trigger: &str,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let ActionConfig::Chain(steps) = config else {
+            return self
+                .run_agent_step(pipeline, agent_def, config, trigger)
+                .await;
+        };
+
+        let limits: Vec<u32> = steps.iter().map(|step| step.attempts).collect();
+        let mut current = pipeline.recovery.get(hook).copied();
+        loop {
+            let Some(progress) = ChainProgress::advance(current, &limits) else {
+                tracing::info!(pipeline_id = %pipeline.id, hook, "recovery chain exhausted");
+                let escalate = ActionConfig::simple(AgentAction::Escalate);
+                return self
+                    .run_agent_step(pipeline, agent_def, &escalate, trigger)
+                    .await;
+            };
+            let step = ActionConfig::from_step(&steps[progress.step]);
+            let named = step.name().and_then(|name| self.runbook.get_action(name));
+            match named.map(|def| self.action_readiness(def, &pipeline.id)) {
+                Some(Readiness::Exhausted) => {
+                    current = Some(ChainProgress {
+                        step: progress.step,
+                        attempts: limits[progress.step],
+                    });
+                    continue;
+                }
+                Some(Readiness::CoolingDown) => {
+                    self.executor
+                        .execute(self.start_session_monitor(&pipeline.id))
+                        .await?;
+                    return Ok(vec![]);
+                }
+                Some(Readiness::Ready) | None => {}
+            }
+
+            let effect = Effect::Persist {
+                operation: Operation::RecoveryStep {
+                    pipeline_id: pipeline.id.clone(),
+                    trigger: hook.to_string(),
+                    progress,
+                },
+            };
+            self.executor.execute(effect).await?;
+            return self
+                .run_agent_step(pipeline, agent_def, &step, trigger)
+                .await;
+        }
+    }
+
+    /// Start a pipeline's recovery chains over
+    pub(super) async fn reset_recovery(&self, pipeline: &Pipeline) -> Result<(), RuntimeError> {
+        if pipeline.recovery.is_empty() {
+            return Ok(());
+        }
+        let effect = Effect::Persist {
+            operation: Operation::RecoveryReset {
+                pipeline_id: pipeline.id.clone(),
+            },
+        };
+        self.executor.execute(effect).await?;
+        Ok(())
+    }
+
+    /// Run a single built-in or named agent action
+    ///
+    /// A runbook `[action.*]` of the same name takes the place of the
+    /// built-in action. While it cools down the agent is simply checked again
+    /// later; once it is used up the pipeline is escalated.
+    async fn run_agent_step(
+        &self,
+        pipeline: &Pipeline,
+        agent_def: &AgentDef,
+        config: &ActionConfig,
+        trigger: &str,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let named = config.name().and_then(|name| self.runbook.get_action(name));
+        let config = match (named, config) {
+            (Some(def), _) => {
+                let mut vars = self.template_vars(pipeline);
+                if let Some(session_id) = &pipeline.session_id {
+                    vars.insert("session".to_string(), session_id.clone());
+                }
+                match self.fire_action(def, &pipeline.id, &vars).await? {
+                    Readiness::Ready | Readiness::CoolingDown => {
+                        self.executor
+                            .execute(self.start_session_monitor(&pipeline.id))
+                            .await?;
+                        return Ok(vec![]);
+                    }
+                    Readiness::Exhausted => &ActionConfig::simple(AgentAction::Escalate),
+                }
+            }
+            (None, ActionConfig::Named(name)) => {
+                return Err(RuntimeError::ActionNotFound(name.clone()))
+            }
+            (None, config) => config,
+        };
+        if let Some(name) = config.name() {
+            self.log_pipeline(
+                &pipeline.id,
+                &pipeline.phase,
+                &format!("agent {}: {}", trigger, name),
+            );
+        }
+        let effects =
+            monitor::build_action_effects(pipeline, agent_def, config, trigger, &pipeline.inputs)?;
+        self.execute_action_effects(pipeline, effects).await
+    }
+
+    /// Fire a named action at a target unless it is cooling down or used up
+    ///
+    /// The action's command runs in the project root; a failing command
+    /// still counts as an attempt.
+    pub(super) async fn fire_action(
+        &self,
+        def: &ActionDef,
+        target: &str,
+        vars: &HashMap<String, String>,
+    ) -> Result<Readiness, RuntimeError> {
+        let readiness = self.action_readiness(def, target);
+        if readiness != Readiness::Ready {
+            tracing::debug!(action = %def.name, target, ?readiness, "action not fired");
+            return Ok(readiness);
+        }
+
+        self.executor
+            .execute(action::fire_effect(&def.name, target))
+            .await?;
+        let command = oj_runbook::interpolate_shell(&def.run, vars);
+        match self.executor.capture(&command, &self.project_root).await {
+            Ok(_) => tracing::info!(action = %def.name, target, "action fired"),
+            Err(e) => tracing::warn!(action = %def.name, target, error = %e, "action failed"),
+        }
+        Ok(Readiness::Ready)
+    }
+
+    /// Whether a named action may fire at a target now
+    pub(super) fn action_readiness(&self, def: &ActionDef, target: &str) -> Readiness {
+        let state = self.executor.state();
+        let state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
+        let record = state_guard
+            .actions
+            .get(&def.name)
+            .and_then(|targets| targets.get(target));
+        action::readiness(def, record, self.clock.now())
+    }
+
+    /// Drop the action history of a target that is finished or recovered
+    pub(super) async fn forget_actions(&self, target: &str) -> Result<(), RuntimeError> {
+        let known = {
+            let state = self.executor.state();
+            let state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
+            state_guard
+                .actions
+                .values()
+                .any(|targets| targets.contains_key(target))
+        };
+        if known {
+            let effect = Effect::Persist {
+                operation: Operation::ActionReset {
+                    target: target.to_string(),
+                },
+            };
+            self.executor.execute(effect).await?;
+        }
+        Ok(())
+    }
+
+    pub(super) async fn execute_action_effects(
+        &self,
+        pipeline: &Pipeline,
+        effects: ActionEffects,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        match effects {
+            ActionEffects::Nudge { mut effects } => {
+                effects.push(self.start_session_monitor(&pipeline.id));
+                Ok(self.executor.execute_all(effects).await?)
+            }
+            ActionEffects::AdvancePipeline => self.complete_phase(pipeline).await,
+            ActionEffects::FailPipeline { error } => self.fail_phase(pipeline, &error).await,
+            ActionEffects::Restart {
+                kill_session,
+                workspace_path,
+                agent_name,
+                inputs,
+            } => {
+                self.kill_and_respawn(
+                    kill_session,
+                    Some(workspace_path),
+                    &pipeline.id,
+                    &agent_name,
+                    &inputs,
+                )
+                .await
+            }
+            ActionEffects::Recover {
+                kill_session,
+                agent_name,
+                inputs,
+            } => {
+                self.kill_and_respawn(kill_session, None, &pipeline.id, &agent_name, &inputs)
+                    .await
+            }
+            ActionEffects::Escalate { effects } => Ok(self.executor.execute_all(effects).await?),
+        }
+    }
+
+    pub(super) async fn kill_and_respawn(
+        &self,
+        kill_session: Option<String>,
+        workspace_path: Option<Option<std::path::PathBuf>>,
+        pipeline_id: &str,
+        agent_name: &str,
+        inputs: &HashMap<String, String>,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        if let Some(sid) = kill_session {
+            self.executor
+                .execute(Effect::Kill { session_id: sid })
+                .await?;
+        }
+        if let Some(Some(path)) = workspace_path {
+            self.executor
+                .execute(Effect::WorktreeRemove { path })
+                .await?;
+        }
+        self.spawn_agent(pipeline_id, agent_name, inputs).await
+    }
+}
@@ -0,0 +1,224 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Agent sessions: spawning, monitoring and exits
+
+use super::Runtime;
+use crate::error::RuntimeError;
+use crate::monitor;
+use crate::session_log::{find_session_log, SessionLogWatcher, SessionState};
+use oj_adapters::{NotifyAdapter, RepoAdapter, SessionAdapter};
+use oj_core::{Clock, Effect, Event, IdGen, PhaseStatus};
+use std::collections::HashMap;
+use std::time::Duration;
+
+impl<S, R, N, C, I> Runtime<S, R, N, C, I>
+where
+    S: SessionAdapter,
+    R: RepoAdapter,
+    N: NotifyAdapter,
+    C: Clock,
+    I: IdGen,
+{
+    pub(super) async fn handle_session_exit(
+        &self,
+        session_id: &str,
+        exit_code: i32,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let pipeline = {
+            let state = self.executor.state();
+            let guard = state.lock().unwrap_or_else(|e| e.into_inner());
+            guard
+                .pipelines
+                .values()
+                .find(|p| p.session_id.as_ref() == Some(&session_id.to_string()))
+                .cloned()
+        };
+        let Some(pipeline) = pipeline else {
+            return Ok(vec![]);
+        };
+
+        self.record_phase_exit(&pipeline.id, exit_code).await?;
+        if exit_code != 0 {
+            // Fails like any other phase: on_fail routing, hooks, workers and parents
+            let error = format!("exit code: {}", exit_code);
+            return self.fail_phase(&pipeline, &error).await;
+        }
+        let event = Event::SessionExited {
+            session_id: session_id.to_string(),
+            exit_code,
+        };
+        let (new_pipeline, effects) = pipeline.transition(&event, &self.clock);
+        let mut events = self.executor.execute_all(effects).await?;
+        if new_pipeline.phase_status == PhaseStatus::Completed {
+            events.extend(self.advance_pipeline(&new_pipeline).await?);
+        }
+        Ok(events)
+    }
+
+    pub(super) async fn handle_agent_event(
+        &self,
+        pipeline_id: &str,
+        event: &Event,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let pipeline = self
+            .get_pipeline(pipeline_id)
+            .ok_or_else(|| RuntimeError::PipelineNotFound(pipeline_id.to_string()))?;
+
+        match event {
+            Event::AgentDone { .. } => self.complete_phase(&pipeline).await,
+            Event::AgentError { error, .. } => self.fail_phase(&pipeline, error).await,
+            _ => Ok(vec![]),
+        }
+    }
+
+    /// Spawn an agent for a pipeline
+    pub(super) async fn spawn_agent(
+        &self,
+        pipeline_id: &str,
+        agent_name: &str,
+        inputs: &HashMap<String, String>,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let agent_def = self
+            .runbook
+            .get_agent(agent_name)
+            .ok_or_else(|| RuntimeError::AgentNotFound(agent_name.to_string()))?;
+
+        let pipeline = self
+            .get_pipeline(pipeline_id)
+            .ok_or_else(|| RuntimeError::PipelineNotFound(pipeline_id.to_string()))?;
+        let workspace_path = self.workspace_path(&pipeline);
+
+        let mut effects = crate::spawn::build_spawn_effects(
+            agent_def,
+            &pipeline,
+            pipeline_id,
+            agent_name,
+            inputs,
+            &workspace_path,
+            &self.project_root,
+        )?;
+
+        // Start session monitoring after spawn
+        effects.push(self.start_session_monitor(pipeline_id));
+        effects.push(self.schedule_output_snapshot(pipeline_id, Duration::from_secs(10)));
+
+        let result_events = self.executor.execute_all(effects).await?;
+        self.log_pipeline(
+            pipeline_id,
+            &pipeline.phase,
+            &format!("started agent {}", agent_name),
+        );
+        Ok(result_events)
+    }
+
+    /// Start session monitoring for an agent
+    ///
+    /// Sets a timer that will periodically check the session log state.
+    pub(super) fn start_session_monitor(&self, pipeline_id: &str) -> Effect {
+        Effect::SetTimer {
+            id: format!("session:{}:check", pipeline_id),
+            duration: Duration::from_secs(10),
+        }
+    }
+
+    /// Handle session monitor timer
+    pub(super) async fn handle_session_monitor(
+        &self,
+        pipeline_id: &str,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let pipeline = self
+            .get_pipeline(pipeline_id)
+            .ok_or_else(|| RuntimeError::PipelineNotFound(pipeline_id.to_string()))?;
+
+        if pipeline.is_terminal() {
+            return Ok(vec![]);
+        }
+
+        let agent_def = monitor::get_agent_def(&self.runbook, &pipeline)?.clone();
+        let workspace_path = pipeline
+            .workspace_path
+            .as_ref()
+            .ok_or_else(|| RuntimeError::PipelineNotFound("no workspace".into()))?;
+
+        let session_id = pipeline.session_id.clone().unwrap_or_default();
+        let log_path = match find_session_log(workspace_path, &session_id) {
+            Some(path) => path,
+            None => {
+                self.executor
+                    .execute(self.start_session_monitor(pipeline_id))
+                    .await?;
+                return Ok(vec![]);
+            }
+        };
+
+        let state = {
+            let mut watchers = self
+                .session_watchers
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            let watcher = watchers
+                .entry(pipeline_id.to_string())
+                .or_insert_with(|| SessionLogWatcher::new(log_path));
+            watcher.check_state()
+        };
+
+        match state {
+            SessionState::Working => {
+                self.reset_recovery(&pipeline).await?;
+                self.executor
+                    .execute(self.start_session_monitor(pipeline_id))
+                    .await?;
+                Ok(vec![])
+            }
+            SessionState::Unknown => {
+                self.executor
+                    .execute(self.start_session_monitor(pipeline_id))
+                    .await?;
+                Ok(vec![])
+            }
+            SessionState::WaitingForInput => {
+                self.run_agent_action(&pipeline, &agent_def, "idle", &agent_def.on_idle, "idle")
+                    .await
+            }
+            SessionState::Failed(reason) => {
+                let error_msg = monitor::failure_to_message(&reason);
+                let error_type = monitor::failure_to_error_type(&reason);
+                tracing::error!(pipeline_id = %pipeline.id, error = error_msg, "agent error");
+                let action = agent_def.on_error.action_for(error_type.as_ref());
+                self.run_agent_action(&pipeline, &agent_def, "error", &action, error_msg)
+                    .await
+            }
+        }
+    }
+
+    /// Handle Claude process exit (on_exit trigger)
+    pub async fn handle_claude_exited(
+        &self,
+        pipeline_id: &str,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let pipeline = self
+            .get_pipeline(pipeline_id)
+            .ok_or_else(|| RuntimeError::PipelineNotFound(pipeline_id.to_string()))?;
+
+        if pipeline.is_terminal() {
+            return Ok(vec![]);
+        }
+
+        let agent_def = monitor::get_agent_def(&self.runbook, &pipeline)?.clone();
+        tracing::info!(pipeline_id = %pipeline.id, "claude process exited");
+
+        self.run_agent_action(&pipeline, &agent_def, "exit", &agent_def.on_exit, "exit")
+            .await
+    }
+
+    /// Handle tmux session exit (session is gone)
+    pub async fn handle_tmux_exited(&self, pipeline_id: &str) -> Result<Vec<Event>, RuntimeError> {
+        let pipeline = self
+            .get_pipeline(pipeline_id)
+            .ok_or_else(|| RuntimeError::PipelineNotFound(pipeline_id.to_string()))?;
+
+        tracing::error!(pipeline_id = %pipeline.id, "tmux session exited unexpectedly");
+        self.fail_pipeline(&pipeline, "tmux session exited").await
+    }
+}
@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Child pipelines run by a phase of another pipeline
+
+use super::Runtime;
+use crate::error::RuntimeError;
+use oj_adapters::{NotifyAdapter, RepoAdapter, SessionAdapter};
+use oj_core::{Clock, Event, IdGen, PhaseStatus, Pipeline, PipelineParent};
+use std::collections::HashMap;
+
+impl<S, R, N, C, I> Runtime<S, R, N, C, I>
+where
+    S: SessionAdapter,
+    R: RepoAdapter,
+    N: NotifyAdapter,
+    C: Clock,
+    I: IdGen,
+{
+    /// Create and start the child pipeline a phase runs
+    ///
+    /// The child's inputs are interpolated with the parent's variables. The
+    /// parent phase stays `Running` until the child finishes.
+    pub(super) async fn start_child_pipeline(
+        &self,
+        parent: &Pipeline,
+        phase: &str,
+        kind: &str,
+        inputs: &HashMap<String, String>,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let vars = self.template_vars(parent);
+        let inputs: HashMap<String, String> = inputs
+            .iter()
+            .map(|(key, value)| (key.clone(), oj_runbook::interpolate(value, &vars)))
+            .collect();
+        let link = PipelineParent {
+            id: parent.id.clone(),
+            phase: phase.to_string(),
+        };
+
+        let (child_id, mut result_events) = self.create_pipeline(kind, &inputs, Some(link)).await?;
+        tracing::info!(pipeline_id = %parent.id, phase, child_id, kind, "started child pipeline");
+        self.log_pipeline(
+            &parent.id,
+            phase,
+            &format!("started child pipeline {} ({})", child_id, kind),
+        );
+        result_events.extend(Box::pin(self.start_pipeline(&child_id)).await?);
+        Ok(result_events)
+    }
+
+    /// Settle the parent phase of a child pipeline that has just ended
+    ///
+    /// A child that reached `done` completes the phase, so the parent moves on
+    /// to its `next`; a child that failed fails it, routing to `on_fail`.
+    /// Nothing happens if the parent has since left the phase.
+    pub(super) async fn child_pipeline_finished(
+        &self,
+        child_id: &str,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let Some(child) = self.get_pipeline(child_id) else {
+            return Ok(vec![]);
+        };
+        let Some(link) = &child.parent else {
+            return Ok(vec![]);
+        };
+        let Some(parent) = self.get_pipeline(&link.id) else {
+            return Ok(vec![]);
+        };
+        if parent.phase != link.phase || parent.phase_status != PhaseStatus::Running {
+            tracing::warn!(pipeline_id = %parent.id, child_id, phase = %link.phase, "parent left the phase before its child pipeline finished");
+            return Ok(vec![]);
+        }
+
+        if child.phase == "done" {
+            self.log_pipeline(
+                &parent.id,
+                &parent.phase,
+                &format!("child pipeline {} completed", child.id),
+            );
+            Box::pin(self.complete_phase(&parent)).await
+        } else {
+            let error = format!(
+                "child pipeline {} failed: {}",
+                child.id,
+                child.error.as_deref().unwrap_or("unknown error")
+            );
+            Box::pin(self.fail_phase(&parent, &error)).await
+        }
+    }
+
+    /// The child pipeline running the parent's current phase, if it has one
+    ///
+    /// Children started by earlier visits to the phase are ignored.
+    pub(super) fn current_child_pipeline(&self, parent: &Pipeline) -> Option<Pipeline> {
+        let state = self.executor.state();
+        let state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
+        state_guard
+            .child_pipelines(&parent.id)
+            .into_iter()
+            .rfind(|child| {
+                child
+                    .parent
+                    .as_ref()
+                    .is_some_and(|p| p.phase == parent.phase)
+                    && child.created_at_ms >= parent.phase_started_at_ms
+            })
+            .cloned()
+    }
+}
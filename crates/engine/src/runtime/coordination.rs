@@ -0,0 +1,329 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Lock and semaphore handling for pipeline phases
+
+use super::Runtime;
+use crate::coordination;
+use crate::error::RuntimeError;
+use oj_adapters::{NotifyAdapter, RepoAdapter, SessionAdapter};
+use oj_core::{Clock, Event, IdGen, Lock, PhaseStatus, Pipeline, Semaphore, SemaphoreResult};
+use oj_runbook::{LockDef, PhaseDef, SemaphoreDef};
+use std::collections::HashMap;
+
+/// Coordination resources given up when a phase ends
+#[derive(Debug, Default)]
+pub(super) struct Released {
+    lock: Option<String>,
+    semaphore: Option<String>,
+    /// The lock was handed back by a phase whose semaphore was full
+    ///
+    /// Only lock waiters that can also get their slot are woken then, so the
+    /// lock is not passed between phases that would all hand it back.
+    handed_back: bool,
+}
+
+/// Whether a phase got the lock and semaphore slot it needs
+pub(super) enum Acquisition {
+    Acquired,
+    /// The phase stays Pending; anything taken meanwhile was handed back
+    Blocked(Released),
+}
+
+impl<S, R, N, C, I> Runtime<S, R, N, C, I>
+where
+    S: SessionAdapter,
+    R: RepoAdapter,
+    N: NotifyAdapter,
+    C: Clock,
+    I: IdGen,
+{
+    /// Take the lock and semaphore slot required by a phase
+    ///
+    /// If either is unavailable the phase stays Pending until a holder
+    /// releases or a retry timer finds a stale holder. A lock taken while the
+    /// semaphore is full is handed back rather than held idle, and returned so
+    /// its other waiters can be woken.
+    pub(super) async fn acquire_phase_resources(
+        &self,
+        pipeline_id: &str,
+        phase_def: &PhaseDef,
+    ) -> Result<Acquisition, RuntimeError> {
+        if !self.acquire_phase_lock(pipeline_id, phase_def).await? {
+            return Ok(Acquisition::Blocked(Released::default()));
+        }
+        if !self.acquire_phase_slot(pipeline_id, phase_def).await? {
+            let mut released = Released {
+                handed_back: true,
+                ..Released::default()
+            };
+            if let Some(lock_name) = &phase_def.lock {
+                let effects = coordination::lock_release_effects(lock_name, pipeline_id);
+                self.executor.execute_all(effects).await?;
+                released.lock = Some(lock_name.clone());
+            }
+            return Ok(Acquisition::Blocked(released));
+        }
+        Ok(Acquisition::Acquired)
+    }
+
+    /// Release the lock and semaphore slot held for the pipeline's current phase
+    pub(super) async fn release_phase_resources(
+        &self,
+        pipeline: &Pipeline,
+    ) -> Result<Released, RuntimeError> {
+        Ok(Released {
+            lock: self.release_phase_lock(pipeline).await?,
+            semaphore: self.release_phase_slot(pipeline).await?,
+            handed_back: false,
+        })
+    }
+
+    /// Start the oldest pipelines waiting on what was released
+    pub(super) async fn wake_waiters(
+        &self,
+        released: Released,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let mut result_events = Vec::new();
+        if let Some(lock_name) = released.lock {
+            let available = self.available_slots();
+            let waiter = self.oldest_waiter(|d| {
+                let has_slot = || {
+                    d.semaphore
+                        .as_ref()
+                        .and_then(|name| available.get(name))
+                        .is_none_or(|free| *free >= d.slots)
+                };
+                d.lock.as_deref() == Some(&lock_name) && (!released.handed_back || has_slot())
+            });
+            result_events.extend(self.restart_waiter(waiter).await?);
+        }
+        if let Some(semaphore_name) = released.semaphore {
+            let waiter = self.oldest_waiter(|d| d.semaphore.as_deref() == Some(&semaphore_name));
+            result_events.extend(self.restart_waiter(waiter).await?);
+        }
+        Ok(result_events)
+    }
+
+    /// Retry a phase that was blocked on a lock or semaphore
+    pub(super) async fn handle_coordination_retry(
+        &self,
+        pipeline_id: &str,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let waiter = self
+            .get_pipeline(pipeline_id)
+            .filter(|p| !p.is_terminal() && p.phase_status == PhaseStatus::Pending);
+        self.restart_waiter(waiter).await
+    }
+
+    /// Refresh the heartbeat of a lock held by a running phase
+    pub(super) async fn handle_lock_heartbeat(
+        &self,
+        pipeline_id: &str,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let Some(pipeline) = self.get_pipeline(pipeline_id) else {
+            return Ok(vec![]);
+        };
+        let Some(lock_name) = self.phase_def(&pipeline).and_then(|p| p.lock.clone()) else {
+            return Ok(vec![]);
+        };
+        if pipeline.is_terminal() || !self.holds_lock(&lock_name, pipeline_id) {
+            return Ok(vec![]);
+        }
+
+        let effects = coordination::lock_heartbeat_effects(&self.lock_def(&lock_name), pipeline_id);
+        Ok(self.executor.execute_all(effects).await?)
+    }
+
+    /// Refresh the heartbeat of a semaphore slot held by a running phase
+    pub(super) async fn handle_semaphore_heartbeat(
+        &self,
+        pipeline_id: &str,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let Some(pipeline) = self.get_pipeline(pipeline_id) else {
+            return Ok(vec![]);
+        };
+        let Some(semaphore_name) = self.phase_def(&pipeline).and_then(|p| p.semaphore.clone())
+        else {
+            return Ok(vec![]);
+        };
+        if pipeline.is_terminal() || !self.holds_slot(&semaphore_name, pipeline_id) {
+            return Ok(vec![]);
+        }
+
+        let effects = coordination::semaphore_heartbeat_effects(
+            &self.semaphore_def(&semaphore_name),
+            pipeline_id,
+        );
+        Ok(self.executor.execute_all(effects).await?)
+    }
+
+    async fn acquire_phase_lock(
+        &self,
+        pipeline_id: &str,
+        phase_def: &PhaseDef,
+    ) -> Result<bool, RuntimeError> {
+        let Some(lock_name) = &phase_def.lock else {
+            return Ok(true);
+        };
+
+        let def = self.lock_def(lock_name);
+        let result = {
+            let state = self.executor.state();
+            let state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
+            let mut lock = state_guard
+                .locks
+                .get(lock_name)
+                .cloned()
+                .unwrap_or_else(|| Lock::new(lock_name.clone(), def.config()));
+            lock.config = def.config();
+            lock.acquire(pipeline_id, &self.clock)
+        };
+
+        tracing::info!(pipeline_id, lock = %lock_name, ?result, "lock acquisition");
+        let effects = coordination::lock_acquire_effects(&def, pipeline_id, &result);
+        self.executor.execute_all(effects).await?;
+        Ok(result.is_held())
+    }
+
+    async fn acquire_phase_slot(
+        &self,
+        pipeline_id: &str,
+        phase_def: &PhaseDef,
+    ) -> Result<bool, RuntimeError> {
+        let Some(semaphore_name) = &phase_def.semaphore else {
+            return Ok(true);
+        };
+
+        let def = self.semaphore_def(semaphore_name);
+        let (stale, result) = {
+            let state = self.executor.state();
+            let state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
+            let mut semaphore = state_guard
+                .semaphores
+                .get(semaphore_name)
+                .cloned()
+                .unwrap_or_else(|| Semaphore::new(semaphore_name.clone(), def.config()));
+            semaphore.config = def.config();
+            let stale = semaphore.reclaim_stale(&self.clock);
+            (
+                stale,
+                semaphore.acquire(pipeline_id, phase_def.slots, &self.clock),
+            )
+        };
+
+        tracing::info!(pipeline_id, semaphore = %semaphore_name, ?result, "semaphore acquisition");
+        let mut effects = coordination::semaphore_reclaim_effects(semaphore_name, &stale);
+        effects.extend(coordination::semaphore_acquire_effects(
+            &def,
+            pipeline_id,
+            phase_def.slots,
+            &result,
+        ));
+        self.executor.execute_all(effects).await?;
+        Ok(!matches!(result, SemaphoreResult::Full { .. }))
+    }
+
+    /// Returns the name of the released lock, if one was held
+    async fn release_phase_lock(
+        &self,
+        pipeline: &Pipeline,
+    ) -> Result<Option<String>, RuntimeError> {
+        let Some(lock_name) = self.phase_def(pipeline).and_then(|p| p.lock.clone()) else {
+            return Ok(None);
+        };
+        if !self.holds_lock(&lock_name, &pipeline.id) {
+            return Ok(None);
+        }
+
+        let effects = coordination::lock_release_effects(&lock_name, &pipeline.id);
+        self.executor.execute_all(effects).await?;
+        Ok(Some(lock_name))
+    }
+
+    /// Returns the name of the semaphore whose slot was released, if one was held
+    async fn release_phase_slot(
+        &self,
+        pipeline: &Pipeline,
+    ) -> Result<Option<String>, RuntimeError> {
+        let Some(semaphore_name) = self.phase_def(pipeline).and_then(|p| p.semaphore.clone())
+        else {
+            return Ok(None);
+        };
+        if !self.holds_slot(&semaphore_name, &pipeline.id) {
+            return Ok(None);
+        }
+
+        let effects = coordination::semaphore_release_effects(&semaphore_name, &pipeline.id);
+        self.executor.execute_all(effects).await?;
+        Ok(Some(semaphore_name))
+    }
+
+    /// Find the longest-waiting Pending pipeline whose phase matches `waits_on`
+    fn oldest_waiter(&self, waits_on: impl Fn(&PhaseDef) -> bool) -> Option<Pipeline> {
+        let state = self.executor.state();
+        let state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
+        state_guard
+            .pipelines
+            .values()
+            .filter(|p| !p.is_terminal() && p.phase_status == PhaseStatus::Pending)
+            .filter(|p| self.phase_def(p).is_some_and(&waits_on))
+            .min_by_key(|p| (p.created_at_ms, p.id.clone()))
+            .cloned()
+    }
+
+    async fn restart_waiter(&self, waiter: Option<Pipeline>) -> Result<Vec<Event>, RuntimeError> {
+        match waiter {
+            Some(p) => {
+                self.start_phase(&p.id, &p.phase, &p.inputs, &self.workspace_path(&p))
+                    .await
+            }
+            None => Ok(vec![]),
+        }
+    }
+
+    /// Free slots of every semaphore that has been used
+    fn available_slots(&self) -> HashMap<String, u32> {
+        let state = self.executor.state();
+        let state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
+        state_guard
+            .semaphores
+            .iter()
+            .map(|(name, semaphore)| (name.clone(), semaphore.available()))
+            .collect()
+    }
+
+    fn holds_lock(&self, lock_name: &str, pipeline_id: &str) -> bool {
+        let state = self.executor.state();
+        let state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
+        state_guard
+            .locks
+            .get(lock_name)
+            .is_some_and(|l| l.is_held_by(pipeline_id))
+    }
+
+    fn holds_slot(&self, semaphore_name: &str, pipeline_id: &str) -> bool {
+        let state = self.executor.state();
+        let state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
+        state_guard
+            .semaphores
+            .get(semaphore_name)
+            .is_some_and(|s| s.is_held_by(pipeline_id))
+    }
+
+    /// Get a lock definition, falling back to defaults for undeclared locks
+    fn lock_def(&self, name: &str) -> LockDef {
+        self.runbook
+            .get_lock(name)
+            .cloned()
+            .unwrap_or_else(|| LockDef::new(name))
+    }
+
+    /// Get a semaphore definition, falling back to a single slot for undeclared semaphores
+    fn semaphore_def(&self, name: &str) -> SemaphoreDef {
+        self.runbook
+            .get_semaphore(name)
+            .cloned()
+            .unwrap_or_else(|| SemaphoreDef::new(name))
+    }
+}
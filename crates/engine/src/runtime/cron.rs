@@ -0,0 +1,146 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Time-driven crons
+
+use super::Runtime;
+use crate::cron;
+use crate::error::RuntimeError;
+use oj_adapters::{NotifyAdapter, RepoAdapter, SessionAdapter};
+use oj_core::{Clock, Effect, Event, IdGen, Operation};
+use oj_runbook::{CronDef, RunDirective};
+use std::collections::HashMap;
+use std::time::Duration;
+
+impl<S, R, N, C, I> Runtime<S, R, N, C, I>
+where
+    S: SessionAdapter,
+    R: RepoAdapter,
+    N: NotifyAdapter,
+    C: Clock,
+    I: IdGen,
+{
+    /// Enable a cron and schedule its next run
+    pub(super) async fn handle_cron_enable(&self, name: &str) -> Result<Vec<Event>, RuntimeError> {
+        let def = self.cron_def(name)?;
+        let effect = Effect::Persist {
+            operation: Operation::CronEnable {
+                name: name.to_string(),
+            },
+        };
+        self.executor.execute(effect).await?;
+        self.schedule_cron(def).await?;
+        tracing::info!(cron = name, "cron enabled");
+        Ok(vec![])
+    }
+
+    /// Disable a cron and drop its pending run
+    pub(super) async fn handle_cron_disable(&self, name: &str) -> Result<Vec<Event>, RuntimeError> {
+        self.cron_def(name)?;
+        let effects = vec![
+            Effect::Persist {
+                operation: Operation::CronDisable {
+                    name: name.to_string(),
+                },
+            },
+            Effect::CancelTimer {
+                id: cron::cron_tick_timer(name),
+            },
+        ];
+        tracing::info!(cron = name, "cron disabled");
+        Ok(self.executor.execute_all(effects).await?)
+    }
+
+    /// Run a cron once, whether or not it is enabled
+    pub(super) async fn handle_cron_run(&self, name: &str) -> Result<Vec<Event>, RuntimeError> {
+        let def = self.cron_def(name)?;
+        self.run_cron(def).await
+    }
+
+    /// Schedule an enabled cron's next run, then run it
+    ///
+    /// Scheduling comes first so a failed run does not stop the cron.
+    pub(super) async fn handle_cron_tick(&self, name: &str) -> Result<Vec<Event>, RuntimeError> {
+        let def = self.cron_def(name)?;
+        if !self.cron_enabled(name) {
+            return Ok(vec![]);
+        }
+        self.schedule_cron(def).await?;
+        self.run_cron(def).await
+    }
+
+    /// Schedule the next run of every enabled cron
+    ///
+    /// A cron that cannot be scheduled is logged and skipped.
+    pub async fn start_crons(&self) -> Result<(), RuntimeError> {
+        let mut names: Vec<_> = self
+            .runbook
+            .crons
+            .keys()
+            .filter(|name| self.cron_enabled(name))
+            .collect();
+        names.sort();
+        for name in names {
+            if let Err(e) = self.schedule_cron(self.cron_def(name)?).await {
+                tracing::warn!(cron = %name, error = %e, "failed to schedule cron");
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether a cron has been enabled
+    pub fn cron_enabled(&self, name: &str) -> bool {
+        let state = self.executor.state();
+        let state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
+        state_guard.crons.get(name).is_some_and(|cron| cron.enabled)
+    }
+
+    /// Check the cron's monitors, then run its command or pipeline
+    async fn run_cron(&self, def: &CronDef) -> Result<Vec<Event>, RuntimeError> {
+        tracing::info!(cron = %def.name, "running cron");
+        let mut result_events = Vec::new();
+        for monitor in &def.monitors {
+            result_events.extend(self.run_monitor(monitor).await?);
+        }
+
+        match &def.run {
+            Some(RunDirective::Shell(command)) => {
+                match self.executor.capture(command, &self.project_root).await {
+                    Ok(output) => tracing::info!(cron = %def.name, output, "cron command finished"),
+                    Err(e) => tracing::warn!(cron = %def.name, error = %e, "cron command failed"),
+                }
+                Ok(result_events)
+            }
+            Some(RunDirective::Pipeline { pipeline, .. }) => {
+                let (pipeline_id, events) = self
+                    .create_pipeline(pipeline, &HashMap::new(), None)
+                    .await?;
+                result_events.extend(events);
+                result_events.extend(self.start_pipeline(&pipeline_id).await?);
+                Ok(result_events)
+            }
+            Some(run) => Err(RuntimeError::InvalidRunDirective {
+                context: format!("cron {}", def.name),
+                directive: format!("{:?}", run),
+            }),
+            None => Ok(result_events),
+        }
+    }
+
+    async fn schedule_cron(&self, def: &CronDef) -> Result<(), RuntimeError> {
+        let now = Duration::from_millis(self.clock.epoch_ms());
+        match cron::schedule_effect(def, now) {
+            Some(effect) => {
+                self.executor.execute(effect).await?;
+            }
+            None => tracing::warn!(cron = %def.name, "cron schedule never matches"),
+        }
+        Ok(())
+    }
+
+    fn cron_def(&self, name: &str) -> Result<&CronDef, RuntimeError> {
+        self.runbook
+            .get_cron(name)
+            .ok_or_else(|| RuntimeError::CronNotFound(name.to_string()))
+    }
+}
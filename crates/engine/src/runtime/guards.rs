@@ -0,0 +1,368 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Pre/post guard evaluation for pipeline phases
+
+use super::Runtime;
+use crate::error::RuntimeError;
+use crate::guards::{self, GuardFailure};
+use crate::ExecuteError;
+use oj_adapters::{NotifyAdapter, RepoAdapter, SessionAdapter};
+use oj_core::{Clock, Event, IdGen, PhaseStatus, Pipeline};
+use oj_runbook::{GuardAction, GuardDef, PhaseDef};
+use std::time::{Duration, Instant};
+
+/// When a guard is checked relative to its phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum GuardStage {
+    /// Before the phase starts
+    Pre,
+    /// After the phase completes, before the pipeline moves on
+    Post,
+}
+
+/// Outcome of checking a phase's guards
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum GuardCheck {
+    Passed,
+    /// A guard is unsatisfied; a re-check is scheduled or a human was alerted
+    Waiting,
+    /// A guard failed for good
+    Failed(String),
+}
+
+/// A pipeline held up by a failing guard
+#[derive(Debug, Clone)]
+pub(super) struct GuardWait {
+    stage: GuardStage,
+    phase: String,
+    guard: String,
+    attempts: u32,
+    since: Instant,
+    /// Timed out and handed to a human: only `wake_on` events or
+    /// `oj pipeline resume` check it again
+    escalated: bool,
+}
+
+impl<S, R, N, C, I> Runtime<S, R, N, C, I>
+where
+    S: SessionAdapter,
+    R: RepoAdapter,
+    N: NotifyAdapter,
+    C: Clock,
+    I: IdGen,
+{
+    /// Check the guards for `stage` of a phase in order, stopping at the first failure
+    pub(super) async fn check_guards(
+        &self,
+        pipeline: &Pipeline,
+        phase_def: &PhaseDef,
+        stage: GuardStage,
+    ) -> Result<GuardCheck, RuntimeError> {
+        let names = match stage {
+            GuardStage::Pre => &phase_def.pre,
+            GuardStage::Post => &phase_def.post,
+        };
+
+        for name in names {
+            let def = self
+                .runbook
+                .get_guard(name)
+                .ok_or_else(|| RuntimeError::GuardNotFound(name.clone()))?;
+            if self.evaluate_guard(pipeline, def).await? {
+                continue;
+            }
+            if self.guard_escalated(pipeline, stage, name) {
+                // Still with a human: keep listening without alerting again
+                return self.wait_on_guard(pipeline, def, None).await;
+            }
+
+            let (attempts, elapsed) = self.record_guard_failure(pipeline, stage, name);
+            let step = guards::next_step(def, attempts, elapsed);
+            tracing::info!(pipeline_id = %pipeline.id, guard = %name, attempts, ?step, "guard failed");
+
+            let check = match step {
+                GuardFailure::Retry(delay) => {
+                    return self.wait_on_guard(pipeline, def, Some(delay)).await;
+                }
+                GuardFailure::TimedOut(GuardAction::Block) => {
+                    return self
+                        .wait_on_guard(pipeline, def, Some(def.poll_interval()))
+                        .await;
+                }
+                GuardFailure::AwaitEvent => return self.wait_on_guard(pipeline, def, None).await,
+                GuardFailure::TimedOut(GuardAction::Escalate) => {
+                    let effects = guards::guard_escalate_effects(pipeline, name);
+                    self.executor.execute_all(effects).await?;
+                    self.mark_guard_escalated(&pipeline.id);
+                    return self.wait_on_guard(pipeline, def, None).await;
+                }
+                GuardFailure::TimedOut(GuardAction::Fail) => {
+                    GuardCheck::Failed(format!("guard {} timed out", name))
+                }
+                GuardFailure::Exhausted => GuardCheck::Failed(format!("guard {} failed", name)),
+            };
+            self.clear_guard_wait(&pipeline.id);
+            return Ok(check);
+        }
+
+        self.clear_guard_wait(&pipeline.id);
+        Ok(GuardCheck::Passed)
+    }
+
+    /// Re-check the guard a pipeline is waiting on, resuming where it left off
+    pub(super) async fn handle_guard_retry(
+        &self,
+        pipeline_id: &str,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let wait = {
+            let waits = self.guard_waits.lock().unwrap_or_else(|e| e.into_inner());
+            waits.get(pipeline_id).cloned()
+        };
+        let (Some(wait), Some(pipeline)) = (wait, self.get_pipeline(pipeline_id)) else {
+            return Ok(vec![]);
+        };
+        if pipeline.is_terminal() || pipeline.phase != wait.phase {
+            return Ok(vec![]);
+        }
+
+        match wait.stage {
+            // An escalated pre-guard leaves the phase Waiting rather than Pending
+            GuardStage::Pre
+                if matches!(
+                    pipeline.phase_status,
+                    PhaseStatus::Pending | PhaseStatus::Waiting
+                ) =>
+            {
+                self.start_phase(
+                    &pipeline.id,
+                    &pipeline.phase,
+                    &pipeline.inputs,
+                    &self.workspace_path(&pipeline),
+                )
+                .await
+            }
+            GuardStage::Pre => Ok(vec![]),
+            GuardStage::Post => self.advance_pipeline(&pipeline).await,
+        }
+    }
+
+    /// Hand a pipeline parked on an escalated guard back to the guard's policy
+    ///
+    /// The guard's retries and timeout start over and it is checked right
+    /// away. Returns `None` if the pipeline is not parked on a guard.
+    pub(super) async fn resume_guard(
+        &self,
+        pipeline_id: &str,
+    ) -> Result<Option<Vec<Event>>, RuntimeError> {
+        let Some(pipeline) = self.get_pipeline(pipeline_id) else {
+            return Ok(None);
+        };
+        if pipeline.phase_status != PhaseStatus::Waiting || pipeline.session_id.is_some() {
+            return Ok(None);
+        }
+
+        let escalated = {
+            let mut waits = self.guard_waits.lock().unwrap_or_else(|e| e.into_inner());
+            match waits.get_mut(&pipeline.id).filter(|w| w.escalated) {
+                Some(wait) => {
+                    wait.escalated = false;
+                    wait.attempts = 0;
+                    wait.since = self.clock.now();
+                    true
+                }
+                None => false,
+            }
+        };
+        tracing::info!(pipeline_id = %pipeline.id, phase = %pipeline.phase, "resuming pipeline held by a guard");
+        if escalated {
+            return self.handle_guard_retry(&pipeline.id).await.map(Some);
+        }
+
+        // Which guard escalated was lost with a restart: start the phase over,
+        // which checks its pre-guards again
+        self.start_phase(
+            &pipeline.id,
+            &pipeline.phase,
+            &pipeline.inputs,
+            &self.workspace_path(&pipeline),
+        )
+        .await
+        .map(Some)
+    }
+
+    /// Listen again for the guard a pipeline was parked on before a restart
+    ///
+    /// Which guard escalated was lost with the restart, so the phase's guards
+    /// are checked to find it: its pre-guards unless the phase already ran,
+    /// then its post-guards. The first failing one is still with a human and
+    /// only its `wake_on` events check it again.
+    pub(super) async fn rearm_guard_wait(&self, pipeline: &Pipeline) -> Result<(), RuntimeError> {
+        let Some(phase_def) = self.phase_def(pipeline) else {
+            return Ok(());
+        };
+        let ran = pipeline
+            .history
+            .last()
+            .is_some_and(|record| record.exit_code.is_some());
+        let mut stages = Vec::new();
+        if !ran {
+            stages.push((GuardStage::Pre, &phase_def.pre));
+        }
+        stages.push((GuardStage::Post, &phase_def.post));
+
+        for (stage, names) in stages {
+            for name in names {
+                let def = self
+                    .runbook
+                    .get_guard(name)
+                    .ok_or_else(|| RuntimeError::GuardNotFound(name.clone()))?;
+                if self.evaluate_guard(pipeline, def).await? {
+                    continue;
+                }
+                {
+                    let mut waits = self.guard_waits.lock().unwrap_or_else(|e| e.into_inner());
+                    waits.insert(
+                        pipeline.id.clone(),
+                        GuardWait {
+                            stage,
+                            phase: pipeline.phase.clone(),
+                            guard: name.clone(),
+                            attempts: 0,
+                            since: self.clock.now(),
+                            escalated: true,
+                        },
+                    );
+                }
+                tracing::info!(pipeline_id = %pipeline.id, guard = %name, "re-armed escalated guard");
+                self.wait_on_guard(pipeline, def, None).await?;
+                return Ok(());
+            }
+        }
+        tracing::info!(pipeline_id = %pipeline.id, "guards of escalated pipeline pass, awaiting resume");
+        Ok(())
+    }
+
+    /// Re-check guards of pipelines subscribed to the event `name`
+    pub(super) async fn wake_guards(&self, name: &str) -> Result<Vec<Event>, RuntimeError> {
+        let subscribers = {
+            let subscriptions = self
+                .guard_subscriptions
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            subscriptions.subscribers(name)
+        };
+
+        let mut result_events = Vec::new();
+        for pipeline_id in subscribers {
+            tracing::info!(pipeline_id, event = name, "waking guard");
+            result_events.extend(self.handle_guard_retry(&pipeline_id).await?);
+        }
+        Ok(result_events)
+    }
+
+    /// Hold the pipeline on a failing guard until its `wake_on` events or `delay`
+    async fn wait_on_guard(
+        &self,
+        pipeline: &Pipeline,
+        def: &GuardDef,
+        delay: Option<Duration>,
+    ) -> Result<GuardCheck, RuntimeError> {
+        {
+            let vars = self.template_vars(pipeline);
+            let mut subscriptions = self
+                .guard_subscriptions
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            subscriptions.unsubscribe(&pipeline.id);
+            for pattern in &def.wake_on {
+                subscriptions.subscribe(&oj_runbook::interpolate(pattern, &vars), &pipeline.id);
+            }
+        }
+
+        let effects = guards::guard_wait_effects(&pipeline.id, &def.name, delay);
+        self.executor.execute_all(effects).await?;
+        Ok(GuardCheck::Waiting)
+    }
+
+    /// Run a guard's condition in the pipeline's workspace
+    ///
+    /// A condition still running after the command timeout is killed and
+    /// counts as unsatisfied.
+    async fn evaluate_guard(
+        &self,
+        pipeline: &Pipeline,
+        def: &GuardDef,
+    ) -> Result<bool, RuntimeError> {
+        let command = oj_runbook::interpolate(&def.condition, &self.template_vars(pipeline));
+        let exit_code = match self
+            .executor
+            .exit_code(&command, &self.workspace_path(pipeline))
+            .await
+        {
+            Ok(exit_code) => exit_code,
+            Err(ExecuteError::ShellTimeout(_, timeout)) => {
+                tracing::warn!(pipeline_id = %pipeline.id, guard = %def.name, ?timeout, "guard condition timed out");
+                return Ok(false);
+            }
+            Err(e) => return Err(e.into()),
+        };
+        tracing::debug!(pipeline_id = %pipeline.id, guard = %def.name, exit_code, "guard checked");
+        Ok(exit_code == 0)
+    }
+
+    /// Count a failed check, returning the attempts so far and time since the first
+    fn record_guard_failure(
+        &self,
+        pipeline: &Pipeline,
+        stage: GuardStage,
+        guard: &str,
+    ) -> (u32, Duration) {
+        let now = self.clock.now();
+        let mut waits = self.guard_waits.lock().unwrap_or_else(|e| e.into_inner());
+        let fresh = GuardWait {
+            stage,
+            phase: pipeline.phase.clone(),
+            guard: guard.to_string(),
+            attempts: 0,
+            since: now,
+            escalated: false,
+        };
+        let wait = waits
+            .entry(pipeline.id.clone())
+            .or_insert_with(|| fresh.clone());
+        if wait.stage != stage || wait.phase != pipeline.phase || wait.guard != guard {
+            *wait = fresh;
+        }
+        wait.attempts += 1;
+        (wait.attempts, now.saturating_duration_since(wait.since))
+    }
+
+    /// Check whether the pipeline's wait on this guard has been escalated
+    fn guard_escalated(&self, pipeline: &Pipeline, stage: GuardStage, guard: &str) -> bool {
+        let waits = self.guard_waits.lock().unwrap_or_else(|e| e.into_inner());
+        waits.get(&pipeline.id).is_some_and(|wait| {
+            wait.escalated
+                && wait.stage == stage
+                && wait.phase == pipeline.phase
+                && wait.guard == guard
+        })
+    }
+
+    fn mark_guard_escalated(&self, pipeline_id: &str) {
+        let mut waits = self.guard_waits.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(wait) = waits.get_mut(pipeline_id) {
+            wait.escalated = true;
+        }
+    }
+
+    fn clear_guard_wait(&self, pipeline_id: &str) {
+        let mut waits = self.guard_waits.lock().unwrap_or_else(|e| e.into_inner());
+        waits.remove(pipeline_id);
+        let mut subscriptions = self
+            .guard_subscriptions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        subscriptions.unsubscribe(pipeline_id);
+    }
+}
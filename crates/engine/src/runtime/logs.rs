@@ -0,0 +1,97 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Writing to per-pipeline log files
+
+use super::Runtime;
+use crate::error::RuntimeError;
+use crate::pipeline_log::{self, PipelineLog};
+use oj_adapters::{NotifyAdapter, RepoAdapter, SessionAdapter};
+use oj_core::{Clock, Effect, Event, IdGen};
+use std::time::Duration;
+
+/// How often an agent's recent output is copied into its pipeline log
+const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How many lines of agent output a snapshot holds
+const SNAPSHOT_LINES: u32 = 50;
+
+impl<S, R, N, C, I> Runtime<S, R, N, C, I>
+where
+    S: SessionAdapter,
+    R: RepoAdapter,
+    N: NotifyAdapter,
+    C: Clock,
+    I: IdGen,
+{
+    /// Append a line about a pipeline to its log
+    pub(super) fn log_pipeline(&self, pipeline_id: &str, phase: &str, text: &str) {
+        PipelineLog::open(&self.log_path(pipeline_id)).write(phase, text);
+    }
+
+    /// Schedule the next snapshot of a pipeline agent's output
+    pub(super) fn schedule_output_snapshot(&self, pipeline_id: &str, duration: Duration) -> Effect {
+        Effect::SetTimer {
+            id: pipeline_log::snapshot_timer(pipeline_id),
+            duration,
+        }
+    }
+
+    /// Copy a running agent's recent output into its pipeline log
+    ///
+    /// Output is only written when it changed since the last snapshot, so an
+    /// idle agent does not fill the log. Snapshots stop once the pipeline
+    /// leaves its agent phase.
+    pub(super) async fn handle_output_snapshot(
+        &self,
+        pipeline_id: &str,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let pipeline = self
+            .get_pipeline(pipeline_id)
+            .filter(|p| !p.is_terminal())
+            .filter(|p| self.phase_def(p).is_some_and(|def| def.is_agent()));
+        let Some((pipeline, session_id)) =
+            pipeline.and_then(|p| p.session_id.clone().map(|id| (p, id)))
+        else {
+            self.output_snapshots
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .remove(pipeline_id);
+            return Ok(vec![]);
+        };
+
+        match self
+            .executor
+            .session_output(&session_id, SNAPSHOT_LINES)
+            .await
+        {
+            Ok(output) => {
+                let changed = {
+                    let mut snapshots = self
+                        .output_snapshots
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner());
+                    snapshots
+                        .insert(pipeline_id.to_string(), output.clone())
+                        .as_ref()
+                        != Some(&output)
+                };
+                if changed && !output.trim().is_empty() {
+                    self.log_pipeline(
+                        pipeline_id,
+                        &pipeline.phase,
+                        &format!("agent output:\n{}", output),
+                    );
+                }
+            }
+            // The session may have ended since the last check; its monitor handles that
+            Err(e) => {
+                tracing::debug!(pipeline_id, session_id, error = %e, "cannot capture agent output")
+            }
+        }
+
+        let effect = self.schedule_output_snapshot(pipeline_id, SNAPSHOT_INTERVAL);
+        self.executor.execute(effect).await?;
+        Ok(vec![])
+    }
+}
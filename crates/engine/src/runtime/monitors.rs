@@ -0,0 +1,224 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Runbook monitors that check a source's items and respond to matches
+
+use super::Runtime;
+use crate::action::Readiness;
+use crate::error::RuntimeError;
+use crate::{monitor, queue};
+use oj_adapters::{NotifyAdapter, RepoAdapter, SessionAdapter};
+use oj_core::{ChainProgress, Clock, Effect, Event, IdGen, Operation, Pipeline};
+use oj_runbook::{ActionConfig, AgentAction, MonitorDef, MonitorResponse};
+use std::collections::{HashMap, HashSet};
+
+impl<S, R, N, C, I> Runtime<S, R, N, C, I>
+where
+    S: SessionAdapter,
+    R: RepoAdapter,
+    N: NotifyAdapter,
+    C: Clock,
+    I: IdGen,
+{
+    /// Check a monitor once, responding to every source item whose condition passes
+    ///
+    /// A response chain advances one step per check for as long as the item
+    /// keeps matching, skipping named actions that are used up, and starts over
+    /// once it stops matching or leaves the source. A failing source is logged
+    /// and skipped until the next check.
+    pub(super) async fn run_monitor(&self, name: &str) -> Result<Vec<Event>, RuntimeError> {
+        let def = self
+            .runbook
+            .get_monitor(name)
+            .ok_or_else(|| RuntimeError::MonitorNotFound(name.to_string()))?;
+
+        let items = match self.executor.capture(&def.source, &self.project_root).await {
+            Ok(output) => queue::parse_source_items(&output).map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        let items = match items {
+            Ok(items) => items,
+            Err(error) => {
+                tracing::warn!(monitor = name, error, "monitor source failed");
+                return Ok(vec![]);
+            }
+        };
+
+        let mut result_events = Vec::new();
+        let mut matched = HashSet::new();
+        for item in &items {
+            let Some(key) = monitor::source_item_key(item) else {
+                tracing::warn!(monitor = name, "source item without id, name or path");
+                continue;
+            };
+            if !self.monitor_condition_passes(def, item).await {
+                continue;
+            }
+            matched.insert(key.to_string());
+            result_events.extend(self.respond_to_item(def, key, item).await?);
+        }
+
+        let stale: Vec<String> = {
+            let state = self.executor.state();
+            let state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
+            state_guard
+                .monitors
+                .get(name)
+                .map(|progress| {
+                    progress
+                        .keys()
+                        .filter(|key| !matched.contains(*key))
+                        .cloned()
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        for item_id in stale {
+            self.forget_actions(&item_id).await?;
+            let effect = Effect::Persist {
+                operation: Operation::MonitorReset {
+                    name: name.to_string(),
+                    item_id,
+                },
+            };
+            self.executor.execute(effect).await?;
+        }
+        Ok(result_events)
+    }
+
+    async fn monitor_condition_passes(
+        &self,
+        def: &MonitorDef,
+        item: &HashMap<String, String>,
+    ) -> bool {
+        let Some(condition) = &def.condition else {
+            return true;
+        };
+        let command = oj_runbook::interpolate_shell(condition, item);
+        self.executor
+            .capture(&command, &self.project_root)
+            .await
+            .is_ok()
+    }
+
+    async fn respond_to_item(
+        &self,
+        def: &MonitorDef,
+        key: &str,
+        item: &HashMap<String, String>,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let steps = match &def.response {
+            MonitorResponse::Run(command) => {
+                let command = oj_runbook::interpolate_shell(command, item);
+                match self.executor.capture(&command, &self.project_root).await {
+                    Ok(_) => tracing::info!(monitor = %def.name, item = key, "monitor ran"),
+                    Err(e) => {
+                        tracing::warn!(monitor = %def.name, item = key, error = %e, "monitor run failed")
+                    }
+                }
+                return Ok(vec![]);
+            }
+            MonitorResponse::Chain(steps) => steps,
+        };
+
+        let limits: Vec<u32> = steps.iter().map(|step| step.attempts).collect();
+        let mut current = self.monitor_progress(&def.name, key);
+        loop {
+            let Some(progress) = ChainProgress::advance(current, &limits) else {
+                tracing::debug!(monitor = %def.name, item = key, "response chain exhausted");
+                return Ok(vec![]);
+            };
+            let action = &steps[progress.step].action;
+            if let Some(action_def) = self.runbook.get_action(action) {
+                match self.action_readiness(action_def, key) {
+                    Readiness::Ready => {}
+                    // Retry the same step on a later check
+                    Readiness::CoolingDown => return Ok(vec![]),
+                    // Fall through to the next step
+                    Readiness::Exhausted => {
+                        current = Some(ChainProgress {
+                            step: progress.step,
+                            attempts: limits[progress.step],
+                        });
+                        continue;
+                    }
+                }
+            }
+
+            let effect = Effect::Persist {
+                operation: Operation::MonitorRespond {
+                    name: def.name.clone(),
+                    item_id: key.to_string(),
+                    progress,
+                },
+            };
+            self.executor.execute(effect).await?;
+            tracing::info!(monitor = %def.name, item = key, action, "monitor responding");
+            return self.take_monitor_action(def, key, item, action).await;
+        }
+    }
+
+    /// Fire a named action at the item, or apply a built-in agent action to
+    /// the item's pipeline, escalating items that aren't pipelines
+    async fn take_monitor_action(
+        &self,
+        def: &MonitorDef,
+        key: &str,
+        item: &HashMap<String, String>,
+        action: &str,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        if let Some(action_def) = self.runbook.get_action(action) {
+            self.fire_action(action_def, key, item).await?;
+            return Ok(vec![]);
+        }
+        let Some(agent_action) = AgentAction::parse(action) else {
+            tracing::warn!(monitor = %def.name, action, "unknown monitor action");
+            return Ok(vec![]);
+        };
+
+        let Some(pipeline) = self.monitored_pipeline(item) else {
+            if agent_action == AgentAction::Escalate {
+                let effects = monitor::escalate_item_effects(&def.name, key);
+                return Ok(self.executor.execute_all(effects).await?);
+            }
+            tracing::warn!(monitor = %def.name, item = key, action, "item is not an active pipeline");
+            return Ok(vec![]);
+        };
+        let trigger = format!("monitor:{}", def.name);
+        let effects = monitor::get_agent_def(&self.runbook, &pipeline).and_then(|agent_def| {
+            monitor::build_action_effects(
+                &pipeline,
+                agent_def,
+                &ActionConfig::simple(agent_action),
+                &trigger,
+                &pipeline.inputs,
+            )
+        });
+        let effects = match effects {
+            Ok(effects) => effects,
+            Err(e) => {
+                tracing::warn!(monitor = %def.name, item = key, error = %e, "cannot act on pipeline");
+                return Ok(vec![]);
+            }
+        };
+        self.execute_action_effects(&pipeline, effects).await
+    }
+
+    /// The active pipeline a source item refers to by `pipeline_id` or `id`
+    fn monitored_pipeline(&self, item: &HashMap<String, String>) -> Option<Pipeline> {
+        let state = self.executor.state();
+        let state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
+        ["pipeline_id", "id"]
+            .iter()
+            .filter_map(|field| item.get(*field))
+            .find_map(|id| state_guard.pipelines.get(id))
+            .filter(|pipeline| !pipeline.is_terminal())
+            .cloned()
+    }
+
+    fn monitor_progress(&self, name: &str, key: &str) -> Option<ChainProgress> {
+        let state = self.executor.state();
+        let state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
+        state_guard.monitors.get(name)?.get(key).copied()
+    }
+}
@@ -0,0 +1,264 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Creating, starting and finishing pipelines, and their event hooks
+
+use super::Runtime;
+use crate::error::RuntimeError;
+use crate::phases;
+use oj_adapters::{NotifyAdapter, RepoAdapter, SessionAdapter};
+use oj_core::{Clock, Effect, Event, IdGen, Operation, Pipeline, PipelineParent};
+use std::collections::HashMap;
+
+impl<S, R, N, C, I> Runtime<S, R, N, C, I>
+where
+    S: SessionAdapter,
+    R: RepoAdapter,
+    N: NotifyAdapter,
+    C: Clock,
+    I: IdGen,
+{
+    pub(super) async fn handle_command(
+        &self,
+        command: &str,
+        args: &HashMap<String, String>,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        use oj_runbook::RunDirective;
+
+        let cmd_def = self
+            .runbook
+            .get_command(command)
+            .ok_or_else(|| RuntimeError::CommandNotFound(command.to_string()))?;
+
+        match &cmd_def.run {
+            RunDirective::Pipeline {
+                pipeline: pipeline_name,
+                ..
+            } => {
+                let (pipeline_id, mut result_events) =
+                    self.create_pipeline(pipeline_name, args, None).await?;
+                result_events.extend(self.start_pipeline(&pipeline_id).await?);
+                Ok(result_events)
+            }
+            RunDirective::Shell(cmd) => Err(RuntimeError::InvalidRunDirective {
+                context: "command".to_string(),
+                directive: format!("shell ({})", cmd),
+            }),
+            RunDirective::Agent { agent } => Err(RuntimeError::InvalidRunDirective {
+                context: "command".to_string(),
+                directive: format!("agent ({})", agent),
+            }),
+            RunDirective::Strategy { strategy } => Err(RuntimeError::InvalidRunDirective {
+                context: "command".to_string(),
+                directive: format!("strategy ({})", strategy),
+            }),
+        }
+    }
+
+    /// Persist a new pipeline and its workspace without starting it
+    ///
+    /// A child pipeline shares its parent's workspace instead of getting its
+    /// own. Returns the new pipeline's ID along with any emitted events.
+    pub(super) async fn create_pipeline(
+        &self,
+        pipeline_name: &str,
+        args: &HashMap<String, String>,
+        parent: Option<PipelineParent>,
+    ) -> Result<(String, Vec<Event>), RuntimeError> {
+        let pipeline_id = self.id_gen.next();
+        let events = self
+            .create_pipeline_with_id(&pipeline_id, pipeline_name, args, parent)
+            .await?;
+        Ok((pipeline_id, events))
+    }
+
+    /// Create a pipeline under an ID the caller has already handed out
+    pub(super) async fn create_pipeline_with_id(
+        &self,
+        pipeline_id: &str,
+        pipeline_name: &str,
+        args: &HashMap<String, String>,
+        parent: Option<PipelineParent>,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let pipeline_def = self
+            .runbook
+            .get_pipeline(pipeline_name)
+            .ok_or_else(|| RuntimeError::PipelineDefNotFound(pipeline_name.to_string()))?;
+
+        let pipeline_id = pipeline_id.to_string();
+        let name = args
+            .get("name")
+            .cloned()
+            .unwrap_or_else(|| pipeline_id.clone());
+        let workspace_path = self.worktree_root.join(&name);
+        let initial_phase = pipeline_def
+            .first_phase()
+            .map(|p| p.name.clone())
+            .unwrap_or_else(|| "init".to_string());
+
+        let mut effects = Vec::new();
+        if parent.is_none() {
+            effects.push(Effect::Persist {
+                operation: Operation::WorkspaceCreate {
+                    id: pipeline_id.clone(),
+                    path: workspace_path.clone(),
+                    branch: format!("feature/{}", name),
+                },
+            });
+            effects.push(Effect::WorktreeAdd {
+                branch: format!("feature/{}", name),
+                path: workspace_path,
+            });
+        }
+        effects.extend([
+            Effect::Persist {
+                operation: Operation::PipelineCreate {
+                    id: pipeline_id.clone(),
+                    kind: pipeline_name.to_string(),
+                    name: name.clone(),
+                    inputs: args.clone(),
+                    initial_phase,
+                    parent,
+                },
+            },
+            Effect::Emit {
+                event: Event::Custom {
+                    name: "pipeline:created".to_string(),
+                    data: serde_json::json!({"id": pipeline_id, "name": name, "kind": pipeline_name}),
+                },
+            },
+        ]);
+
+        Ok(self.executor.execute_all(effects).await?)
+    }
+
+    /// Start the first phase of a newly created pipeline
+    pub(super) async fn start_pipeline(
+        &self,
+        pipeline_id: &str,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let pipeline = self
+            .get_pipeline(pipeline_id)
+            .ok_or_else(|| RuntimeError::PipelineNotFound(pipeline_id.to_string()))?;
+        let Some(first_phase) = self
+            .runbook
+            .get_pipeline(&pipeline.kind)
+            .and_then(|def| def.first_phase())
+        else {
+            return Ok(vec![]);
+        };
+        self.start_phase(
+            &pipeline.id,
+            &first_phase.name,
+            &pipeline.inputs,
+            &self.workspace_path(&pipeline),
+        )
+        .await
+    }
+
+    /// Complete a pipeline
+    pub(super) async fn complete_pipeline(
+        &self,
+        pipeline: &Pipeline,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let effects = phases::completion_effects(pipeline);
+        let mut result_events = self.executor.execute_all(effects).await?;
+        self.log_pipeline(&pipeline.id, "done", "pipeline completed");
+        self.run_pipeline_hook(pipeline, "on_complete", "done", None)
+            .await?;
+        self.forget_actions(&pipeline.id).await?;
+        result_events.extend(self.worker_pipeline_finished(&pipeline.id, None).await?);
+        result_events.extend(self.child_pipeline_finished(&pipeline.id).await?);
+        Ok(result_events)
+    }
+
+    /// Handle pipeline failure
+    pub(super) async fn fail_pipeline(
+        &self,
+        pipeline: &Pipeline,
+        error: &str,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let pipeline_def = self.runbook.get_pipeline(&pipeline.kind);
+        let on_fail = pipeline_def
+            .as_ref()
+            .and_then(|p| p.get_phase(&pipeline.phase))
+            .and_then(|p| p.on_fail.as_ref());
+
+        let released = self.release_phase_resources(pipeline).await?;
+        let mut result_events = Vec::new();
+        // A failed phase publishes nothing
+        self.phase_outputs
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&pipeline.id);
+
+        if let Some(on_fail) = on_fail {
+            let effects = phases::failure_transition_effects(pipeline, on_fail, error);
+            result_events.extend(self.executor.execute_all(effects).await?);
+            self.log_pipeline(
+                &pipeline.id,
+                &pipeline.phase,
+                &format!("failed: {}; on_fail: {}", error, on_fail),
+            );
+            self.run_pipeline_hook(pipeline, "on_phase", on_fail, Some(error))
+                .await?;
+            result_events.extend(
+                self.start_phase(
+                    &pipeline.id,
+                    on_fail,
+                    &pipeline.inputs,
+                    &self.workspace_path(pipeline),
+                )
+                .await?,
+            );
+        } else {
+            let effects = phases::failure_effects(pipeline, error);
+            result_events.extend(self.executor.execute_all(effects).await?);
+            self.log_pipeline(
+                &pipeline.id,
+                &pipeline.phase,
+                &format!("pipeline failed: {}", error),
+            );
+            self.run_pipeline_hook(pipeline, "on_fail", &pipeline.phase, Some(error))
+                .await?;
+            self.forget_actions(&pipeline.id).await?;
+            result_events.extend(
+                self.worker_pipeline_finished(&pipeline.id, Some(error))
+                    .await?,
+            );
+            result_events.extend(self.child_pipeline_finished(&pipeline.id).await?);
+        }
+
+        result_events.extend(self.wake_waiters(released).await?);
+
+        Ok(result_events)
+    }
+
+    /// Start a `[pipeline.X.events]` hook without waiting for it to finish
+    pub(super) async fn run_pipeline_hook(
+        &self,
+        pipeline: &Pipeline,
+        hook: &str,
+        phase: &str,
+        error: Option<&str>,
+    ) -> Result<(), RuntimeError> {
+        let Some(pipeline_def) = self.runbook.get_pipeline(&pipeline.kind) else {
+            return Ok(());
+        };
+        let mut vars = self.template_vars(pipeline);
+        vars.insert("phase".to_string(), phase.to_string());
+        if let Some(error) = error {
+            vars.insert("error".to_string(), error.to_string());
+        }
+        if let Some(effect) = phases::hook_effect(
+            pipeline,
+            &pipeline_def.events,
+            hook,
+            &vars,
+            &self.project_root,
+        ) {
+            self.executor.execute(effect).await?;
+        }
+        Ok(())
+    }
+}
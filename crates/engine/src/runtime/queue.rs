@@ -0,0 +1,270 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Durable work queues
+
+use super::Runtime;
+use crate::error::RuntimeError;
+use crate::queue;
+use oj_adapters::{NotifyAdapter, RepoAdapter, SessionAdapter};
+use oj_core::{Clock, Effect, Event, IdGen, Operation, Queue, QueueItem};
+use oj_runbook::QueueDef;
+use std::collections::HashMap;
+use std::time::Duration;
+
+impl<S, R, N, C, I> Runtime<S, R, N, C, I>
+where
+    S: SessionAdapter,
+    R: RepoAdapter,
+    N: NotifyAdapter,
+    C: Clock,
+    I: IdGen,
+{
+    /// Add an item to a queue
+    ///
+    /// The item ID and priority are taken from the queue's `id_field` and
+    /// `priority_field` when present, so pushing the same item twice is a no-op,
+    /// also after it finished.
+    pub async fn queue_push(
+        &self,
+        name: &str,
+        data: HashMap<String, String>,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let def = self.queue_def(name)?;
+        let item_id = data
+            .get(&def.id_field)
+            .cloned()
+            .unwrap_or_else(|| self.id_gen.next());
+        if self
+            .get_queue(name)
+            .is_some_and(|q| q.get(&item_id).is_some() || q.finished.contains(&item_id))
+        {
+            return Ok(vec![]);
+        }
+        let priority = data
+            .get(&def.priority_field)
+            .and_then(|p| p.parse().ok())
+            .unwrap_or_default();
+
+        let effects = queue::push_effects(name, &item_id, data, priority);
+        Ok(self.executor.execute_all(effects).await?)
+    }
+
+    /// Claim the next pending item of a queue for `holder`
+    pub async fn queue_claim(
+        &self,
+        name: &str,
+        holder: &str,
+    ) -> Result<Option<QueueItem>, RuntimeError> {
+        let queue = self.queue_state(name)?;
+        let Some(item_id) = queue.next_pending().map(|item| item.id.clone()) else {
+            return Ok(None);
+        };
+
+        let effect = Effect::Persist {
+            operation: Operation::QueueClaim {
+                name: name.to_string(),
+                item_id: item_id.clone(),
+                holder: holder.to_string(),
+            },
+        };
+        self.executor.execute(effect).await?;
+        self.schedule_queue_sweep(name).await?;
+        tracing::info!(queue = name, item_id, holder, "claimed queue item");
+        Ok(self.get_queue(name).and_then(|q| q.get(&item_id).cloned()))
+    }
+
+    /// Remove an item `holder` claimed and processed successfully
+    ///
+    /// Ignored if the item is no longer claimed by `holder`, e.g. because its
+    /// claim expired and it was handed to someone else.
+    pub async fn queue_complete(
+        &self,
+        name: &str,
+        item_id: &str,
+        holder: &str,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let queue = self.queue_state(name)?;
+        if queue.get(item_id).and_then(|item| item.holder()) != Some(holder) {
+            tracing::warn!(
+                queue = name,
+                item_id,
+                holder,
+                "complete for queue item not held"
+            );
+            return Ok(vec![]);
+        }
+
+        let effect = Effect::Persist {
+            operation: Operation::QueueComplete {
+                name: name.to_string(),
+                item_id: item_id.to_string(),
+            },
+        };
+        Ok(self.executor.execute_all(vec![effect]).await?)
+    }
+
+    /// Settle `holder`'s failed attempt: retry, dead-letter or drop the item
+    ///
+    /// Ignored if the item is no longer claimed by `holder`.
+    pub async fn queue_fail(
+        &self,
+        name: &str,
+        item_id: &str,
+        holder: &str,
+        reason: &str,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let queue = self.queue_state(name)?;
+        if queue.get(item_id).and_then(|item| item.holder()) != Some(holder) {
+            tracing::warn!(
+                queue = name,
+                item_id,
+                holder,
+                "fail for queue item not held"
+            );
+            return Ok(vec![]);
+        }
+
+        let effects = queue::fail_effects(&queue, item_id, reason);
+        let events = self.executor.execute_all(effects).await?;
+        self.schedule_queue_sweep(name).await?;
+        Ok(events)
+    }
+
+    /// Hand a claimed item back without counting the attempt
+    pub async fn queue_release(
+        &self,
+        name: &str,
+        item_id: &str,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        self.queue_def(name)?;
+        let effect = Effect::Persist {
+            operation: Operation::QueueRelease {
+                name: name.to_string(),
+                item_id: item_id.to_string(),
+            },
+        };
+        Ok(self.executor.execute_all(vec![effect]).await?)
+    }
+
+    /// Run a queue's source and push the items it lists that aren't queued yet
+    ///
+    /// A failing source is logged and retried at the next poll.
+    pub async fn poll_queue(&self, name: &str) -> Result<Vec<Event>, RuntimeError> {
+        let def = self.queue_def(name)?;
+        let Some(source) = &def.source else {
+            return Ok(vec![]);
+        };
+
+        let items = match self.executor.capture(source, &self.project_root).await {
+            Ok(output) => queue::parse_source_items(&output).map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        let mut result_events = Vec::new();
+        match items {
+            Ok(items) => {
+                for item in items {
+                    if !item.contains_key(&def.id_field) {
+                        tracing::warn!(queue = name, field = %def.id_field, "source item without id");
+                        continue;
+                    }
+                    result_events.extend(self.queue_push(name, item).await?);
+                }
+            }
+            Err(error) => tracing::warn!(queue = name, error, "queue source failed"),
+        }
+
+        let effect = Effect::SetTimer {
+            id: queue::queue_poll_timer(name),
+            duration: def.poll_interval,
+        };
+        self.executor.execute(effect).await?;
+        Ok(result_events)
+    }
+
+    /// Schedule an immediate poll of every queue with a source
+    pub async fn start_queue_sources(&self) -> Result<(), RuntimeError> {
+        let effects = self
+            .runbook
+            .queues
+            .values()
+            .filter(|def| def.source.is_some())
+            .map(|def| Effect::SetTimer {
+                id: queue::queue_poll_timer(&def.name),
+                duration: Duration::ZERO,
+            })
+            .collect();
+        self.executor.execute_all(effects).await?;
+        Ok(())
+    }
+
+    /// Get a queue by name
+    pub fn get_queue(&self, name: &str) -> Option<Queue> {
+        let state = self.executor.state();
+        let state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
+        state_guard.queues.get(name).cloned()
+    }
+
+    /// Expire overdue claims and purge dead items, then wait for the next deadline
+    ///
+    /// Claims held by a pipeline that is still running are renewed instead,
+    /// so a long handler does not lose its item to another worker.
+    pub(super) async fn handle_queue_sweep(&self, name: &str) -> Result<Vec<Event>, RuntimeError> {
+        let queue = self.queue_state(name)?;
+        let renewals = queue
+            .expired(&self.clock)
+            .into_iter()
+            .filter_map(|item_id| {
+                let holder = queue.get(&item_id)?.holder()?.to_string();
+                self.pipeline_running(&holder).then(|| Effect::Persist {
+                    operation: Operation::QueueExtend {
+                        name: name.to_string(),
+                        item_id,
+                        holder,
+                    },
+                })
+            })
+            .collect();
+        self.executor.execute_all(renewals).await?;
+
+        let queue = self.queue_state(name)?;
+        let effects = queue::sweep_effects(&queue, &self.clock);
+        let events = self.executor.execute_all(effects).await?;
+        self.schedule_queue_sweep(name).await?;
+        Ok(events)
+    }
+
+    /// Check whether `id` names a pipeline that has not finished
+    fn pipeline_running(&self, id: &str) -> bool {
+        let state = self.executor.state();
+        let state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
+        state_guard
+            .pipelines
+            .get(id)
+            .is_some_and(|pipeline| !pipeline.is_terminal())
+    }
+
+    async fn schedule_queue_sweep(&self, name: &str) -> Result<(), RuntimeError> {
+        let queue = self.queue_state(name)?;
+        if let Some(effect) = queue::sweep_timer_effect(&queue, &self.clock) {
+            self.executor.execute(effect).await?;
+        }
+        Ok(())
+    }
+
+    /// The queue's state with the runbook's current configuration applied
+    pub(super) fn queue_state(&self, name: &str) -> Result<Queue, RuntimeError> {
+        let config = self.queue_def(name)?.config();
+        let mut queue = self
+            .get_queue(name)
+            .unwrap_or_else(|| Queue::new(name.to_string(), config.clone()));
+        queue.config = config;
+        Ok(queue)
+    }
+
+    fn queue_def(&self, name: &str) -> Result<&QueueDef, RuntimeError> {
+        self.runbook
+            .get_queue(name)
+            .ok_or_else(|| RuntimeError::QueueNotFound(name.to_string()))
+    }
+}
@@ -0,0 +1,161 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Picking up in-flight pipelines after a restart
+
+use super::Runtime;
+use crate::coordination;
+use crate::error::RuntimeError;
+use crate::resume::{self, Resume};
+use oj_adapters::{NotifyAdapter, RepoAdapter, SessionAdapter};
+use oj_core::{Clock, Effect, Event, IdGen, Operation};
+use std::time::Duration;
+
+impl<S, R, N, C, I> Runtime<S, R, N, C, I>
+where
+    S: SessionAdapter,
+    R: RepoAdapter,
+    N: NotifyAdapter,
+    C: Clock,
+    I: IdGen,
+{
+    /// Reconcile persisted state with what survived a restart
+    ///
+    /// Session records of finished pipelines whose session has ended, and
+    /// workspace records whose directory is gone, are dropped. Every pipeline
+    /// still in flight gets a resume timer that decides how it carries on.
+    /// A session that cannot be checked is logged and its record kept.
+    pub async fn resume_pipelines(&self) -> Result<(), RuntimeError> {
+        let (sessions, workspaces, mut in_flight) = {
+            let state = self.executor.state();
+            let state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
+            let in_flight: Vec<String> = state_guard
+                .pipelines
+                .values()
+                .filter(|p| !p.is_terminal())
+                .map(|p| p.id.clone())
+                .collect();
+            let sessions: Vec<String> = state_guard
+                .sessions
+                .values()
+                .filter(|s| !in_flight.contains(&s.pipeline_id))
+                .map(|s| s.id.clone())
+                .collect();
+            let workspaces: Vec<String> = state_guard
+                .workspaces
+                .values()
+                .filter(|w| !w.path.exists())
+                .map(|w| w.id.clone())
+                .collect();
+            (sessions, workspaces, in_flight)
+        };
+
+        let mut effects = Vec::new();
+        for id in sessions {
+            match self.executor.session_alive(&id).await {
+                Ok(false) => {}
+                Ok(true) => {
+                    tracing::warn!(session_id = %id, "session outlived its pipeline");
+                    continue;
+                }
+                Err(e) => {
+                    tracing::warn!(session_id = %id, error = %e, "failed to check session");
+                    continue;
+                }
+            }
+            effects.push(Effect::Persist {
+                operation: Operation::SessionDelete { id },
+            });
+        }
+        for id in workspaces {
+            tracing::info!(workspace_id = %id, "dropping record of removed workspace");
+            effects.push(Effect::Persist {
+                operation: Operation::WorkspaceDelete { id },
+            });
+        }
+
+        in_flight.sort();
+        for pipeline_id in &in_flight {
+            effects.push(Effect::SetTimer {
+                id: resume::resume_timer(pipeline_id),
+                duration: Duration::ZERO,
+            });
+        }
+        if !in_flight.is_empty() {
+            tracing::info!(count = in_flight.len(), "resuming in-flight pipelines");
+        }
+        self.executor.execute_all(effects).await?;
+        Ok(())
+    }
+
+    /// Carry on with a pipeline that was in flight when the daemon stopped
+    pub(super) async fn handle_pipeline_resume(
+        &self,
+        pipeline_id: &str,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let Some(pipeline) = self.get_pipeline(pipeline_id) else {
+            return Ok(vec![]);
+        };
+
+        let session_alive = match &pipeline.session_id {
+            Some(id) => Some(self.executor.session_alive(id).await?),
+            None => None,
+        };
+        let workspace = self.workspace_path(&pipeline);
+        let action = resume::resume_action(
+            &pipeline,
+            self.phase_def(&pipeline),
+            session_alive,
+            Some(workspace.as_path()).filter(|path| !path.exists()),
+        );
+        tracing::info!(pipeline_id, phase = %pipeline.phase, ?action, "resuming pipeline");
+
+        // The record of a session that ended is no use to whatever runs next
+        if let (Some(id), Some(false)) = (&pipeline.session_id, session_alive) {
+            let effect = Effect::Persist {
+                operation: Operation::SessionDelete { id: id.clone() },
+            };
+            self.executor.execute(effect).await?;
+        }
+
+        match action {
+            Resume::Leave => Ok(vec![]),
+            Resume::AwaitGuard => {
+                self.rearm_guard_wait(&pipeline).await?;
+                Ok(vec![])
+            }
+            Resume::Start => {
+                self.start_phase(&pipeline.id, &pipeline.phase, &pipeline.inputs, &workspace)
+                    .await
+            }
+            Resume::Complete => self.complete_phase(&pipeline).await,
+            Resume::Fail { error } => self.fail_phase(&pipeline, &error).await,
+            Resume::Monitor => {
+                // Heartbeat handlers check the pipeline still holds what they refresh
+                let effects = vec![
+                    self.start_session_monitor(pipeline_id),
+                    self.schedule_output_snapshot(pipeline_id, Duration::ZERO),
+                    Effect::SetTimer {
+                        id: coordination::lock_heartbeat_timer(pipeline_id),
+                        duration: Duration::ZERO,
+                    },
+                    Effect::SetTimer {
+                        id: coordination::semaphore_heartbeat_timer(pipeline_id),
+                        duration: Duration::ZERO,
+                    },
+                ];
+                Ok(self.executor.execute_all(effects).await?)
+            }
+            Resume::SessionExited => self.handle_claude_exited(pipeline_id).await,
+            Resume::AwaitChild => match self.current_child_pipeline(&pipeline) {
+                // The child resumes on its own and settles the phase when it ends
+                Some(child) if !child.is_terminal() => Ok(vec![]),
+                Some(child) => self.child_pipeline_finished(&child.id).await,
+                None => {
+                    self.start_phase(&pipeline.id, &pipeline.phase, &pipeline.inputs, &workspace)
+                        .await
+                }
+            },
+        }
+    }
+}
@@ -0,0 +1,87 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Runbook `[[on]]` event rules
+
+use super::Runtime;
+use crate::error::RuntimeError;
+use crate::rules;
+use oj_adapters::{NotifyAdapter, RepoAdapter, SessionAdapter};
+use oj_core::{Clock, Effect, Event, IdGen};
+use oj_runbook::{EventRule, RuleAction};
+use std::collections::HashMap;
+
+impl<S, R, N, C, I> Runtime<S, R, N, C, I>
+where
+    S: SessionAdapter,
+    R: RepoAdapter,
+    N: NotifyAdapter,
+    C: Clock,
+    I: IdGen,
+{
+    /// Run the action of every `[[on]]` rule matching a custom event
+    ///
+    /// A failing rule is logged and does not stop the rules after it.
+    pub(super) async fn run_event_rules(
+        &self,
+        event: &str,
+        data: &serde_json::Value,
+    ) -> Vec<Event> {
+        let fields = rules::event_fields(data);
+        let mut result_events = Vec::new();
+        for (index, rule) in self.runbook.rules.iter().enumerate() {
+            if !rule.matches(event, &fields) {
+                continue;
+            }
+            tracing::info!(event, rule = index, "event rule matched");
+            match self.run_rule_action(rule, data, &fields).await {
+                Ok(events) => result_events.extend(events),
+                Err(e) => tracing::warn!(event, rule = index, error = %e, "event rule failed"),
+            }
+        }
+        result_events
+    }
+
+    async fn run_rule_action(
+        &self,
+        rule: &EventRule,
+        data: &serde_json::Value,
+        fields: &HashMap<String, String>,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        match &rule.action {
+            RuleAction::Run(command) => {
+                let effect = Effect::Hook {
+                    name: format!("on.{}", rule.event),
+                    command: oj_runbook::interpolate(command, fields),
+                    cwd: self.project_root.clone(),
+                };
+                self.executor.execute(effect).await?;
+                Ok(vec![])
+            }
+            RuleAction::Command { command, args } => {
+                let args = rules::rule_values(args.as_ref(), fields);
+                self.handle_command(command, &args).await
+            }
+            RuleAction::Queue { queue, data } => {
+                self.queue_push(queue, rules::rule_values(data.as_ref(), fields))
+                    .await
+            }
+            RuleAction::Emit {
+                event,
+                data: values,
+            } => {
+                let data = match values {
+                    Some(values) => serde_json::json!(rules::rule_values(Some(values), fields)),
+                    None => data.clone(),
+                };
+                let effect = Effect::Emit {
+                    event: Event::Custom {
+                        name: event.clone(),
+                        data,
+                    },
+                };
+                Ok(self.executor.execute(effect).await?.into_iter().collect())
+            }
+        }
+    }
+}
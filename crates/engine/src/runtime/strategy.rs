@@ -0,0 +1,228 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Strategy fallback chains for pipeline phases
+
+use super::Runtime;
+use crate::error::RuntimeError;
+use crate::strategy;
+use oj_adapters::{NotifyAdapter, RepoAdapter, SessionAdapter};
+use oj_core::{Clock, Effect, Event, IdGen, Pipeline, StrategyState};
+use oj_runbook::{ExhaustAction, RunDirective, StrategyDef};
+use std::collections::HashMap;
+
+impl<S, R, N, C, I> Runtime<S, R, N, C, I>
+where
+    S: SessionAdapter,
+    R: RepoAdapter,
+    N: NotifyAdapter,
+    C: Clock,
+    I: IdGen,
+{
+    /// Run a strategy phase, resuming at the persisted attempt if there is one
+    pub(super) async fn start_strategy(
+        &self,
+        pipeline: &Pipeline,
+        name: &str,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let def = self
+            .runbook
+            .get_strategy(name)
+            .ok_or_else(|| RuntimeError::StrategyNotFound(name.to_string()))?;
+
+        if let Some(state) = self.active_strategy(pipeline) {
+            tracing::info!(pipeline_id = %pipeline.id, strategy = name, attempt = state.attempt, "resuming strategy");
+            return self
+                .start_attempt(pipeline, def, state.attempt, state.checkpoint)
+                .await;
+        }
+
+        let checkpoint = match &def.checkpoint {
+            Some(command) => {
+                let command = oj_runbook::interpolate(command, &self.template_vars(pipeline));
+                Some(
+                    self.executor
+                        .capture(&command, &self.workspace_path(pipeline))
+                        .await?,
+                )
+            }
+            None => None,
+        };
+        self.start_attempt(pipeline, def, 0, checkpoint).await
+    }
+
+    /// Finish the phase successfully, ending any running strategy attempt
+    pub(super) async fn complete_phase(
+        &self,
+        pipeline: &Pipeline,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        if self.active_strategy(pipeline).is_some() {
+            self.executor
+                .execute(Effect::CancelTimer {
+                    id: strategy::strategy_timeout_timer(&pipeline.id),
+                })
+                .await?;
+        }
+        self.advance_pipeline(pipeline).await
+    }
+
+    /// Fail the phase, or only the current attempt if a strategy has more to try
+    pub(super) async fn fail_phase(
+        &self,
+        pipeline: &Pipeline,
+        error: &str,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let Some(state) = self.active_strategy(pipeline) else {
+            return self.fail_pipeline(pipeline, error).await;
+        };
+        let def = self.strategy_def(pipeline)?;
+        let vars = self.strategy_vars(pipeline, state.checkpoint.as_deref());
+        tracing::info!(pipeline_id = %pipeline.id, strategy = %def.name, attempt = state.attempt, error, "strategy attempt failed");
+
+        let mut effects = vec![Effect::CancelTimer {
+            id: strategy::strategy_timeout_timer(&pipeline.id),
+        }];
+        let attempt = def.attempt(state.attempt);
+        if attempt.is_some_and(|a| matches!(a.run, RunDirective::Shell(_))) {
+            effects.push(Effect::CancelShell {
+                pipeline_id: pipeline.id.clone(),
+            });
+        }
+        if let Some(session_id) = &pipeline.session_id {
+            effects.push(Effect::CancelTimer {
+                id: format!("session:{}:check", pipeline.id),
+            });
+            effects.push(Effect::Kill {
+                session_id: session_id.clone(),
+            });
+        }
+        self.executor.execute_all(effects).await?;
+
+        if let Some(rollback) = attempt.and_then(|a| a.rollback.as_ref()) {
+            let command = oj_runbook::interpolate(rollback, &vars);
+            let exit_code = self
+                .executor
+                .exit_code(&command, &self.workspace_path(pipeline))
+                .await?;
+            if exit_code != 0 {
+                tracing::warn!(pipeline_id = %pipeline.id, exit_code, "strategy rollback failed");
+            }
+        }
+
+        let next = state.attempt + 1;
+        if def.attempt(next).is_some() {
+            return self
+                .start_attempt(pipeline, def, next, state.checkpoint)
+                .await;
+        }
+
+        let reason = format!("strategy {} exhausted: {}", def.name, error);
+        match def.on_exhaust {
+            ExhaustAction::Fail => self.fail_pipeline(pipeline, &reason).await,
+            ExhaustAction::Escalate => {
+                let effects = strategy::exhaust_escalate_effects(pipeline, def);
+                Ok(self.executor.execute_all(effects).await?)
+            }
+        }
+    }
+
+    /// Fail the running attempt of a pipeline once its timeout expires
+    pub(super) async fn handle_strategy_timeout(
+        &self,
+        pipeline_id: &str,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let Some(pipeline) = self.get_pipeline(pipeline_id) else {
+            return Ok(vec![]);
+        };
+        let Some(state) = self.active_strategy(&pipeline) else {
+            return Ok(vec![]);
+        };
+        if pipeline.is_terminal() {
+            return Ok(vec![]);
+        }
+        let error = format!("attempt {} timed out", state.attempt + 1);
+        self.fail_phase(&pipeline, &error).await
+    }
+
+    /// Persist the attempt index, then run the attempt
+    async fn start_attempt(
+        &self,
+        pipeline: &Pipeline,
+        def: &StrategyDef,
+        index: usize,
+        checkpoint: Option<String>,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let attempt = def
+            .attempt(index)
+            .ok_or_else(|| RuntimeError::StrategyNotFound(format!("{}[{}]", def.name, index)))?;
+
+        let effects = strategy::attempt_start_effects(pipeline, def, index, checkpoint.as_deref());
+        let mut result_events = self.executor.execute_all(effects).await?;
+
+        match &attempt.run {
+            RunDirective::Shell(cmd) => {
+                let vars = self.strategy_vars(pipeline, checkpoint.as_deref());
+                let effect = Effect::Shell {
+                    pipeline_id: pipeline.id.clone(),
+                    phase: pipeline.phase.clone(),
+                    command: oj_runbook::interpolate(cmd, &vars),
+                    cwd: self.workspace_path(pipeline),
+                    env: HashMap::new(),
+                    // Attempt timeouts are timers, since they apply to agents too
+                    timeout: None,
+                    log_path: self.log_path(&pipeline.id),
+                    stdout_vars: Vec::new(),
+                };
+                result_events.extend(self.executor.execute(effect).await?);
+            }
+            RunDirective::Agent { agent } => {
+                result_events.extend(
+                    self.spawn_agent(&pipeline.id, agent, &pipeline.inputs)
+                        .await?,
+                );
+            }
+            run => {
+                return Err(RuntimeError::InvalidRunDirective {
+                    context: format!("strategy {} attempt {}", def.name, attempt.name),
+                    directive: format!("{:?}", run),
+                });
+            }
+        }
+
+        Ok(result_events)
+    }
+
+    /// The strategy progress of a pipeline, if it belongs to the current phase
+    fn active_strategy(&self, pipeline: &Pipeline) -> Option<StrategyState> {
+        pipeline
+            .strategy
+            .clone()
+            .filter(|state| state.phase == pipeline.phase)
+    }
+
+    fn strategy_def(&self, pipeline: &Pipeline) -> Result<&StrategyDef, RuntimeError> {
+        match self.phase_def(pipeline).map(|p| &p.run) {
+            Some(RunDirective::Strategy { strategy }) => self
+                .runbook
+                .get_strategy(strategy)
+                .ok_or_else(|| RuntimeError::StrategyNotFound(strategy.clone())),
+            _ => Err(RuntimeError::InvalidRunDirective {
+                context: format!("phase {}", pipeline.phase),
+                directive: "not a strategy phase".to_string(),
+            }),
+        }
+    }
+
+    /// Template variables plus the strategy's `{checkpoint}`
+    fn strategy_vars(
+        &self,
+        pipeline: &Pipeline,
+        checkpoint: Option<&str>,
+    ) -> HashMap<String, String> {
+        let mut vars = self.template_vars(pipeline);
+        if let Some(checkpoint) = checkpoint {
+            vars.insert("checkpoint".to_string(), checkpoint.to_string());
+        }
+        vars
+    }
+}
@@ -0,0 +1,295 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Workers that turn queue items into pipelines
+
+use super::Runtime;
+use crate::error::RuntimeError;
+use crate::worker;
+use oj_adapters::{NotifyAdapter, RepoAdapter, SessionAdapter};
+use oj_core::{Clock, Effect, Event, IdGen, Operation, Worker};
+use oj_runbook::WorkerDef;
+use std::time::Duration;
+
+impl<S, R, N, C, I> Runtime<S, R, N, C, I>
+where
+    S: SessionAdapter,
+    R: RepoAdapter,
+    N: NotifyAdapter,
+    C: Clock,
+    I: IdGen,
+{
+    /// Mark a worker as running and claim work up to its concurrency
+    pub(super) async fn handle_worker_start(&self, name: &str) -> Result<Vec<Event>, RuntimeError> {
+        let def = self.worker_def(name)?;
+        let effects = vec![
+            Effect::Persist {
+                operation: Operation::WorkerStart {
+                    name: name.to_string(),
+                },
+            },
+            worker::lifecycle_effect(name, "started"),
+        ];
+        let mut result_events = self.executor.execute_all(effects).await?;
+        self.subscribe_worker(def);
+        tracing::info!(worker = name, "worker started");
+        result_events.extend(self.fill_worker(name, true).await?);
+        Ok(result_events)
+    }
+
+    /// Stop a worker from claiming more work
+    ///
+    /// Pipelines already running are left to finish.
+    pub(super) async fn handle_worker_stop(&self, name: &str) -> Result<Vec<Event>, RuntimeError> {
+        self.worker_def(name)?;
+        let effects = vec![
+            Effect::Persist {
+                operation: Operation::WorkerStop {
+                    name: name.to_string(),
+                },
+            },
+            Effect::CancelTimer {
+                id: worker::worker_wake_timer(name),
+            },
+            worker::lifecycle_effect(name, "stopped"),
+        ];
+        self.worker_subscriptions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .unsubscribe(name);
+        tracing::info!(worker = name, "worker stopped");
+        Ok(self.executor.execute_all(effects).await?)
+    }
+
+    /// Poll the worker's queue source, then claim whatever it has room for
+    ///
+    /// Stopped workers ignore wake-ups.
+    pub(super) async fn handle_worker_wake(&self, name: &str) -> Result<Vec<Event>, RuntimeError> {
+        let def = self.worker_def(name)?;
+        if !self.get_worker(name).is_some_and(|w| w.is_running()) {
+            tracing::debug!(worker = name, "ignoring wake for stopped worker");
+            return Ok(vec![]);
+        }
+        // Subscriptions are not persisted, so a worker resumed after a
+        // restart subscribes on its first wake
+        self.subscribe_worker(def);
+
+        let mut result_events = Vec::new();
+        if let Some(queue) = &def.queue {
+            result_events.extend(self.poll_queue(queue).await?);
+        }
+        result_events.extend(self.fill_worker(name, false).await?);
+        Ok(result_events)
+    }
+
+    /// Translate a custom event into wake-ups for workers subscribed via `wake_on`
+    pub(super) fn wake_subscribed_workers(&self, event_name: &str) -> Vec<Event> {
+        let subscriptions = self
+            .worker_subscriptions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        subscriptions
+            .subscribers(event_name)
+            .into_iter()
+            .map(|worker| Event::WorkerWake { worker })
+            .collect()
+    }
+
+    /// Let running workers of a queue claim a freshly pushed item
+    pub(super) async fn wake_queue_workers(
+        &self,
+        event_name: &str,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let Some(queue) = event_name
+            .strip_prefix("queue:")
+            .and_then(|rest| rest.strip_suffix(":pushed"))
+        else {
+            return Ok(vec![]);
+        };
+
+        let mut names: Vec<_> = self
+            .runbook
+            .workers
+            .values()
+            .filter(|def| def.queue.as_deref() == Some(queue))
+            .map(|def| def.name.clone())
+            .collect();
+        names.sort();
+
+        let mut result_events = Vec::new();
+        for name in names {
+            result_events.extend(self.fill_worker(&name, false).await?);
+        }
+        Ok(result_events)
+    }
+
+    /// Settle the queue item of a worker's pipeline once the pipeline ends
+    ///
+    /// The item is completed if the pipeline reached `done` and failed
+    /// otherwise; the worker then claims its next item.
+    pub(super) async fn worker_pipeline_finished(
+        &self,
+        pipeline_id: &str,
+        error: Option<&str>,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let Some((name, item_id)) = self.worker_for_pipeline(pipeline_id) else {
+            return Ok(vec![]);
+        };
+        let def = self.worker_def(&name)?;
+
+        let effect = Effect::Persist {
+            operation: Operation::WorkerRelease {
+                name: name.clone(),
+                pipeline_id: pipeline_id.to_string(),
+            },
+        };
+        self.executor.execute(effect).await?;
+
+        let mut result_events = Vec::new();
+        if let Some(queue) = &def.queue {
+            let succeeded = error.is_none()
+                && self
+                    .get_pipeline(pipeline_id)
+                    .is_some_and(|p| p.phase == "done");
+            if succeeded {
+                result_events.extend(self.queue_complete(queue, &item_id, pipeline_id).await?);
+            } else {
+                let reason = error.unwrap_or("pipeline failed");
+                result_events.extend(
+                    self.queue_fail(queue, &item_id, pipeline_id, reason)
+                        .await?,
+                );
+            }
+        }
+        tracing::info!(worker = %name, pipeline_id, item_id, "worker pipeline finished");
+
+        result_events.extend(Box::pin(self.fill_worker(&name, true)).await?);
+        Ok(result_events)
+    }
+
+    /// Schedule a wake for every worker that was running before a restart
+    pub async fn resume_workers(&self) -> Result<(), RuntimeError> {
+        let names: Vec<_> = {
+            let state = self.executor.state();
+            let state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
+            state_guard
+                .workers
+                .values()
+                .filter(|worker| worker.is_running())
+                .map(|worker| worker.name.clone())
+                .collect()
+        };
+        let effects = names
+            .iter()
+            .map(|name| Effect::SetTimer {
+                id: worker::worker_wake_timer(name),
+                duration: Duration::ZERO,
+            })
+            .collect();
+        self.executor.execute_all(effects).await?;
+        Ok(())
+    }
+
+    /// Get a worker by name
+    pub fn get_worker(&self, name: &str) -> Option<Worker> {
+        let state = self.executor.state();
+        let state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
+        state_guard.workers.get(name).cloned()
+    }
+
+    /// Claim queue items and start a handler pipeline for each, up to concurrency
+    ///
+    /// A worker left without work re-arms its `idle_action` wait, and emits
+    /// `worker:idle` when `announce_idle` is set.
+    async fn fill_worker(
+        &self,
+        name: &str,
+        announce_idle: bool,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        let def = self.worker_def(name)?;
+        let Some(queue) = &def.queue else {
+            return self.settle_idle(def, announce_idle).await;
+        };
+        let pipeline_name = def.handler_pipeline().ok_or_else(|| {
+            RuntimeError::PipelineDefNotFound(format!("handler for worker {}", name))
+        })?;
+
+        let mut result_events = Vec::new();
+        while self
+            .get_worker(name)
+            .is_some_and(|worker| worker.has_capacity(def.concurrency))
+        {
+            if self.queue_state(queue)?.next_pending().is_none() {
+                break;
+            }
+            // The item is held by the pipeline handling it, so a late result
+            // from an attempt whose claim expired is ignored
+            let pipeline_id = self.id_gen.next();
+            let Some(item) = self.queue_claim(queue, &pipeline_id).await? else {
+                break;
+            };
+            let mut inputs = item.data.clone();
+            let item_name = inputs.get("name").unwrap_or(&item.id);
+            let item_name = worker::item_pipeline_name(item_name, item.attempts);
+            inputs.insert("name".to_string(), item_name);
+
+            let events = self
+                .create_pipeline_with_id(&pipeline_id, pipeline_name, &inputs, None)
+                .await?;
+            result_events.extend(events);
+            let effect = Effect::Persist {
+                operation: Operation::WorkerAssign {
+                    name: name.to_string(),
+                    pipeline_id: pipeline_id.clone(),
+                    item_id: item.id.clone(),
+                },
+            };
+            self.executor.execute(effect).await?;
+            tracing::info!(worker = name, item_id = %item.id, pipeline_id, "worker claimed item");
+
+            result_events.extend(self.start_pipeline(&pipeline_id).await?);
+        }
+        result_events.extend(self.settle_idle(def, announce_idle).await?);
+        Ok(result_events)
+    }
+
+    async fn settle_idle(
+        &self,
+        def: &WorkerDef,
+        announce: bool,
+    ) -> Result<Vec<Event>, RuntimeError> {
+        if !self.get_worker(&def.name).is_some_and(|w| w.is_available()) {
+            return Ok(vec![]);
+        }
+        let effects = worker::idle_effects(def, announce);
+        Ok(self.executor.execute_all(effects).await?)
+    }
+
+    fn subscribe_worker(&self, def: &WorkerDef) {
+        let mut subscriptions = self
+            .worker_subscriptions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        for event in &def.wake_on {
+            subscriptions.subscribe(event, &def.name);
+        }
+    }
+
+    /// The worker running a pipeline and the queue item it handles
+    fn worker_for_pipeline(&self, pipeline_id: &str) -> Option<(String, String)> {
+        let state = self.executor.state();
+        let state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
+        state_guard.workers.values().find_map(|worker| {
+            worker
+                .pipelines
+                .get(pipeline_id)
+                .map(|item_id| (worker.name.clone(), item_id.clone()))
+        })
+    }
+
+    fn worker_def(&self, name: &str) -> Result<&WorkerDef, RuntimeError> {
+        self.runbook
+            .get_worker(name)
+            .ok_or_else(|| RuntimeError::WorkerNotFound(name.to_string()))
+    }
+}
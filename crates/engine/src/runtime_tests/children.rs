@@ -0,0 +1,173 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+
+const NESTED_RUNBOOK: &str = r#"
+[command.release]
+args = "<name>"
+run = { pipeline = "release" }
+
+[pipeline.release]
+inputs = ["name"]
+
+[[pipeline.release.phase]]
+name = "build"
+run = { pipeline = "build", inputs = { name = "{name}-build", target = "{name}" } }
+next = "ship"
+on_fail = "rollback"
+
+[[pipeline.release.phase]]
+name = "ship"
+run = "touch shipped"
+next = "done"
+
+[[pipeline.release.phase]]
+name = "rollback"
+run = "touch rolled-back"
+next = "done"
+
+[pipeline.build]
+inputs = ["target"]
+
+[[pipeline.build.phase]]
+name = "compile"
+run = "echo {target} > built; test ! -e fail-build"
+"#;
+
+/// The parent `release` pipeline and its child `build` pipeline
+fn release_pipelines(runtime: &TestRuntime) -> (Pipeline, Pipeline) {
+    let pipelines = runtime.pipelines();
+    let find = |kind: &str| {
+        pipelines
+            .values()
+            .find(|p| p.kind == kind)
+            .cloned()
+            .unwrap()
+    };
+    (find("release"), find("build"))
+}
+
+#[tokio::test]
+async fn child_pipeline_completes_parent_phase() {
+    let runtime = setup_with(NESTED_RUNBOOK, &["a"]);
+    let workspace = runtime.worktree_root.join("a");
+
+    invoke(&runtime, "release", "a").await;
+    let (parent, child) = release_pipelines(&runtime);
+    assert_eq!(parent.phase, "build");
+    assert_eq!(parent.phase_status, PhaseStatus::Running);
+    assert_eq!(child.name, "a-build");
+    assert_eq!(child.inputs["target"], "a");
+    assert_eq!(
+        child.parent,
+        Some(PipelineParent {
+            id: parent.id.clone(),
+            phase: "build".to_string(),
+        })
+    );
+
+    drain(
+        &runtime,
+        Event::ShellCompleted {
+            pipeline_id: child.id.clone(),
+            phase: "compile".to_string(),
+            exit_code: 0,
+            outputs: HashMap::new(),
+        },
+    )
+    .await;
+
+    let (parent, child) = release_pipelines(&runtime);
+    assert_eq!(child.phase, "done");
+    assert_eq!(parent.phase, "done");
+    // The child ran in the parent's workspace
+    assert_eq!(
+        std::fs::read_to_string(workspace.join("built")).unwrap(),
+        "a\n"
+    );
+    assert!(workspace.join("shipped").exists());
+}
+
+#[tokio::test]
+async fn child_pipeline_failure_routes_parent_to_on_fail() {
+    let runtime = setup_with(NESTED_RUNBOOK, &["a"]);
+    let workspace = runtime.worktree_root.join("a");
+    std::fs::write(workspace.join("fail-build"), "").unwrap();
+
+    let command = Event::CommandInvoked {
+        command: "release".to_string(),
+        args: [("name".to_string(), "a".to_string())]
+            .into_iter()
+            .collect(),
+    };
+    drain(&runtime, command).await;
+
+    let (parent, child) = release_pipelines(&runtime);
+    assert_eq!(child.phase, "failed");
+    assert_eq!(parent.phase, "done");
+    assert!(parent.history.iter().any(|r| r.phase == "rollback"));
+    assert_eq!(
+        parent.error.as_deref(),
+        Some(
+            format!(
+                "child pipeline {} failed: shell exited with code 1",
+                child.id
+            )
+            .as_str()
+        )
+    );
+    assert!(workspace.join("rolled-back").exists());
+    assert!(!workspace.join("shipped").exists());
+}
+
+#[tokio::test]
+async fn child_agent_session_failure_routes_parent_to_on_fail() {
+    let runbook = format!(
+        "{}\n[pipeline.review]\ninputs = [\"target\"]\n\n\
+         [[pipeline.review.phase]]\nname = \"read\"\nrun = {{ agent = \"reader\" }}\n\n\
+         [agent.reader]\nrun = \"claude\"\n",
+        NESTED_RUNBOOK.replace(
+            "run = { pipeline = \"build\",",
+            "run = { pipeline = \"review\","
+        )
+    );
+    let runtime = setup_with(&runbook, &["a"]);
+    invoke(&runtime, "release", "a").await;
+
+    let pipelines = runtime.pipelines();
+    let child = pipelines.values().find(|p| p.kind == "review").unwrap();
+    let session_id = child.session_id.clone().unwrap();
+    drain(
+        &runtime,
+        Event::SessionExited {
+            session_id,
+            exit_code: 1,
+        },
+    )
+    .await;
+
+    let pipelines = runtime.pipelines();
+    let child = pipelines.values().find(|p| p.kind == "review").unwrap();
+    let parent = pipelines.values().find(|p| p.kind == "release").unwrap();
+    assert_eq!(child.phase, "failed");
+    assert_eq!(child.error.as_deref(), Some("exit code: 1"));
+    assert_eq!(parent.phase, "done");
+    assert!(parent.history.iter().any(|r| r.phase == "rollback"));
+    assert!(runtime.worktree_root.join("a/rolled-back").exists());
+}
+
+#[tokio::test]
+async fn resume_waits_for_child_pipeline() {
+    let runtime = setup_with(NESTED_RUNBOOK, &["a"]);
+    invoke(&runtime, "release", "a").await;
+
+    // Both pipelines were in flight when the daemon stopped; the child's
+    // shell died with it
+    restart(&runtime).await;
+
+    let (parent, child) = release_pipelines(&runtime);
+    assert_eq!(child.phase, "done");
+    assert_eq!(parent.phase, "done");
+    assert_eq!(runtime.pipelines().len(), 2);
+}
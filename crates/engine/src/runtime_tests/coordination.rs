@@ -0,0 +1,293 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+
+const LOCK_RUNBOOK: &str = r#"
+[command.merge]
+args = "<name>"
+run = { pipeline = "merge" }
+
+[pipeline.merge]
+inputs = ["name"]
+
+[[pipeline.merge.phase]]
+name = "merge"
+run = "echo merge"
+lock = "main_branch"
+
+[lock.main_branch]
+timeout = "30m"
+heartbeat = "30s"
+"#;
+
+fn lock_holder(runtime: &TestRuntime, name: &str) -> Option<String> {
+    let state = runtime.executor.state();
+    let state_guard = state.lock().unwrap();
+    state_guard
+        .locks
+        .get(name)
+        .and_then(|l| l.holder().map(String::from))
+}
+
+#[tokio::test]
+async fn phase_waits_for_lock_and_starts_on_release() {
+    let runtime = setup_with(LOCK_RUNBOOK, &["a", "b"]);
+    let first = invoke(&runtime, "merge", "a").await;
+    let second = invoke(&runtime, "merge", "b").await;
+
+    assert_eq!(lock_holder(&runtime, "main_branch"), Some(first.clone()));
+    let waiting = runtime.get_pipeline(&second).unwrap();
+    assert_eq!(waiting.phase, "merge");
+    assert_eq!(waiting.phase_status, PhaseStatus::Pending);
+
+    runtime
+        .handle_event(Event::ShellCompleted {
+            pipeline_id: first.clone(),
+            phase: "merge".to_string(),
+            exit_code: 0,
+            outputs: HashMap::new(),
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(runtime.get_pipeline(&first).unwrap().phase, "done");
+    assert_eq!(lock_holder(&runtime, "main_branch"), Some(second.clone()));
+    assert_eq!(
+        runtime.get_pipeline(&second).unwrap().phase_status,
+        PhaseStatus::Running
+    );
+}
+
+#[tokio::test]
+async fn lock_released_when_phase_fails() {
+    let runtime = setup_with(LOCK_RUNBOOK, &["a"]);
+    let pipeline_id = invoke(&runtime, "merge", "a").await;
+
+    runtime
+        .handle_event(Event::ShellCompleted {
+            pipeline_id: pipeline_id.clone(),
+            phase: "merge".to_string(),
+            exit_code: 1,
+            outputs: HashMap::new(),
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(runtime.get_pipeline(&pipeline_id).unwrap().phase, "failed");
+    assert_eq!(lock_holder(&runtime, "main_branch"), None);
+}
+
+#[tokio::test]
+async fn stale_lock_reclaimed_on_retry() {
+    let runtime = setup_with(LOCK_RUNBOOK, &["a", "b"]);
+    let first = invoke(&runtime, "merge", "a").await;
+    let second = invoke(&runtime, "merge", "b").await;
+
+    // Retrying before the holder goes stale keeps waiting
+    runtime
+        .handle_event(Event::Timer {
+            id: format!("lock:{}:retry", second),
+        })
+        .await
+        .unwrap();
+    assert_eq!(lock_holder(&runtime, "main_branch"), Some(first.clone()));
+
+    runtime.clock.advance(Duration::from_secs(31 * 60));
+    runtime
+        .handle_event(Event::Timer {
+            id: format!("lock:{}:retry", second),
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(lock_holder(&runtime, "main_branch"), Some(second.clone()));
+    assert_eq!(
+        runtime.get_pipeline(&second).unwrap().phase_status,
+        PhaseStatus::Running
+    );
+}
+
+const SEMAPHORE_RUNBOOK: &str = r#"
+[command.build]
+args = "<name>"
+run = { pipeline = "build" }
+
+[pipeline.build]
+inputs = ["name"]
+
+[[pipeline.build.phase]]
+name = "work"
+run = "echo work"
+semaphore = "agents"
+
+[semaphore.agents]
+max = 2
+slot_timeout = "1h"
+"#;
+
+fn slot_holders(runtime: &TestRuntime, name: &str) -> Vec<String> {
+    let state = runtime.executor.state();
+    let state_guard = state.lock().unwrap();
+    state_guard
+        .semaphores
+        .get(name)
+        .map(|s| s.holders.iter().map(|h| h.id.clone()).collect())
+        .unwrap_or_default()
+}
+
+#[tokio::test]
+async fn phase_queues_until_semaphore_slot_frees() {
+    let runtime = setup_with(SEMAPHORE_RUNBOOK, &["a", "b", "c"]);
+    let first = invoke(&runtime, "build", "a").await;
+    let second = invoke(&runtime, "build", "b").await;
+    let third = invoke(&runtime, "build", "c").await;
+
+    assert_eq!(
+        slot_holders(&runtime, "agents"),
+        vec![first.clone(), second]
+    );
+    assert_eq!(
+        runtime.get_pipeline(&third).unwrap().phase_status,
+        PhaseStatus::Pending
+    );
+
+    runtime
+        .handle_event(Event::ShellCompleted {
+            pipeline_id: first.clone(),
+            phase: "work".to_string(),
+            exit_code: 0,
+            outputs: HashMap::new(),
+        })
+        .await
+        .unwrap();
+
+    assert!(slot_holders(&runtime, "agents").contains(&third));
+    assert!(!slot_holders(&runtime, "agents").contains(&first));
+    assert_eq!(
+        runtime.get_pipeline(&third).unwrap().phase_status,
+        PhaseStatus::Running
+    );
+}
+
+#[tokio::test]
+async fn weighted_phase_waits_for_all_its_slots() {
+    let runbook = format!(
+        "{}\n[command.heavy]\nargs = \"<name>\"\nrun = {{ pipeline = \"heavy\" }}\n\n\
+         [pipeline.heavy]\ninputs = [\"name\"]\n\n[[pipeline.heavy.phase]]\n\
+         name = \"work\"\nrun = \"echo heavy\"\nsemaphore = \"agents\"\nslots = 2\n",
+        SEMAPHORE_RUNBOOK
+    );
+    let runtime = setup_with(&runbook, &["a", "h"]);
+    let light = invoke(&runtime, "build", "a").await;
+    let heavy = invoke(&runtime, "heavy", "h").await;
+
+    assert_eq!(slot_holders(&runtime, "agents"), vec![light.clone()]);
+    assert_eq!(
+        runtime.get_pipeline(&heavy).unwrap().phase_status,
+        PhaseStatus::Pending
+    );
+
+    runtime
+        .handle_event(Event::ShellCompleted {
+            pipeline_id: light,
+            phase: "work".to_string(),
+            exit_code: 0,
+            outputs: HashMap::new(),
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(slot_holders(&runtime, "agents"), vec![heavy.clone()]);
+    let state = runtime.executor.state();
+    assert_eq!(state.lock().unwrap().semaphores["agents"].used(), 2);
+    assert_eq!(
+        runtime.get_pipeline(&heavy).unwrap().phase_status,
+        PhaseStatus::Running
+    );
+}
+
+#[tokio::test]
+async fn stale_semaphore_slot_reclaimed_on_retry() {
+    let runtime = setup_with(SEMAPHORE_RUNBOOK, &["a", "b", "c"]);
+    let first = invoke(&runtime, "build", "a").await;
+    let _second = invoke(&runtime, "build", "b").await;
+    let third = invoke(&runtime, "build", "c").await;
+
+    runtime.clock.advance(Duration::from_secs(2 * 60 * 60));
+    runtime
+        .handle_event(Event::Timer {
+            id: format!("semaphore:{}:retry", third),
+        })
+        .await
+        .unwrap();
+
+    let holders = slot_holders(&runtime, "agents");
+    assert!(!holders.contains(&first));
+    assert!(holders.contains(&third));
+    assert_eq!(
+        runtime.get_pipeline(&third).unwrap().phase_status,
+        PhaseStatus::Running
+    );
+}
+
+#[tokio::test]
+async fn lock_not_held_while_waiting_for_semaphore() {
+    let runbook = format!(
+        "{}\n[command.merge]\nargs = \"<name>\"\nrun = {{ pipeline = \"merge\" }}\n\n\
+         [pipeline.merge]\ninputs = [\"name\"]\n\n[[pipeline.merge.phase]]\n\
+         name = \"merge\"\nrun = \"echo merge\"\nlock = \"main_branch\"\nsemaphore = \"agents\"\n",
+        SEMAPHORE_RUNBOOK
+    );
+    let runtime = setup_with(&runbook, &["a", "b", "c"]);
+    invoke(&runtime, "build", "a").await;
+    invoke(&runtime, "build", "b").await;
+    let merge = invoke(&runtime, "merge", "c").await;
+
+    assert_eq!(
+        runtime.get_pipeline(&merge).unwrap().phase_status,
+        PhaseStatus::Pending
+    );
+    assert_eq!(lock_holder(&runtime, "main_branch"), None);
+}
+
+#[tokio::test]
+async fn lock_handed_back_for_full_semaphore_wakes_other_waiters() {
+    let runbook = format!(
+        "{}\n[command.merge]\nargs = \"<name>\"\nrun = {{ pipeline = \"merge\" }}\n\n\
+         [pipeline.merge]\ninputs = [\"name\"]\n\n[[pipeline.merge.phase]]\n\
+         name = \"merge\"\nrun = \"echo merge\"\nlock = \"main_branch\"\nsemaphore = \"agents\"\n\n\
+         [command.tag]\nargs = \"<name>\"\nrun = {{ pipeline = \"tag\" }}\n\n\
+         [pipeline.tag]\ninputs = [\"name\"]\n\n[[pipeline.tag.phase]]\n\
+         name = \"tag\"\nrun = \"echo tag\"\nlock = \"main_branch\"\n",
+        SEMAPHORE_RUNBOOK
+    );
+    let runtime = setup_with(&runbook, &["a", "b", "x", "y", "z"]);
+    invoke(&runtime, "build", "a").await;
+    invoke(&runtime, "build", "b").await;
+    let holder = invoke(&runtime, "tag", "x").await;
+    let merge = invoke(&runtime, "merge", "y").await;
+    let tag = invoke(&runtime, "tag", "z").await;
+    assert_eq!(lock_holder(&runtime, "main_branch"), Some(holder.clone()));
+
+    // The merge is woken first, cannot get a slot, and hands the lock on
+    runtime
+        .handle_event(Event::ShellCompleted {
+            pipeline_id: holder,
+            phase: "tag".to_string(),
+            exit_code: 0,
+            outputs: HashMap::new(),
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(
+        runtime.get_pipeline(&merge).unwrap().phase_status,
+        PhaseStatus::Pending
+    );
+    assert_eq!(lock_holder(&runtime, "main_branch"), Some(tag.clone()));
+    assert_eq!(
+        runtime.get_pipeline(&tag).unwrap().phase_status,
+        PhaseStatus::Running
+    );
+}
@@ -0,0 +1,149 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+
+const CRON_RUNBOOK: &str = r#"
+[cron.janitor]
+interval = "30s"
+run = "echo swept >> swept.log"
+
+[cron.nightly]
+schedule = "0 3 * * *"
+run = { pipeline = "cleanup" }
+
+[pipeline.cleanup]
+
+[[pipeline.cleanup.phase]]
+name = "sweep"
+run = "true"
+next = "done"
+"#;
+
+#[tokio::test]
+async fn enabled_cron_runs_on_schedule_until_disabled() {
+    let runtime = setup_with(CRON_RUNBOOK, &[]);
+    let log = runtime.project_root.join("swept.log");
+
+    // Disabled crons ignore stray ticks
+    runtime
+        .handle_event(Event::Timer {
+            id: "cron:janitor:tick".to_string(),
+        })
+        .await
+        .unwrap();
+    assert!(!log.exists());
+
+    runtime
+        .handle_event(Event::CronEnable {
+            cron: "janitor".to_string(),
+        })
+        .await
+        .unwrap();
+    assert!(runtime.cron_enabled("janitor"));
+    let fired = runtime
+        .scheduler()
+        .lock()
+        .unwrap()
+        .fired_timers(std::time::Instant::now() + Duration::from_secs(31));
+    assert_eq!(
+        fired,
+        vec![Event::Timer {
+            id: "cron:janitor:tick".to_string()
+        }]
+    );
+    for event in fired {
+        runtime.handle_event(event).await.unwrap();
+    }
+    assert_eq!(std::fs::read_to_string(&log).unwrap(), "swept\n");
+    assert!(runtime.scheduler().lock().unwrap().has_timers());
+
+    runtime
+        .handle_event(Event::CronDisable {
+            cron: "janitor".to_string(),
+        })
+        .await
+        .unwrap();
+    assert!(!runtime.cron_enabled("janitor"));
+    assert!(!runtime.scheduler().lock().unwrap().has_timers());
+}
+
+#[tokio::test]
+async fn failed_cron_run_keeps_schedule() {
+    let runbook = format!(
+        "{}\n[cron.broken]\ninterval = \"30s\"\nrun = {{ pipeline = \"missing\" }}\n",
+        CRON_RUNBOOK
+    );
+    let runtime = setup_with(&runbook, &[]);
+    runtime
+        .handle_event(Event::CronEnable {
+            cron: "broken".to_string(),
+        })
+        .await
+        .unwrap();
+
+    let fired = runtime
+        .scheduler()
+        .lock()
+        .unwrap()
+        .fired_timers(runtime.clock.now() + Duration::from_secs(31));
+    assert_eq!(fired.len(), 1);
+    assert!(!runtime.scheduler().lock().unwrap().has_timers());
+
+    for event in fired {
+        assert!(runtime.handle_event(event).await.is_err());
+    }
+    assert!(runtime.scheduler().lock().unwrap().has_timers());
+}
+
+#[tokio::test]
+async fn cron_schedule_follows_runtime_clock() {
+    let runtime = setup_with(CRON_RUNBOOK, &[]);
+    runtime
+        .handle_event(Event::CronEnable {
+            cron: "nightly".to_string(),
+        })
+        .await
+        .unwrap();
+
+    // The fake clock starts at midnight UTC, three hours before the run
+    let scheduler = runtime.scheduler();
+    let mut scheduler = scheduler.lock().unwrap();
+    let before = runtime.clock.now() + Duration::from_secs(3 * 60 * 60 - 1);
+    assert!(scheduler.fired_timers(before).is_empty());
+    let at = runtime.clock.now() + Duration::from_secs(3 * 60 * 60);
+    assert_eq!(scheduler.fired_timers(at).len(), 1);
+}
+
+#[tokio::test]
+async fn cron_run_starts_pipeline_once() {
+    let runtime = setup_with(CRON_RUNBOOK, &["pipe-1"]);
+
+    drain(
+        &runtime,
+        Event::CronRun {
+            cron: "nightly".to_string(),
+        },
+    )
+    .await;
+
+    let pipelines = runtime.pipelines();
+    assert_eq!(pipelines.len(), 1);
+    let pipeline = pipelines.values().next().unwrap();
+    assert_eq!(pipeline.kind, "cleanup");
+    assert_eq!(pipeline.phase, "done");
+    // Running by hand neither enables nor schedules the cron
+    assert!(!runtime.cron_enabled("nightly"));
+    assert!(!runtime.scheduler().lock().unwrap().has_timers());
+}
+
+#[tokio::test]
+async fn unknown_cron_is_an_error() {
+    let runtime = setup_with(CRON_RUNBOOK, &[]);
+    let result = runtime
+        .handle_event(Event::CronEnable {
+            cron: "nope".to_string(),
+        })
+        .await;
+    assert!(matches!(result, Err(RuntimeError::CronNotFound(_))));
+}
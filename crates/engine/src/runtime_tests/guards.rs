@@ -0,0 +1,314 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+
+const GUARD_RUNBOOK: &str = r#"
+[command.build]
+args = "<name>"
+run = { pipeline = "build" }
+
+[pipeline.build]
+inputs = ["name"]
+
+[[pipeline.build.phase]]
+name = "work"
+run = "echo work"
+pre = ["ready"]
+post = ["tests_pass"]
+on_fail = "cleanup"
+
+[[pipeline.build.phase]]
+name = "ship"
+run = "echo ship"
+
+[[pipeline.build.phase]]
+name = "cleanup"
+run = "echo cleanup"
+
+[guard.ready]
+condition = "test -f ready-{name}"
+retry = { max = 2, interval = "1s" }
+
+[guard.tests_pass]
+condition = "test -f passed"
+"#;
+
+#[tokio::test]
+async fn pre_guard_holds_phase_until_condition_passes() {
+    let runtime = setup_with(GUARD_RUNBOOK, &["a"]);
+    let pipeline_id = invoke(&runtime, "build", "a").await;
+    assert_eq!(
+        runtime.get_pipeline(&pipeline_id).unwrap().phase_status,
+        PhaseStatus::Pending
+    );
+
+    std::fs::write(runtime.worktree_root.join("a/ready-a"), "").unwrap();
+    runtime
+        .handle_event(Event::Timer {
+            id: format!("guard:{}:retry", pipeline_id),
+        })
+        .await
+        .unwrap();
+
+    let pipeline = runtime.get_pipeline(&pipeline_id).unwrap();
+    assert_eq!(pipeline.phase, "work");
+    assert_eq!(pipeline.phase_status, PhaseStatus::Running);
+}
+
+#[tokio::test]
+async fn pre_guard_fails_phase_after_retries() {
+    let runtime = setup_with(GUARD_RUNBOOK, &["a"]);
+    let pipeline_id = invoke(&runtime, "build", "a").await;
+
+    for _ in 0..2 {
+        runtime
+            .handle_event(Event::Timer {
+                id: format!("guard:{}:retry", pipeline_id),
+            })
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(runtime.get_pipeline(&pipeline_id).unwrap().phase, "cleanup");
+}
+
+#[tokio::test]
+async fn failing_post_guard_routes_to_on_fail() {
+    let runtime = setup_with(GUARD_RUNBOOK, &["a"]);
+    std::fs::write(runtime.worktree_root.join("a/ready-a"), "").unwrap();
+    let pipeline_id = invoke(&runtime, "build", "a").await;
+
+    runtime
+        .handle_event(Event::ShellCompleted {
+            pipeline_id: pipeline_id.clone(),
+            phase: "work".to_string(),
+            exit_code: 0,
+            outputs: HashMap::new(),
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(runtime.get_pipeline(&pipeline_id).unwrap().phase, "cleanup");
+}
+
+#[tokio::test]
+async fn passing_post_guard_advances() {
+    let runtime = setup_with(GUARD_RUNBOOK, &["a"]);
+    std::fs::write(runtime.worktree_root.join("a/ready-a"), "").unwrap();
+    std::fs::write(runtime.worktree_root.join("a/passed"), "").unwrap();
+    let pipeline_id = invoke(&runtime, "build", "a").await;
+
+    runtime
+        .handle_event(Event::ShellCompleted {
+            pipeline_id: pipeline_id.clone(),
+            phase: "work".to_string(),
+            exit_code: 0,
+            outputs: HashMap::new(),
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(runtime.get_pipeline(&pipeline_id).unwrap().phase, "ship");
+    assert_eq!(
+        runtime.get_pipeline(&pipeline_id).unwrap().phase_status,
+        PhaseStatus::Running
+    );
+}
+
+#[tokio::test]
+async fn guard_timeout_escalates() {
+    let runbook = GUARD_RUNBOOK.replace(
+        "retry = { max = 2, interval = \"1s\" }",
+        "timeout = \"1m\"\non_timeout = \"escalate\"",
+    );
+    let runtime = setup_with(&runbook, &["a"]);
+    let pipeline_id = invoke(&runtime, "build", "a").await;
+
+    runtime.clock.advance(Duration::from_secs(61));
+    runtime
+        .handle_event(Event::Timer {
+            id: format!("guard:{}:retry", pipeline_id),
+        })
+        .await
+        .unwrap();
+
+    let pipeline = runtime.get_pipeline(&pipeline_id).unwrap();
+    assert_eq!(pipeline.phase, "work");
+    assert_eq!(pipeline.phase_status, PhaseStatus::Waiting);
+}
+
+#[tokio::test]
+async fn escalated_guard_resumes_on_request() {
+    let runbook = GUARD_RUNBOOK.replace(
+        "retry = { max = 2, interval = \"1s\" }",
+        "timeout = \"1m\"\non_timeout = \"escalate\"",
+    );
+    let runtime = setup_with(&runbook, &["a"]);
+    let pipeline_id = invoke(&runtime, "build", "a").await;
+    runtime.clock.advance(Duration::from_secs(61));
+    runtime
+        .handle_event(Event::Timer {
+            id: format!("guard:{}:retry", pipeline_id),
+        })
+        .await
+        .unwrap();
+
+    let resume = custom(
+        "pipeline:resume",
+        serde_json::json!({ "pipeline_id": pipeline_id }),
+    );
+    runtime.handle_event(resume.clone()).await.unwrap();
+    assert_eq!(
+        runtime.get_pipeline(&pipeline_id).unwrap().phase_status,
+        PhaseStatus::Waiting
+    );
+
+    std::fs::write(runtime.worktree_root.join("a/ready-a"), "").unwrap();
+    runtime.handle_event(resume).await.unwrap();
+
+    let pipeline = runtime.get_pipeline(&pipeline_id).unwrap();
+    assert_eq!(pipeline.phase, "work");
+    assert_eq!(pipeline.phase_status, PhaseStatus::Running);
+}
+
+#[tokio::test]
+async fn escalated_guard_listens_for_wake_on_after_restart() {
+    let runbook = GUARD_RUNBOOK.replace(
+        "retry = { max = 2, interval = \"1s\" }",
+        "timeout = \"1m\"\non_timeout = \"escalate\"\nwake_on = [\"ready:{name}\"]",
+    );
+    let runtime = setup_with(&runbook, &["a"]);
+    let pipeline_id = invoke(&runtime, "build", "a").await;
+    runtime.clock.advance(Duration::from_secs(61));
+    drain(
+        &runtime,
+        Event::Timer {
+            id: format!("guard:{}:retry", pipeline_id),
+        },
+    )
+    .await;
+    assert_eq!(
+        runtime.get_pipeline(&pipeline_id).unwrap().phase_status,
+        PhaseStatus::Waiting
+    );
+
+    // Guard waits are only kept in memory
+    runtime.guard_waits.lock().unwrap().clear();
+    *runtime.guard_subscriptions.lock().unwrap() = Subscriptions::new();
+    restart(&runtime).await;
+
+    std::fs::write(runtime.worktree_root.join("a/ready-a"), "").unwrap();
+    runtime
+        .handle_event(custom("ready:a", serde_json::json!({})))
+        .await
+        .unwrap();
+
+    let pipeline = runtime.get_pipeline(&pipeline_id).unwrap();
+    assert_eq!(pipeline.phase, "work");
+    assert_eq!(pipeline.phase_status, PhaseStatus::Running);
+}
+
+#[tokio::test]
+async fn hanging_guard_condition_counts_as_failed() {
+    let runbook = GUARD_RUNBOOK.replace("test -f ready-{name}", "sleep 10");
+    let runtime = setup_with_timeout(&runbook, &["a"], Duration::from_millis(100));
+    let pipeline_id = invoke(&runtime, "build", "a").await;
+
+    let pipeline = runtime.get_pipeline(&pipeline_id).unwrap();
+    assert_eq!(pipeline.phase, "work");
+    assert_eq!(pipeline.phase_status, PhaseStatus::Pending);
+    assert!(runtime.scheduler().lock().unwrap().has_timers());
+}
+
+const WAKE_ON_RUNBOOK: &str = r#"
+[command.dep]
+args = "<name>"
+run = { pipeline = "dep" }
+
+[command.build]
+args = "<name> <after>"
+run = { pipeline = "build" }
+
+[pipeline.dep]
+inputs = ["name"]
+
+[[pipeline.dep.phase]]
+name = "work"
+run = "echo dep"
+
+[pipeline.build]
+inputs = ["name", "after"]
+
+[[pipeline.build.phase]]
+name = "work"
+run = "echo build"
+pre = ["blocker_merged"]
+
+[guard.blocker_merged]
+condition = "test -f unblocked"
+wake_on = ["pipeline:{after}:complete"]
+"#;
+
+#[tokio::test]
+async fn wake_on_event_rechecks_blocked_guard() {
+    let runtime = setup_with(WAKE_ON_RUNBOOK, &["a", "b"]);
+    let dep = invoke(&runtime, "dep", "a").await;
+    runtime
+        .handle_event(Event::CommandInvoked {
+            command: "build".to_string(),
+            args: [("name", "b"), ("after", "a")]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        })
+        .await
+        .unwrap();
+    let build = runtime
+        .pipelines()
+        .into_values()
+        .find(|p| p.kind == "build")
+        .unwrap()
+        .id;
+
+    // Without wake_on polling, the guard just waits
+    assert_eq!(
+        runtime.get_pipeline(&build).unwrap().phase_status,
+        PhaseStatus::Pending
+    );
+    assert!(!runtime.scheduler().lock().unwrap().has_timers());
+
+    std::fs::write(runtime.worktree_root.join("b/unblocked"), "").unwrap();
+
+    // Unrelated events don't wake it
+    runtime
+        .handle_event(Event::Custom {
+            name: "pipeline:other:complete".to_string(),
+            data: serde_json::json!({}),
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        runtime.get_pipeline(&build).unwrap().phase_status,
+        PhaseStatus::Pending
+    );
+
+    // The dependency completing emits the event the guard subscribed to
+    let events = runtime
+        .handle_event(Event::ShellCompleted {
+            pipeline_id: dep,
+            phase: "work".to_string(),
+            exit_code: 0,
+            outputs: HashMap::new(),
+        })
+        .await
+        .unwrap();
+    for event in events {
+        runtime.handle_event(event).await.unwrap();
+    }
+
+    assert_eq!(
+        runtime.get_pipeline(&build).unwrap().phase_status,
+        PhaseStatus::Running
+    );
+}
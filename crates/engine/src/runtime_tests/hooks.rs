@@ -0,0 +1,88 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+
+const HOOK_RUNBOOK: &str = r#"
+[command.build]
+args = "<name>"
+run = { pipeline = "build" }
+
+[pipeline.build]
+inputs = ["name"]
+
+[[pipeline.build.phase]]
+name = "check"
+run = "test {name} != broken"
+
+[[pipeline.build.phase]]
+name = "ship"
+run = "true"
+
+[pipeline.build.events]
+on_phase = "echo '{name} -> {phase}' >> hooks.log"
+on_complete = "echo '{name} complete' >> hooks.log"
+on_fail = "echo '{name} failed in {phase}: {error}' >> hooks.log"
+"#;
+
+#[tokio::test]
+async fn pipeline_hooks_run_on_phase_and_completion() {
+    let runtime = setup_with(HOOK_RUNBOOK, &["app"]);
+    drain(&runtime, build_command("app")).await;
+
+    assert_eq!(
+        wait_for_log_lines(&runtime, "hooks.log", 3).await,
+        vec!["app -> done", "app -> ship", "app complete"]
+    );
+}
+
+#[tokio::test]
+async fn pipeline_hook_on_fail_gets_error() {
+    let runtime = setup_with(HOOK_RUNBOOK, &["broken"]);
+    drain(&runtime, build_command("broken")).await;
+
+    let lines = wait_for_log_lines(&runtime, "hooks.log", 1).await;
+    assert_eq!(lines.len(), 1);
+    assert!(
+        lines[0].starts_with("broken failed in check: "),
+        "{}",
+        lines[0]
+    );
+}
+
+#[tokio::test]
+async fn pipeline_hook_on_fail_runs_when_agent_session_fails() {
+    let runbook = format!(
+        "{}\n[agent.checker]\nrun = \"claude\"\n",
+        HOOK_RUNBOOK.replace(
+            "run = \"test {name} != broken\"",
+            "run = { agent = \"checker\" }"
+        )
+    );
+    let runtime = setup_with(&runbook, &["app"]);
+    drain(&runtime, build_command("app")).await;
+
+    let pipeline = runtime.pipelines().into_values().next().unwrap();
+    drain(
+        &runtime,
+        Event::SessionExited {
+            session_id: pipeline.session_id.unwrap(),
+            exit_code: 1,
+        },
+    )
+    .await;
+
+    assert_eq!(
+        wait_for_log_lines(&runtime, "hooks.log", 1).await,
+        vec!["app failed in check: exit code: 1"]
+    );
+}
+
+fn build_command(name: &str) -> Event {
+    Event::CommandInvoked {
+        command: "build".to_string(),
+        args: [("name".to_string(), name.to_string())]
+            .into_iter()
+            .collect(),
+    }
+}
@@ -0,0 +1,250 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Runtime tests
+
+use super::*;
+use crate::{RuntimeConfig, RuntimeDeps, DEFAULT_COMMAND_TIMEOUT};
+use oj_adapters::{FakeNotifyAdapter, FakeRepoAdapter, FakeSessionAdapter};
+use oj_core::{
+    ChainProgress, FakeClock, PhaseStatus, PhaseTrigger, PipelineParent, SequentialIdGen,
+    WorkerStatus,
+};
+use oj_runbook::parse_runbook;
+use tempfile::tempdir;
+
+mod children;
+mod coordination;
+mod cron;
+mod guards;
+mod hooks;
+mod monitors;
+mod phases;
+mod pipelines;
+mod queue;
+mod resume;
+mod rules;
+mod strategy;
+mod worker;
+
+async fn create_pipeline(
+    runtime: &Runtime<
+        FakeSessionAdapter,
+        FakeRepoAdapter,
+        FakeNotifyAdapter,
+        FakeClock,
+        SequentialIdGen,
+    >,
+) -> String {
+    let args: HashMap<String, String> = [
+        ("name".to_string(), "test-feature".to_string()),
+        ("prompt".to_string(), "Add login".to_string()),
+    ]
+    .into_iter()
+    .collect();
+
+    runtime
+        .handle_event(Event::CommandInvoked {
+            command: "build".to_string(),
+            args,
+        })
+        .await
+        .unwrap();
+
+    let pipelines = runtime.pipelines();
+    pipelines.keys().next().unwrap().clone()
+}
+
+type TestRuntime =
+    Runtime<FakeSessionAdapter, FakeRepoAdapter, FakeNotifyAdapter, FakeClock, SequentialIdGen>;
+
+/// Build a runtime for `runbook` with workspace directories for `names`
+fn setup_with(runbook: &str, names: &[&str]) -> TestRuntime {
+    setup_with_sessions(runbook, names, FakeSessionAdapter::new())
+}
+
+/// Build a runtime whose sessions the test can still inspect and end
+fn setup_with_sessions(runbook: &str, names: &[&str], sessions: FakeSessionAdapter) -> TestRuntime {
+    build_runtime(runbook, names, sessions, None, DEFAULT_COMMAND_TIMEOUT)
+}
+
+/// Build a runtime that gives up on checks and captures after `command_timeout`
+fn setup_with_timeout(runbook: &str, names: &[&str], command_timeout: Duration) -> TestRuntime {
+    build_runtime(
+        runbook,
+        names,
+        FakeSessionAdapter::new(),
+        None,
+        command_timeout,
+    )
+}
+
+/// Build a runtime that runs shells in the background, reporting back on the receiver
+fn setup_with_events(runbook: &str, names: &[&str]) -> (TestRuntime, mpsc::Receiver<Event>) {
+    let (tx, rx) = mpsc::channel(16);
+    let runtime = build_runtime(
+        runbook,
+        names,
+        FakeSessionAdapter::new(),
+        Some(tx),
+        DEFAULT_COMMAND_TIMEOUT,
+    );
+    (runtime, rx)
+}
+
+fn build_runtime(
+    runbook: &str,
+    names: &[&str],
+    sessions: FakeSessionAdapter,
+    events: Option<mpsc::Sender<Event>>,
+    command_timeout: Duration,
+) -> TestRuntime {
+    let dir_path = tempdir().unwrap().keep();
+    let wal = Wal::open(&dir_path.join("test.wal")).unwrap();
+    let runbook = parse_runbook(runbook).unwrap();
+
+    let worktrees = dir_path.join("worktrees");
+    for name in names {
+        std::fs::create_dir_all(worktrees.join(name)).unwrap();
+    }
+
+    Runtime::new(
+        RuntimeDeps {
+            sessions,
+            repos: FakeRepoAdapter::new(),
+            notify: FakeNotifyAdapter::new(),
+            wal: Arc::new(Mutex::new(wal)),
+            state: Arc::new(Mutex::new(MaterializedState::default())),
+            events,
+        },
+        runbook,
+        FakeClock::new(),
+        SequentialIdGen::new("pipe"),
+        RuntimeConfig {
+            project_root: dir_path.clone(),
+            worktree_root: worktrees,
+            log_root: dir_path.join("logs"),
+            command_timeout,
+        },
+    )
+}
+
+/// Invoke `command` with a single `name` argument and return the new pipeline ID
+async fn invoke(runtime: &TestRuntime, command: &str, name: &str) -> String {
+    let before: Vec<String> = runtime.pipelines().into_keys().collect();
+    runtime
+        .handle_event(Event::CommandInvoked {
+            command: command.to_string(),
+            args: [("name".to_string(), name.to_string())]
+                .into_iter()
+                .collect(),
+        })
+        .await
+        .unwrap();
+    runtime
+        .pipelines()
+        .into_keys()
+        .find(|id| !before.contains(id))
+        .unwrap()
+}
+
+/// Handle `event` and every event it produces until the runtime is idle
+async fn drain(runtime: &TestRuntime, event: Event) {
+    let mut pending = vec![event];
+    while let Some(event) = pending.pop() {
+        pending.extend(runtime.handle_event(event).await.unwrap());
+    }
+}
+
+fn bug(id: &str, priority: i64) -> HashMap<String, String> {
+    [("id", id.to_string()), ("priority", priority.to_string())]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect()
+}
+
+fn custom_names(events: &[Event]) -> Vec<&str> {
+    events
+        .iter()
+        .filter_map(|e| match e {
+            Event::Custom { name, .. } => Some(name.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn read_log(runtime: &TestRuntime, name: &str) -> String {
+    std::fs::read_to_string(runtime.project_root.join(name)).unwrap_or_default()
+}
+
+/// Wait for background hooks to write `count` lines to a log, returned sorted
+async fn wait_for_log_lines(runtime: &TestRuntime, name: &str, count: usize) -> Vec<String> {
+    for _ in 0..200 {
+        let mut lines: Vec<String> = read_log(runtime, name).lines().map(String::from).collect();
+        if lines.len() >= count {
+            lines.sort();
+            return lines;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    panic!("timed out waiting for {} lines in {}", count, name);
+}
+
+fn custom(name: &str, data: serde_json::Value) -> Event {
+    Event::Custom {
+        name: name.to_string(),
+        data,
+    }
+}
+
+const RESUME_RUNBOOK: &str = r#"
+[command.work]
+args = "<name>"
+run = { pipeline = "work" }
+
+[pipeline.work]
+inputs = ["name"]
+
+[[pipeline.work.phase]]
+name = "build"
+run = "touch built"
+
+[[pipeline.work.phase]]
+name = "review"
+run = { agent = "reviewer" }
+
+[agent.reviewer]
+run = "claude"
+on_exit = "done"
+"#;
+
+/// Reconcile as a restarted daemon would, then run what the resume timers start
+async fn restart(runtime: &TestRuntime) {
+    runtime.resume_pipelines().await.unwrap();
+    let fired = runtime
+        .scheduler()
+        .lock()
+        .unwrap()
+        .fired_timers(runtime.clock.now());
+    for event in fired {
+        if matches!(&event, Event::Timer { id } if id.ends_with(":resume")) {
+            drain(runtime, event).await;
+        }
+    }
+}
+
+/// Start a `work` pipeline and run it up to its agent phase
+async fn start_review(runtime: &TestRuntime) -> String {
+    let pipeline_id = invoke(runtime, "work", "a").await;
+    drain(
+        runtime,
+        Event::ShellCompleted {
+            pipeline_id: pipeline_id.clone(),
+            phase: "build".to_string(),
+            exit_code: 0,
+            outputs: HashMap::new(),
+        },
+    )
+    .await;
+    pipeline_id
+}
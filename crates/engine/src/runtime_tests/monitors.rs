@@ -0,0 +1,323 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+
+const MONITOR_RUNBOOK: &str = r#"
+[command.build]
+args = "<name> <prompt>"
+run = { pipeline = "build" }
+
+[pipeline.build]
+inputs = ["name", "prompt"]
+
+[[pipeline.build.phase]]
+name = "init"
+run = "echo init"
+
+[[pipeline.build.phase]]
+name = "plan"
+run = { agent = "planner" }
+
+[[pipeline.build.phase]]
+name = "done"
+run = "echo done"
+
+[agent.planner]
+run = "claude"
+
+[monitor.stuck]
+source = "cat stuck.json"
+condition = "test ! -f {id}.ok"
+response = ["escalate", "fail"]
+
+[monitor.tidy]
+source = "echo '[{\"path\": \"a.tmp\"}, {\"path\": \"b.tmp\"}]'"
+condition = "test {path} != b.tmp"
+run = "echo {path} >> tidied.log"
+
+[cron.watchdog]
+interval = "1m"
+monitors = ["stuck"]
+
+[cron.janitor]
+interval = "1h"
+monitors = ["tidy"]
+"#;
+
+fn run_cron(name: &str) -> Event {
+    Event::CronRun {
+        cron: name.to_string(),
+    }
+}
+
+fn monitor_progress(runtime: &TestRuntime, monitor: &str) -> HashMap<String, ChainProgress> {
+    let state = runtime.executor.state();
+    let state_guard = state.lock().unwrap();
+    state_guard
+        .monitors
+        .get(monitor)
+        .cloned()
+        .unwrap_or_default()
+}
+
+#[tokio::test]
+async fn monitor_runs_command_for_matching_items() {
+    let runtime = setup_with(MONITOR_RUNBOOK, &[]);
+
+    runtime.handle_event(run_cron("janitor")).await.unwrap();
+
+    let log = runtime.project_root.join("tidied.log");
+    assert_eq!(std::fs::read_to_string(log).unwrap(), "a.tmp\n");
+    assert!(monitor_progress(&runtime, "tidy").is_empty());
+}
+
+#[tokio::test]
+async fn monitor_quotes_item_fields_in_commands() {
+    let runbook = r#"
+[monitor.tidy]
+source = "echo '[{\"path\": \"a.tmp; touch pwned\"}]'"
+condition = "test -n {path}"
+run = "echo {path} >> tidied.log"
+
+[cron.janitor]
+interval = "1h"
+monitors = ["tidy"]
+"#;
+    let runtime = setup_with(runbook, &[]);
+
+    runtime.handle_event(run_cron("janitor")).await.unwrap();
+
+    let log = runtime.project_root.join("tidied.log");
+    assert_eq!(
+        std::fs::read_to_string(log).unwrap(),
+        "a.tmp; touch pwned\n"
+    );
+    assert!(!runtime.project_root.join("pwned").exists());
+}
+
+#[tokio::test]
+async fn monitor_response_chain_advances_per_check() {
+    let runtime = setup_with(MONITOR_RUNBOOK, &["test-feature"]);
+    let pipeline_id = create_pipeline(&runtime).await;
+    drain(
+        &runtime,
+        Event::ShellCompleted {
+            pipeline_id: pipeline_id.clone(),
+            phase: "init".to_string(),
+            exit_code: 0,
+            outputs: HashMap::new(),
+        },
+    )
+    .await;
+    assert_eq!(runtime.get_pipeline(&pipeline_id).unwrap().phase, "plan");
+    std::fs::write(
+        runtime.project_root.join("stuck.json"),
+        format!(r#"[{{"id": "{}"}}]"#, pipeline_id),
+    )
+    .unwrap();
+
+    // First check escalates the agent's pipeline
+    let events = runtime.handle_event(run_cron("watchdog")).await.unwrap();
+    assert_eq!(custom_names(&events), vec!["pipeline:escalate"]);
+    assert_eq!(
+        monitor_progress(&runtime, "stuck")[&pipeline_id],
+        ChainProgress {
+            step: 0,
+            attempts: 1
+        }
+    );
+    let pipeline = runtime.get_pipeline(&pipeline_id).unwrap();
+    assert_eq!(pipeline.phase_status, PhaseStatus::Waiting);
+
+    // Second check fails it
+    runtime.handle_event(run_cron("watchdog")).await.unwrap();
+    assert!(runtime.get_pipeline(&pipeline_id).unwrap().is_terminal());
+
+    // The chain is used up
+    let events = runtime.handle_event(run_cron("watchdog")).await.unwrap();
+    assert!(custom_names(&events).is_empty());
+    assert_eq!(monitor_progress(&runtime, "stuck")[&pipeline_id].step, 1);
+
+    // Progress is forgotten once the item stops matching
+    std::fs::write(runtime.project_root.join(format!("{}.ok", pipeline_id)), "").unwrap();
+    runtime.handle_event(run_cron("watchdog")).await.unwrap();
+    assert!(monitor_progress(&runtime, "stuck").is_empty());
+}
+
+#[tokio::test]
+async fn monitor_escalates_items_that_are_not_pipelines() {
+    let runtime = setup_with(MONITOR_RUNBOOK, &[]);
+    std::fs::write(
+        runtime.project_root.join("stuck.json"),
+        r#"[{"id": "lock-1"}]"#,
+    )
+    .unwrap();
+
+    let events = runtime.handle_event(run_cron("watchdog")).await.unwrap();
+    assert_eq!(custom_names(&events), vec!["monitor:escalate"]);
+}
+
+const ACTION_RUNBOOK: &str = r#"
+[command.build]
+args = "<name> <prompt>"
+run = { pipeline = "build" }
+
+[pipeline.build]
+inputs = ["name", "prompt"]
+
+[[pipeline.build.phase]]
+name = "init"
+run = "echo init"
+
+[[pipeline.build.phase]]
+name = "plan"
+run = { agent = "planner" }
+
+[[pipeline.build.phase]]
+name = "execute"
+run = { agent = "executor" }
+
+[[pipeline.build.phase]]
+name = "done"
+run = "echo done"
+
+[agent.planner]
+run = "claude"
+on_exit = "resume"
+
+[agent.executor]
+run = "claude"
+on_exit = ["log_exit:2", "escalate"]
+
+[action.log_exit]
+run = "echo {name} >> exits.log"
+
+[action.resume]
+run = "echo {name} >> resumed.log"
+max_attempts = 1
+
+[action.retry]
+run = "echo {id} >> retried.log"
+max_attempts = 2
+
+[action.mark]
+run = "echo {id} >> marked.log"
+cooldown = "1h"
+
+[monitor.flaky]
+source = "cat items.json"
+response = ["retry:3", "mark:2"]
+
+[cron.triage]
+interval = "1m"
+monitors = ["flaky"]
+"#;
+
+#[tokio::test]
+async fn monitor_chain_falls_through_exhausted_actions() {
+    let runtime = setup_with(ACTION_RUNBOOK, &[]);
+    let items = runtime.project_root.join("items.json");
+    std::fs::write(&items, r#"[{"id": "test-a"}]"#).unwrap();
+
+    for _ in 0..2 {
+        runtime.handle_event(run_cron("triage")).await.unwrap();
+    }
+    assert_eq!(read_log(&runtime, "retried.log"), "test-a\ntest-a\n");
+
+    // The chain allows a third retry, but the action is used up
+    runtime.handle_event(run_cron("triage")).await.unwrap();
+    assert_eq!(read_log(&runtime, "retried.log"), "test-a\ntest-a\n");
+    assert_eq!(read_log(&runtime, "marked.log"), "test-a\n");
+
+    // The second mark waits out the cooldown
+    runtime.handle_event(run_cron("triage")).await.unwrap();
+    assert_eq!(read_log(&runtime, "marked.log"), "test-a\n");
+    assert_eq!(
+        monitor_progress(&runtime, "flaky")["test-a"],
+        ChainProgress {
+            step: 1,
+            attempts: 1
+        }
+    );
+
+    // Once the item is gone its action history is forgotten
+    std::fs::write(&items, "[]").unwrap();
+    runtime.handle_event(run_cron("triage")).await.unwrap();
+    let state = runtime.executor.state();
+    assert!(state.lock().unwrap().actions.is_empty());
+}
+
+#[tokio::test]
+async fn agent_named_action_escalates_when_used_up() {
+    let runtime = setup_with(ACTION_RUNBOOK, &["test-feature"]);
+    let pipeline_id = create_pipeline(&runtime).await;
+    drain(
+        &runtime,
+        Event::ShellCompleted {
+            pipeline_id: pipeline_id.clone(),
+            phase: "init".to_string(),
+            exit_code: 0,
+            outputs: HashMap::new(),
+        },
+    )
+    .await;
+
+    let events = runtime.handle_claude_exited(&pipeline_id).await.unwrap();
+    assert!(custom_names(&events).is_empty());
+    assert_eq!(read_log(&runtime, "resumed.log"), "test-feature\n");
+
+    let events = runtime.handle_claude_exited(&pipeline_id).await.unwrap();
+    assert_eq!(custom_names(&events), vec!["pipeline:escalate"]);
+    assert_eq!(read_log(&runtime, "resumed.log"), "test-feature\n");
+    let pipeline = runtime.get_pipeline(&pipeline_id).unwrap();
+    assert_eq!(pipeline.phase_status, PhaseStatus::Waiting);
+}
+
+#[tokio::test]
+async fn agent_exit_chain_advances_per_trigger() {
+    let runtime = setup_with(ACTION_RUNBOOK, &["test-feature"]);
+    let pipeline_id = create_pipeline(&runtime).await;
+    drain(
+        &runtime,
+        Event::ShellCompleted {
+            pipeline_id: pipeline_id.clone(),
+            phase: "init".to_string(),
+            exit_code: 0,
+            outputs: HashMap::new(),
+        },
+    )
+    .await;
+    drain(
+        &runtime,
+        Event::AgentDone {
+            pipeline_id: pipeline_id.clone(),
+        },
+    )
+    .await;
+    assert_eq!(runtime.get_pipeline(&pipeline_id).unwrap().phase, "execute");
+
+    for _ in 0..2 {
+        let events = runtime.handle_claude_exited(&pipeline_id).await.unwrap();
+        assert!(custom_names(&events).is_empty());
+    }
+    assert_eq!(
+        read_log(&runtime, "exits.log"),
+        "test-feature\ntest-feature\n"
+    );
+    let pipeline = runtime.get_pipeline(&pipeline_id).unwrap();
+    assert_eq!(
+        pipeline.recovery["exit"],
+        ChainProgress {
+            step: 0,
+            attempts: 2
+        }
+    );
+
+    let events = runtime.handle_claude_exited(&pipeline_id).await.unwrap();
+    assert_eq!(custom_names(&events), vec!["pipeline:escalate"]);
+    assert_eq!(
+        read_log(&runtime, "exits.log"),
+        "test-feature\ntest-feature\n"
+    );
+}
@@ -0,0 +1,177 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+
+const TIMEOUT_RUNBOOK: &str = r#"
+[command.work]
+args = "<name>"
+run = { pipeline = "work" }
+
+[pipeline.work]
+inputs = ["name"]
+
+[[pipeline.work.phase]]
+name = "build"
+run = "echo started; sleep 30"
+timeout = "200ms"
+"#;
+
+#[tokio::test]
+async fn shell_timeout_fails_phase_and_logs_output() {
+    let runtime = setup_with(TIMEOUT_RUNBOOK, &["a"]);
+    let command = Event::CommandInvoked {
+        command: "work".to_string(),
+        args: [("name".to_string(), "a".to_string())]
+            .into_iter()
+            .collect(),
+    };
+    drain(&runtime, command).await;
+    let pipeline_id = runtime.pipelines().into_keys().next().unwrap();
+
+    let pipeline = runtime.get_pipeline(&pipeline_id).unwrap();
+    assert_eq!(pipeline.phase, "failed");
+    assert_eq!(
+        pipeline.error.as_deref(),
+        Some("shell timed out after 200ms")
+    );
+
+    let log = std::fs::read_to_string(runtime.log_path(&pipeline_id)).unwrap();
+    assert!(log.contains("[build] started"));
+    assert!(log.contains("[build] timed out, killed"));
+}
+
+#[tokio::test]
+async fn pipeline_log_records_phases_and_agent_output() {
+    let sessions = FakeSessionAdapter::new();
+    let runtime = setup_with_sessions(RESUME_RUNBOOK, &["a"], sessions.clone());
+    let pipeline_id = start_review(&runtime).await;
+    let session_id = runtime
+        .get_pipeline(&pipeline_id)
+        .unwrap()
+        .session_id
+        .unwrap();
+    sessions.set_output(&session_id, vec!["Reviewing diff".to_string()]);
+
+    let snapshot = Event::Timer {
+        id: format!("session:{}:snapshot", pipeline_id),
+    };
+    drain(&runtime, snapshot.clone()).await;
+    // Unchanged output is not written again
+    drain(&runtime, snapshot).await;
+
+    let log = std::fs::read_to_string(runtime.log_path(&pipeline_id)).unwrap();
+    assert!(log.contains("[build] phase started"));
+    assert!(log.contains("[build] $ touch built"));
+    assert!(log.contains("[build] next: review"));
+    assert!(log.contains("[review] started agent reviewer"));
+    assert_eq!(log.matches("[review] Reviewing diff").count(), 1);
+}
+
+const OUTPUTS_RUNBOOK: &str = r#"
+[command.work]
+args = "<name>"
+run = { pipeline = "work" }
+
+[pipeline.work]
+inputs = ["name"]
+
+[[pipeline.work.phase]]
+name = "commit"
+run = "echo abc123; echo BRANCH=fix-{name} >> $OJ_OUTPUT"
+outputs = { sha = "stdout" }
+
+[[pipeline.work.phase]]
+name = "record"
+run = "echo {sha} {BRANCH} > recorded"
+"#;
+
+#[tokio::test]
+async fn phase_outputs_are_available_to_later_phases() {
+    let runtime = setup_with(OUTPUTS_RUNBOOK, &["a"]);
+    let command = Event::CommandInvoked {
+        command: "work".to_string(),
+        args: [("name".to_string(), "a".to_string())]
+            .into_iter()
+            .collect(),
+    };
+    drain(&runtime, command).await;
+
+    let pipeline = runtime.pipelines().into_values().next().unwrap();
+    assert_eq!(pipeline.phase, "done");
+    assert_eq!(pipeline.outputs["sha"], "abc123");
+    let recorded = std::fs::read_to_string(runtime.worktree_root.join("a/recorded")).unwrap();
+    assert_eq!(recorded.trim(), "abc123 fix-a");
+}
+
+const ROUTING_RUNBOOK: &str = r#"
+[command.triage]
+args = "<name> <code> <kind>"
+run = { pipeline = "triage" }
+
+[pipeline.triage]
+inputs = ["name", "code", "kind"]
+
+[[pipeline.triage.phase]]
+name = "classify"
+run = "echo KIND={kind} >> $OJ_OUTPUT; exit {code}"
+next = { 2 = "needs_review", default = "fix" }
+when = [{ if = "{KIND} == 'docs'", next = "docs" }]
+
+[[pipeline.triage.phase]]
+name = "fix"
+run = "true"
+next = "done"
+
+[[pipeline.triage.phase]]
+name = "docs"
+run = "true"
+next = "done"
+
+[[pipeline.triage.phase]]
+name = "needs_review"
+run = "true"
+next = "done"
+"#;
+
+async fn triage_route(code: &str, kind: &str) -> Pipeline {
+    let runtime = setup_with(ROUTING_RUNBOOK, &["a"]);
+    let args = [("name", "a"), ("code", code), ("kind", kind)];
+    let command = Event::CommandInvoked {
+        command: "triage".to_string(),
+        args: args
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+    };
+    drain(&runtime, command).await;
+    runtime.pipelines().into_values().next().unwrap()
+}
+
+/// The first two phases the pipeline visited
+fn route(pipeline: &Pipeline) -> Vec<&str> {
+    pipeline
+        .history
+        .iter()
+        .take(2)
+        .map(|r| r.phase.as_str())
+        .collect()
+}
+
+#[tokio::test]
+async fn phases_route_on_exit_codes_and_conditions() {
+    let review = triage_route("2", "bug").await;
+    assert_eq!(review.phase, "done");
+    assert_eq!(route(&review), ["classify", "needs_review"]);
+    assert_eq!(review.outputs["KIND"], "bug");
+
+    let docs = triage_route("0", "docs").await;
+    assert_eq!(route(&docs), ["classify", "docs"]);
+
+    let fix = triage_route("0", "bug").await;
+    assert_eq!(route(&fix), ["classify", "fix"]);
+
+    let failed = triage_route("1", "bug").await;
+    assert_eq!(failed.phase, "failed");
+    assert_eq!(route(&failed), ["classify", "failed"]);
+}
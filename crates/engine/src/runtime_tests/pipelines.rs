@@ -1,14 +1,7 @@
 // SPDX-License-Identifier: BUSL-1.1
 // Copyright (c) 2026 Alfred Jean LLC
 
-//! Runtime tests
-
 use super::*;
-use crate::{RuntimeConfig, RuntimeDeps};
-use oj_adapters::{FakeNotifyAdapter, FakeRepoAdapter, FakeSessionAdapter};
-use oj_core::{FakeClock, SequentialIdGen};
-use oj_runbook::parse_runbook;
-use tempfile::tempdir;
 
 const TEST_RUNBOOK: &str = r#"
 [command.build]
@@ -74,6 +67,7 @@ async fn setup(
             notify: FakeNotifyAdapter::new(),
             wal: Arc::new(Mutex::new(wal)),
             state: Arc::new(Mutex::new(MaterializedState::default())),
+            events: None,
         },
         runbook,
         FakeClock::new(),
@@ -81,38 +75,12 @@ async fn setup(
         RuntimeConfig {
             project_root: dir_path.clone(),
             worktree_root: worktrees,
+            log_root: dir_path.join("logs"),
+            command_timeout: DEFAULT_COMMAND_TIMEOUT,
         },
     )
 }
 
-async fn create_pipeline(
-    runtime: &Runtime<
-        FakeSessionAdapter,
-        FakeRepoAdapter,
-        FakeNotifyAdapter,
-        FakeClock,
-        SequentialIdGen,
-    >,
-) -> String {
-    let args: HashMap<String, String> = [
-        ("name".to_string(), "test-feature".to_string()),
-        ("prompt".to_string(), "Add login".to_string()),
-    ]
-    .into_iter()
-    .collect();
-
-    runtime
-        .handle_event(Event::CommandInvoked {
-            command: "build".to_string(),
-            args,
-        })
-        .await
-        .unwrap();
-
-    let pipelines = runtime.pipelines();
-    pipelines.keys().next().unwrap().clone()
-}
-
 #[tokio::test]
 async fn runtime_handle_command() {
     let runtime = setup().await;
@@ -141,6 +109,7 @@ async fn shell_completion_advances_phase() {
             pipeline_id: pipeline_id.clone(),
             phase: "init".to_string(),
             exit_code: 0,
+            outputs: HashMap::new(),
         })
         .await
         .unwrap();
@@ -160,6 +129,7 @@ async fn agent_done_advances_phase() {
             pipeline_id: pipeline_id.clone(),
             phase: "init".to_string(),
             exit_code: 0,
+            outputs: HashMap::new(),
         })
         .await
         .unwrap();
@@ -190,6 +160,7 @@ async fn shell_failure_fails_pipeline() {
             pipeline_id: pipeline_id.clone(),
             phase: "init".to_string(),
             exit_code: 1,
+            outputs: HashMap::new(),
         })
         .await
         .unwrap();
@@ -209,6 +180,7 @@ async fn agent_error_fails_pipeline() {
             pipeline_id: pipeline_id.clone(),
             phase: "init".to_string(),
             exit_code: 0,
+            outputs: HashMap::new(),
         })
         .await
         .unwrap();
@@ -238,6 +210,7 @@ async fn on_fail_transition_executes() {
             pipeline_id: pipeline_id.clone(),
             phase: "init".to_string(),
             exit_code: 0,
+            outputs: HashMap::new(),
         })
         .await
         .unwrap();
@@ -265,6 +238,7 @@ async fn on_fail_transition_executes() {
             pipeline_id: pipeline_id.clone(),
             phase: "merge".to_string(),
             exit_code: 1,
+            outputs: HashMap::new(),
         })
         .await
         .unwrap();
@@ -276,6 +250,31 @@ async fn on_fail_transition_executes() {
         "Expected cleanup phase, got {}",
         pipeline.phase
     );
+
+    // The history keeps the failed merge, its exit code and how cleanup was reached
+    let phases: Vec<_> = pipeline
+        .history
+        .iter()
+        .map(|r| (r.phase.as_str(), r.trigger))
+        .collect();
+    assert_eq!(
+        phases,
+        vec![
+            ("init", PhaseTrigger::Start),
+            ("plan", PhaseTrigger::Next),
+            ("execute", PhaseTrigger::Next),
+            ("merge", PhaseTrigger::Next),
+            ("cleanup", PhaseTrigger::OnFail),
+        ]
+    );
+    let merge = &pipeline.history[3];
+    assert_eq!(merge.status, PhaseStatus::Failed);
+    assert_eq!(merge.exit_code, Some(1));
+    assert_eq!(merge.error.as_deref(), Some("shell exited with code 1"));
+    assert!(merge.ended_at_ms.is_some());
+    assert_eq!(pipeline.history[0].exit_code, Some(0));
+    assert_eq!(pipeline.history[0].status, PhaseStatus::Completed);
+    assert_eq!(pipeline.history[4].ended_at_ms, None);
 }
 
 #[tokio::test]
@@ -290,6 +289,7 @@ async fn final_phase_completes_pipeline() {
             pipeline_id: pipeline_id.clone(),
             phase: "init".to_string(),
             exit_code: 0,
+            outputs: HashMap::new(),
         })
         .await
         .unwrap();
@@ -313,6 +313,7 @@ async fn final_phase_completes_pipeline() {
             pipeline_id: pipeline_id.clone(),
             phase: "merge".to_string(),
             exit_code: 0,
+            outputs: HashMap::new(),
         })
         .await
         .unwrap();
@@ -332,6 +333,7 @@ async fn done_phase_run_command_executes() {
             pipeline_id: pipeline_id.clone(),
             phase: "init".to_string(),
             exit_code: 0,
+            outputs: HashMap::new(),
         })
         .await
         .unwrap();
@@ -355,6 +357,7 @@ async fn done_phase_run_command_executes() {
             pipeline_id: pipeline_id.clone(),
             phase: "merge".to_string(),
             exit_code: 0,
+            outputs: HashMap::new(),
         })
         .await
         .unwrap();
@@ -371,6 +374,7 @@ async fn done_phase_run_command_executes() {
             pipeline_id: pipeline_id.clone(),
             phase: "done".to_string(),
             exit_code: 0,
+            outputs: HashMap::new(),
         })
         .await
         .unwrap();
@@ -392,6 +396,7 @@ async fn wrong_phase_shell_completed_ignored() {
             pipeline_id: pipeline_id.clone(),
             phase: "merge".to_string(), // We're in init, not merge
             exit_code: 0,
+            outputs: HashMap::new(),
         })
         .await
         .unwrap();
@@ -441,6 +446,7 @@ async fn setup_no_done_phase(
             notify: FakeNotifyAdapter::new(),
             wal: Arc::new(Mutex::new(wal)),
             state: Arc::new(Mutex::new(MaterializedState::default())),
+            events: None,
         },
         runbook,
         FakeClock::new(),
@@ -448,6 +454,8 @@ async fn setup_no_done_phase(
         RuntimeConfig {
             project_root: dir_path.clone(),
             worktree_root: worktrees,
+            log_root: dir_path.join("logs"),
+            command_timeout: DEFAULT_COMMAND_TIMEOUT,
         },
     )
 }
@@ -477,6 +485,7 @@ async fn pipeline_completes_without_explicit_done_phase() {
             pipeline_id: pipeline_id.clone(),
             phase: "init".to_string(),
             exit_code: 0,
+            outputs: HashMap::new(),
         })
         .await
         .unwrap();
@@ -489,6 +498,7 @@ async fn pipeline_completes_without_explicit_done_phase() {
             pipeline_id: pipeline_id.clone(),
             phase: "execute".to_string(),
             exit_code: 0,
+            outputs: HashMap::new(),
         })
         .await
         .unwrap();
@@ -501,6 +511,7 @@ async fn pipeline_completes_without_explicit_done_phase() {
             pipeline_id: pipeline_id.clone(),
             phase: "merge".to_string(),
             exit_code: 0,
+            outputs: HashMap::new(),
         })
         .await
         .unwrap();
@@ -0,0 +1,223 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+
+const QUEUE_RUNBOOK: &str = r#"
+[queue.bugs]
+visibility_timeout = "1m"
+max_retries = 1
+on_exhaust = "dead"
+
+[queue.bugs.dead]
+retention = "1h"
+"#;
+
+#[tokio::test]
+async fn queue_claims_by_priority_and_completes() {
+    let runtime = setup_with(QUEUE_RUNBOOK, &[]);
+    runtime.queue_push("bugs", bug("bug-1", 2)).await.unwrap();
+    runtime.queue_push("bugs", bug("bug-2", 1)).await.unwrap();
+    // Re-pushing a known item is a no-op
+    assert!(runtime
+        .queue_push("bugs", bug("bug-2", 0))
+        .await
+        .unwrap()
+        .is_empty());
+
+    let item = runtime
+        .queue_claim("bugs", "worker-1")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(item.id, "bug-2");
+    assert_eq!(item.holder(), Some("worker-1"));
+
+    runtime
+        .queue_complete("bugs", "bug-2", "worker-1")
+        .await
+        .unwrap();
+    let queue = runtime.get_queue("bugs").unwrap();
+    assert!(queue.get("bug-2").is_none());
+    assert_eq!(queue.pending_count(), 1);
+}
+
+#[tokio::test]
+async fn queue_failures_retry_then_dead_letter() {
+    let runtime = setup_with(QUEUE_RUNBOOK, &[]);
+    runtime.queue_push("bugs", bug("bug-1", 0)).await.unwrap();
+
+    runtime.queue_claim("bugs", "worker-1").await.unwrap();
+    runtime
+        .queue_fail("bugs", "bug-1", "worker-1", "tests failed")
+        .await
+        .unwrap();
+    assert!(runtime
+        .get_queue("bugs")
+        .unwrap()
+        .get("bug-1")
+        .unwrap()
+        .is_pending());
+
+    runtime.queue_claim("bugs", "worker-1").await.unwrap();
+    runtime
+        .queue_fail("bugs", "bug-1", "worker-1", "tests failed")
+        .await
+        .unwrap();
+    let queue = runtime.get_queue("bugs").unwrap();
+    assert!(queue.get("bug-1").unwrap().is_dead());
+    assert!(runtime
+        .queue_claim("bugs", "worker-1")
+        .await
+        .unwrap()
+        .is_none());
+
+    // Dead items are purged once retention passes
+    runtime.clock.advance(Duration::from_secs(2 * 60 * 60));
+    runtime
+        .handle_event(Event::Timer {
+            id: "queue:bugs:sweep".to_string(),
+        })
+        .await
+        .unwrap();
+    assert!(runtime.get_queue("bugs").unwrap().items.is_empty());
+}
+
+#[tokio::test]
+async fn queue_visibility_timeout_returns_item() {
+    let runtime = setup_with(QUEUE_RUNBOOK, &[]);
+    runtime.queue_push("bugs", bug("bug-1", 0)).await.unwrap();
+    runtime.queue_claim("bugs", "worker-1").await.unwrap();
+    assert!(runtime.scheduler().lock().unwrap().has_timers());
+
+    runtime.clock.advance(Duration::from_secs(2 * 60));
+    runtime
+        .handle_event(Event::Timer {
+            id: "queue:bugs:sweep".to_string(),
+        })
+        .await
+        .unwrap();
+
+    let item = runtime
+        .queue_claim("bugs", "worker-2")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(item.id, "bug-1");
+    assert_eq!(item.attempts, 2);
+    assert_eq!(item.error.as_deref(), Some("visibility timeout expired"));
+}
+
+#[tokio::test]
+async fn stale_completion_after_requeue_is_ignored() {
+    let runtime = setup_with(QUEUE_RUNBOOK, &[]);
+    runtime.queue_push("bugs", bug("bug-1", 0)).await.unwrap();
+    runtime.queue_claim("bugs", "worker-1").await.unwrap();
+
+    runtime.clock.advance(Duration::from_secs(2 * 60));
+    runtime
+        .handle_event(Event::Timer {
+            id: "queue:bugs:sweep".to_string(),
+        })
+        .await
+        .unwrap();
+    runtime.queue_claim("bugs", "worker-2").await.unwrap();
+
+    // The first holder finishing late must not settle the second's claim
+    runtime
+        .queue_complete("bugs", "bug-1", "worker-1")
+        .await
+        .unwrap();
+    runtime
+        .queue_fail("bugs", "bug-1", "worker-1", "tests failed")
+        .await
+        .unwrap();
+    let item = runtime.get_queue("bugs").unwrap().get("bug-1").cloned();
+    assert_eq!(item.unwrap().holder(), Some("worker-2"));
+
+    runtime
+        .queue_complete("bugs", "bug-1", "worker-2")
+        .await
+        .unwrap();
+    assert!(runtime.get_queue("bugs").unwrap().get("bug-1").is_none());
+}
+
+#[tokio::test]
+async fn push_to_undefined_queue_fails() {
+    let runtime = setup_with(QUEUE_RUNBOOK, &[]);
+    let err = runtime
+        .queue_push("missing", HashMap::new())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RuntimeError::QueueNotFound(name) if name == "missing"));
+}
+
+const SOURCE_RUNBOOK: &str = r#"
+[queue.bugs]
+source = "cat bugs.json"
+poll_interval = "5m"
+id_field = "key"
+priority_field = "rank"
+"#;
+
+#[tokio::test]
+async fn queue_source_pushes_new_items_and_reschedules() {
+    let runtime = setup_with(SOURCE_RUNBOOK, &[]);
+    let source = runtime.project_root.join("bugs.json");
+    std::fs::write(
+        &source,
+        r#"[{"key": "bug-1", "rank": 2}, {"key": "bug-2", "rank": 1}, {"title": "no key"}]"#,
+    )
+    .unwrap();
+
+    runtime.start_queue_sources().await.unwrap();
+    let fired = runtime
+        .scheduler()
+        .lock()
+        .unwrap()
+        .fired_timers(std::time::Instant::now() + Duration::from_secs(1));
+    assert_eq!(
+        fired,
+        vec![Event::Timer {
+            id: "queue:bugs:poll".to_string()
+        }]
+    );
+    for event in fired {
+        runtime.handle_event(event).await.unwrap();
+    }
+
+    let queue = runtime.get_queue("bugs").unwrap();
+    assert_eq!(queue.items.len(), 2);
+    assert_eq!(queue.next_pending().unwrap().id, "bug-2");
+    assert!(runtime.scheduler().lock().unwrap().has_timers());
+
+    // Items already in the queue are not pushed again
+    runtime.queue_claim("bugs", "worker-1").await.unwrap();
+    std::fs::write(&source, r#"[{"key": "bug-2"}, {"key": "bug-3"}]"#).unwrap();
+    runtime.poll_queue("bugs").await.unwrap();
+
+    let queue = runtime.get_queue("bugs").unwrap();
+    assert_eq!(queue.items.len(), 3);
+    assert_eq!(queue.get("bug-2").unwrap().holder(), Some("worker-1"));
+
+    // Nor are items that already completed
+    runtime
+        .queue_complete("bugs", "bug-2", "worker-1")
+        .await
+        .unwrap();
+    runtime.poll_queue("bugs").await.unwrap();
+
+    let queue = runtime.get_queue("bugs").unwrap();
+    assert_eq!(queue.items.len(), 2);
+    assert!(queue.get("bug-2").is_none());
+}
+
+#[tokio::test]
+async fn failing_queue_source_is_retried_later() {
+    let runtime = setup_with(SOURCE_RUNBOOK, &[]);
+
+    runtime.poll_queue("bugs").await.unwrap();
+
+    assert!(runtime.get_queue("bugs").is_none());
+    assert!(runtime.scheduler().lock().unwrap().has_timers());
+}
@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+
+#[tokio::test]
+async fn resume_reruns_interrupted_shell_phase() {
+    let runtime = setup_with(RESUME_RUNBOOK, &["a"]);
+    let built = runtime.worktree_root.join("a/built");
+    // The shell finished but the daemon stopped before handling its completion
+    let pipeline_id = invoke(&runtime, "work", "a").await;
+    std::fs::remove_file(&built).unwrap();
+
+    restart(&runtime).await;
+
+    assert!(built.exists());
+    assert_eq!(runtime.get_pipeline(&pipeline_id).unwrap().phase, "review");
+}
+
+#[tokio::test]
+async fn resume_rearms_monitor_for_live_agent() {
+    let sessions = FakeSessionAdapter::new();
+    let runtime = setup_with_sessions(RESUME_RUNBOOK, &["a"], sessions.clone());
+    let pipeline_id = start_review(&runtime).await;
+    let session_id = runtime.get_pipeline(&pipeline_id).unwrap().session_id;
+    assert!(sessions
+        .get_session(session_id.as_deref().unwrap())
+        .is_some());
+
+    // Timers do not survive a restart
+    let monitor_timer = format!("session:{}:check", pipeline_id);
+    runtime
+        .scheduler()
+        .lock()
+        .unwrap()
+        .cancel_timer(&monitor_timer);
+    restart(&runtime).await;
+
+    let pipeline = runtime.get_pipeline(&pipeline_id).unwrap();
+    assert_eq!(pipeline.phase, "review");
+    assert_eq!(pipeline.session_id, session_id);
+    let fired = runtime
+        .scheduler()
+        .lock()
+        .unwrap()
+        .fired_timers(std::time::Instant::now() + Duration::from_secs(11));
+    assert!(fired.contains(&Event::Timer { id: monitor_timer }));
+}
+
+#[tokio::test]
+async fn resume_applies_on_exit_for_dead_agent() {
+    let sessions = FakeSessionAdapter::new();
+    let runtime = setup_with_sessions(RESUME_RUNBOOK, &["a"], sessions.clone());
+    let pipeline_id = start_review(&runtime).await;
+    let session_id = runtime
+        .get_pipeline(&pipeline_id)
+        .unwrap()
+        .session_id
+        .unwrap();
+    sessions.set_exited(&session_id, 0);
+
+    restart(&runtime).await;
+
+    let pipeline = runtime.get_pipeline(&pipeline_id).unwrap();
+    assert_eq!(pipeline.phase, "done");
+    assert_eq!(pipeline.session_id, None);
+    let state = runtime.executor.state();
+    assert!(state.lock().unwrap().sessions.is_empty());
+}
+
+#[tokio::test]
+async fn resume_drops_records_that_no_longer_exist() {
+    let runtime = setup_with(RESUME_RUNBOOK, &["a"]);
+    let pipeline_id = start_review(&runtime).await;
+    runtime
+        .executor
+        .execute(Effect::Persist {
+            operation: Operation::SessionCreate {
+                id: "oj-gone".to_string(),
+                pipeline_id: "gone".to_string(),
+            },
+        })
+        .await
+        .unwrap();
+    std::fs::remove_dir_all(runtime.worktree_root.join("a")).unwrap();
+
+    restart(&runtime).await;
+
+    let pipeline = runtime.get_pipeline(&pipeline_id).unwrap();
+    assert_eq!(pipeline.phase, "failed");
+    assert!(pipeline.error.unwrap().contains("no longer exists"));
+    let state = runtime.executor.state();
+    let state_guard = state.lock().unwrap();
+    assert!(state_guard.workspaces.is_empty());
+    assert!(!state_guard.sessions.contains_key("oj-gone"));
+}
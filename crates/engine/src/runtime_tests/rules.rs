@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+
+const RULE_RUNBOOK: &str = r#"
+[command.fix]
+args = "<name>"
+run = { pipeline = "fix" }
+
+[pipeline.fix]
+inputs = ["name"]
+
+[[pipeline.fix.phase]]
+name = "work"
+run = "true"
+
+[queue.bugs]
+
+[[on]]
+event = "merge:complete"
+where = { branch = "main" }
+run = "echo {pipeline} >> merged.log"
+
+[[on]]
+event = "merge:complete"
+emit = "deploy:start"
+data = { ref = "{branch}" }
+
+[[on]]
+event = "bug:created"
+command = "missing"
+
+[[on]]
+event = "bug:created"
+queue = "bugs"
+
+[[on]]
+event = "bug:fix"
+command = "fix"
+args = { name = "bug-{bug.id}" }
+"#;
+
+#[tokio::test]
+async fn event_rule_runs_shell_when_filters_match() {
+    let runtime = setup_with(RULE_RUNBOOK, &[]);
+    for (branch, pipeline) in [("dev", "p1"), ("main", "p2")] {
+        let data = serde_json::json!({ "branch": branch, "pipeline": pipeline });
+        runtime
+            .handle_event(custom("merge:complete", data))
+            .await
+            .unwrap();
+    }
+    assert_eq!(
+        wait_for_log_lines(&runtime, "merged.log", 1).await,
+        vec!["p2"]
+    );
+}
+
+#[tokio::test]
+async fn event_rule_emits_another_event() {
+    let runtime = setup_with(RULE_RUNBOOK, &[]);
+    let events = runtime
+        .handle_event(custom(
+            "merge:complete",
+            serde_json::json!({ "branch": "dev" }),
+        ))
+        .await
+        .unwrap();
+
+    let emitted = events
+        .iter()
+        .find_map(|event| match event {
+            Event::Custom { name, data } if name == "deploy:start" => Some(data),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(emitted["ref"], "dev");
+}
+
+#[tokio::test]
+async fn event_rule_pushes_to_queue_after_failing_rule() {
+    let runtime = setup_with(RULE_RUNBOOK, &[]);
+    runtime
+        .handle_event(custom("bug:created", serde_json::json!({ "id": "b1" })))
+        .await
+        .unwrap();
+
+    let queue = runtime.get_queue("bugs").unwrap();
+    assert_eq!(queue.get("b1").unwrap().data["id"], "b1");
+}
+
+#[tokio::test]
+async fn event_rule_invokes_command() {
+    let runtime = setup_with(RULE_RUNBOOK, &["bug-7"]);
+    drain(
+        &runtime,
+        custom("bug:fix", serde_json::json!({ "bug": { "id": 7 } })),
+    )
+    .await;
+
+    let pipelines = runtime.pipelines();
+    let pipeline = pipelines.values().next().unwrap();
+    assert_eq!(pipeline.name, "bug-7");
+    assert_eq!(pipeline.phase, "done");
+}
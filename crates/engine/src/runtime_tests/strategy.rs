@@ -0,0 +1,171 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+
+const STRATEGY_RUNBOOK: &str = r#"
+[command.merge]
+args = "<name>"
+run = { pipeline = "merge" }
+
+[pipeline.merge]
+inputs = ["name"]
+
+[[pipeline.merge.phase]]
+name = "merge"
+run = { strategy = "merge" }
+on_fail = "cleanup"
+
+[[pipeline.merge.phase]]
+name = "ship"
+run = "touch shipped"
+next = "done"
+
+[[pipeline.merge.phase]]
+name = "cleanup"
+run = "touch cleaned-up"
+
+[strategy.merge]
+checkpoint = "echo base-{name}"
+
+[[strategy.merge.attempt]]
+name = "ff"
+run = "touch ran-ff && test -f ff-ok"
+timeout = "5m"
+rollback = "echo {checkpoint} > rolled-back"
+
+[[strategy.merge.attempt]]
+name = "rebase"
+run = "touch ran-rebase && test -f rebase-ok"
+"#;
+
+fn merge_command(name: &str) -> Event {
+    Event::CommandInvoked {
+        command: "merge".to_string(),
+        args: [("name".to_string(), name.to_string())]
+            .into_iter()
+            .collect(),
+    }
+}
+
+#[tokio::test]
+async fn strategy_falls_back_after_rollback() {
+    let runtime = setup_with(STRATEGY_RUNBOOK, &["a"]);
+    let workspace = runtime.worktree_root.join("a");
+    std::fs::write(workspace.join("rebase-ok"), "").unwrap();
+
+    drain(&runtime, merge_command("a")).await;
+
+    let pipeline = runtime.pipelines().into_values().next().unwrap();
+    assert_eq!(pipeline.phase, "done");
+    assert_eq!(pipeline.strategy, None);
+    assert!(workspace.join("shipped").exists());
+    assert_eq!(
+        std::fs::read_to_string(workspace.join("rolled-back")).unwrap(),
+        "base-a\n"
+    );
+}
+
+#[tokio::test]
+async fn exhausted_strategy_routes_to_on_fail() {
+    let runtime = setup_with(STRATEGY_RUNBOOK, &["a"]);
+
+    drain(&runtime, merge_command("a")).await;
+
+    let workspace = runtime.worktree_root.join("a");
+    assert!(workspace.join("ran-rebase").exists());
+    assert!(workspace.join("cleaned-up").exists());
+    assert!(!workspace.join("shipped").exists());
+}
+
+#[tokio::test]
+async fn exhausted_strategy_can_escalate() {
+    let runbook = STRATEGY_RUNBOOK.replace(
+        "checkpoint = \"echo base-{name}\"",
+        "on_exhaust = \"escalate\"",
+    );
+    let runtime = setup_with(&runbook, &["a"]);
+
+    drain(&runtime, merge_command("a")).await;
+
+    let pipeline = runtime.pipelines().into_values().next().unwrap();
+    assert_eq!(pipeline.phase, "merge");
+    assert_eq!(pipeline.phase_status, PhaseStatus::Waiting);
+}
+
+#[tokio::test]
+async fn attempt_timeout_moves_to_next_attempt() {
+    let runtime = setup_with(STRATEGY_RUNBOOK, &["a"]);
+    let pipeline_id = invoke(&runtime, "merge", "a").await;
+    assert!(runtime.scheduler().lock().unwrap().has_timers());
+
+    runtime
+        .handle_event(Event::Timer {
+            id: format!("strategy:{}:timeout", pipeline_id),
+        })
+        .await
+        .unwrap();
+
+    let strategy = runtime
+        .get_pipeline(&pipeline_id)
+        .unwrap()
+        .strategy
+        .unwrap();
+    assert_eq!(strategy.attempt, 1);
+    assert_eq!(strategy.checkpoint.as_deref(), Some("base-a"));
+    assert!(runtime.worktree_root.join("a/rolled-back").exists());
+}
+
+#[tokio::test]
+async fn attempt_timeout_cancels_its_shell() {
+    // The last attempt, so no later command replaces its shell
+    let runbook = STRATEGY_RUNBOOK
+        .replace("touch ran-ff && test -f ff-ok", "sleep 0.3 && touch ff-late")
+        .replace("checkpoint = \"echo base-{name}\"", "on_exhaust = \"escalate\"")
+        .replace(
+            "[[strategy.merge.attempt]]\nname = \"rebase\"\nrun = \"touch ran-rebase && test -f rebase-ok\"\n",
+            "",
+        );
+    let (runtime, _events) = setup_with_events(&runbook, &["a"]);
+    let pipeline_id = invoke(&runtime, "merge", "a").await;
+
+    runtime
+        .handle_event(Event::Timer {
+            id: format!("strategy:{}:timeout", pipeline_id),
+        })
+        .await
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(600)).await;
+
+    assert!(!runtime.worktree_root.join("a/ff-late").exists());
+}
+
+#[tokio::test]
+async fn strategy_resumes_at_persisted_attempt() {
+    let runtime = setup_with(STRATEGY_RUNBOOK, &["a"]);
+    let workspace = runtime.worktree_root.join("a");
+    let pipeline_id = invoke(&runtime, "merge", "a").await;
+    std::fs::remove_file(workspace.join("ran-ff")).unwrap();
+
+    // As if the daemon stopped after recording the second attempt
+    runtime
+        .executor
+        .execute(Effect::Persist {
+            operation: Operation::StrategyAttempt {
+                pipeline_id: pipeline_id.clone(),
+                phase: "merge".to_string(),
+                attempt: 1,
+                checkpoint: Some("base-a".to_string()),
+            },
+        })
+        .await
+        .unwrap();
+    let pipeline = runtime.get_pipeline(&pipeline_id).unwrap();
+    runtime
+        .start_phase(&pipeline_id, "merge", &pipeline.inputs, &workspace)
+        .await
+        .unwrap();
+
+    assert!(!workspace.join("ran-ff").exists());
+    assert!(workspace.join("ran-rebase").exists());
+}
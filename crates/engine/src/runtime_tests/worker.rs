@@ -0,0 +1,242 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+
+const WORKER_RUNBOOK: &str = r#"
+[queue.bugs]
+on_exhaust = "dead"
+
+[worker.fixers]
+concurrency = 2
+queue = "bugs"
+handler = "pipeline.fix"
+idle_action = "wait:30s"
+wake_on = ["bug:created"]
+
+[pipeline.fix]
+inputs = ["name"]
+
+[[pipeline.fix.phase]]
+name = "work"
+run = "test {name} != bug-bad"
+next = "done"
+"#;
+
+fn worker_start() -> Event {
+    Event::WorkerStart {
+        worker: "fixers".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn worker_claims_up_to_concurrency_then_drains_queue() {
+    let runtime = setup_with(WORKER_RUNBOOK, &["bug-1", "bug-2", "bug-3"]);
+    for id in ["bug-1", "bug-2", "bug-3"] {
+        runtime.queue_push("bugs", bug(id, 0)).await.unwrap();
+    }
+
+    let events = runtime.handle_event(worker_start()).await.unwrap();
+    let worker = runtime.get_worker("fixers").unwrap();
+    assert_eq!(worker.status, WorkerStatus::Processing);
+    assert_eq!(worker.pipelines.len(), 2);
+    assert_eq!(runtime.get_queue("bugs").unwrap().pending_count(), 1);
+
+    for event in events {
+        drain(&runtime, event).await;
+    }
+
+    // Finished pipelines settle their items and free a slot for the next one
+    assert!(runtime.get_queue("bugs").unwrap().items.is_empty());
+    assert_eq!(runtime.pipelines().len(), 3);
+    assert!(runtime.pipelines().values().all(|p| p.phase == "done"));
+    let worker = runtime.get_worker("fixers").unwrap();
+    assert_eq!(worker.status, WorkerStatus::Idle);
+    assert!(worker.pipelines.is_empty());
+}
+
+#[tokio::test]
+async fn failed_worker_pipeline_fails_its_item() {
+    let runtime = setup_with(WORKER_RUNBOOK, &["bug-bad"]);
+    runtime.queue_push("bugs", bug("bug-bad", 0)).await.unwrap();
+
+    drain(&runtime, worker_start()).await;
+
+    let queue = runtime.get_queue("bugs").unwrap();
+    let item = queue.get("bug-bad").unwrap();
+    assert!(item.is_dead());
+    assert!(item
+        .error
+        .as_deref()
+        .unwrap()
+        .contains("exited with code 1"));
+    assert_eq!(
+        runtime.get_worker("fixers").unwrap().status,
+        WorkerStatus::Idle
+    );
+}
+
+#[tokio::test]
+async fn failed_worker_agent_session_fails_its_item() {
+    let runbook = format!(
+        "{}\n[agent.fixer]\nrun = \"claude\"\n",
+        WORKER_RUNBOOK.replace(
+            "run = \"test {name} != bug-bad\"",
+            "run = { agent = \"fixer\" }"
+        )
+    );
+    let runtime = setup_with(&runbook, &["bug-bad"]);
+    runtime.queue_push("bugs", bug("bug-bad", 0)).await.unwrap();
+    drain(&runtime, worker_start()).await;
+
+    let pipeline = runtime.pipelines().into_values().next().unwrap();
+    drain(
+        &runtime,
+        Event::SessionExited {
+            session_id: pipeline.session_id.unwrap(),
+            exit_code: 1,
+        },
+    )
+    .await;
+
+    let queue = runtime.get_queue("bugs").unwrap();
+    let item = queue.get("bug-bad").unwrap();
+    assert!(item.is_dead());
+    assert_eq!(item.error.as_deref(), Some("exit code: 1"));
+    let worker = runtime.get_worker("fixers").unwrap();
+    assert!(worker.pipelines.is_empty());
+    assert_eq!(worker.status, WorkerStatus::Idle);
+}
+
+#[tokio::test]
+async fn retried_item_gets_its_own_worktree() {
+    let runbook = WORKER_RUNBOOK.replace("on_exhaust = \"dead\"", "max_retries = 1");
+    let runtime = setup_with(&runbook, &["bug-bad", "bug-bad-2"]);
+    runtime.queue_push("bugs", bug("bug-bad", 0)).await.unwrap();
+
+    drain(&runtime, worker_start()).await;
+
+    let mut names: Vec<_> = runtime.pipelines().into_values().map(|p| p.name).collect();
+    names.sort();
+    assert_eq!(names, vec!["bug-bad", "bug-bad-2"]);
+    assert!(runtime.get_queue("bugs").unwrap().items.is_empty());
+}
+
+#[tokio::test]
+async fn running_handler_keeps_its_claim() {
+    let runbook = WORKER_RUNBOOK
+        .replace("on_exhaust = \"dead\"", "visibility_timeout = \"1m\"")
+        .replace("test {name} != bug-bad", "sleep 1");
+    let (runtime, _events) = setup_with_events(&runbook, &["bug-1"]);
+    runtime.queue_push("bugs", bug("bug-1", 0)).await.unwrap();
+    runtime.handle_event(worker_start()).await.unwrap();
+
+    runtime.clock.advance(Duration::from_secs(2 * 60));
+    runtime
+        .handle_event(Event::Timer {
+            id: "queue:bugs:sweep".to_string(),
+        })
+        .await
+        .unwrap();
+
+    let pipeline_id = runtime.pipelines().into_keys().next().unwrap();
+    let item = runtime.get_queue("bugs").unwrap().get("bug-1").cloned();
+    let item = item.unwrap();
+    assert_eq!(item.holder(), Some(pipeline_id.as_str()));
+    assert_eq!(item.attempts, 1);
+}
+
+#[tokio::test]
+async fn pushed_items_wake_running_workers_only() {
+    let runtime = setup_with(WORKER_RUNBOOK, &["bug-1", "bug-2"]);
+
+    // Stopped workers leave new items alone
+    for event in runtime.queue_push("bugs", bug("bug-1", 0)).await.unwrap() {
+        drain(&runtime, event).await;
+    }
+    assert_eq!(runtime.get_queue("bugs").unwrap().pending_count(), 1);
+
+    drain(&runtime, worker_start()).await;
+    for event in runtime.queue_push("bugs", bug("bug-2", 0)).await.unwrap() {
+        drain(&runtime, event).await;
+    }
+    assert!(runtime.get_queue("bugs").unwrap().items.is_empty());
+
+    drain(
+        &runtime,
+        Event::WorkerStop {
+            worker: "fixers".to_string(),
+        },
+    )
+    .await;
+    assert_eq!(
+        runtime.get_worker("fixers").unwrap().status,
+        WorkerStatus::Stopped
+    );
+}
+
+#[tokio::test]
+async fn unknown_worker_is_an_error() {
+    let runtime = setup_with(WORKER_RUNBOOK, &[]);
+    let result = runtime
+        .handle_event(Event::WorkerStart {
+            worker: "nope".to_string(),
+        })
+        .await;
+    assert!(matches!(result, Err(RuntimeError::WorkerNotFound(_))));
+}
+
+#[tokio::test]
+async fn idle_worker_announces_and_waits_for_work() {
+    let runtime = setup_with(WORKER_RUNBOOK, &["bug-1"]);
+
+    let events = runtime.handle_event(worker_start()).await.unwrap();
+    assert_eq!(custom_names(&events), vec!["worker:started", "worker:idle"]);
+
+    // An item pushed without a `queue:bugs:pushed` event is found by the idle wait
+    runtime.queue_push("bugs", bug("bug-1", 0)).await.unwrap();
+    let fired = runtime
+        .scheduler()
+        .lock()
+        .unwrap()
+        .fired_timers(std::time::Instant::now() + Duration::from_secs(31));
+    assert_eq!(
+        fired,
+        vec![Event::Timer {
+            id: "worker:fixers:wake".to_string()
+        }]
+    );
+    for event in fired {
+        drain(&runtime, event).await;
+    }
+    assert!(runtime.get_queue("bugs").unwrap().items.is_empty());
+}
+
+#[tokio::test]
+async fn wake_on_events_wake_running_workers() {
+    let runtime = setup_with(WORKER_RUNBOOK, &[]);
+    let bug_created = Event::Custom {
+        name: "bug:created".to_string(),
+        data: serde_json::json!({}),
+    };
+
+    // Stopped workers are not subscribed
+    let events = runtime.handle_event(bug_created.clone()).await.unwrap();
+    assert!(!events.iter().any(|e| matches!(e, Event::WorkerWake { .. })));
+
+    runtime.handle_event(worker_start()).await.unwrap();
+    let events = runtime.handle_event(bug_created.clone()).await.unwrap();
+    assert!(events.contains(&Event::WorkerWake {
+        worker: "fixers".to_string()
+    }));
+
+    let events = runtime
+        .handle_event(Event::WorkerStop {
+            worker: "fixers".to_string(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(custom_names(&events), vec!["worker:stopped"]);
+    let events = runtime.handle_event(bug_created).await.unwrap();
+    assert!(!events.iter().any(|e| matches!(e, Event::WorkerWake { .. })));
+}
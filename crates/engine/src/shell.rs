@@ -0,0 +1,262 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Running phase shell commands
+
+use crate::pipeline_log::PipelineLog;
+use oj_core::Event;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::{ExitStatus, Stdio};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::{Child, Command};
+use tokio::sync::{mpsc, oneshot};
+
+/// How long output is still read after a command exits
+///
+/// A process the command left running in the background can hold its output
+/// open indefinitely, so the remaining lines are not waited on forever.
+const OUTPUT_DRAIN_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A phase's shell command, ready to run
+#[derive(Debug, Clone)]
+pub struct ShellJob {
+    pub pipeline_id: String,
+    pub phase: String,
+    pub command: String,
+    pub cwd: PathBuf,
+    pub env: HashMap<String, String>,
+    pub timeout: Option<Duration>,
+    pub log_path: PathBuf,
+    /// Output variables set to the command's trimmed stdout
+    pub stdout_vars: Vec<String>,
+}
+
+/// Which of the command's streams a line came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stream {
+    Stdout,
+    Stderr,
+}
+
+/// How a shell command ended
+enum Outcome {
+    Exited(ExitStatus),
+    TimedOut,
+    Cancelled,
+}
+
+/// Parse the `KEY=value` lines a command wrote to `$OJ_OUTPUT`
+///
+/// Blank lines and lines without a `=` are skipped; a key written twice
+/// keeps its last value.
+pub fn parse_outputs(text: &str) -> HashMap<String, String> {
+    text.lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim(), value))
+        .filter(|(key, _)| !key.is_empty())
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+/// Run a shell command to completion, streaming its output to the pipeline log
+///
+/// Returns the event reporting how the command ended, or `None` if it was
+/// cancelled. Commands that time out or are cancelled are killed. A command
+/// that exits reports the variables it published, whatever its exit code:
+/// those named in `stdout_vars`, and whatever it wrote to the file named by
+/// `$OJ_OUTPUT`. The runtime decides whether the phase keeps them.
+pub async fn run(job: ShellJob, cancel: oneshot::Receiver<()>) -> Option<Event> {
+    let mut log = PipelineLog::open(&job.log_path);
+    log.write(&job.phase, &format!("$ {}", job.command));
+
+    // Left over if an earlier command was killed before it was read
+    let output_path = job.log_path.with_extension("output");
+    let _ = std::fs::remove_file(&output_path);
+
+    let spawned = Command::new("sh")
+        .arg("-c")
+        .arg(&job.command)
+        .current_dir(&job.cwd)
+        .envs(&job.env)
+        .env("OJ_OUTPUT", &output_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        // Its own process group, so everything the command starts can be killed
+        .process_group(0)
+        .kill_on_drop(true)
+        .spawn();
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(e) => {
+            tracing::warn!(pipeline_id = %job.pipeline_id, phase = %job.phase, error = %e, "shell failed to start");
+            log.write(&job.phase, &format!("failed to start: {}", e));
+            return Some(completed(&job, -1, HashMap::new()));
+        }
+    };
+
+    let mut stdout = Vec::new();
+    let outcome = wait(&mut child, &job, &mut log, &mut stdout, cancel).await;
+    let written = std::fs::read_to_string(&output_path).unwrap_or_default();
+    let _ = std::fs::remove_file(&output_path);
+    let outcome = match outcome {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            tracing::warn!(pipeline_id = %job.pipeline_id, phase = %job.phase, error = %e, "lost track of shell");
+            log.write(&job.phase, &format!("lost track of command: {}", e));
+            return Some(completed(&job, -1, HashMap::new()));
+        }
+    };
+
+    match outcome {
+        Outcome::Exited(status) => {
+            let exit_code = status.code().unwrap_or(-1);
+            tracing::info!(pipeline_id = %job.pipeline_id, phase = %job.phase, exit_code, "shell finished");
+            log.write(&job.phase, &format!("exited with code {}", exit_code));
+
+            let mut outputs = parse_outputs(&written);
+            let stdout = stdout.join("\n").trim().to_string();
+            for var in &job.stdout_vars {
+                outputs.insert(var.clone(), stdout.clone());
+            }
+            Some(completed(&job, exit_code, outputs))
+        }
+        Outcome::TimedOut => {
+            tracing::warn!(pipeline_id = %job.pipeline_id, phase = %job.phase, "shell timed out");
+            log.write(&job.phase, "timed out, killed");
+            Some(Event::ShellTimedOut {
+                pipeline_id: job.pipeline_id,
+                phase: job.phase,
+            })
+        }
+        Outcome::Cancelled => {
+            tracing::info!(pipeline_id = %job.pipeline_id, phase = %job.phase, "shell cancelled");
+            log.write(&job.phase, "cancelled, killed");
+            None
+        }
+    }
+}
+
+/// Log the child's output until it exits, times out or is cancelled
+///
+/// Stdout lines are also collected into `stdout` when the job has
+/// variables to set from them.
+async fn wait(
+    child: &mut Child,
+    job: &ShellJob,
+    log: &mut PipelineLog,
+    stdout: &mut Vec<String>,
+    mut cancel: oneshot::Receiver<()>,
+) -> std::io::Result<Outcome> {
+    let (line_tx, mut lines) = mpsc::channel(64);
+    if let Some(out) = child.stdout.take() {
+        forward_lines(out, Stream::Stdout, line_tx.clone());
+    }
+    if let Some(err) = child.stderr.take() {
+        forward_lines(err, Stream::Stderr, line_tx);
+    }
+    let capture = !job.stdout_vars.is_empty();
+    let mut record = |stream: Stream, line: String| {
+        log.write(&job.phase, &line);
+        if capture && stream == Stream::Stdout {
+            stdout.push(line);
+        }
+    };
+
+    let deadline = async {
+        match job.timeout {
+            Some(timeout) => tokio::time::sleep(timeout).await,
+            None => std::future::pending().await,
+        }
+    };
+    tokio::pin!(deadline);
+
+    let mut output_open = true;
+    let mut cancellable = true;
+    let outcome = loop {
+        tokio::select! {
+            line = lines.recv(), if output_open => match line {
+                Some((stream, line)) => record(stream, line),
+                None => output_open = false,
+            },
+            status = child.wait() => break Outcome::Exited(status?),
+            () = &mut deadline => break Outcome::TimedOut,
+            cancelled = &mut cancel, if cancellable => match cancelled {
+                Ok(()) => break Outcome::Cancelled,
+                // Nobody can cancel the command any more
+                Err(_) => cancellable = false,
+            },
+        }
+    };
+
+    match outcome {
+        // Pick up whatever the command wrote just before it exited
+        Outcome::Exited(_) => {
+            let drain = async {
+                while let Some((stream, line)) = lines.recv().await {
+                    record(stream, line);
+                }
+            };
+            if tokio::time::timeout(OUTPUT_DRAIN_TIMEOUT, drain)
+                .await
+                .is_err()
+            {
+                tracing::debug!(pipeline_id = %job.pipeline_id, phase = %job.phase, "output still open after exit");
+            }
+        }
+        Outcome::TimedOut | Outcome::Cancelled => {
+            if let Some(pid) = child.id() {
+                kill_process_group(pid).await;
+            }
+            child.kill().await?;
+        }
+    }
+    Ok(outcome)
+}
+
+/// Kill every process in the group led by `pid`
+///
+/// Commands run in their own process group, so this reaches whatever they
+/// started in the background as well. Uses `kill(1)`, as signalling a group
+/// directly would need unsafe code.
+pub(crate) async fn kill_process_group(pid: u32) {
+    let killed = Command::new("kill")
+        .args(["-KILL", "--", &format!("-{}", pid)])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .await;
+    if let Err(e) = killed {
+        tracing::warn!(pid, error = %e, "failed to kill process group");
+    }
+}
+
+/// Send each line read from `reader` to `lines` until it closes
+fn forward_lines<R>(reader: R, stream: Stream, lines: mpsc::Sender<(Stream, String)>)
+where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut reader = BufReader::new(reader).lines();
+        while let Ok(Some(line)) = reader.next_line().await {
+            if lines.send((stream, line)).await.is_err() {
+                break;
+            }
+        }
+    });
+}
+
+fn completed(job: &ShellJob, exit_code: i32, outputs: HashMap<String, String>) -> Event {
+    Event::ShellCompleted {
+        pipeline_id: job.pipeline_id.clone(),
+        phase: job.phase.clone(),
+        exit_code,
+        outputs,
+    }
+}
+
+#[cfg(test)]
+#[path = "shell_tests.rs"]
+mod tests;
@@ -0,0 +1,169 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+use tempfile::tempdir;
+
+fn job(dir: &std::path::Path, command: &str, timeout: Option<Duration>) -> ShellJob {
+    ShellJob {
+        pipeline_id: "pipe-1".to_string(),
+        phase: "build".to_string(),
+        command: command.to_string(),
+        cwd: dir.to_path_buf(),
+        env: HashMap::new(),
+        timeout,
+        log_path: dir.join("logs/pipe-1.log"),
+        stdout_vars: Vec::new(),
+    }
+}
+
+fn read_log(dir: &std::path::Path) -> String {
+    std::fs::read_to_string(dir.join("logs/pipe-1.log")).unwrap()
+}
+
+#[tokio::test]
+async fn output_is_streamed_to_pipeline_log() {
+    let dir = tempdir().unwrap();
+    let (_cancel, cancelled) = oneshot::channel();
+
+    let event = run(
+        job(dir.path(), "echo out; echo err >&2; exit 3", None),
+        cancelled,
+    )
+    .await;
+
+    assert_eq!(
+        event,
+        Some(Event::ShellCompleted {
+            pipeline_id: "pipe-1".to_string(),
+            phase: "build".to_string(),
+            exit_code: 3,
+            outputs: HashMap::new(),
+        })
+    );
+    let log = read_log(dir.path());
+    assert!(log.contains("[build] $ echo out"));
+    assert!(log.contains("[build] out\n"));
+    assert!(log.contains("[build] err\n"));
+    assert!(log.contains("[build] exited with code 3"));
+}
+
+#[tokio::test]
+async fn command_past_timeout_is_killed() {
+    let dir = tempdir().unwrap();
+    let (_cancel, cancelled) = oneshot::channel();
+
+    let event = run(
+        job(dir.path(), "sleep 30", Some(Duration::from_millis(100))),
+        cancelled,
+    )
+    .await;
+
+    assert_eq!(
+        event,
+        Some(Event::ShellTimedOut {
+            pipeline_id: "pipe-1".to_string(),
+            phase: "build".to_string(),
+        })
+    );
+    assert!(read_log(dir.path()).contains("timed out"));
+}
+
+#[tokio::test]
+async fn cancelled_command_reports_nothing() {
+    let dir = tempdir().unwrap();
+    let (cancel, cancelled) = oneshot::channel();
+    let task = tokio::spawn(run(job(dir.path(), "sleep 30", None), cancelled));
+
+    cancel.send(()).unwrap();
+
+    assert_eq!(task.await.unwrap(), None);
+    assert!(read_log(dir.path()).contains("cancelled"));
+}
+
+#[tokio::test]
+async fn successful_command_publishes_outputs() {
+    let dir = tempdir().unwrap();
+    let (_cancel, cancelled) = oneshot::channel();
+    let mut job = job(
+        dir.path(),
+        "echo '  abc123 '; echo BRANCH=fix/login >> \"$OJ_OUTPUT\"",
+        None,
+    );
+    job.stdout_vars = vec!["sha".to_string()];
+
+    let Some(Event::ShellCompleted { outputs, .. }) = run(job, cancelled).await else {
+        panic!("expected completion");
+    };
+
+    assert_eq!(outputs.get("sha").map(String::as_str), Some("abc123"));
+    assert_eq!(outputs.get("BRANCH").map(String::as_str), Some("fix/login"));
+    assert!(!dir.path().join("logs/pipe-1.output").exists());
+}
+
+#[tokio::test]
+async fn failed_command_still_reports_outputs() {
+    let dir = tempdir().unwrap();
+    let (_cancel, cancelled) = oneshot::channel();
+    let job = job(dir.path(), "echo KEY=value > \"$OJ_OUTPUT\"; exit 2", None);
+
+    let Some(Event::ShellCompleted {
+        exit_code, outputs, ..
+    }) = run(job, cancelled).await
+    else {
+        panic!("expected completion");
+    };
+
+    assert_eq!(exit_code, 2);
+    assert_eq!(outputs.get("KEY").map(String::as_str), Some("value"));
+}
+
+#[tokio::test]
+async fn background_process_does_not_hold_up_completion() {
+    let dir = tempdir().unwrap();
+    let (_cancel, cancelled) = oneshot::channel();
+    let job = job(dir.path(), "sleep 60 & echo started", None);
+
+    let event = tokio::time::timeout(Duration::from_secs(10), run(job, cancelled))
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        event,
+        Some(Event::ShellCompleted { exit_code: 0, .. })
+    ));
+    assert!(read_log(dir.path()).contains("started"));
+}
+
+#[tokio::test]
+async fn timeout_kills_background_processes_too() {
+    let dir = tempdir().unwrap();
+    let (_cancel, cancelled) = oneshot::channel();
+    let job = job(
+        dir.path(),
+        "sleep 60 & echo $! > bg.pid; wait",
+        Some(Duration::from_millis(200)),
+    );
+
+    let event = tokio::time::timeout(Duration::from_secs(10), run(job, cancelled))
+        .await
+        .unwrap();
+
+    assert!(matches!(event, Some(Event::ShellTimedOut { .. })));
+    // Gone, or a zombie waiting to be reaped by whoever adopted it
+    let pid = std::fs::read_to_string(dir.path().join("bg.pid")).unwrap();
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid.trim())).unwrap_or_default();
+    assert!(
+        stat.is_empty() || stat.contains(") Z "),
+        "background sleep survived: {}",
+        stat
+    );
+}
+
+#[test]
+fn output_lines_are_key_value_pairs() {
+    let outputs = parse_outputs("A=1\n\nnot a pair\nURL=http://x?a=b\r\nA=2\n=lost\n");
+    assert_eq!(outputs.len(), 2);
+    assert_eq!(outputs["A"], "2");
+    assert_eq!(outputs["URL"], "http://x?a=b");
+}
@@ -5,7 +5,7 @@
 
 use crate::error::RuntimeError;
 use crate::ExecuteError;
-use oj_core::{Effect, Operation, Pipeline};
+use oj_core::{Effect, Pipeline};
 use oj_runbook::AgentDef;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -34,8 +34,9 @@ pub fn build_spawn_effects(
         "building spawn effects"
     );
 
-    // Build variables for interpolation
+    // Build variables for interpolation; phase outputs take precedence over inputs
     let mut vars = inputs.clone();
+    vars.extend(pipeline.outputs.clone());
     vars.insert("pipeline_id".to_string(), pipeline_id.to_string());
     vars.insert("name".to_string(), pipeline.name.clone());
     vars.insert(
@@ -73,15 +74,20 @@ pub fn build_spawn_effects(
         env.push(("OJ_SOCKET_DIR".to_string(), socket_dir));
     }
 
-    // Determine effective working directory from agent cwd config
-    let effective_cwd = agent_def.cwd.as_ref().map(|cwd_template| {
-        let cwd_str = oj_runbook::interpolate(cwd_template, &vars);
-        if Path::new(&cwd_str).is_absolute() {
-            PathBuf::from(cwd_str)
-        } else {
-            workspace_path.join(cwd_str)
+    // Determine effective working directory from agent cwd config. Child
+    // pipelines have no workspace record of their own, so the path is always
+    // passed along rather than looked up by pipeline.
+    let effective_cwd = match &agent_def.cwd {
+        Some(cwd_template) => {
+            let cwd_str = oj_runbook::interpolate(cwd_template, &vars);
+            if Path::new(&cwd_str).is_absolute() {
+                PathBuf::from(cwd_str)
+            } else {
+                workspace_path.join(cwd_str)
+            }
         }
-    });
+        None => workspace_path.to_path_buf(),
+    };
 
     tracing::info!(
         pipeline_id,
@@ -92,17 +98,11 @@ pub fn build_spawn_effects(
     );
 
     Ok(vec![
-        Effect::Persist {
-            operation: Operation::SessionCreate {
-                id: pipeline_id.to_string(),
-                pipeline_id: pipeline_id.to_string(),
-            },
-        },
         Effect::Spawn {
             workspace_id: pipeline_id.to_string(),
             command,
             env,
-            cwd: effective_cwd,
+            cwd: Some(effective_cwd),
         },
         // Start session monitoring timer
         Effect::SetTimer {
@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Strategy attempt effects.
+//!
+//! The attempt index is persisted before each attempt starts so a restarted
+//! daemon picks the chain back up at the same attempt.
+
+use oj_core::{Effect, Event, Operation, PhaseStatus, Pipeline};
+use oj_runbook::StrategyDef;
+
+/// Timer that fails a strategy attempt that runs too long
+pub fn strategy_timeout_timer(pipeline_id: &str) -> String {
+    format!("strategy:{}:timeout", pipeline_id)
+}
+
+/// Build effects to record and announce the start of attempt `index`
+pub fn attempt_start_effects(
+    pipeline: &Pipeline,
+    def: &StrategyDef,
+    index: usize,
+    checkpoint: Option<&str>,
+) -> Vec<Effect> {
+    let mut effects = vec![
+        Effect::Persist {
+            operation: Operation::StrategyAttempt {
+                pipeline_id: pipeline.id.clone(),
+                phase: pipeline.phase.clone(),
+                attempt: index,
+                checkpoint: checkpoint.map(String::from),
+            },
+        },
+        Effect::Emit {
+            event: Event::Custom {
+                name: "strategy:attempt".to_string(),
+                data: serde_json::json!({
+                    "pipeline_id": pipeline.id,
+                    "strategy": def.name,
+                    "attempt": index,
+                }),
+            },
+        },
+    ];
+    if let Some(timeout) = def.attempt(index).and_then(|a| a.timeout) {
+        effects.push(Effect::SetTimer {
+            id: strategy_timeout_timer(&pipeline.id),
+            duration: timeout,
+        });
+    }
+    effects
+}
+
+/// Build effects to hand an exhausted strategy to a human
+pub fn exhaust_escalate_effects(pipeline: &Pipeline, def: &StrategyDef) -> Vec<Effect> {
+    let reason = format!("strategy {} exhausted", def.name);
+    vec![
+        Effect::Emit {
+            event: Event::Custom {
+                name: "pipeline:escalate".to_string(),
+                data: serde_json::json!({
+                    "pipeline_id": pipeline.id,
+                    "pipeline_name": pipeline.name,
+                    "phase": pipeline.phase,
+                    "reason": reason,
+                }),
+            },
+        },
+        Effect::Notify {
+            title: format!("Pipeline needs attention: {}", pipeline.name),
+            message: reason,
+        },
+        Effect::Persist {
+            operation: Operation::PhaseStatusUpdate {
+                pipeline_id: pipeline.id.clone(),
+                status: PhaseStatus::Waiting,
+            },
+        },
+    ]
+}
+
+#[cfg(test)]
+#[path = "strategy_tests.rs"]
+mod tests;
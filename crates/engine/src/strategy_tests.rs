@@ -0,0 +1,77 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+use oj_core::FakeClock;
+use oj_runbook::{AttemptDef, ExhaustAction, RunDirective};
+use std::collections::HashMap;
+use std::time::Duration;
+
+fn test_pipeline() -> Pipeline {
+    Pipeline::new(
+        "pipe-1".to_string(),
+        "feature".to_string(),
+        "merge".to_string(),
+        HashMap::new(),
+        "merge".to_string(),
+        &FakeClock::new(),
+    )
+}
+
+fn test_strategy() -> StrategyDef {
+    let attempt = |name: &str, timeout| AttemptDef {
+        name: name.to_string(),
+        run: RunDirective::Shell(format!("./{}.sh", name)),
+        timeout,
+        rollback: None,
+    };
+    StrategyDef {
+        name: "merge".to_string(),
+        checkpoint: None,
+        attempts: vec![
+            attempt("ff", None),
+            attempt("rebase", Some(Duration::from_secs(300))),
+        ],
+        on_exhaust: ExhaustAction::Fail,
+    }
+}
+
+#[test]
+fn attempt_start_persists_index_and_checkpoint() {
+    let effects = attempt_start_effects(&test_pipeline(), &test_strategy(), 0, Some("abc123"));
+
+    assert_eq!(effects.len(), 2);
+    assert!(matches!(
+        &effects[0],
+        Effect::Persist {
+            operation: Operation::StrategyAttempt { attempt: 0, checkpoint: Some(c), .. }
+        } if c == "abc123"
+    ));
+}
+
+#[test]
+fn attempt_with_timeout_sets_timer() {
+    let effects = attempt_start_effects(&test_pipeline(), &test_strategy(), 1, None);
+
+    assert!(effects.iter().any(|e| matches!(
+        e,
+        Effect::SetTimer { id, duration }
+            if id == "strategy:pipe-1:timeout" && *duration == Duration::from_secs(300)
+    )));
+}
+
+#[test]
+fn exhaust_escalate_waits_for_human() {
+    let effects = exhaust_escalate_effects(&test_pipeline(), &test_strategy());
+
+    assert!(effects.iter().any(|e| matches!(e, Effect::Notify { .. })));
+    assert!(effects.iter().any(|e| matches!(
+        e,
+        Effect::Persist {
+            operation: Operation::PhaseStatusUpdate {
+                status: PhaseStatus::Waiting,
+                ..
+            }
+        }
+    )));
+}
@@ -0,0 +1,50 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Event subscriptions, indexed by event name
+
+use std::collections::{BTreeSet, HashMap};
+
+/// Subscribers waiting on named events
+///
+/// Lookups by event name are a single map access, so every custom event can
+/// be checked without scanning everything that is waiting.
+#[derive(Debug, Default)]
+pub struct Subscriptions {
+    by_event: HashMap<String, BTreeSet<String>>,
+}
+
+impl Subscriptions {
+    /// Create an empty index
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Subscribe `subscriber` to events named `event`
+    pub fn subscribe(&mut self, event: &str, subscriber: &str) {
+        self.by_event
+            .entry(event.to_string())
+            .or_default()
+            .insert(subscriber.to_string());
+    }
+
+    /// Remove every subscription held by `subscriber`
+    pub fn unsubscribe(&mut self, subscriber: &str) {
+        self.by_event.retain(|_, subscribers| {
+            subscribers.remove(subscriber);
+            !subscribers.is_empty()
+        });
+    }
+
+    /// Subscribers waiting on events named `event`, in a stable order
+    pub fn subscribers(&self, event: &str) -> Vec<String> {
+        self.by_event
+            .get(event)
+            .map(|s| s.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+#[path = "subscriptions_tests.rs"]
+mod tests;
@@ -0,0 +1,35 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+
+#[test]
+fn subscribers_are_keyed_by_event_name() {
+    let mut subs = Subscriptions::new();
+    subs.subscribe("pipeline:auth:complete", "pipe-2");
+    subs.subscribe("pipeline:auth:complete", "pipe-1");
+    subs.subscribe("build:queued", "pipe-3");
+
+    assert_eq!(
+        subs.subscribers("pipeline:auth:complete"),
+        vec!["pipe-1", "pipe-2"]
+    );
+    assert_eq!(subs.subscribers("build:queued"), vec!["pipe-3"]);
+    assert!(subs.subscribers("pipeline:other:complete").is_empty());
+}
+
+#[test]
+fn unsubscribe_removes_all_events_for_subscriber() {
+    let mut subs = Subscriptions::new();
+    subs.subscribe("a", "pipe-1");
+    subs.subscribe("b", "pipe-1");
+    subs.subscribe("b", "pipe-2");
+
+    subs.unsubscribe("pipe-1");
+
+    assert!(subs.subscribers("a").is_empty());
+    assert_eq!(subs.subscribers("b"), vec!["pipe-2"]);
+
+    subs.unsubscribe("pipe-2");
+    assert!(subs.by_event.is_empty());
+}
@@ -0,0 +1,68 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Worker lifecycle effects
+
+use oj_core::{Effect, Event};
+use oj_runbook::{IdleAction, WorkerDef};
+
+/// Timer that wakes a worker to look for work
+pub fn worker_wake_timer(name: &str) -> String {
+    format!("worker:{}:wake", name)
+}
+
+/// Build the effect announcing a worker lifecycle change (`started`, `idle`, `stopped`)
+pub fn lifecycle_effect(name: &str, change: &str) -> Effect {
+    Effect::Emit {
+        event: Event::Custom {
+            name: format!("worker:{}", change),
+            data: serde_json::json!({ "worker": name }),
+        },
+    }
+}
+
+/// Build effects for a worker that ran out of work
+///
+/// `announce` emits `worker:idle`; the `idle_action` wait is re-armed either way.
+pub fn idle_effects(def: &WorkerDef, announce: bool) -> Vec<Effect> {
+    let mut effects = Vec::new();
+    if announce {
+        effects.push(lifecycle_effect(&def.name, "idle"));
+    }
+    if let Some(IdleAction::Wait(duration)) = def.idle_action {
+        effects.push(Effect::SetTimer {
+            id: worker_wake_timer(&def.name),
+            duration,
+        });
+    }
+    effects
+}
+
+/// Name of the pipeline handling a queue item, used for its worktree and branch
+///
+/// Items come from outside the runbook, so the name is reduced to a slug:
+/// each run of characters other than ASCII letters, digits, `_` and `-`
+/// becomes one `-`. Retries get an `-<attempt>` suffix, as an earlier
+/// attempt's worktree and branch may still be around.
+pub fn item_pipeline_name(name: &str, attempts: u32) -> String {
+    let mut slug = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let mut slug = slug.trim_matches('-').to_string();
+    if slug.is_empty() {
+        slug = "item".to_string();
+    }
+    if attempts > 1 {
+        slug = format!("{}-{}", slug, attempts);
+    }
+    slug
+}
+
+#[cfg(test)]
+#[path = "worker_tests.rs"]
+mod tests;
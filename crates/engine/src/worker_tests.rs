@@ -0,0 +1,50 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+use std::time::Duration;
+
+fn worker_def(idle_action: Option<IdleAction>) -> WorkerDef {
+    WorkerDef {
+        name: "fixers".to_string(),
+        concurrency: 1,
+        pipelines: vec![],
+        queue: Some("bugs".to_string()),
+        handler: Some("fix".to_string()),
+        idle_action,
+        wake_on: vec![],
+    }
+}
+
+#[test]
+fn idle_announces_and_arms_wait() {
+    let def = worker_def(Some(IdleAction::Wait(Duration::from_secs(30))));
+
+    let effects = idle_effects(&def, true);
+    assert_eq!(effects.len(), 2);
+    assert!(matches!(
+        &effects[0],
+        Effect::Emit { event: Event::Custom { name, .. } } if name == "worker:idle"
+    ));
+    assert!(matches!(
+        &effects[1],
+        Effect::SetTimer { id, duration }
+            if id == "worker:fixers:wake" && *duration == Duration::from_secs(30)
+    ));
+}
+
+#[test]
+fn quiet_idle_without_idle_action_does_nothing() {
+    assert!(idle_effects(&worker_def(None), false).is_empty());
+    assert_eq!(idle_effects(&worker_def(None), true).len(), 1);
+}
+
+#[test]
+fn item_names_are_slugged_for_worktrees() {
+    assert_eq!(item_pipeline_name("bug-7", 1), "bug-7");
+    assert_eq!(item_pipeline_name("bug-7", 3), "bug-7-3");
+    assert_eq!(item_pipeline_name("../x", 1), "x");
+    assert_eq!(item_pipeline_name("a b/c..d", 1), "a-b-c-d");
+    assert_eq!(item_pipeline_name("fix: login!", 2), "fix-login-2");
+    assert_eq!(item_pipeline_name("..", 1), "item");
+}
@@ -0,0 +1,23 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Named action definitions
+
+use std::time::Duration;
+
+/// A named action from the runbook
+///
+/// Actions are shell commands referenced by name from monitor response
+/// chains and agent `on_idle`/`on_exit`. Each target (a pipeline or monitored
+/// item) is rate-limited by `cooldown` and capped at `max_attempts`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActionDef {
+    /// Action name
+    pub name: String,
+    /// Shell command, interpolated with the target's variables
+    pub run: String,
+    /// Minimum time between firings at the same target
+    pub cooldown: Option<Duration>,
+    /// Firings allowed per target before the action is used up
+    pub max_attempts: Option<u32>,
+}
@@ -3,6 +3,7 @@
 
 //! Agent definitions
 
+use crate::ResponseStep;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::io;
@@ -55,6 +56,10 @@ pub enum ActionConfig {
         #[serde(default)]
         append: bool,
     },
+    /// A runbook `[action.*]` definition, referenced by name
+    Named(String),
+    /// Actions tried in turn on successive triggers: `["nudge:2", "restart", "escalate"]`
+    Chain(Vec<ResponseStep>),
 }
 
 impl Default for ActionConfig {
@@ -87,24 +92,58 @@ impl ActionConfig {
         }
     }
 
-    pub fn action(&self) -> &AgentAction {
+    /// The built-in action, or `None` for a named action
+    pub fn action(&self) -> Option<&AgentAction> {
         match self {
-            ActionConfig::Simple(a) => a,
-            ActionConfig::WithOptions { action, .. } => action,
+            ActionConfig::Simple(a) => Some(a),
+            ActionConfig::WithOptions { action, .. } => Some(action),
+            ActionConfig::Named(_) | ActionConfig::Chain(_) => None,
+        }
+    }
+
+    /// The name an `[action.*]` definition would be looked up by
+    ///
+    /// Options only apply to built-in actions, so configs with options have
+    /// no name; nor do chains, whose steps are each looked up in turn.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            ActionConfig::Simple(a) => Some(a.as_str()),
+            ActionConfig::Named(name) => Some(name),
+            ActionConfig::WithOptions { .. } | ActionConfig::Chain(_) => None,
+        }
+    }
+
+    /// Names this config looks up as `[action.*]` definitions, leaving out built-ins
+    pub fn named_actions(&self) -> Vec<&str> {
+        match self {
+            ActionConfig::Named(name) => vec![name],
+            ActionConfig::Chain(steps) => steps
+                .iter()
+                .filter_map(ResponseStep::named_action)
+                .collect(),
+            ActionConfig::Simple(_) | ActionConfig::WithOptions { .. } => vec![],
+        }
+    }
+
+    /// The action config for one step of a chain
+    pub fn from_step(step: &ResponseStep) -> Self {
+        match AgentAction::parse(&step.action) {
+            Some(action) => ActionConfig::Simple(action),
+            None => ActionConfig::Named(step.action.clone()),
         }
     }
 
     pub fn message(&self) -> Option<&str> {
         match self {
-            ActionConfig::Simple(_) => None,
             ActionConfig::WithOptions { message, .. } => message.as_deref(),
+            _ => None,
         }
     }
 
     pub fn append(&self) -> bool {
         match self {
-            ActionConfig::Simple(_) => false,
             ActionConfig::WithOptions { append, .. } => *append,
+            _ => false,
         }
     }
 }
@@ -121,6 +160,33 @@ pub enum AgentAction {
     Escalate, // Notify human
 }
 
+impl AgentAction {
+    /// Parse an action name such as `"restart"`
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "nudge" => Some(AgentAction::Nudge),
+            "done" => Some(AgentAction::Done),
+            "fail" => Some(AgentAction::Fail),
+            "restart" => Some(AgentAction::Restart),
+            "recover" => Some(AgentAction::Recover),
+            "escalate" => Some(AgentAction::Escalate),
+            _ => None,
+        }
+    }
+
+    /// The action's name as written in a runbook
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AgentAction::Nudge => "nudge",
+            AgentAction::Done => "done",
+            AgentAction::Fail => "fail",
+            AgentAction::Restart => "restart",
+            AgentAction::Recover => "recover",
+            AgentAction::Escalate => "escalate",
+        }
+    }
+}
+
 /// Error action configuration - simple or per-error-type
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
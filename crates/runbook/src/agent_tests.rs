@@ -167,8 +167,8 @@ fn parses_simple_action() {
         on_exit = "escalate"
     "#;
     let config: TestConfig = toml::from_str(toml).unwrap();
-    assert_eq!(config.on_idle.action(), &AgentAction::Nudge);
-    assert_eq!(config.on_exit.action(), &AgentAction::Escalate);
+    assert_eq!(config.on_idle.action(), Some(&AgentAction::Nudge));
+    assert_eq!(config.on_exit.action(), Some(&AgentAction::Escalate));
 }
 
 #[test]
@@ -183,7 +183,7 @@ fn parses_action_with_message() {
         on_idle = { action = "nudge", message = "Keep going" }
     "#;
     let config: TestConfig = toml::from_str(toml).unwrap();
-    assert_eq!(config.on_idle.action(), &AgentAction::Nudge);
+    assert_eq!(config.on_idle.action(), Some(&AgentAction::Nudge));
     assert_eq!(config.on_idle.message(), Some("Keep going"));
     assert!(!config.on_idle.append());
 }
@@ -200,7 +200,7 @@ fn parses_action_with_append() {
         on_exit = { action = "recover", message = "Previous attempt exited.", append = true }
     "#;
     let config: TestConfig = toml::from_str(toml).unwrap();
-    assert_eq!(config.on_exit.action(), &AgentAction::Recover);
+    assert_eq!(config.on_exit.action(), Some(&AgentAction::Recover));
     assert_eq!(config.on_exit.message(), Some("Previous attempt exited."));
     assert!(config.on_exit.append());
 }
@@ -226,12 +226,12 @@ fn parses_per_error_actions() {
 
     // Match specific error type
     let action = config.on_error.action_for(Some(&ErrorType::NoInternet));
-    assert_eq!(action.action(), &AgentAction::Recover);
+    assert_eq!(action.action(), Some(&AgentAction::Recover));
     assert_eq!(action.message(), Some("Network restored"));
 
     // Fall through to catch-all
     let action = config.on_error.action_for(Some(&ErrorType::Unauthorized));
-    assert_eq!(action.action(), &AgentAction::Escalate);
+    assert_eq!(action.action(), Some(&AgentAction::Escalate));
 }
 
 #[test]
@@ -248,7 +248,7 @@ fn error_action_config_simple() {
     let config: TestConfig = toml::from_str(toml).unwrap();
 
     let action = config.on_error.action_for(Some(&ErrorType::NoInternet));
-    assert_eq!(action.action(), &AgentAction::Escalate);
+    assert_eq!(action.action(), Some(&AgentAction::Escalate));
 }
 
 #[test]
@@ -269,21 +269,21 @@ fn error_action_config_default_when_no_match() {
 
     // Should default to escalate when no match
     let action = config.on_error.action_for(Some(&ErrorType::NoInternet));
-    assert_eq!(action.action(), &AgentAction::Escalate);
+    assert_eq!(action.action(), Some(&AgentAction::Escalate));
 }
 
 #[test]
 fn action_config_defaults() {
     // Defaults: on_idle = "nudge", on_exit = "escalate", on_error = "escalate"
     let default_idle = ActionConfig::default();
-    assert_eq!(default_idle.action(), &AgentAction::Nudge);
+    assert_eq!(default_idle.action(), Some(&AgentAction::Nudge));
 
     let default_exit = default_on_exit();
-    assert_eq!(default_exit.action(), &AgentAction::Escalate);
+    assert_eq!(default_exit.action(), Some(&AgentAction::Escalate));
 
     let default_error = default_on_error();
     let action = default_error.action_for(Some(&ErrorType::Unauthorized));
-    assert_eq!(action.action(), &AgentAction::Escalate);
+    assert_eq!(action.action(), Some(&AgentAction::Escalate));
 }
 
 #[test]
@@ -297,7 +297,69 @@ fn parses_full_agent_with_actions() {
         on_error = "escalate"
     "#;
     let agent: AgentDef = toml::from_str(toml).unwrap();
-    assert_eq!(agent.on_idle.action(), &AgentAction::Nudge);
+    assert_eq!(agent.on_idle.action(), Some(&AgentAction::Nudge));
     assert_eq!(agent.on_idle.message(), Some("Keep going"));
-    assert_eq!(agent.on_exit.action(), &AgentAction::Escalate);
+    assert_eq!(agent.on_exit.action(), Some(&AgentAction::Escalate));
+}
+
+#[test]
+fn agent_action_parse() {
+    assert_eq!(AgentAction::parse("restart"), Some(AgentAction::Restart));
+    assert_eq!(AgentAction::parse("escalate"), Some(AgentAction::Escalate));
+    assert_eq!(AgentAction::parse("mark_flaky"), None);
+}
+
+#[test]
+fn parses_named_action() {
+    #[derive(Debug, Deserialize)]
+    struct TestConfig {
+        #[serde(default)]
+        on_idle: ActionConfig,
+    }
+
+    let config: TestConfig = toml::from_str(r#"on_idle = "mark_flaky""#).unwrap();
+    assert_eq!(
+        config.on_idle,
+        ActionConfig::Named("mark_flaky".to_string())
+    );
+    assert_eq!(config.on_idle.action(), None);
+    assert_eq!(config.on_idle.name(), Some("mark_flaky"));
+
+    // Built-in actions can be overridden by an [action.*] of the same name
+    assert_eq!(ActionConfig::default().name(), Some("nudge"));
+}
+
+#[test]
+fn parses_action_chain() {
+    #[derive(Debug, Deserialize)]
+    struct TestConfig {
+        #[serde(default)]
+        on_idle: ActionConfig,
+    }
+
+    let toml = r#"on_idle = ["nudge:2", "restart:1", "escalate"]"#;
+    let config: TestConfig = toml::from_str(toml).unwrap();
+    let ActionConfig::Chain(steps) = &config.on_idle else {
+        panic!("expected a chain, got {:?}", config.on_idle);
+    };
+    let steps: Vec<_> = steps.iter().map(ToString::to_string).collect();
+    assert_eq!(steps, vec!["nudge:2", "restart", "escalate"]);
+    assert_eq!(config.on_idle.action(), None);
+    assert_eq!(config.on_idle.name(), None);
+
+    assert!(toml::from_str::<TestConfig>(r#"on_idle = ["nudge:0"]"#).is_err());
+}
+
+#[test]
+fn chain_steps_resolve_to_builtin_or_named() {
+    let step = ResponseStep::parse("restart:2").unwrap();
+    assert_eq!(
+        ActionConfig::from_step(&step),
+        ActionConfig::Simple(AgentAction::Restart)
+    );
+    let step = ResponseStep::parse("mark_flaky").unwrap();
+    assert_eq!(
+        ActionConfig::from_step(&step),
+        ActionConfig::Named("mark_flaky".to_string())
+    );
 }
@@ -414,7 +414,14 @@ pub enum RunDirective {
     /// Shell command string: `run = "echo hello"`
     Shell(String),
     /// Pipeline reference: `run = { pipeline = "build" }`
-    Pipeline { pipeline: String },
+    ///
+    /// In a phase, the pipeline runs as a child; `inputs` sets its inputs,
+    /// interpolated with the parent's variables.
+    Pipeline {
+        pipeline: String,
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        inputs: HashMap<String, String>,
+    },
     /// Agent reference: `run = { agent = "planning" }`
     Agent { agent: String },
     /// Strategy reference: `run = { strategy = "merge" }`
@@ -453,7 +460,7 @@ impl RunDirective {
     /// Get the pipeline name if this is a pipeline directive
     pub fn pipeline_name(&self) -> Option<&str> {
         match self {
-            RunDirective::Pipeline { pipeline } => Some(pipeline),
+            RunDirective::Pipeline { pipeline, .. } => Some(pipeline),
             _ => None,
         }
     }
@@ -221,6 +221,7 @@ fn run_directive_shell() {
 fn run_directive_pipeline() {
     let directive = RunDirective::Pipeline {
         pipeline: "build".to_string(),
+        inputs: HashMap::new(),
     };
     assert!(directive.is_pipeline());
     assert!(!directive.is_shell());
@@ -332,6 +333,7 @@ fn command_parse_args() {
             .collect(),
         run: RunDirective::Pipeline {
             pipeline: "build".to_string(),
+            inputs: HashMap::new(),
         },
     };
 
@@ -367,6 +369,7 @@ fn command_named_overrides() {
             .collect(),
         run: RunDirective::Pipeline {
             pipeline: "build".to_string(),
+            inputs: HashMap::new(),
         },
     };
 
@@ -0,0 +1,159 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! `when` conditions for routing between phases ("{kind} == 'bug'", "{count} > 3")
+
+use crate::template::interpolate_or_empty;
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Error returned for malformed or uncomparable conditions
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ConditionError {
+    #[error("empty condition")]
+    Empty,
+    #[error("unclosed quote in {0:?}")]
+    UnclosedQuote(String),
+    #[error("missing operand in {0:?}")]
+    MissingOperand(String),
+    #[error("use == to compare in {0:?}")]
+    SingleEquals(String),
+    #[error("only one comparison is allowed in {0:?}")]
+    Chained(String),
+    #[error("cannot compare {0:?} and {1:?} as numbers")]
+    NotNumeric(String, String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Ne,
+    Le,
+    Ge,
+    Lt,
+    Gt,
+}
+
+// Two-character operators come first so `<=` is not read as `<`
+const OPERATORS: [(&str, Op); 6] = [
+    ("==", Op::Eq),
+    ("!=", Op::Ne),
+    ("<=", Op::Le),
+    (">=", Op::Ge),
+    ("<", Op::Lt),
+    (">", Op::Gt),
+];
+
+/// A parsed condition: one comparison, or a single value tested for truth
+///
+/// Operands are interpolated with the pipeline's variables when evaluated;
+/// unknown variables are empty. Quoted operands keep their inner whitespace.
+/// `==` and `!=` compare text, the ordering operators compare numbers. A bare
+/// value is true unless it is empty, `0` or `false`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Condition {
+    lhs: String,
+    comparison: Option<(Op, String)>,
+}
+
+impl Condition {
+    pub fn parse(expr: &str) -> Result<Self, ConditionError> {
+        if expr.trim().is_empty() {
+            return Err(ConditionError::Empty);
+        }
+        let (lhs, comparison) = split(expr)?;
+        let lhs = lhs.trim();
+        let Some((op, rhs)) = comparison else {
+            return Ok(Condition {
+                lhs: lhs.to_string(),
+                comparison: None,
+            });
+        };
+
+        let rhs = rhs.trim();
+        if lhs.is_empty() || rhs.is_empty() {
+            return Err(ConditionError::MissingOperand(expr.to_string()));
+        }
+        if split(rhs)?.1.is_some() {
+            return Err(ConditionError::Chained(expr.to_string()));
+        }
+        Ok(Condition {
+            lhs: lhs.to_string(),
+            comparison: Some((op, rhs.to_string())),
+        })
+    }
+
+    pub fn evaluate(&self, vars: &HashMap<String, String>) -> Result<bool, ConditionError> {
+        let lhs = resolve(&self.lhs, vars);
+        let Some((op, rhs)) = &self.comparison else {
+            return Ok(!matches!(
+                lhs.to_ascii_lowercase().as_str(),
+                "" | "0" | "false"
+            ));
+        };
+        let rhs = resolve(rhs, vars);
+
+        match op {
+            Op::Eq => Ok(lhs == rhs),
+            Op::Ne => Ok(lhs != rhs),
+            Op::Le | Op::Ge | Op::Lt | Op::Gt => {
+                let (Ok(l), Ok(r)) = (lhs.parse::<f64>(), rhs.parse::<f64>()) else {
+                    return Err(ConditionError::NotNumeric(lhs, rhs));
+                };
+                Ok(match op {
+                    Op::Le => l <= r,
+                    Op::Ge => l >= r,
+                    Op::Lt => l < r,
+                    _ => l > r,
+                })
+            }
+        }
+    }
+}
+
+/// An expression's left operand, and the operator and rest if it compares
+type Split<'a> = (&'a str, Option<(Op, &'a str)>);
+
+/// Split an expression at its first operator outside quotes
+fn split(expr: &str) -> Result<Split<'_>, ConditionError> {
+    let mut quote = None;
+    for (i, c) in expr.char_indices() {
+        if let Some(q) = quote {
+            if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        if c == '\'' || c == '"' {
+            quote = Some(c);
+            continue;
+        }
+        let rest = &expr[i..];
+        if let Some((symbol, op)) = OPERATORS.iter().find(|(s, _)| rest.starts_with(s)) {
+            return Ok((&expr[..i], Some((*op, &rest[symbol.len()..]))));
+        }
+        if c == '=' {
+            return Err(ConditionError::SingleEquals(expr.to_string()));
+        }
+    }
+    if quote.is_some() {
+        return Err(ConditionError::UnclosedQuote(expr.to_string()));
+    }
+    Ok((expr, None))
+}
+
+fn resolve(operand: &str, vars: &HashMap<String, String>) -> String {
+    let quoted = operand.len() >= 2
+        && ["'", "\""]
+            .iter()
+            .any(|q| operand.starts_with(q) && operand.ends_with(q));
+    if quoted {
+        interpolate_or_empty(&operand[1..operand.len() - 1], vars)
+    } else {
+        interpolate_or_empty(operand, vars).trim().to_string()
+    }
+}
+
+#[cfg(test)]
+#[path = "condition_tests.rs"]
+mod tests;
@@ -0,0 +1,72 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+
+fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+fn eval(expr: &str, pairs: &[(&str, &str)]) -> Result<bool, ConditionError> {
+    Condition::parse(expr).unwrap().evaluate(&vars(pairs))
+}
+
+#[test]
+fn equality_compares_interpolated_text() {
+    assert_eq!(eval("{kind} == 'bug'", &[("kind", "bug")]), Ok(true));
+    assert_eq!(eval("{kind} == bug", &[("kind", " bug\n")]), Ok(true));
+    assert_eq!(eval("{kind} != \"bug\"", &[("kind", "feature")]), Ok(true));
+    assert_eq!(eval("'{kind}' == 'a b'", &[("kind", "a b")]), Ok(true));
+    assert_eq!(eval("'{kind}' == '=='", &[("kind", "==")]), Ok(true));
+}
+
+#[test]
+fn ordering_compares_numbers() {
+    assert_eq!(eval("{count} > 3", &[("count", "10")]), Ok(true));
+    assert_eq!(eval("{count} <= 3", &[("count", "3")]), Ok(true));
+    assert_eq!(eval("{count} < 2.5", &[("count", "3")]), Ok(false));
+    assert_eq!(
+        eval("{count} >= 1", &[("count", "many")]),
+        Err(ConditionError::NotNumeric(
+            "many".to_string(),
+            "1".to_string()
+        ))
+    );
+}
+
+#[test]
+fn bare_value_is_tested_for_truth() {
+    assert_eq!(eval("{flaky}", &[("flaky", "yes")]), Ok(true));
+    assert_eq!(eval("{flaky}", &[("flaky", "false")]), Ok(false));
+    assert_eq!(eval("{flaky}", &[("flaky", "0")]), Ok(false));
+    assert_eq!(eval("{flaky}", &[]), Ok(false));
+}
+
+#[test]
+fn unknown_variables_are_empty() {
+    assert_eq!(eval("{missing} == ''", &[]), Ok(true));
+}
+
+#[test]
+fn malformed_conditions_are_rejected() {
+    assert_eq!(Condition::parse("  "), Err(ConditionError::Empty));
+    assert!(matches!(
+        Condition::parse("{kind} = bug"),
+        Err(ConditionError::SingleEquals(_))
+    ));
+    assert!(matches!(
+        Condition::parse("{kind} == "),
+        Err(ConditionError::MissingOperand(_))
+    ));
+    assert!(matches!(
+        Condition::parse("1 < {n} < 3"),
+        Err(ConditionError::Chained(_))
+    ));
+    assert!(matches!(
+        Condition::parse("{kind} == 'bug"),
+        Err(ConditionError::UnclosedQuote(_))
+    ));
+}
@@ -0,0 +1,197 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Cron definitions and 5-field cron expressions
+
+use crate::RunDirective;
+use oj_core::clock::civil_from_days;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Error returned for malformed cron expressions
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("invalid cron expression {expr:?}: {reason}")]
+pub struct CronExprError {
+    pub expr: String,
+    pub reason: String,
+}
+
+/// A 5-field cron expression (`minute hour day-of-month month day-of-week`), in UTC
+///
+/// Fields accept `*`, numbers, ranges (`1-5`), steps (`*/15`, `0-30/10`) and
+/// comma-separated lists. Day-of-week is 0-7 with both 0 and 7 meaning Sunday.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronExpr {
+    source: String,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// Whether day-of-month and day-of-week were both restricted, in which
+    /// case a day matching either one fires
+    either_day: bool,
+}
+
+impl CronExpr {
+    /// Parse a cron expression
+    pub fn parse(expr: &str) -> Result<Self, CronExprError> {
+        let error = |reason: String| CronExprError {
+            expr: expr.to_string(),
+            reason,
+        };
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(error(format!("expected 5 fields, got {}", fields.len())));
+        };
+
+        let mut weekdays = parse_field(weekday, 0, 7).map_err(&error)?;
+        // 7 is an alias for Sunday
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays | 1) & !(1 << 7);
+        }
+        Ok(Self {
+            source: expr.to_string(),
+            minutes: parse_field(minute, 0, 59).map_err(&error)?,
+            hours: parse_field(hour, 0, 23).map_err(&error)?,
+            days: parse_field(day, 1, 31).map_err(&error)?,
+            months: parse_field(month, 1, 12).map_err(&error)?,
+            weekdays,
+            either_day: day != "*" && weekday != "*",
+        })
+    }
+
+    /// The expression as written in the runbook
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// The first matching minute strictly after `unix_secs`, as Unix seconds
+    ///
+    /// Returns `None` if nothing matches within the next few years, e.g. for
+    /// `0 0 31 2 *`.
+    pub fn next_after(&self, unix_secs: u64) -> Option<u64> {
+        const SEARCH_DAYS: u64 = 5 * 366;
+        let start = unix_secs / 60 + 1;
+        let first_day = start / (24 * 60);
+
+        for day in first_day..first_day + SEARCH_DAYS {
+            if !self.matches_day(day) {
+                continue;
+            }
+            let from = if day == first_day {
+                start % (24 * 60)
+            } else {
+                0
+            };
+            for minute_of_day in from..24 * 60 {
+                let (hour, minute) = (minute_of_day / 60, minute_of_day % 60);
+                if self.hours & (1 << hour) != 0 && self.minutes & (1 << minute) != 0 {
+                    return Some((day * 24 * 60 + minute_of_day) * 60);
+                }
+            }
+        }
+        None
+    }
+
+    fn matches_day(&self, days_since_epoch: u64) -> bool {
+        let (_, month, day) = civil_from_days(days_since_epoch);
+        if self.months & (1 << month) == 0 {
+            return false;
+        }
+        // 1970-01-01 was a Thursday
+        let weekday = (days_since_epoch + 4) % 7;
+        let day_match = self.days & (1 << day) != 0;
+        let weekday_match = self.weekdays & (1 << weekday) != 0;
+        if self.either_day {
+            day_match || weekday_match
+        } else {
+            day_match && weekday_match
+        }
+    }
+}
+
+/// Parse one field into a bitmask of allowed values
+fn parse_field(field: &str, min: u64, max: u64) -> Result<u64, String> {
+    let mut mask = 0;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u64 = step
+                    .parse()
+                    .ok()
+                    .filter(|s| *s > 0)
+                    .ok_or_else(|| format!("invalid step in {:?}", part))?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (low, high) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((low, high)) => (parse_value(low, min, max)?, parse_value(high, min, max)?),
+                None => {
+                    let value = parse_value(range, min, max)?;
+                    (value, if step > 1 { max } else { value })
+                }
+            },
+        };
+        if low > high {
+            return Err(format!("empty range {:?}", part));
+        }
+        for value in (low..=high).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+fn parse_value(value: &str, min: u64, max: u64) -> Result<u64, String> {
+    value
+        .parse()
+        .ok()
+        .filter(|v| (min..=max).contains(v))
+        .ok_or_else(|| format!("{:?} is not in {}-{}", value, min, max))
+}
+
+/// When a cron fires
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CronSchedule {
+    /// Every `interval = "30s"`
+    Interval(Duration),
+    /// On a `schedule = "*/5 * * * *"` cron expression
+    Expression(CronExpr),
+}
+
+impl std::fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CronSchedule::Interval(interval) => {
+                let secs = interval.as_secs();
+                match secs {
+                    s if s > 0 && s % 3600 == 0 => write!(f, "every {}h", s / 3600),
+                    s if s > 0 && s % 60 == 0 => write!(f, "every {}m", s / 60),
+                    s => write!(f, "every {}s", s),
+                }
+            }
+            CronSchedule::Expression(expr) => f.write_str(expr.as_str()),
+        }
+    }
+}
+
+/// A cron definition from the runbook
+#[derive(Debug, Clone, PartialEq)]
+pub struct CronDef {
+    /// Cron name
+    pub name: String,
+    /// When the cron fires
+    pub schedule: CronSchedule,
+    /// Monitors checked on each tick
+    pub monitors: Vec<String>,
+    /// Shell command or pipeline run on each tick
+    pub run: Option<RunDirective>,
+}
+
+#[cfg(test)]
+#[path = "cron_tests.rs"]
+mod tests;
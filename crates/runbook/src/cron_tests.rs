@@ -0,0 +1,88 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+
+/// 2026-01-01 00:00:00 UTC, a Thursday
+const NEW_YEAR_2026: u64 = 1_767_225_600;
+
+fn next(expr: &str, after: u64) -> u64 {
+    CronExpr::parse(expr).unwrap().next_after(after).unwrap()
+}
+
+#[test]
+fn every_minute_fires_on_next_minute_boundary() {
+    assert_eq!(next("* * * * *", NEW_YEAR_2026), NEW_YEAR_2026 + 60);
+    assert_eq!(next("* * * * *", NEW_YEAR_2026 + 59), NEW_YEAR_2026 + 60);
+}
+
+#[test]
+fn steps_and_ranges() {
+    assert_eq!(
+        next("*/15 * * * *", NEW_YEAR_2026 + 60),
+        NEW_YEAR_2026 + 15 * 60
+    );
+    assert_eq!(
+        next("30 9-17 * * *", NEW_YEAR_2026),
+        NEW_YEAR_2026 + 9 * 3600 + 30 * 60
+    );
+    assert_eq!(
+        next("0 0,12 * * *", NEW_YEAR_2026),
+        NEW_YEAR_2026 + 12 * 3600
+    );
+}
+
+#[test]
+fn weekday_and_month_restrictions() {
+    // Next Monday is 2026-01-05
+    assert_eq!(
+        next("0 9 * * 1", NEW_YEAR_2026),
+        NEW_YEAR_2026 + 4 * 86_400 + 9 * 3600
+    );
+    // Sunday can be written as 7
+    assert_eq!(next("0 0 * * 7", NEW_YEAR_2026), NEW_YEAR_2026 + 3 * 86_400);
+    // March 1st
+    assert_eq!(
+        next("0 0 1 3 *", NEW_YEAR_2026),
+        NEW_YEAR_2026 + 59 * 86_400
+    );
+}
+
+#[test]
+fn restricted_day_and_weekday_match_either() {
+    // The 15th, or any Friday: Friday 2026-01-02 comes first
+    assert_eq!(next("0 0 15 * 5", NEW_YEAR_2026), NEW_YEAR_2026 + 86_400);
+}
+
+#[test]
+fn impossible_date_never_fires() {
+    let expr = CronExpr::parse("0 0 31 2 *").unwrap();
+    assert_eq!(expr.next_after(NEW_YEAR_2026), None);
+}
+
+#[test]
+fn invalid_expressions() {
+    for expr in [
+        "* * * *",
+        "60 * * * *",
+        "*/0 * * * *",
+        "5-1 * * * *",
+        "a * * * *",
+    ] {
+        assert!(
+            CronExpr::parse(expr).is_err(),
+            "{} should be rejected",
+            expr
+        );
+    }
+}
+
+#[test]
+fn schedule_display() {
+    let every = |secs| CronSchedule::Interval(Duration::from_secs(secs)).to_string();
+    assert_eq!(every(30), "every 30s");
+    assert_eq!(every(300), "every 5m");
+    assert_eq!(every(7200), "every 2h");
+    let expr = CronSchedule::Expression(CronExpr::parse("0 3 * * *").unwrap());
+    assert_eq!(expr.to_string(), "0 3 * * *");
+}
@@ -0,0 +1,65 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Human-readable duration parsing ("30s", "5m", "1h30m", "7d")
+
+use std::time::Duration;
+use thiserror::Error;
+
+/// Error returned for malformed duration strings
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("invalid duration: {0:?}")]
+pub struct DurationError(pub String);
+
+/// Parse a duration string like `"30s"`, `"5m"`, `"2h"`, `"7d"` or `"1h30m"`
+///
+/// A bare number is interpreted as seconds.
+pub fn parse_duration(input: &str) -> Result<Duration, DurationError> {
+    let invalid = || DurationError(input.to_string());
+    let s = input.trim();
+    if s.is_empty() {
+        return Err(invalid());
+    }
+
+    if let Ok(secs) = s.parse::<u64>() {
+        return Ok(Duration::from_secs(secs));
+    }
+
+    let mut total = Duration::ZERO;
+    let mut rest = s;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if digits == 0 {
+            return Err(invalid());
+        }
+        let value: u64 = rest[..digits].parse().map_err(|_| invalid())?;
+        rest = &rest[digits..];
+
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let unit = &rest[..unit_len];
+        rest = &rest[unit_len..];
+
+        let part = match unit {
+            "ms" => Some(Duration::from_millis(value)),
+            "s" => Some(Duration::from_secs(value)),
+            "m" => value.checked_mul(60).map(Duration::from_secs),
+            "h" => value.checked_mul(60 * 60).map(Duration::from_secs),
+            "d" => value.checked_mul(60 * 60 * 24).map(Duration::from_secs),
+            _ => return Err(invalid()),
+        };
+        // Values too large to represent are as invalid as malformed ones
+        total = part
+            .and_then(|part| total.checked_add(part))
+            .ok_or_else(invalid)?;
+    }
+
+    Ok(total)
+}
+
+#[cfg(test)]
+#[path = "duration_tests.rs"]
+mod tests;
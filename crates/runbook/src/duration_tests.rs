@@ -0,0 +1,43 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+
+#[test]
+fn parses_single_units() {
+    assert_eq!(parse_duration("250ms").unwrap(), Duration::from_millis(250));
+    assert_eq!(parse_duration("30s").unwrap(), Duration::from_secs(30));
+    assert_eq!(parse_duration("5m").unwrap(), Duration::from_secs(300));
+    assert_eq!(parse_duration("2h").unwrap(), Duration::from_secs(7200));
+    assert_eq!(parse_duration("7d").unwrap(), Duration::from_secs(604_800));
+}
+
+#[test]
+fn parses_bare_seconds() {
+    assert_eq!(parse_duration("45").unwrap(), Duration::from_secs(45));
+}
+
+#[test]
+fn parses_compound_durations() {
+    assert_eq!(parse_duration("1h30m").unwrap(), Duration::from_secs(5400));
+    assert_eq!(parse_duration("1m30s").unwrap(), Duration::from_secs(90));
+}
+
+#[test]
+fn rejects_invalid_durations() {
+    for input in ["", "m", "10x", "1.5h", "-5s"] {
+        assert!(parse_duration(input).is_err(), "{:?} should fail", input);
+    }
+}
+
+#[test]
+fn rejects_overflowing_durations() {
+    let max = u64::MAX;
+    for input in [
+        format!("{}d", max),
+        format!("{}h", max / 60),
+        format!("{}s{}s", max, max),
+    ] {
+        assert!(parse_duration(&input).is_err(), "{:?} should fail", input);
+    }
+}
@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Guard definitions
+
+use std::time::Duration;
+
+/// How often a failing guard is re-checked when no retry interval is given
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// A guard definition from the runbook
+///
+/// Guards are shell conditions (exit 0 = pass) checked before (`pre`) or
+/// after (`post`) a phase.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuardDef {
+    /// Guard name
+    pub name: String,
+    /// Shell command, interpolated with pipeline variables
+    pub condition: String,
+    /// How long the guard may stay unsatisfied before `on_timeout` applies
+    pub timeout: Option<Duration>,
+    /// Bounded re-checks for conditions that may fail transiently
+    pub retry: Option<RetryConfig>,
+    /// What to do when the timeout elapses
+    pub on_timeout: GuardAction,
+    /// Event names (interpolated with pipeline variables) that trigger a re-check
+    pub wake_on: Vec<String>,
+}
+
+/// Re-check policy for a failing guard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryConfig {
+    /// Re-checks after the first failure
+    pub max: u32,
+    /// Delay between checks
+    pub interval: Duration,
+}
+
+/// Action taken when a guard times out
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GuardAction {
+    /// Keep waiting
+    Block,
+    /// Fail the phase
+    #[default]
+    Fail,
+    /// Alert a human and wait for them
+    Escalate,
+}
+
+impl GuardAction {
+    /// Parse an `on_timeout` value
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "block" => Some(GuardAction::Block),
+            "fail" => Some(GuardAction::Fail),
+            "escalate" => Some(GuardAction::Escalate),
+            _ => None,
+        }
+    }
+}
+
+impl GuardDef {
+    /// Create a guard with no timeout or retries
+    pub fn new(name: &str, condition: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            condition: condition.to_string(),
+            timeout: None,
+            retry: None,
+            on_timeout: GuardAction::default(),
+            wake_on: Vec::new(),
+        }
+    }
+
+    /// Delay before a failing guard is checked again
+    pub fn poll_interval(&self) -> Duration {
+        self.retry
+            .map(|r| r.interval)
+            .unwrap_or(DEFAULT_POLL_INTERVAL)
+    }
+}
@@ -8,19 +8,41 @@
 
 //! Runbook parsing and definition
 
+mod action;
 mod agent;
 mod command;
+mod condition;
+mod cron;
+mod duration;
+mod guard;
+mod lock;
+mod monitor;
 mod parser;
 mod pipeline;
+mod queue;
+mod rule;
+mod semaphore;
+mod strategy;
 mod template;
 mod worker;
 
+pub use action::ActionDef;
 pub use agent::{ActionConfig, AgentAction, AgentDef, ErrorActionConfig, ErrorMatch, ErrorType};
 pub use command::{
     parse_arg_spec, ArgDef, ArgSpec, ArgSpecError, ArgValidationError, CommandDef, FlagDef,
     OptionDef, RunDirective, VariadicDef,
 };
+pub use condition::{Condition, ConditionError};
+pub use cron::{CronDef, CronExpr, CronExprError, CronSchedule};
+pub use duration::{parse_duration, DurationError};
+pub use guard::{GuardAction, GuardDef, RetryConfig};
+pub use lock::LockDef;
+pub use monitor::{MonitorDef, MonitorResponse, ResponseStep};
 pub use parser::{parse_runbook, ParseError, Runbook};
-pub use pipeline::{PhaseDef, PipelineDef};
-pub use template::interpolate;
-pub use worker::WorkerDef;
+pub use pipeline::{OutputSource, PhaseDef, PhaseRoute, PipelineDef, PipelineEvents};
+pub use queue::{QueueDef, QueueExhaust};
+pub use rule::{EventRule, RuleAction};
+pub use semaphore::SemaphoreDef;
+pub use strategy::{AttemptDef, ExhaustAction, StrategyDef};
+pub use template::{interpolate, interpolate_shell};
+pub use worker::{IdleAction, IdleActionError, WorkerDef};
@@ -0,0 +1,37 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Lock definitions
+
+use oj_core::LockConfig;
+use std::time::Duration;
+
+/// A lock definition from the runbook
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockDef {
+    /// Lock name
+    pub name: String,
+    /// How long the holder may go without a heartbeat before the lock is stale
+    pub timeout: Duration,
+    /// How often the holder refreshes its heartbeat
+    pub heartbeat: Duration,
+}
+
+impl LockDef {
+    /// Create a lock definition with default timings
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            timeout: Duration::from_secs(30 * 60),
+            heartbeat: Duration::from_secs(30),
+        }
+    }
+
+    /// Build the core lock configuration
+    pub fn config(&self) -> LockConfig {
+        LockConfig {
+            heartbeat_timeout: self.timeout,
+            max_hold_duration: None,
+        }
+    }
+}
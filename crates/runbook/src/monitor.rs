@@ -0,0 +1,98 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Monitor definitions
+
+use crate::AgentAction;
+use serde::{Deserialize, Serialize};
+
+/// A monitor definition from the runbook
+///
+/// Each check runs `source` for a JSON array of items, keeps those whose
+/// `condition` passes, and responds to each of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MonitorDef {
+    /// Monitor name
+    pub name: String,
+    /// Shell command printing a JSON array of items
+    pub source: String,
+    /// Shell command interpolated with item fields (exit 0 = respond);
+    /// without one every item gets a response
+    pub condition: Option<String>,
+    /// What to do for each matching item
+    pub response: MonitorResponse,
+}
+
+/// How a monitor responds to a matching item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MonitorResponse {
+    /// `run = "..."`: a shell command interpolated with item fields, run every time
+    Run(String),
+    /// `response = [...]`: actions taken in turn on successive checks while
+    /// the item keeps matching
+    Chain(Vec<ResponseStep>),
+}
+
+/// One action in a response chain, written `"restart:2"` or just `"nudge"`
+///
+/// Agent `on_idle`/`on_exit`/`on_error` chains use the same notation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ResponseStep {
+    /// Action name
+    pub action: String,
+    /// How many times the action is taken before moving to the next step
+    pub attempts: u32,
+}
+
+impl ResponseStep {
+    /// Parse a chain entry such as `"restart:2"`
+    pub fn parse(s: &str) -> Option<Self> {
+        let (action, attempts) = match s.split_once(':') {
+            Some((action, count)) => (action, count.parse().ok().filter(|n| *n > 0)?),
+            None => (s, 1),
+        };
+        if action.is_empty() {
+            return None;
+        }
+        Some(Self {
+            action: action.to_string(),
+            attempts,
+        })
+    }
+
+    /// The `[action.*]` definition the step names, unless it is a built-in agent action
+    pub fn named_action(&self) -> Option<&str> {
+        match AgentAction::parse(&self.action) {
+            Some(_) => None,
+            None => Some(&self.action),
+        }
+    }
+}
+
+impl std::fmt::Display for ResponseStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.attempts {
+            1 => f.write_str(&self.action),
+            n => write!(f, "{}:{}", self.action, n),
+        }
+    }
+}
+
+impl TryFrom<String> for ResponseStep {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::parse(&s).ok_or_else(|| format!("expected action or action:count, got {:?}", s))
+    }
+}
+
+impl From<ResponseStep> for String {
+    fn from(step: ResponseStep) -> Self {
+        step.to_string()
+    }
+}
+
+#[cfg(test)]
+#[path = "monitor_tests.rs"]
+mod tests;
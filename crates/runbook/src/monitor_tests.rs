@@ -0,0 +1,33 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+
+#[test]
+fn parse_response_steps() {
+    assert_eq!(
+        ResponseStep::parse("nudge"),
+        Some(ResponseStep {
+            action: "nudge".to_string(),
+            attempts: 1,
+        })
+    );
+    assert_eq!(
+        ResponseStep::parse("restart:2"),
+        Some(ResponseStep {
+            action: "restart".to_string(),
+            attempts: 2,
+        })
+    );
+    for invalid in ["", ":2", "restart:", "restart:0", "restart:x"] {
+        assert_eq!(ResponseStep::parse(invalid), None, "{:?}", invalid);
+    }
+}
+
+#[test]
+fn response_step_round_trips_through_text() {
+    for text in ["nudge", "restart:2"] {
+        assert_eq!(ResponseStep::parse(text).unwrap().to_string(), text);
+    }
+    assert_eq!(ResponseStep::parse("nudge:1").unwrap().to_string(), "nudge");
+}
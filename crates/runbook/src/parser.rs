@@ -4,11 +4,22 @@
 //! Runbook TOML parsing
 
 use crate::{
-    AgentDef, ArgSpec, ArgSpecError, CommandDef, PhaseDef, PipelineDef, RunDirective, WorkerDef,
+    parse_duration, ActionDef, AgentDef, ArgSpec, ArgSpecError, CommandDef, CronDef, EventRule,
+    GuardAction, GuardDef, LockDef, MonitorDef, PipelineDef, QueueDef, RetryConfig, RunDirective,
+    SemaphoreDef, StrategyDef, WorkerDef,
 };
 use std::collections::HashMap;
+use std::time::Duration;
 use thiserror::Error;
 
+mod actions;
+mod pipelines;
+mod workers;
+
+use actions::{check_action_references, parse_action, parse_monitor, parse_rule};
+use pipelines::{check_phase_slots, parse_pipeline, parse_strategy};
+use workers::{parse_cron, parse_queue, parse_worker};
+
 /// Errors that can occur during runbook parsing
 #[derive(Debug, Error)]
 pub enum ParseError {
@@ -29,6 +40,16 @@ pub struct Runbook {
     pub workers: HashMap<String, WorkerDef>,
     pub pipelines: HashMap<String, PipelineDef>,
     pub agents: HashMap<String, AgentDef>,
+    pub locks: HashMap<String, LockDef>,
+    pub semaphores: HashMap<String, SemaphoreDef>,
+    pub guards: HashMap<String, GuardDef>,
+    pub strategies: HashMap<String, StrategyDef>,
+    pub queues: HashMap<String, QueueDef>,
+    pub crons: HashMap<String, CronDef>,
+    pub monitors: HashMap<String, MonitorDef>,
+    pub actions: HashMap<String, ActionDef>,
+    /// `[[on]]` event rules, in runbook order
+    pub rules: Vec<EventRule>,
 }
 
 impl Runbook {
@@ -51,6 +72,46 @@ impl Runbook {
     pub fn get_worker(&self, name: &str) -> Option<&WorkerDef> {
         self.workers.get(name)
     }
+
+    /// Get a lock definition by name
+    pub fn get_lock(&self, name: &str) -> Option<&LockDef> {
+        self.locks.get(name)
+    }
+
+    /// Get a semaphore definition by name
+    pub fn get_semaphore(&self, name: &str) -> Option<&SemaphoreDef> {
+        self.semaphores.get(name)
+    }
+
+    /// Get a guard definition by name
+    pub fn get_guard(&self, name: &str) -> Option<&GuardDef> {
+        self.guards.get(name)
+    }
+
+    /// Get a strategy definition by name
+    pub fn get_strategy(&self, name: &str) -> Option<&StrategyDef> {
+        self.strategies.get(name)
+    }
+
+    /// Get a queue definition by name
+    pub fn get_queue(&self, name: &str) -> Option<&QueueDef> {
+        self.queues.get(name)
+    }
+
+    /// Get a cron definition by name
+    pub fn get_cron(&self, name: &str) -> Option<&CronDef> {
+        self.crons.get(name)
+    }
+
+    /// Get a monitor definition by name
+    pub fn get_monitor(&self, name: &str) -> Option<&MonitorDef> {
+        self.monitors.get(name)
+    }
+
+    /// Get a named action definition by name
+    pub fn get_action(&self, name: &str) -> Option<&ActionDef> {
+        self.actions.get(name)
+    }
 }
 
 /// Parse a runbook from TOML content
@@ -94,6 +155,82 @@ pub fn parse_runbook(content: &str) -> Result<Runbook, ParseError> {
         }
     }
 
+    // Parse locks
+    if let Some(locks) = table.get("lock").and_then(|v| v.as_table()) {
+        for (name, value) in locks {
+            let lock = parse_lock(name, value)?;
+            runbook.locks.insert(name.clone(), lock);
+        }
+    }
+
+    // Parse semaphores
+    if let Some(semaphores) = table.get("semaphore").and_then(|v| v.as_table()) {
+        for (name, value) in semaphores {
+            let semaphore = parse_semaphore(name, value)?;
+            runbook.semaphores.insert(name.clone(), semaphore);
+        }
+    }
+
+    // Parse guards
+    if let Some(guards) = table.get("guard").and_then(|v| v.as_table()) {
+        for (name, value) in guards {
+            let guard = parse_guard(name, value)?;
+            runbook.guards.insert(name.clone(), guard);
+        }
+    }
+
+    // Parse strategies
+    if let Some(strategies) = table.get("strategy").and_then(|v| v.as_table()) {
+        for (name, value) in strategies {
+            let strategy = parse_strategy(name, value)?;
+            runbook.strategies.insert(name.clone(), strategy);
+        }
+    }
+
+    // Parse queues
+    if let Some(queues) = table.get("queue").and_then(|v| v.as_table()) {
+        for (name, value) in queues {
+            let queue = parse_queue(name, value)?;
+            runbook.queues.insert(name.clone(), queue);
+        }
+    }
+
+    // Parse crons
+    if let Some(crons) = table.get("cron").and_then(|v| v.as_table()) {
+        for (name, value) in crons {
+            let cron = parse_cron(name, value)?;
+            runbook.crons.insert(name.clone(), cron);
+        }
+    }
+
+    // Parse monitors
+    if let Some(monitors) = table.get("monitor").and_then(|v| v.as_table()) {
+        for (name, value) in monitors {
+            let monitor = parse_monitor(name, value)?;
+            runbook.monitors.insert(name.clone(), monitor);
+        }
+    }
+
+    // Parse actions
+    if let Some(actions) = table.get("action").and_then(|v| v.as_table()) {
+        for (name, value) in actions {
+            let action = parse_action(name, value)?;
+            runbook.actions.insert(name.clone(), action);
+        }
+    }
+
+    // Parse event rules
+    if let Some(rules) = table.get("on") {
+        let rules = rules.as_array().ok_or_else(|| {
+            ParseError::InvalidFormat("on must be an array of tables ([[on]])".to_string())
+        })?;
+        for (index, value) in rules.iter().enumerate() {
+            runbook.rules.push(parse_rule(index, value)?);
+        }
+    }
+
+    check_action_references(&runbook)?;
+    check_phase_slots(&runbook)?;
     Ok(runbook)
 }
 
@@ -139,127 +276,178 @@ fn parse_command(name: &str, value: &toml::Value) -> Result<CommandDef, ParseErr
     })
 }
 
-fn parse_worker(name: &str, value: &toml::Value) -> Result<WorkerDef, ParseError> {
+fn parse_agent(name: &str, value: &toml::Value) -> Result<AgentDef, ParseError> {
+    // Deserialize using serde to get proper handling of on_idle/on_exit/on_error
+    let mut agent: AgentDef = value.clone().try_into().map_err(|e: toml::de::Error| {
+        ParseError::InvalidFormat(format!("agent.{}: {}", name, e))
+    })?;
+    agent.name = name.to_string();
+    Ok(agent)
+}
+
+fn parse_lock(name: &str, value: &toml::Value) -> Result<LockDef, ParseError> {
     let table = value
         .as_table()
-        .ok_or_else(|| ParseError::InvalidFormat(format!("worker.{} must be a table", name)))?;
-
-    let concurrency = table
-        .get("concurrency")
-        .and_then(|v| v.as_integer())
-        .map(|v| v as u32)
-        .unwrap_or(1);
-
-    let pipelines = table
-        .get("pipelines")
-        .and_then(|v| v.as_array())
-        .map(|arr| {
-            arr.iter()
-                .filter_map(|v| v.as_str().map(String::from))
-                .collect()
-        })
-        .unwrap_or_default();
+        .ok_or_else(|| ParseError::InvalidFormat(format!("lock.{} must be a table", name)))?;
 
-    Ok(WorkerDef {
-        name: name.to_string(),
-        concurrency,
-        pipelines,
-    })
+    let mut lock = LockDef::new(name);
+    if let Some(timeout) = parse_duration_field(table, "timeout", "lock", name)? {
+        lock.timeout = timeout;
+    }
+    if let Some(heartbeat) = parse_duration_field(table, "heartbeat", "lock", name)? {
+        lock.heartbeat = heartbeat;
+    }
+    Ok(lock)
 }
 
-fn parse_pipeline(name: &str, value: &toml::Value) -> Result<PipelineDef, ParseError> {
+fn parse_semaphore(name: &str, value: &toml::Value) -> Result<SemaphoreDef, ParseError> {
     let table = value
         .as_table()
-        .ok_or_else(|| ParseError::InvalidFormat(format!("pipeline.{} must be a table", name)))?;
-
-    let inputs = table
-        .get("inputs")
-        .and_then(|v| v.as_array())
-        .map(|arr| {
-            arr.iter()
-                .filter_map(|v| v.as_str().map(String::from))
-                .collect()
-        })
-        .unwrap_or_default();
-
-    let defaults = table
-        .get("defaults")
-        .and_then(|v| v.as_table())
-        .map(|t| {
-            t.iter()
-                .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
-                .collect()
-        })
-        .unwrap_or_default();
-
-    // Support both "phase" (from [[pipeline.X.phase]]) and "phases" key names
-    let phases_arr = table
-        .get("phase")
-        .and_then(|v| v.as_array())
-        .or_else(|| table.get("phases").and_then(|v| v.as_array()));
-
-    let phases = if let Some(arr) = phases_arr {
-        arr.iter().filter_map(|v| parse_phase(v).ok()).collect()
-    } else {
-        Vec::new()
-    };
-
-    Ok(PipelineDef {
-        name: name.to_string(),
-        inputs,
-        defaults,
-        phases,
-    })
+        .ok_or_else(|| ParseError::InvalidFormat(format!("semaphore.{} must be a table", name)))?;
+
+    let mut semaphore = SemaphoreDef::new(name);
+    if let Some(max) = table.get("max") {
+        semaphore.max = max
+            .as_integer()
+            .filter(|n| *n > 0)
+            .and_then(|n| u32::try_from(n).ok())
+            .ok_or_else(|| {
+                ParseError::InvalidFormat(format!(
+                    "semaphore.{}.max: expected positive integer, got {}",
+                    name, max
+                ))
+            })?;
+    }
+    if let Some(timeout) = parse_duration_field(table, "slot_timeout", "semaphore", name)? {
+        semaphore.slot_timeout = timeout;
+    }
+    if let Some(heartbeat) = parse_duration_field(table, "slot_heartbeat", "semaphore", name)? {
+        semaphore.slot_heartbeat = heartbeat;
+    }
+    Ok(semaphore)
 }
 
-fn parse_phase(value: &toml::Value) -> Result<PhaseDef, ParseError> {
+fn parse_guard(name: &str, value: &toml::Value) -> Result<GuardDef, ParseError> {
     let table = value
         .as_table()
-        .ok_or_else(|| ParseError::InvalidFormat("phase must be a table".to_string()))?;
+        .ok_or_else(|| ParseError::InvalidFormat(format!("guard.{} must be a table", name)))?;
 
-    let name = table
-        .get("name")
+    let condition = table
+        .get("condition")
         .and_then(|v| v.as_str())
-        .ok_or_else(|| ParseError::MissingField("phase.name".to_string()))?
-        .to_string();
+        .ok_or_else(|| ParseError::MissingField(format!("guard.{}.condition", name)))?;
+
+    let mut guard = GuardDef::new(name, condition);
+    guard.timeout = parse_duration_field(table, "timeout", "guard", name)?;
+    guard.wake_on = parse_string_list(table, "wake_on", "guard", name)?;
+
+    if let Some(retry) = table.get("retry") {
+        let retry_table = retry.as_table().ok_or_else(|| {
+            ParseError::InvalidFormat(format!("guard.{}.retry must be a table", name))
+        })?;
+        let max = retry_table
+            .get("max")
+            .and_then(|v| v.as_integer())
+            .and_then(|n| u32::try_from(n).ok())
+            .ok_or_else(|| ParseError::MissingField(format!("guard.{}.retry.max", name)))?;
+        let interval = parse_duration_field(retry_table, "interval", "guard", name)?
+            .ok_or_else(|| ParseError::MissingField(format!("guard.{}.retry.interval", name)))?;
+        guard.retry = Some(RetryConfig { max, interval });
+    }
 
-    // Parse run directive - can be string or table
-    // Also support legacy "agent" field for backwards compatibility
-    let run = if let Some(run_value) = table.get("run") {
-        run_value
-            .clone()
-            .try_into()
-            .map_err(|e| ParseError::InvalidFormat(format!("phase.{}.run: {}", name, e)))?
-    } else if let Some(agent_name) = table.get("agent").and_then(|v| v.as_str()) {
-        // Legacy support: phase.agent = "name" => run = { agent = "name" }
-        RunDirective::Agent {
-            agent: agent_name.to_string(),
-        }
-    } else {
-        return Err(ParseError::MissingField(format!("phase.{}.run", name)));
-    };
+    if let Some(on_timeout) = table.get("on_timeout") {
+        guard.on_timeout = on_timeout
+            .as_str()
+            .and_then(GuardAction::parse)
+            .ok_or_else(|| {
+                ParseError::InvalidFormat(format!(
+                    "guard.{}.on_timeout: expected block, fail, or escalate, got {}",
+                    name, on_timeout
+                ))
+            })?;
+    }
 
-    let next = table.get("next").and_then(|v| v.as_str()).map(String::from);
-    let on_fail = table
-        .get("on_fail")
-        .and_then(|v| v.as_str())
-        .map(String::from);
+    Ok(guard)
+}
 
-    Ok(PhaseDef {
-        name,
-        run,
-        next,
-        on_fail,
-    })
+/// Parse an optional table of scalar values such as `where = { branch = "main" }`
+///
+/// Numbers and booleans are kept in their string form.
+fn parse_string_map(
+    table: &toml::map::Map<String, toml::Value>,
+    key: &str,
+    context: &str,
+) -> Result<Option<HashMap<String, String>>, ParseError> {
+    let Some(value) = table.get(key) else {
+        return Ok(None);
+    };
+    value
+        .as_table()
+        .and_then(|entries| {
+            entries
+                .iter()
+                .map(|(k, v)| {
+                    let v = match v {
+                        toml::Value::String(s) => s.clone(),
+                        toml::Value::Integer(n) => n.to_string(),
+                        toml::Value::Float(f) => f.to_string(),
+                        toml::Value::Boolean(b) => b.to_string(),
+                        _ => return None,
+                    };
+                    Some((k.clone(), v))
+                })
+                .collect::<Option<HashMap<_, _>>>()
+        })
+        .map(Some)
+        .ok_or_else(|| {
+            ParseError::InvalidFormat(format!("{}.{}: expected table of strings", context, key))
+        })
 }
 
-fn parse_agent(name: &str, value: &toml::Value) -> Result<AgentDef, ParseError> {
-    // Deserialize using serde to get proper handling of on_idle/on_exit/on_error
-    let mut agent: AgentDef = value.clone().try_into().map_err(|e: toml::de::Error| {
-        ParseError::InvalidFormat(format!("agent.{}: {}", name, e))
-    })?;
-    agent.name = name.to_string();
-    Ok(agent)
+/// Parse an optional list of strings such as `pre = ["plan_exists"]`
+fn parse_string_list(
+    table: &toml::map::Map<String, toml::Value>,
+    key: &str,
+    section: &str,
+    name: &str,
+) -> Result<Vec<String>, ParseError> {
+    let Some(value) = table.get(key) else {
+        return Ok(Vec::new());
+    };
+    value
+        .as_array()
+        .and_then(|items| {
+            items
+                .iter()
+                .map(|v| v.as_str().map(String::from))
+                .collect::<Option<Vec<_>>>()
+        })
+        .ok_or_else(|| {
+            ParseError::InvalidFormat(format!(
+                "{}.{}.{}: expected list of strings",
+                section, name, key
+            ))
+        })
+}
+
+/// Parse an optional duration field such as `timeout = "30m"`
+fn parse_duration_field(
+    table: &toml::map::Map<String, toml::Value>,
+    key: &str,
+    section: &str,
+    name: &str,
+) -> Result<Option<Duration>, ParseError> {
+    let Some(value) = table.get(key) else {
+        return Ok(None);
+    };
+    let duration = match value {
+        toml::Value::String(s) => parse_duration(s).map_err(|e| e.to_string()),
+        toml::Value::Integer(secs) if *secs >= 0 => Ok(Duration::from_secs(*secs as u64)),
+        _ => Err(format!("expected duration string, got {}", value)),
+    };
+    duration
+        .map(Some)
+        .map_err(|e| ParseError::InvalidFormat(format!("{}.{}.{}: {}", section, name, key, e)))
 }
 
 #[cfg(test)]
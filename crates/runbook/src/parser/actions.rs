@@ -0,0 +1,218 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Action, monitor and event rule sections
+
+use super::{parse_duration_field, parse_string_list, parse_string_map, ParseError, Runbook};
+use crate::{
+    ActionDef, ErrorActionConfig, EventRule, MonitorDef, MonitorResponse, ResponseStep, RuleAction,
+};
+
+/// Check that monitor responses and agent actions only name defined actions
+pub(super) fn check_action_references(runbook: &Runbook) -> Result<(), ParseError> {
+    let check = |context: String, names: Vec<&str>| match names
+        .into_iter()
+        .find(|name| !runbook.actions.contains_key(*name))
+    {
+        Some(name) => Err(ParseError::InvalidFormat(format!(
+            "{}: undefined action {:?}",
+            context, name
+        ))),
+        None => Ok(()),
+    };
+
+    for monitor in runbook.monitors.values() {
+        if let MonitorResponse::Chain(steps) = &monitor.response {
+            let names = steps
+                .iter()
+                .filter_map(ResponseStep::named_action)
+                .collect();
+            check(format!("monitor.{}.response", monitor.name), names)?;
+        }
+    }
+    for agent in runbook.agents.values() {
+        let mut hooks = vec![("on_idle", &agent.on_idle), ("on_exit", &agent.on_exit)];
+        if let ErrorActionConfig::Simple(config) = &agent.on_error {
+            hooks.push(("on_error", config));
+        }
+        for (hook, config) in hooks {
+            check(
+                format!("agent.{}.{}", agent.name, hook),
+                config.named_actions(),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+pub(super) fn parse_action(name: &str, value: &toml::Value) -> Result<ActionDef, ParseError> {
+    let table = value
+        .as_table()
+        .ok_or_else(|| ParseError::InvalidFormat(format!("action.{} must be a table", name)))?;
+
+    let run = table
+        .get("run")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| ParseError::MissingField(format!("action.{}.run", name)))?
+        .trim()
+        .to_string();
+    let max_attempts = match table.get("max_attempts") {
+        Some(value) => Some(
+            value
+                .as_integer()
+                .filter(|n| *n > 0)
+                .and_then(|n| u32::try_from(n).ok())
+                .ok_or_else(|| {
+                    ParseError::InvalidFormat(format!(
+                        "action.{}.max_attempts: expected positive integer, got {}",
+                        name, value
+                    ))
+                })?,
+        ),
+        None => None,
+    };
+
+    Ok(ActionDef {
+        name: name.to_string(),
+        run,
+        cooldown: parse_duration_field(table, "cooldown", "action", name)?,
+        max_attempts,
+    })
+}
+
+pub(super) fn parse_monitor(name: &str, value: &toml::Value) -> Result<MonitorDef, ParseError> {
+    let table = value
+        .as_table()
+        .ok_or_else(|| ParseError::InvalidFormat(format!("monitor.{} must be a table", name)))?;
+
+    let source = table
+        .get("source")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| ParseError::MissingField(format!("monitor.{}.source", name)))?
+        .to_string();
+    let condition = table
+        .get("condition")
+        .and_then(|v| v.as_str())
+        .map(|s| s.trim().to_string());
+
+    let run = table.get("run").and_then(|v| v.as_str());
+    let steps = parse_string_list(table, "response", "monitor", name)?;
+    let response = match (run, steps.is_empty()) {
+        (Some(run), true) => MonitorResponse::Run(run.to_string()),
+        (None, false) => MonitorResponse::Chain(
+            steps
+                .iter()
+                .map(|step| {
+                    ResponseStep::parse(step).ok_or_else(|| {
+                        ParseError::InvalidFormat(format!(
+                            "monitor.{}.response: expected action or action:count, got {:?}",
+                            name, step
+                        ))
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        ),
+        (Some(_), false) => {
+            return Err(ParseError::InvalidFormat(format!(
+                "monitor.{}: run and response are mutually exclusive",
+                name
+            )))
+        }
+        (None, true) => {
+            return Err(ParseError::MissingField(format!(
+                "monitor.{}.run or monitor.{}.response",
+                name, name
+            )))
+        }
+    };
+
+    Ok(MonitorDef {
+        name: name.to_string(),
+        source,
+        condition,
+        response,
+    })
+}
+
+pub(super) fn parse_rule(index: usize, value: &toml::Value) -> Result<EventRule, ParseError> {
+    let context = format!("on[{}]", index);
+    let table = value
+        .as_table()
+        .ok_or_else(|| ParseError::InvalidFormat(format!("{} must be a table", context)))?;
+
+    let event = table
+        .get("event")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| ParseError::MissingField(format!("{}.event", context)))?
+        .to_string();
+    let filters = parse_string_map(table, "where", &context)?.unwrap_or_default();
+    let args = parse_string_map(table, "args", &context)?;
+    let data = parse_string_map(table, "data", &context)?;
+
+    let mut actions = Vec::new();
+    for key in ["run", "command", "queue", "emit"] {
+        let Some(value) = table.get(key) else {
+            continue;
+        };
+        let value = value
+            .as_str()
+            .ok_or_else(|| {
+                ParseError::InvalidFormat(format!("{}.{}: expected string", context, key))
+            })?
+            .to_string();
+        actions.push(match key {
+            "run" => RuleAction::Run(value),
+            "command" => RuleAction::Command {
+                command: value,
+                args: args.clone(),
+            },
+            "queue" => RuleAction::Queue {
+                queue: value,
+                data: data.clone(),
+            },
+            _ => RuleAction::Emit {
+                event: value,
+                data: data.clone(),
+            },
+        });
+    }
+    let action = match actions.pop() {
+        Some(action) if actions.is_empty() => action,
+        Some(_) => {
+            return Err(ParseError::InvalidFormat(format!(
+                "{}: run, command, queue and emit are mutually exclusive",
+                context
+            )))
+        }
+        None => {
+            return Err(ParseError::MissingField(format!(
+                "{}.run, command, queue or emit",
+                context
+            )))
+        }
+    };
+
+    match &action {
+        RuleAction::Command { .. } if data.is_some() => Err(ParseError::InvalidFormat(format!(
+            "{}.data: only used with queue or emit",
+            context
+        ))),
+        RuleAction::Queue { .. } | RuleAction::Emit { .. } if args.is_some() => Err(
+            ParseError::InvalidFormat(format!("{}.args: only used with command", context)),
+        ),
+        RuleAction::Run(_) if args.is_some() || data.is_some() => Err(ParseError::InvalidFormat(
+            format!("{}: args and data are not used with run", context),
+        )),
+        RuleAction::Emit { event: emitted, .. } if *emitted == event => {
+            Err(ParseError::InvalidFormat(format!(
+                "{}.emit: re-emitting {:?} would trigger the rule again",
+                context, event
+            )))
+        }
+        _ => Ok(EventRule {
+            event,
+            filters,
+            action,
+        }),
+    }
+}
@@ -0,0 +1,353 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Pipeline, phase and strategy sections
+
+use super::{parse_duration_field, parse_string_list, parse_string_map, ParseError, Runbook};
+use crate::{
+    AttemptDef, Condition, ExhaustAction, OutputSource, PhaseDef, PhaseRoute, PipelineDef,
+    PipelineEvents, RunDirective, StrategyDef,
+};
+use std::collections::{BTreeMap, HashMap};
+
+/// Reject phases that ask for more slots than their semaphore has
+///
+/// Semaphores a runbook uses without declaring have a single slot.
+pub(super) fn check_phase_slots(runbook: &Runbook) -> Result<(), ParseError> {
+    for pipeline in runbook.pipelines.values() {
+        for phase in &pipeline.phases {
+            let Some(name) = &phase.semaphore else {
+                continue;
+            };
+            let max = runbook.get_semaphore(name).map_or(1, |s| s.max);
+            if phase.slots > max {
+                return Err(ParseError::InvalidFormat(format!(
+                    "phase.{}.slots: {} is more than semaphore {} holds ({})",
+                    phase.name, phase.slots, name, max
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub(super) fn parse_pipeline(name: &str, value: &toml::Value) -> Result<PipelineDef, ParseError> {
+    let table = value
+        .as_table()
+        .ok_or_else(|| ParseError::InvalidFormat(format!("pipeline.{} must be a table", name)))?;
+
+    let inputs = table
+        .get("inputs")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let defaults = table
+        .get("defaults")
+        .and_then(|v| v.as_table())
+        .map(|t| {
+            t.iter()
+                .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // Support both "phase" (from [[pipeline.X.phase]]) and "phases" key names
+    let phases_arr = table
+        .get("phase")
+        .and_then(|v| v.as_array())
+        .or_else(|| table.get("phases").and_then(|v| v.as_array()));
+
+    let phases = if let Some(arr) = phases_arr {
+        arr.iter().map(parse_phase).collect::<Result<_, _>>()?
+    } else {
+        Vec::new()
+    };
+
+    let events = match table.get("events") {
+        Some(value) => value
+            .clone()
+            .try_into()
+            .map_err(|e| ParseError::InvalidFormat(format!("pipeline.{}.events: {}", name, e)))?,
+        None => PipelineEvents::default(),
+    };
+
+    Ok(PipelineDef {
+        name: name.to_string(),
+        inputs,
+        defaults,
+        phases,
+        events,
+    })
+}
+
+pub(super) fn parse_phase(value: &toml::Value) -> Result<PhaseDef, ParseError> {
+    let table = value
+        .as_table()
+        .ok_or_else(|| ParseError::InvalidFormat("phase must be a table".to_string()))?;
+
+    let name = table
+        .get("name")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| ParseError::MissingField("phase.name".to_string()))?
+        .to_string();
+
+    // Parse run directive - can be string or table
+    // Also support legacy "agent" field for backwards compatibility
+    let run = if let Some(run_value) = table.get("run") {
+        run_value
+            .clone()
+            .try_into()
+            .map_err(|e| ParseError::InvalidFormat(format!("phase.{}.run: {}", name, e)))?
+    } else if let Some(agent_name) = table.get("agent").and_then(|v| v.as_str()) {
+        // Legacy support: phase.agent = "name" => run = { agent = "name" }
+        RunDirective::Agent {
+            agent: agent_name.to_string(),
+        }
+    } else {
+        return Err(ParseError::MissingField(format!("phase.{}.run", name)));
+    };
+
+    let (next, next_on_exit) = parse_phase_next(table, &name)?;
+    if !next_on_exit.is_empty() && !run.is_shell() {
+        return Err(ParseError::InvalidFormat(format!(
+            "phase.{}.next: only shell phases can route on exit codes",
+            name
+        )));
+    }
+    let when = parse_phase_when(table, &name)?;
+    let on_fail = table
+        .get("on_fail")
+        .and_then(|v| v.as_str())
+        .map(String::from);
+    let lock = table.get("lock").and_then(|v| v.as_str()).map(String::from);
+    let semaphore = table
+        .get("semaphore")
+        .and_then(|v| v.as_str())
+        .map(String::from);
+    let slots = match table.get("slots") {
+        Some(value) => value
+            .as_integer()
+            .filter(|n| *n > 0)
+            .and_then(|n| u32::try_from(n).ok())
+            .ok_or_else(|| {
+                ParseError::InvalidFormat(format!(
+                    "phase.{}.slots: expected positive integer, got {}",
+                    name, value
+                ))
+            })?,
+        None => 1,
+    };
+    if slots != 1 && semaphore.is_none() {
+        return Err(ParseError::InvalidFormat(format!(
+            "phase.{}.slots: only phases with a semaphore take slots",
+            name
+        )));
+    }
+    let pre = parse_string_list(table, "pre", "phase", &name)?;
+    let post = parse_string_list(table, "post", "phase", &name)?;
+    let timeout = parse_duration_field(table, "timeout", "phase", &name)?;
+    if timeout.is_some() && !run.is_shell() {
+        return Err(ParseError::InvalidFormat(format!(
+            "phase.{}.timeout: only shell phases can have a timeout",
+            name
+        )));
+    }
+    let outputs = parse_phase_outputs(table, &name)?;
+    if !outputs.is_empty() && !run.is_shell() {
+        return Err(ParseError::InvalidFormat(format!(
+            "phase.{}.outputs: only shell phases can have outputs",
+            name
+        )));
+    }
+
+    Ok(PhaseDef {
+        name,
+        run,
+        next,
+        next_on_exit,
+        when,
+        on_fail,
+        lock,
+        semaphore,
+        slots,
+        pre,
+        post,
+        timeout,
+        outputs,
+    })
+}
+
+/// Parse a phase's `next`, either a phase name or a table of exit codes
+///
+/// In the table form, `default` sets the phase for any other successful exit.
+pub(super) fn parse_phase_next(
+    table: &toml::map::Map<String, toml::Value>,
+    name: &str,
+) -> Result<(Option<String>, BTreeMap<i32, String>), ParseError> {
+    let mut routes = BTreeMap::new();
+    let routes_table = match table.get("next") {
+        None => return Ok((None, routes)),
+        Some(toml::Value::String(next)) => return Ok((Some(next.clone()), routes)),
+        Some(toml::Value::Table(routes_table)) => routes_table,
+        Some(_) => {
+            return Err(ParseError::InvalidFormat(format!(
+                "phase.{}.next must be a phase name or a table of exit codes",
+                name
+            )))
+        }
+    };
+
+    let mut default = None;
+    for (key, value) in routes_table {
+        let target = value.as_str().ok_or_else(|| {
+            ParseError::InvalidFormat(format!("phase.{}.next.{} must be a string", name, key))
+        })?;
+        if key == "default" {
+            default = Some(target.to_string());
+        } else if let Ok(code) = key.parse::<i32>() {
+            routes.insert(code, target.to_string());
+        } else {
+            return Err(ParseError::InvalidFormat(format!(
+                "phase.{}.next.{}: expected an exit code or \"default\"",
+                name, key
+            )));
+        }
+    }
+    Ok((default, routes))
+}
+
+/// Parse a phase's `when = [{ if = "...", next = "..." }]` routes
+pub(super) fn parse_phase_when(
+    table: &toml::map::Map<String, toml::Value>,
+    name: &str,
+) -> Result<Vec<PhaseRoute>, ParseError> {
+    let Some(value) = table.get("when") else {
+        return Ok(Vec::new());
+    };
+    let routes: Vec<PhaseRoute> = value
+        .clone()
+        .try_into()
+        .map_err(|e| ParseError::InvalidFormat(format!("phase.{}.when: {}", name, e)))?;
+    for (i, route) in routes.iter().enumerate() {
+        Condition::parse(&route.condition)
+            .map_err(|e| ParseError::InvalidFormat(format!("phase.{}.when[{}]: {}", name, i, e)))?;
+    }
+    Ok(routes)
+}
+
+/// Parse a phase's `outputs = { sha = "stdout" }` table
+pub(super) fn parse_phase_outputs(
+    table: &toml::map::Map<String, toml::Value>,
+    name: &str,
+) -> Result<HashMap<String, OutputSource>, ParseError> {
+    let context = format!("phase.{}", name);
+    let outputs = parse_string_map(table, "outputs", &context)?.unwrap_or_default();
+    outputs
+        .into_iter()
+        .map(|(key, source)| match OutputSource::parse(&source) {
+            Some(source) => Ok((key, source)),
+            None => Err(ParseError::InvalidFormat(format!(
+                "{}.outputs.{}: unknown source \"{}\", expected \"stdout\"",
+                context, key, source
+            ))),
+        })
+        .collect()
+}
+
+pub(super) fn parse_strategy(name: &str, value: &toml::Value) -> Result<StrategyDef, ParseError> {
+    let table = value
+        .as_table()
+        .ok_or_else(|| ParseError::InvalidFormat(format!("strategy.{} must be a table", name)))?;
+
+    let checkpoint = table
+        .get("checkpoint")
+        .and_then(|v| v.as_str())
+        .map(String::from);
+
+    let on_exhaust = match table.get("on_exhaust") {
+        Some(value) => value
+            .as_str()
+            .and_then(ExhaustAction::parse)
+            .ok_or_else(|| {
+                ParseError::InvalidFormat(format!(
+                    "strategy.{}.on_exhaust: expected fail or escalate, got {}",
+                    name, value
+                ))
+            })?,
+        None => ExhaustAction::default(),
+    };
+
+    let attempts = table
+        .get("attempt")
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| ParseError::MissingField(format!("strategy.{}.attempt", name)))?
+        .iter()
+        .enumerate()
+        .map(|(i, value)| parse_attempt(name, i, value))
+        .collect::<Result<Vec<_>, _>>()?;
+    if attempts.is_empty() {
+        return Err(ParseError::MissingField(format!(
+            "strategy.{}.attempt",
+            name
+        )));
+    }
+
+    Ok(StrategyDef {
+        name: name.to_string(),
+        checkpoint,
+        attempts,
+        on_exhaust,
+    })
+}
+
+pub(super) fn parse_attempt(
+    strategy: &str,
+    index: usize,
+    value: &toml::Value,
+) -> Result<AttemptDef, ParseError> {
+    let context = format!("strategy.{}.attempt[{}]", strategy, index);
+    let table = value
+        .as_table()
+        .ok_or_else(|| ParseError::InvalidFormat(format!("{} must be a table", context)))?;
+
+    let name = table
+        .get("name")
+        .and_then(|v| v.as_str())
+        .map(String::from)
+        .unwrap_or_else(|| format!("attempt-{}", index + 1));
+
+    let run: RunDirective = table
+        .get("run")
+        .ok_or_else(|| ParseError::MissingField(format!("{}.run", context)))?
+        .clone()
+        .try_into()
+        .map_err(|e| ParseError::InvalidFormat(format!("{}.run: {}", context, e)))?;
+    if !run.is_shell() && !run.is_agent() {
+        return Err(ParseError::InvalidFormat(format!(
+            "{}.run: must be a shell command or agent",
+            context
+        )));
+    }
+
+    let rollback = table
+        .get("rollback")
+        .and_then(|v| v.as_str())
+        .map(String::from);
+
+    Ok(AttemptDef {
+        timeout: parse_duration_field(
+            table,
+            "timeout",
+            "strategy",
+            &format!("{}.attempt", strategy),
+        )?,
+        name,
+        run,
+        rollback,
+    })
+}
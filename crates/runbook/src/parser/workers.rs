@@ -0,0 +1,219 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Queue, worker and cron sections
+
+use super::{parse_duration_field, parse_string_list, ParseError};
+use crate::{
+    CronDef, CronExpr, CronSchedule, IdleAction, QueueDef, QueueExhaust, RunDirective, WorkerDef,
+};
+use oj_core::QueueOrder;
+
+pub(super) fn parse_queue(name: &str, value: &toml::Value) -> Result<QueueDef, ParseError> {
+    let table = value
+        .as_table()
+        .ok_or_else(|| ParseError::InvalidFormat(format!("queue.{} must be a table", name)))?;
+
+    let mut queue = QueueDef::new(name);
+    queue.source = table
+        .get("source")
+        .and_then(|v| v.as_str())
+        .map(String::from);
+    if let Some(interval) = parse_duration_field(table, "poll_interval", "queue", name)? {
+        queue.poll_interval = interval;
+    }
+    if let Some(field) = table.get("id_field").and_then(|v| v.as_str()) {
+        queue.id_field = field.to_string();
+    }
+    if let Some(field) = table.get("priority_field").and_then(|v| v.as_str()) {
+        queue.priority_field = field.to_string();
+    }
+    if let Some(order) = table.get("order") {
+        queue.order = match order.as_str() {
+            Some("priority") => QueueOrder::Priority,
+            Some("created_at") => QueueOrder::CreatedAt,
+            _ => {
+                return Err(ParseError::InvalidFormat(format!(
+                    "queue.{}.order: expected priority or created_at, got {}",
+                    name, order
+                )))
+            }
+        };
+    }
+    if let Some(timeout) = parse_duration_field(table, "visibility_timeout", "queue", name)? {
+        queue.visibility_timeout = timeout;
+    }
+    if let Some(max_retries) = table.get("max_retries") {
+        queue.max_retries = max_retries
+            .as_integer()
+            .and_then(|n| u32::try_from(n).ok())
+            .ok_or_else(|| {
+                ParseError::InvalidFormat(format!(
+                    "queue.{}.max_retries: expected non-negative integer, got {}",
+                    name, max_retries
+                ))
+            })?;
+    }
+    if let Some(on_exhaust) = table.get("on_exhaust") {
+        queue.on_exhaust = on_exhaust
+            .as_str()
+            .and_then(QueueExhaust::parse)
+            .ok_or_else(|| {
+                ParseError::InvalidFormat(format!(
+                    "queue.{}.on_exhaust: expected dead or drop, got {}",
+                    name, on_exhaust
+                ))
+            })?;
+    }
+    if let Some(dead) = table.get("dead") {
+        let dead = dead.as_table().ok_or_else(|| {
+            ParseError::InvalidFormat(format!("queue.{}.dead must be a table", name))
+        })?;
+        if let Some(retention) =
+            parse_duration_field(dead, "retention", "queue", &format!("{}.dead", name))?
+        {
+            queue.dead_retention = retention;
+        }
+    }
+    Ok(queue)
+}
+
+pub(super) fn parse_worker(name: &str, value: &toml::Value) -> Result<WorkerDef, ParseError> {
+    let table = value
+        .as_table()
+        .ok_or_else(|| ParseError::InvalidFormat(format!("worker.{} must be a table", name)))?;
+
+    let concurrency = table
+        .get("concurrency")
+        .and_then(|v| v.as_integer())
+        .map(|v| v as u32)
+        .unwrap_or(1);
+
+    let pipelines: Vec<String> = table
+        .get("pipelines")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let queue = table
+        .get("queue")
+        .and_then(|v| v.as_str())
+        .map(String::from);
+
+    let handler = match table.get("handler") {
+        Some(value) => match value.as_str().and_then(|h| h.strip_prefix("pipeline.")) {
+            Some(pipeline) if !pipeline.is_empty() => Some(pipeline.to_string()),
+            _ => {
+                return Err(ParseError::InvalidFormat(format!(
+                    "worker.{}.handler: expected \"pipeline.<name>\", got {}",
+                    name, value
+                )))
+            }
+        },
+        None => None,
+    };
+
+    let idle_action = match table.get("idle_action") {
+        Some(value) => Some(
+            value
+                .as_str()
+                .ok_or_else(|| format!("expected string, got {}", value))
+                .and_then(|s| IdleAction::parse(s).map_err(|e| e.to_string()))
+                .map_err(|e| {
+                    ParseError::InvalidFormat(format!("worker.{}.idle_action: {}", name, e))
+                })?,
+        ),
+        None => None,
+    };
+
+    // A handler runs once per claimed item, so it is useless without a queue
+    if queue.is_none() && handler.is_some() {
+        return Err(ParseError::InvalidFormat(format!(
+            "worker.{}.handler needs a queue to claim items from",
+            name
+        )));
+    }
+
+    Ok(WorkerDef {
+        name: name.to_string(),
+        concurrency,
+        pipelines,
+        queue,
+        handler,
+        idle_action,
+        wake_on: parse_string_list(table, "wake_on", "worker", name)?,
+    })
+}
+
+pub(super) fn parse_cron(name: &str, value: &toml::Value) -> Result<CronDef, ParseError> {
+    let table = value
+        .as_table()
+        .ok_or_else(|| ParseError::InvalidFormat(format!("cron.{} must be a table", name)))?;
+
+    let interval = parse_duration_field(table, "interval", "cron", name)?;
+    let expression =
+        match table.get("schedule") {
+            Some(value) => {
+                let expr = value.as_str().ok_or_else(|| {
+                    ParseError::InvalidFormat(format!(
+                        "cron.{}.schedule: expected string, got {}",
+                        name, value
+                    ))
+                })?;
+                Some(CronExpr::parse(expr).map_err(|e| {
+                    ParseError::InvalidFormat(format!("cron.{}.schedule: {}", name, e))
+                })?)
+            }
+            None => None,
+        };
+    let schedule = match (interval, expression) {
+        (Some(interval), None) if !interval.is_zero() => CronSchedule::Interval(interval),
+        (Some(_), None) => {
+            return Err(ParseError::InvalidFormat(format!(
+                "cron.{}.interval must be greater than zero",
+                name
+            )))
+        }
+        (None, Some(expr)) => CronSchedule::Expression(expr),
+        (Some(_), Some(_)) => {
+            return Err(ParseError::InvalidFormat(format!(
+                "cron.{}: interval and schedule are mutually exclusive",
+                name
+            )))
+        }
+        (None, None) => {
+            return Err(ParseError::MissingField(format!(
+                "cron.{}.interval or cron.{}.schedule",
+                name, name
+            )))
+        }
+    };
+
+    let run = match table.get("run") {
+        Some(run_value) => {
+            let run: RunDirective = run_value
+                .clone()
+                .try_into()
+                .map_err(|e| ParseError::InvalidFormat(format!("cron.{}.run: {}", name, e)))?;
+            if !(run.is_shell() || run.is_pipeline()) {
+                return Err(ParseError::InvalidFormat(format!(
+                    "cron.{}.run: expected a shell command or pipeline",
+                    name
+                )));
+            }
+            Some(run)
+        }
+        None => None,
+    };
+
+    Ok(CronDef {
+        name: name.to_string(),
+        schedule,
+        monitors: parse_string_list(table, "monitors", "cron", name)?,
+        run,
+    })
+}
@@ -2,6 +2,10 @@
 // Copyright (c) 2026 Alfred Jean LLC
 
 use super::*;
+use crate::{
+    CronSchedule, ExhaustAction, IdleAction, MonitorResponse, OutputSource, PhaseRoute,
+    QueueExhaust, RuleAction,
+};
 
 // New format - uses RunDirective tables and args string syntax
 const SAMPLE_RUNBOOK_NEW: &str = r#"
@@ -234,6 +238,11 @@ fn parse_build_minimal_toml() {
     assert_eq!(pipeline.phases[4].name, "done");
     assert!(pipeline.phases[4].run.is_shell());
 
+    // Lifecycle hooks
+    assert!(pipeline.events.get("on_phase").unwrap().contains("{phase}"));
+    assert!(pipeline.events.on_complete.is_some());
+    assert!(pipeline.events.get("on_fail").unwrap().contains("{error}"));
+
     // Verify agents
     let planning = runbook
         .get_agent("planning")
@@ -245,3 +254,825 @@ fn parse_build_minimal_toml() {
         .expect("execution agent should exist");
     assert!(execution.run.contains("claude"));
 }
+
+#[test]
+fn parse_pipeline_events_rejects_unknown_hook() {
+    let toml = r#"
+[pipeline.build]
+
+[pipeline.build.events]
+on_done = "echo done"
+"#;
+    let err = parse_runbook(toml).unwrap_err();
+    assert!(err.to_string().contains("pipeline.build.events"));
+}
+
+#[test]
+fn parse_lock_section() {
+    let toml = r#"
+[lock.main_branch]
+timeout = "10m"
+heartbeat = "15s"
+
+[lock.defaults]
+
+[pipeline.merge]
+[[pipeline.merge.phase]]
+name = "merge"
+run = "git merge"
+lock = "main_branch"
+"#;
+    let runbook = parse_runbook(toml).unwrap();
+
+    let lock = runbook.get_lock("main_branch").unwrap();
+    assert_eq!(lock.timeout, std::time::Duration::from_secs(600));
+    assert_eq!(lock.heartbeat, std::time::Duration::from_secs(15));
+    assert_eq!(
+        runbook.get_lock("defaults"),
+        Some(&LockDef::new("defaults"))
+    );
+
+    let phase = &runbook.get_pipeline("merge").unwrap().phases[0];
+    assert_eq!(phase.lock.as_deref(), Some("main_branch"));
+}
+
+#[test]
+fn parse_lock_rejects_bad_duration() {
+    let toml = r#"
+[lock.main_branch]
+timeout = "soon"
+"#;
+    let err = parse_runbook(toml).unwrap_err();
+    assert!(err.to_string().contains("lock.main_branch.timeout"));
+}
+
+#[test]
+fn parse_phase_timeout() {
+    let toml = r#"
+[pipeline.build]
+[[pipeline.build.phase]]
+name = "test"
+run = "make test"
+timeout = "20m"
+"#;
+    let runbook = parse_runbook(toml).unwrap();
+    let phase = &runbook.get_pipeline("build").unwrap().phases[0];
+    assert_eq!(phase.timeout, Some(std::time::Duration::from_secs(1200)));
+}
+
+#[test]
+fn parse_phase_timeout_rejected_for_agents() {
+    let toml = r#"
+[pipeline.build]
+[[pipeline.build.phase]]
+name = "plan"
+run = { agent = "planner" }
+timeout = "20m"
+"#;
+    let err = parse_runbook(toml).unwrap_err();
+    assert!(err.to_string().contains("phase.plan.timeout"));
+}
+
+#[test]
+fn parse_semaphore_section() {
+    let toml = r#"
+[semaphore.agents]
+max = 3
+slot_timeout = "1h"
+
+[pipeline.build]
+[[pipeline.build.phase]]
+name = "plan"
+run = { agent = "planner" }
+semaphore = "agents"
+"#;
+    let runbook = parse_runbook(toml).unwrap();
+
+    let semaphore = runbook.get_semaphore("agents").unwrap();
+    assert_eq!(semaphore.max, 3);
+    assert_eq!(semaphore.slot_timeout, std::time::Duration::from_secs(3600));
+    assert_eq!(semaphore.config().capacity, 3);
+
+    let phase = &runbook.get_pipeline("build").unwrap().phases[0];
+    assert_eq!(phase.semaphore.as_deref(), Some("agents"));
+}
+
+#[test]
+fn parse_phase_slots() {
+    let toml = r#"
+[semaphore.agents]
+max = 3
+
+[pipeline.build]
+[[pipeline.build.phase]]
+name = "plan"
+run = { agent = "planner" }
+semaphore = "agents"
+
+[[pipeline.build.phase]]
+name = "execute"
+run = { agent = "executor" }
+semaphore = "agents"
+slots = 2
+"#;
+    let runbook = parse_runbook(toml).unwrap();
+
+    let phases = &runbook.get_pipeline("build").unwrap().phases;
+    assert_eq!(phases[0].slots, 1);
+    assert_eq!(phases[1].slots, 2);
+}
+
+#[test]
+fn parse_phase_slots_errors() {
+    let phase = "[pipeline.build]\n[[pipeline.build.phase]]\nname = \"plan\"\nrun = \"true\"\n";
+    for (extra, message) in [
+        (
+            "semaphore = \"agents\"\nslots = 0",
+            "expected positive integer",
+        ),
+        ("slots = 2", "only phases with a semaphore"),
+        (
+            "semaphore = \"agents\"\nslots = 4",
+            "more than semaphore agents holds (3)",
+        ),
+        (
+            "semaphore = \"other\"\nslots = 2",
+            "more than semaphore other holds (1)",
+        ),
+    ] {
+        let toml = format!("[semaphore.agents]\nmax = 3\n\n{}{}\n", phase, extra);
+        let err = parse_runbook(&toml).unwrap_err();
+        assert!(
+            err.to_string().contains("phase.plan.slots"),
+            "{}: {}",
+            toml,
+            err
+        );
+        assert!(err.to_string().contains(message), "{}: {}", toml, err);
+    }
+}
+
+#[test]
+fn parse_semaphore_rejects_zero_max() {
+    let toml = r#"
+[semaphore.agents]
+max = 0
+"#;
+    let err = parse_runbook(toml).unwrap_err();
+    assert!(err.to_string().contains("semaphore.agents.max"));
+}
+
+#[test]
+fn parse_guard_section() {
+    let toml = r#"
+[guard.tests_pass]
+condition = "make test"
+retry = { max = 3, interval = "10s" }
+timeout = "5m"
+on_timeout = "escalate"
+
+[guard.plan_exists]
+condition = "test -f plans/{name}.md"
+
+[pipeline.build]
+[[pipeline.build.phase]]
+name = "execute"
+run = "make"
+pre = ["plan_exists"]
+post = ["tests_pass"]
+"#;
+    let runbook = parse_runbook(toml).unwrap();
+
+    let guard = runbook.get_guard("tests_pass").unwrap();
+    assert_eq!(guard.condition, "make test");
+    assert_eq!(guard.timeout, Some(std::time::Duration::from_secs(300)));
+    assert_eq!(
+        guard.retry,
+        Some(RetryConfig {
+            max: 3,
+            interval: std::time::Duration::from_secs(10),
+        })
+    );
+    assert_eq!(guard.on_timeout, GuardAction::Escalate);
+
+    let plan = runbook.get_guard("plan_exists").unwrap();
+    assert_eq!(plan.on_timeout, GuardAction::Fail);
+    assert_eq!(plan.retry, None);
+
+    let phase = &runbook.get_pipeline("build").unwrap().phases[0];
+    assert_eq!(phase.pre, vec!["plan_exists"]);
+    assert_eq!(phase.post, vec!["tests_pass"]);
+}
+
+#[test]
+fn parse_guard_requires_condition() {
+    let toml = r#"
+[guard.empty]
+timeout = "5m"
+"#;
+    let err = parse_runbook(toml).unwrap_err();
+    assert!(matches!(err, ParseError::MissingField(f) if f == "guard.empty.condition"));
+}
+
+#[test]
+fn parse_guard_rejects_unknown_on_timeout() {
+    let toml = r#"
+[guard.plan_exists]
+condition = "true"
+on_timeout = "panic"
+"#;
+    let err = parse_runbook(toml).unwrap_err();
+    assert!(err.to_string().contains("guard.plan_exists.on_timeout"));
+}
+
+#[test]
+fn parse_guard_wake_on() {
+    let toml = r#"
+[guard.blocker_merged]
+condition = "test -z '{after}'"
+wake_on = ["pipeline:{after}:complete"]
+"#;
+    let runbook = parse_runbook(toml).unwrap();
+    let guard = runbook.get_guard("blocker_merged").unwrap();
+    assert_eq!(guard.wake_on, vec!["pipeline:{after}:complete"]);
+}
+
+#[test]
+fn parse_strategy_section() {
+    let toml = r#"
+[strategy.merge]
+checkpoint = "git rev-parse HEAD"
+on_exhaust = "escalate"
+
+[[strategy.merge.attempt]]
+name = "fast-forward"
+run = "git merge --ff-only FETCH_HEAD"
+timeout = "1m"
+
+[[strategy.merge.attempt]]
+name = "agent-resolve"
+run = { agent = "conflict_resolution" }
+rollback = "git reset --hard {checkpoint}"
+"#;
+    let runbook = parse_runbook(toml).unwrap();
+    let strategy = runbook.get_strategy("merge").unwrap();
+
+    assert_eq!(strategy.checkpoint.as_deref(), Some("git rev-parse HEAD"));
+    assert_eq!(strategy.on_exhaust, ExhaustAction::Escalate);
+    assert_eq!(strategy.attempts.len(), 2);
+
+    let ff = strategy.attempt(0).unwrap();
+    assert_eq!(ff.name, "fast-forward");
+    assert_eq!(ff.timeout, Some(std::time::Duration::from_secs(60)));
+    assert_eq!(ff.rollback, None);
+
+    let agent = strategy.attempt(1).unwrap();
+    assert_eq!(agent.run.agent_name(), Some("conflict_resolution"));
+    assert_eq!(
+        agent.rollback.as_deref(),
+        Some("git reset --hard {checkpoint}")
+    );
+}
+
+#[test]
+fn parse_strategy_requires_attempts() {
+    let toml = r#"
+[strategy.merge]
+checkpoint = "git rev-parse HEAD"
+"#;
+    let err = parse_runbook(toml).unwrap_err();
+    assert!(matches!(err, ParseError::MissingField(f) if f == "strategy.merge.attempt"));
+}
+
+#[test]
+fn parse_strategy_rejects_nested_strategy_attempt() {
+    let toml = r#"
+[[strategy.merge.attempt]]
+run = { strategy = "other" }
+"#;
+    let err = parse_runbook(toml).unwrap_err();
+    assert!(err.to_string().contains("strategy.merge.attempt[0].run"));
+}
+
+#[test]
+fn parse_queue_section() {
+    let toml = r#"
+[queue.bugs]
+order = "created_at"
+visibility_timeout = "30m"
+max_retries = 2
+on_exhaust = "dead"
+
+[queue.bugs.dead]
+retention = "7d"
+
+[queue.merges]
+"#;
+    let runbook = parse_runbook(toml).unwrap();
+
+    let bugs = runbook.get_queue("bugs").unwrap();
+    assert_eq!(bugs.order, oj_core::QueueOrder::CreatedAt);
+    assert_eq!(bugs.max_retries, 2);
+    assert_eq!(bugs.on_exhaust, QueueExhaust::Dead);
+    let config = bugs.config();
+    assert_eq!(
+        config.visibility_timeout,
+        std::time::Duration::from_secs(1800)
+    );
+    assert_eq!(
+        config.dead_letter,
+        Some(std::time::Duration::from_secs(7 * 24 * 60 * 60))
+    );
+
+    let merges = runbook.get_queue("merges").unwrap();
+    assert_eq!(merges.order, oj_core::QueueOrder::Priority);
+    assert_eq!(merges.config().dead_letter, None);
+    assert_eq!(merges.source, None);
+}
+
+#[test]
+fn parse_queue_source() {
+    let toml = r#"
+[queue.bugs]
+source = "wok list -l bug -s todo --json"
+poll_interval = "5m"
+id_field = "key"
+priority_field = "rank"
+"#;
+    let runbook = parse_runbook(toml).unwrap();
+    let bugs = runbook.get_queue("bugs").unwrap();
+
+    assert_eq!(
+        bugs.source.as_deref(),
+        Some("wok list -l bug -s todo --json")
+    );
+    assert_eq!(bugs.poll_interval, std::time::Duration::from_secs(300));
+    assert_eq!(bugs.id_field, "key");
+    assert_eq!(bugs.priority_field, "rank");
+}
+
+#[test]
+fn parse_queue_rejects_unknown_order() {
+    let toml = r#"
+[queue.bugs]
+order = "random"
+"#;
+    let err = parse_runbook(toml).unwrap_err();
+    assert!(err.to_string().contains("queue.bugs.order"));
+}
+
+#[test]
+fn parse_worker_queue_and_handler() {
+    let toml = r#"
+[worker.fixers]
+concurrency = 2
+queue = "bugs"
+handler = "pipeline.fix"
+idle_action = "wait:30s"
+wake_on = ["bug:created", "bug:prioritized"]
+"#;
+    let runbook = parse_runbook(toml).unwrap();
+    let worker = runbook.get_worker("fixers").unwrap();
+
+    assert_eq!(worker.concurrency, 2);
+    assert_eq!(worker.queue.as_deref(), Some("bugs"));
+    assert_eq!(worker.handler.as_deref(), Some("fix"));
+    assert_eq!(worker.handler_pipeline(), Some("fix"));
+    assert_eq!(
+        worker.idle_action,
+        Some(IdleAction::Wait(std::time::Duration::from_secs(30)))
+    );
+    assert_eq!(worker.wake_on, vec!["bug:created", "bug:prioritized"]);
+}
+
+#[test]
+fn parse_worker_rejects_bad_idle_action() {
+    let toml = r#"
+[worker.fixers]
+idle_action = "nap"
+"#;
+    let err = parse_runbook(toml).unwrap_err();
+    assert!(err.to_string().contains("worker.fixers.idle_action"));
+    assert!(err.to_string().contains("wait:<duration>"));
+}
+
+#[test]
+fn parse_worker_rejects_handler_without_queue() {
+    let toml = r#"
+[worker.fixers]
+handler = "pipeline.fix"
+"#;
+    let err = parse_runbook(toml).unwrap_err();
+    assert!(err.to_string().contains("worker.fixers.handler"));
+    assert!(err.to_string().contains("needs a queue"));
+}
+
+#[test]
+fn parse_worker_rejects_non_pipeline_handler() {
+    let toml = r#"
+[worker.fixers]
+handler = "agent.fixer"
+"#;
+    let err = parse_runbook(toml).unwrap_err();
+    assert!(err.to_string().contains("worker.fixers.handler"));
+}
+
+#[test]
+fn parse_crons() {
+    let toml = r#"
+[cron.watchdog]
+interval = "30s"
+monitors = ["agent_idle", "phase_timeout"]
+
+[cron.watchdog.events]
+on_nudge = "oj emit watchdog:nudge --id {name}"
+
+[cron.nightly]
+schedule = "0 3 * * *"
+run = { pipeline = "cleanup" }
+"#;
+    let runbook = parse_runbook(toml).unwrap();
+
+    let watchdog = runbook.get_cron("watchdog").unwrap();
+    assert_eq!(
+        watchdog.schedule,
+        CronSchedule::Interval(std::time::Duration::from_secs(30))
+    );
+    assert_eq!(watchdog.monitors, vec!["agent_idle", "phase_timeout"]);
+    assert_eq!(watchdog.run, None);
+
+    let nightly = runbook.get_cron("nightly").unwrap();
+    assert!(matches!(&nightly.schedule, CronSchedule::Expression(e) if e.as_str() == "0 3 * * *"));
+    assert_eq!(
+        nightly.run.as_ref().and_then(|r| r.pipeline_name()),
+        Some("cleanup")
+    );
+}
+
+#[test]
+fn parse_cron_requires_one_schedule() {
+    for (toml, message) in [
+        (
+            "[cron.c]\nrun = \"true\"",
+            "cron.c.interval or cron.c.schedule",
+        ),
+        (
+            "[cron.c]\ninterval = \"1m\"\nschedule = \"* * * * *\"",
+            "mutually exclusive",
+        ),
+        ("[cron.c]\nschedule = \"* * *\"", "cron.c.schedule"),
+        (
+            "[cron.c]\ninterval = \"1m\"\nrun = { agent = \"a\" }",
+            "cron.c.run",
+        ),
+    ] {
+        let err = parse_runbook(toml).unwrap_err();
+        assert!(err.to_string().contains(message), "{}: {}", toml, err);
+    }
+}
+
+#[test]
+fn parse_monitors() {
+    let toml = r#"
+[monitor.agent_idle]
+source = "oj pipeline list --json"
+condition = "oj session idle-time {session} > 5m"
+response = ["nudge", "restart:2", "escalate"]
+
+[monitor.old_logs]
+source = "find .oj/logs -type f -mtime +30"
+run = "rm {path}"
+"#;
+    let runbook = parse_runbook(toml).unwrap();
+
+    let agent_idle = runbook.get_monitor("agent_idle").unwrap();
+    assert_eq!(agent_idle.source, "oj pipeline list --json");
+    assert_eq!(
+        agent_idle.condition.as_deref(),
+        Some("oj session idle-time {session} > 5m")
+    );
+    let MonitorResponse::Chain(steps) = &agent_idle.response else {
+        panic!("expected a response chain");
+    };
+    let steps: Vec<_> = steps
+        .iter()
+        .map(|s| (s.action.as_str(), s.attempts))
+        .collect();
+    assert_eq!(steps, vec![("nudge", 1), ("restart", 2), ("escalate", 1)]);
+
+    let old_logs = runbook.get_monitor("old_logs").unwrap();
+    assert_eq!(old_logs.condition, None);
+    assert_eq!(
+        old_logs.response,
+        MonitorResponse::Run("rm {path}".to_string())
+    );
+}
+
+#[test]
+fn parse_monitor_requires_one_response() {
+    for (toml, message) in [
+        ("[monitor.m]\nrun = \"true\"", "monitor.m.source"),
+        (
+            "[monitor.m]\nsource = \"true\"",
+            "monitor.m.run or monitor.m.response",
+        ),
+        (
+            "[monitor.m]\nsource = \"true\"\nrun = \"true\"\nresponse = [\"nudge\"]",
+            "mutually exclusive",
+        ),
+        (
+            "[monitor.m]\nsource = \"true\"\nresponse = [\"restart:0\"]",
+            "monitor.m.response",
+        ),
+    ] {
+        let err = parse_runbook(toml).unwrap_err();
+        assert!(err.to_string().contains(message), "{}: {}", toml, err);
+    }
+}
+
+#[test]
+fn parse_actions() {
+    let toml = r#"
+[action.nudge]
+run = "oj session nudge {session}"
+cooldown = "30s"
+
+[action.restart]
+run = """
+oj session kill {session}
+oj pipeline resume {name}
+"""
+cooldown = "5m"
+max_attempts = 2
+"#;
+    let runbook = parse_runbook(toml).unwrap();
+
+    let nudge = runbook.get_action("nudge").unwrap();
+    assert_eq!(nudge.run, "oj session nudge {session}");
+    assert_eq!(nudge.cooldown, Some(std::time::Duration::from_secs(30)));
+    assert_eq!(nudge.max_attempts, None);
+
+    let restart = runbook.get_action("restart").unwrap();
+    assert_eq!(
+        restart.run,
+        "oj session kill {session}\noj pipeline resume {name}"
+    );
+    assert_eq!(restart.cooldown, Some(std::time::Duration::from_secs(300)));
+    assert_eq!(restart.max_attempts, Some(2));
+}
+
+#[test]
+fn parse_action_rejects_bad_fields() {
+    for (toml, message) in [
+        ("[action.a]\ncooldown = \"1m\"", "action.a.run"),
+        (
+            "[action.a]\nrun = \"true\"\nmax_attempts = 0",
+            "action.a.max_attempts",
+        ),
+        (
+            "[action.a]\nrun = \"true\"\ncooldown = \"soon\"",
+            "action.a.cooldown",
+        ),
+    ] {
+        let err = parse_runbook(toml).unwrap_err();
+        assert!(err.to_string().contains(message), "{}: {}", toml, err);
+    }
+}
+
+#[test]
+fn parse_rejects_undefined_action_references() {
+    for (toml, message) in [
+        (
+            "[monitor.m]\nsource = \"true\"\nresponse = [\"nudge\", \"page:2\"]",
+            "monitor.m.response: undefined action \"page\"",
+        ),
+        (
+            "[agent.a]\nrun = \"claude\"\non_idle = \"page\"",
+            "agent.a.on_idle: undefined action \"page\"",
+        ),
+        (
+            "[agent.a]\nrun = \"claude\"\non_exit = [\"restart\", \"page\"]",
+            "agent.a.on_exit: undefined action \"page\"",
+        ),
+    ] {
+        let err = parse_runbook(toml).unwrap_err();
+        assert!(err.to_string().contains(message), "{}: {}", toml, err);
+    }
+}
+
+#[test]
+fn parse_accepts_defined_action_references() {
+    let toml = r#"
+[action.page]
+run = "notify-send {name}"
+
+[monitor.m]
+source = "true"
+response = ["nudge", "page:2", "escalate"]
+
+[agent.a]
+run = "claude"
+on_idle = "page"
+on_exit = ["restart", "page"]
+"#;
+    let runbook = parse_runbook(toml).unwrap();
+    assert!(runbook.get_monitor("m").is_some());
+}
+
+#[test]
+fn parse_event_rules() {
+    let toml = r#"
+[[on]]
+event = "merge:complete"
+where = { branch = "main", attempt = 2 }
+run = "echo {pipeline} >> merged.log"
+
+[[on]]
+event = "bug:created"
+command = "fix"
+args = { name = "bug-{id}" }
+
+[[on]]
+event = "bug:reported"
+queue = "bugs"
+
+[[on]]
+event = "merge:complete"
+emit = "deploy:start"
+data = { ref = "{branch}" }
+"#;
+    let runbook = parse_runbook(toml).unwrap();
+    assert_eq!(runbook.rules.len(), 4);
+
+    let first = &runbook.rules[0];
+    assert_eq!(first.event, "merge:complete");
+    assert_eq!(first.filters["branch"], "main");
+    assert_eq!(first.filters["attempt"], "2");
+    assert_eq!(
+        first.action,
+        RuleAction::Run("echo {pipeline} >> merged.log".to_string())
+    );
+
+    assert_eq!(
+        runbook.rules[1].action,
+        RuleAction::Command {
+            command: "fix".to_string(),
+            args: Some(
+                [("name".to_string(), "bug-{id}".to_string())]
+                    .into_iter()
+                    .collect()
+            ),
+        }
+    );
+    assert_eq!(
+        runbook.rules[2].action,
+        RuleAction::Queue {
+            queue: "bugs".to_string(),
+            data: None,
+        }
+    );
+    assert!(matches!(
+        &runbook.rules[3].action,
+        RuleAction::Emit { event, data: Some(data) } if event == "deploy:start" && data["ref"] == "{branch}"
+    ));
+}
+
+#[test]
+fn parse_event_rule_rejects_bad_fields() {
+    for (toml, message) in [
+        ("[[on]]\nrun = \"true\"", "on[0].event"),
+        ("[[on]]\nevent = \"a\"", "on[0].run, command, queue or emit"),
+        (
+            "[[on]]\nevent = \"a\"\nrun = \"true\"\nemit = \"b\"",
+            "mutually exclusive",
+        ),
+        ("[[on]]\nevent = \"a\"\nemit = \"a\"", "on[0].emit"),
+        (
+            "[[on]]\nevent = \"a\"\nqueue = \"q\"\nargs = { x = \"1\" }",
+            "on[0].args",
+        ),
+        (
+            "[[on]]\nevent = \"a\"\nrun = \"true\"\nwhere = { x = [1] }",
+            "on[0].where",
+        ),
+        ("[on]\nevent = \"a\"", "[[on]]"),
+    ] {
+        let err = parse_runbook(toml).unwrap_err();
+        assert!(err.to_string().contains(message), "{}: {}", toml, err);
+    }
+}
+
+#[test]
+fn parse_phase_outputs() {
+    let toml = r#"
+[pipeline.build]
+[[pipeline.build.phase]]
+name = "commit"
+run = "git rev-parse HEAD"
+outputs = { sha = "stdout" }
+"#;
+    let runbook = parse_runbook(toml).unwrap();
+    let phase = &runbook.get_pipeline("build").unwrap().phases[0];
+    assert_eq!(phase.outputs.get("sha"), Some(&OutputSource::Stdout));
+}
+
+#[test]
+fn parse_phase_outputs_rejects_unknown_source() {
+    let toml = r#"
+[pipeline.build]
+[[pipeline.build.phase]]
+name = "commit"
+run = "git rev-parse HEAD"
+outputs = { sha = "stderr" }
+"#;
+    let err = parse_runbook(toml).unwrap_err();
+    assert!(err.to_string().contains("phase.commit.outputs.sha"));
+}
+
+#[test]
+fn parse_phase_outputs_rejected_for_agents() {
+    let toml = r#"
+[pipeline.build]
+[[pipeline.build.phase]]
+name = "plan"
+run = { agent = "planner" }
+outputs = { plan = "stdout" }
+"#;
+    let err = parse_runbook(toml).unwrap_err();
+    assert!(err.to_string().contains("phase.plan.outputs"));
+}
+
+#[test]
+fn parse_phase_routes() {
+    let toml = r#"
+[pipeline.triage]
+[[pipeline.triage.phase]]
+name = "classify"
+run = "./classify.sh"
+next = { 2 = "needs_review", default = "fix" }
+when = [{ if = "{kind} == 'docs'", next = "docs" }]
+"#;
+    let runbook = parse_runbook(toml).unwrap();
+    let phase = &runbook.get_pipeline("triage").unwrap().phases[0];
+    assert_eq!(phase.next.as_deref(), Some("fix"));
+    assert_eq!(
+        phase.next_on_exit.get(&2).map(String::as_str),
+        Some("needs_review")
+    );
+    assert_eq!(
+        phase.when,
+        vec![PhaseRoute {
+            condition: "{kind} == 'docs'".to_string(),
+            next: "docs".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn parse_phase_routes_errors() {
+    let cases = [
+        (
+            "run = \"x\"\nnext = { two = \"review\" }",
+            "phase.classify.next.two: expected an exit code",
+        ),
+        (
+            "run = { agent = \"a\" }\nnext = { 2 = \"review\" }",
+            "only shell phases can route on exit codes",
+        ),
+        (
+            "run = \"x\"\nwhen = [{ if = \"{kind} = 'bug'\", next = \"fix\" }]",
+            "phase.classify.when[0]: use == to compare",
+        ),
+        (
+            "run = \"x\"\nwhen = [{ if = \"{kind}\" }]",
+            "phase.classify.when",
+        ),
+    ];
+    for (fields, message) in cases {
+        let toml = format!(
+            "[pipeline.triage]\n[[pipeline.triage.phase]]\nname = \"classify\"\n{}\n",
+            fields
+        );
+        let err = parse_runbook(&toml).unwrap_err();
+        assert!(err.to_string().contains(message), "{}: {}", toml, err);
+    }
+}
+
+#[test]
+fn parse_phase_child_pipeline() {
+    let toml = r#"
+[pipeline.release]
+[[pipeline.release.phase]]
+name = "build"
+run = { pipeline = "build", inputs = { target = "{name}" } }
+"#;
+    let runbook = parse_runbook(toml).unwrap();
+    let phase = &runbook.get_pipeline("release").unwrap().phases[0];
+    assert_eq!(
+        phase.run,
+        RunDirective::Pipeline {
+            pipeline: "build".to_string(),
+            inputs: [("target".to_string(), "{name}".to_string())].into(),
+        }
+    );
+}
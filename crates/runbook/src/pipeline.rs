@@ -4,8 +4,10 @@
 //! Pipeline definitions
 
 use crate::command::RunDirective;
+use crate::condition::{Condition, ConditionError};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
 
 /// A phase within a pipeline
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,9 +19,73 @@ pub struct PhaseDef {
     /// Next phase on success
     #[serde(default)]
     pub next: Option<String>,
+    /// Phases to go to by exit code, from a `next = { 2 = "needs_review" }` table
+    ///
+    /// A listed nonzero exit code counts as success rather than failing the phase.
+    #[serde(default)]
+    pub next_on_exit: BTreeMap<i32, String>,
+    /// Conditional routes, the first that holds wins
+    #[serde(default)]
+    pub when: Vec<PhaseRoute>,
     /// Phase to go to on failure
     #[serde(default)]
     pub on_fail: Option<String>,
+    /// Lock held for the duration of the phase
+    #[serde(default)]
+    pub lock: Option<String>,
+    /// Semaphore whose slot is held for the duration of the phase
+    #[serde(default)]
+    pub semaphore: Option<String>,
+    /// Slots the phase takes from its semaphore
+    #[serde(default = "default_slots")]
+    pub slots: u32,
+    /// Guards that must pass before the phase starts
+    #[serde(default)]
+    pub pre: Vec<String>,
+    /// Guards that must pass before the pipeline moves on
+    #[serde(default)]
+    pub post: Vec<String>,
+    /// How long a shell phase may run before it is killed and fails
+    #[serde(default)]
+    pub timeout: Option<Duration>,
+    /// Variables a shell phase sets for later phases, by where each comes from
+    ///
+    /// The command can also write `KEY=value` lines to the file named by
+    /// `$OJ_OUTPUT`.
+    #[serde(default)]
+    pub outputs: HashMap<String, OutputSource>,
+}
+
+fn default_slots() -> u32 {
+    1
+}
+
+/// A `when` entry: go to `next` if the condition holds
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PhaseRoute {
+    /// Condition over the pipeline's variables, e.g. `{kind} == 'bug'`
+    #[serde(rename = "if")]
+    pub condition: String,
+    /// Phase to go to
+    pub next: String,
+}
+
+/// Where the value of a phase output comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputSource {
+    /// The command's standard output, trimmed
+    Stdout,
+}
+
+impl OutputSource {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "stdout" => Some(OutputSource::Stdout),
+            _ => None,
+        }
+    }
 }
 
 impl PhaseDef {
@@ -47,6 +113,61 @@ impl PhaseDef {
     pub fn shell_command(&self) -> Option<&str> {
         self.run.shell_command()
     }
+
+    /// Check if an exit code routes somewhere instead of failing the phase
+    pub fn routes_exit(&self, exit_code: i32) -> bool {
+        self.next_on_exit.contains_key(&exit_code)
+    }
+
+    /// Pick where to go after this phase completes, if the phase says
+    ///
+    /// An exit code route wins, then the first `when` route whose condition
+    /// holds, then `next`. `None` means the next phase in order.
+    pub fn route(
+        &self,
+        exit_code: Option<i32>,
+        vars: &HashMap<String, String>,
+    ) -> Result<Option<&str>, ConditionError> {
+        if let Some(next) = exit_code.and_then(|code| self.next_on_exit.get(&code)) {
+            return Ok(Some(next));
+        }
+        for route in &self.when {
+            if Condition::parse(&route.condition)?.evaluate(vars)? {
+                return Ok(Some(&route.next));
+            }
+        }
+        Ok(self.next.as_deref())
+    }
+}
+
+/// Shell hooks run on pipeline lifecycle transitions (`[pipeline.X.events]`)
+///
+/// Commands are interpolated with the pipeline's variables plus `{phase}`,
+/// and `{error}` for failures.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PipelineEvents {
+    /// Run whenever the pipeline moves to another phase
+    #[serde(default)]
+    pub on_phase: Option<String>,
+    /// Run when the pipeline completes
+    #[serde(default)]
+    pub on_complete: Option<String>,
+    /// Run when the pipeline fails with no `on_fail` phase to go to
+    #[serde(default)]
+    pub on_fail: Option<String>,
+}
+
+impl PipelineEvents {
+    /// Get a hook command by key (`on_phase`, `on_complete` or `on_fail`)
+    pub fn get(&self, hook: &str) -> Option<&str> {
+        match hook {
+            "on_phase" => self.on_phase.as_deref(),
+            "on_complete" => self.on_complete.as_deref(),
+            "on_fail" => self.on_fail.as_deref(),
+            _ => None,
+        }
+    }
 }
 
 /// A pipeline definition from the runbook
@@ -63,6 +184,9 @@ pub struct PipelineDef {
     /// Ordered phases
     #[serde(default)]
     pub phases: Vec<PhaseDef>,
+    /// Lifecycle hooks
+    #[serde(default)]
+    pub events: PipelineEvents,
 }
 
 impl PipelineDef {
@@ -13,7 +13,16 @@ fn sample_pipeline() -> PipelineDef {
                 name: "init".to_string(),
                 run: RunDirective::Shell("git worktree add".to_string()),
                 next: None,
+                next_on_exit: BTreeMap::new(),
+                when: Vec::new(),
                 on_fail: None,
+                lock: None,
+                semaphore: None,
+                slots: 1,
+                pre: Vec::new(),
+                post: Vec::new(),
+                timeout: None,
+                outputs: HashMap::new(),
             },
             PhaseDef {
                 name: "plan".to_string(),
@@ -21,7 +30,16 @@ fn sample_pipeline() -> PipelineDef {
                     agent: "planner".to_string(),
                 },
                 next: None,
+                next_on_exit: BTreeMap::new(),
+                when: Vec::new(),
                 on_fail: None,
+                lock: None,
+                semaphore: None,
+                slots: 1,
+                pre: Vec::new(),
+                post: Vec::new(),
+                timeout: None,
+                outputs: HashMap::new(),
             },
             PhaseDef {
                 name: "execute".to_string(),
@@ -29,21 +47,49 @@ fn sample_pipeline() -> PipelineDef {
                     agent: "executor".to_string(),
                 },
                 next: Some("done".to_string()),
+                next_on_exit: BTreeMap::new(),
+                when: Vec::new(),
                 on_fail: Some("failed".to_string()),
+                lock: None,
+                semaphore: None,
+                slots: 1,
+                pre: Vec::new(),
+                post: Vec::new(),
+                timeout: None,
+                outputs: HashMap::new(),
             },
             PhaseDef {
                 name: "done".to_string(),
                 run: RunDirective::Shell("echo done".to_string()),
                 next: None,
+                next_on_exit: BTreeMap::new(),
+                when: Vec::new(),
                 on_fail: None,
+                lock: None,
+                semaphore: None,
+                slots: 1,
+                pre: Vec::new(),
+                post: Vec::new(),
+                timeout: None,
+                outputs: HashMap::new(),
             },
             PhaseDef {
                 name: "failed".to_string(),
                 run: RunDirective::Shell("echo failed".to_string()),
                 next: None,
+                next_on_exit: BTreeMap::new(),
+                when: Vec::new(),
                 on_fail: None,
+                lock: None,
+                semaphore: None,
+                slots: 1,
+                pre: Vec::new(),
+                post: Vec::new(),
+                timeout: None,
+                outputs: HashMap::new(),
             },
         ],
+        events: PipelineEvents::default(),
     }
 }
 
@@ -82,3 +128,20 @@ fn phase_is_agent() {
     assert!(p.get_phase("plan").unwrap().is_agent());
     assert_eq!(p.get_phase("plan").unwrap().agent_name(), Some("planner"));
 }
+
+#[test]
+fn phase_route_prefers_exit_code_then_when_then_next() {
+    let mut phase = sample_pipeline().get_phase("execute").unwrap().clone();
+    phase.next_on_exit.insert(2, "needs_review".to_string());
+    phase.when.push(PhaseRoute {
+        condition: "{kind} == 'bug'".to_string(),
+        next: "fix".to_string(),
+    });
+    let bug: HashMap<String, String> = [("kind".to_string(), "bug".to_string())].into();
+
+    assert_eq!(phase.route(Some(2), &bug), Ok(Some("needs_review")));
+    assert_eq!(phase.route(Some(0), &bug), Ok(Some("fix")));
+    assert_eq!(phase.route(None, &HashMap::new()), Ok(Some("done")));
+    assert!(phase.routes_exit(2));
+    assert!(!phase.routes_exit(1));
+}
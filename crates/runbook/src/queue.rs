@@ -0,0 +1,84 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Queue definitions
+
+use oj_core::{QueueConfig, QueueOrder};
+use std::time::Duration;
+
+/// What happens to an item that has used up its retries
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QueueExhaust {
+    /// Discard the item
+    #[default]
+    Drop,
+    /// Keep the item in the dead letter queue for `retention`
+    Dead,
+}
+
+impl QueueExhaust {
+    /// Parse an `on_exhaust` value
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "drop" => Some(QueueExhaust::Drop),
+            "dead" => Some(QueueExhaust::Dead),
+            _ => None,
+        }
+    }
+}
+
+/// A queue definition from the runbook
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueueDef {
+    /// Queue name
+    pub name: String,
+    /// Shell command printing a JSON array of items to add to the queue
+    pub source: Option<String>,
+    /// How often the source is polled
+    pub poll_interval: Duration,
+    /// Item field holding the ID used to dedupe items
+    pub id_field: String,
+    /// Item field holding the numeric priority
+    pub priority_field: String,
+    /// Claim order
+    pub order: QueueOrder,
+    /// How long a claimed item is hidden before it is handed out again
+    pub visibility_timeout: Duration,
+    /// Failed attempts allowed after the first
+    pub max_retries: u32,
+    /// What to do with items that run out of retries
+    pub on_exhaust: QueueExhaust,
+    /// How long dead letter items are kept (`[queue.X.dead] retention`)
+    pub dead_retention: Duration,
+}
+
+impl QueueDef {
+    /// Create a queue definition with default settings
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            source: None,
+            poll_interval: Duration::from_secs(60),
+            id_field: "id".to_string(),
+            priority_field: "priority".to_string(),
+            order: QueueOrder::default(),
+            visibility_timeout: Duration::from_secs(30 * 60),
+            max_retries: 0,
+            on_exhaust: QueueExhaust::default(),
+            dead_retention: Duration::from_secs(7 * 24 * 60 * 60),
+        }
+    }
+
+    /// Build the core queue configuration
+    pub fn config(&self) -> QueueConfig {
+        QueueConfig {
+            visibility_timeout: self.visibility_timeout,
+            max_retries: self.max_retries,
+            dead_letter: match self.on_exhaust {
+                QueueExhaust::Drop => None,
+                QueueExhaust::Dead => Some(self.dead_retention),
+            },
+            order: self.order,
+        }
+    }
+}
@@ -0,0 +1,65 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Event subscription rules (`[[on]]`)
+
+use std::collections::HashMap;
+
+/// A top-level `[[on]]` rule that reacts to custom events
+///
+/// ```toml
+/// [[on]]
+/// event = "merge:complete"
+/// where = { branch = "main" }
+/// run = "echo {pipeline} merged >> .oj/merges.log"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventRule {
+    /// Event name the rule reacts to
+    pub event: String,
+    /// Fields the event data must have, with dotted keys reaching into
+    /// nested objects (`bug.id`)
+    pub filters: HashMap<String, String>,
+    /// What to do when the rule matches
+    pub action: RuleAction,
+}
+
+impl EventRule {
+    /// Check the rule against an event and its flattened data fields
+    pub fn matches(&self, event: &str, fields: &HashMap<String, String>) -> bool {
+        self.event == event
+            && self
+                .filters
+                .iter()
+                .all(|(key, value)| fields.get(key) == Some(value))
+    }
+}
+
+/// What an `[[on]]` rule does
+///
+/// Values are interpolated with the event's fields. When `args` or `data`
+/// is left out, the event's fields are passed through unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleAction {
+    /// `run = "..."`: a shell command run in the background
+    Run(String),
+    /// `command = "build"`: invoke a command definition
+    Command {
+        command: String,
+        args: Option<HashMap<String, String>>,
+    },
+    /// `queue = "bugs"`: push an item onto a queue
+    Queue {
+        queue: String,
+        data: Option<HashMap<String, String>>,
+    },
+    /// `emit = "deploy:start"`: emit another custom event
+    Emit {
+        event: String,
+        data: Option<HashMap<String, String>>,
+    },
+}
+
+#[cfg(test)]
+#[path = "rule_tests.rs"]
+mod tests;
@@ -0,0 +1,39 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+
+fn fields(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[test]
+fn rule_matches_event_and_filters() {
+    let rule = EventRule {
+        event: "merge:complete".to_string(),
+        filters: fields(&[("branch", "main"), ("bug.id", "7")]),
+        action: RuleAction::Run("true".to_string()),
+    };
+
+    let event = fields(&[("branch", "main"), ("bug.id", "7"), ("extra", "x")]);
+    assert!(rule.matches("merge:complete", &event));
+    assert!(!rule.matches("merge:failed", &event));
+    assert!(!rule.matches("merge:complete", &fields(&[("branch", "main")])));
+    assert!(!rule.matches(
+        "merge:complete",
+        &fields(&[("branch", "dev"), ("bug.id", "7")])
+    ));
+}
+
+#[test]
+fn rule_without_filters_matches_any_data() {
+    let rule = EventRule {
+        event: "bug:created".to_string(),
+        filters: HashMap::new(),
+        action: RuleAction::Run("true".to_string()),
+    };
+    assert!(rule.matches("bug:created", &HashMap::new()));
+}
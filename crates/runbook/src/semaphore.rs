@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Semaphore definitions
+
+use oj_core::SemaphoreConfig;
+use std::time::Duration;
+
+/// A semaphore definition from the runbook
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SemaphoreDef {
+    /// Semaphore name
+    pub name: String,
+    /// Maximum number of slots held at once
+    pub max: u32,
+    /// How long a slot may go without a heartbeat before it is reclaimed
+    pub slot_timeout: Duration,
+    /// How often holders refresh their slot heartbeat
+    pub slot_heartbeat: Duration,
+}
+
+impl SemaphoreDef {
+    /// Create a single-slot semaphore definition with default timings
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            max: 1,
+            slot_timeout: Duration::from_secs(2 * 60 * 60),
+            slot_heartbeat: Duration::from_secs(60),
+        }
+    }
+
+    /// Build the core semaphore configuration
+    pub fn config(&self) -> SemaphoreConfig {
+        SemaphoreConfig {
+            capacity: self.max,
+            heartbeat_timeout: self.slot_timeout,
+        }
+    }
+}
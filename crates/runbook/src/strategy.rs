@@ -0,0 +1,64 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! Strategy definitions
+
+use crate::command::RunDirective;
+use std::time::Duration;
+
+/// A strategy definition from the runbook
+///
+/// An ordered fallback chain: attempts run one at a time until one succeeds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrategyDef {
+    /// Strategy name
+    pub name: String,
+    /// Shell command whose stdout is captured before the first attempt
+    /// and exposed to rollbacks as `{checkpoint}`
+    pub checkpoint: Option<String>,
+    /// Attempts, in the order they are tried
+    pub attempts: Vec<AttemptDef>,
+    /// What to do when every attempt has failed
+    pub on_exhaust: ExhaustAction,
+}
+
+/// One approach within a strategy
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttemptDef {
+    /// Attempt name
+    pub name: String,
+    /// Shell command or agent to run
+    pub run: RunDirective,
+    /// How long the attempt may run before it counts as failed
+    pub timeout: Option<Duration>,
+    /// Shell command to undo a failed attempt
+    pub rollback: Option<String>,
+}
+
+/// Action taken when a strategy runs out of attempts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExhaustAction {
+    /// Fail the phase
+    #[default]
+    Fail,
+    /// Alert a human and wait for them
+    Escalate,
+}
+
+impl ExhaustAction {
+    /// Parse an `on_exhaust` value
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "fail" => Some(ExhaustAction::Fail),
+            "escalate" => Some(ExhaustAction::Escalate),
+            _ => None,
+        }
+    }
+}
+
+impl StrategyDef {
+    /// Get an attempt by index
+    pub fn attempt(&self, index: usize) -> Option<&AttemptDef> {
+        self.attempts.get(index)
+    }
+}
@@ -7,11 +7,12 @@ use regex::Regex;
 use std::collections::HashMap;
 use std::sync::LazyLock;
 
-// Regex pattern for {variable_name} - this is a constant valid pattern
+// Regex pattern for {variable_name} or dotted {item.field} - this is a constant valid pattern
 // Allow expect here as the regex is compile-time verified to be valid
 #[allow(clippy::expect_used)]
 static VAR_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"\{([a-zA-Z_][a-zA-Z0-9_]*)\}").expect("constant regex pattern is valid")
+    Regex::new(r"\{([a-zA-Z_][a-zA-Z0-9_]*(?:\.[a-zA-Z_][a-zA-Z0-9_]*)*)\}")
+        .expect("constant regex pattern is valid")
 });
 
 // Regex pattern for ${VAR:-default} environment variable expansion
@@ -26,6 +27,45 @@ static ENV_PATTERN: LazyLock<Regex> =
 ///
 /// Unknown template variables are left as-is.
 pub fn interpolate(template: &str, vars: &HashMap<String, String>) -> String {
+    interpolate_with(template, vars, str::to_string, |placeholder| {
+        placeholder.to_string()
+    })
+}
+
+/// Interpolate like [`interpolate`], but replace unknown variables with nothing
+pub(crate) fn interpolate_or_empty(template: &str, vars: &HashMap<String, String>) -> String {
+    interpolate_with(template, vars, str::to_string, |_| String::new())
+}
+
+/// Interpolate like [`interpolate`], quoting each value as one shell word
+///
+/// For commands built from data the runbook does not control, such as queue
+/// items or monitor source output, so a value cannot inject shell syntax.
+pub fn interpolate_shell(template: &str, vars: &HashMap<String, String>) -> String {
+    interpolate_with(template, vars, shell_quote, |placeholder| {
+        placeholder.to_string()
+    })
+}
+
+/// Quote a value for `sh`, leaving plain words bare
+fn shell_quote(value: &str) -> String {
+    let plain = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_-./:@%+=,".contains(c));
+    if plain {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+fn interpolate_with(
+    template: &str,
+    vars: &HashMap<String, String>,
+    value: impl Fn(&str) -> String,
+    missing: impl Fn(&str) -> String,
+) -> String {
     // First expand ${VAR:-default} patterns from environment
     let result = ENV_PATTERN
         .replace_all(template, |caps: &regex::Captures| {
@@ -40,8 +80,8 @@ pub fn interpolate(template: &str, vars: &HashMap<String, String>) -> String {
         .replace_all(&result, |caps: &regex::Captures| {
             let name = &caps[1];
             vars.get(name)
-                .cloned()
-                .unwrap_or_else(|| caps[0].to_string())
+                .map(|v| value(v))
+                .unwrap_or_else(|| missing(&caps[0]))
         })
         .to_string()
 }
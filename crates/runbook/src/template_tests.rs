@@ -77,3 +77,30 @@ fn interpolate_env_and_template_vars() {
     );
     std::env::remove_var("TEMPLATE_CMD_VAR");
 }
+
+#[test]
+fn interpolate_dotted_vars() {
+    let vars: HashMap<String, String> = [("bug.id".to_string(), "7".to_string())]
+        .into_iter()
+        .collect();
+    assert_eq!(
+        interpolate("fix {bug.id} in {name}.md", &vars),
+        "fix 7 in {name}.md"
+    );
+}
+
+#[test]
+fn interpolate_shell_quotes_values() {
+    let vars: HashMap<String, String> = [
+        ("id".to_string(), "bug-7".to_string()),
+        ("title".to_string(), "a; rm -rf x".to_string()),
+        ("quote".to_string(), "it's".to_string()),
+        ("empty".to_string(), String::new()),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(
+        interpolate_shell("fix {id} {title} {quote} {empty} {missing}", &vars),
+        "fix bug-7 'a; rm -rf x' 'it'\\''s' '' {missing}"
+    );
+}
@@ -3,7 +3,10 @@
 
 //! Worker definitions
 
+use crate::duration::{parse_duration, DurationError};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use thiserror::Error;
 
 /// A worker definition from the runbook
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,6 +19,55 @@ pub struct WorkerDef {
     /// Pipelines this worker processes
     #[serde(default)]
     pub pipelines: Vec<String>,
+    /// Queue the worker claims items from
+    #[serde(default)]
+    pub queue: Option<String>,
+    /// Pipeline created for each claimed item (`handler = "pipeline.fix"`)
+    #[serde(default)]
+    pub handler: Option<String>,
+    /// What to do when there is no work left
+    #[serde(default)]
+    pub idle_action: Option<IdleAction>,
+    /// Custom events that wake the worker
+    #[serde(default)]
+    pub wake_on: Vec<String>,
+}
+
+/// What an idle worker does while waiting for work
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IdleAction {
+    /// Check for work again after the duration (`"wait:30s"`)
+    Wait(Duration),
+}
+
+/// Error returned for malformed `idle_action` values
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum IdleActionError {
+    #[error("unknown idle action {0:?}, expected \"wait:<duration>\"")]
+    Unknown(String),
+    #[error(transparent)]
+    Duration(#[from] DurationError),
+}
+
+impl IdleAction {
+    /// Parse an `idle_action` value
+    pub fn parse(s: &str) -> Result<Self, IdleActionError> {
+        match s.strip_prefix("wait:") {
+            Some(duration) => Ok(IdleAction::Wait(parse_duration(duration)?)),
+            None => Err(IdleActionError::Unknown(s.to_string())),
+        }
+    }
+}
+
+impl WorkerDef {
+    /// The pipeline to run for each item: the handler, or the only listed pipeline
+    pub fn handler_pipeline(&self) -> Option<&str> {
+        match (&self.handler, self.pipelines.as_slice()) {
+            (Some(handler), _) => Some(handler),
+            (None, [pipeline]) => Some(pipeline),
+            _ => None,
+        }
+    }
 }
 
 fn default_concurrency() -> u32 {
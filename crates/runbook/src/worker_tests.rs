@@ -9,8 +9,50 @@ fn worker_defaults() {
         name: "builds".to_string(),
         concurrency: 1,
         pipelines: vec!["build".to_string()],
+        queue: None,
+        handler: None,
+        idle_action: None,
+        wake_on: vec![],
     };
 
     assert_eq!(worker.concurrency, 1);
     assert!(worker.pipelines.contains(&"build".to_string()));
+    assert_eq!(worker.handler_pipeline(), Some("build"));
+}
+
+#[test]
+fn handler_overrides_pipelines() {
+    let worker = WorkerDef {
+        name: "fixers".to_string(),
+        concurrency: 2,
+        pipelines: vec!["build".to_string(), "fix".to_string()],
+        queue: Some("bugs".to_string()),
+        handler: Some("fix".to_string()),
+        idle_action: None,
+        wake_on: vec![],
+    };
+    assert_eq!(worker.handler_pipeline(), Some("fix"));
+
+    let ambiguous = WorkerDef {
+        handler: None,
+        ..worker
+    };
+    assert_eq!(ambiguous.handler_pipeline(), None);
+}
+
+#[test]
+fn parse_idle_action() {
+    assert_eq!(
+        IdleAction::parse("wait:30s"),
+        Ok(IdleAction::Wait(Duration::from_secs(30)))
+    );
+    assert!(matches!(
+        IdleAction::parse("wait:soon"),
+        Err(IdleActionError::Duration(_))
+    ));
+    let err = IdleAction::parse("sleep:30s").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unknown idle action \"sleep:30s\", expected \"wait:<duration>\""
+    );
 }
@@ -8,8 +8,10 @@
 
 //! Storage layer for Otter Jobs
 
+mod snapshot;
 mod state;
 mod wal;
 
+pub use snapshot::{checkpoint, Snapshot, SnapshotError, SnapshotStore};
 pub use state::MaterializedState;
-pub use wal::{Wal, WalError};
+pub use wal::{TruncatedEntry, Wal, WalError, WalRecord};
@@ -0,0 +1,139 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+//! State snapshots and WAL compaction
+//!
+//! A snapshot holds the materialized state as of a WAL sequence number, so
+//! startup only replays the entries after it. Snapshot files are named
+//! `snapshot-<sequence>.json`.
+
+use crate::{MaterializedState, Wal, WalError};
+use oj_core::Operation;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// Errors that can occur reading or writing snapshots
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("WAL error: {0}")]
+    Wal(#[from] WalError),
+}
+
+/// Materialized state as of a WAL sequence number
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Last WAL sequence number reflected in `state`
+    pub sequence: u64,
+    pub state: MaterializedState,
+}
+
+/// A directory of snapshot files
+#[derive(Debug, Clone)]
+pub struct SnapshotStore {
+    dir: PathBuf,
+}
+
+impl SnapshotStore {
+    /// Use snapshots in `dir`, which is created on first save
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Write a snapshot, replacing any earlier one for the same sequence
+    ///
+    /// The file is written under a temporary name and renamed into place
+    /// once synced, so a reader never sees a partial snapshot.
+    pub fn save(&self, snapshot: &Snapshot) -> Result<PathBuf, SnapshotError> {
+        std::fs::create_dir_all(&self.dir)?;
+        let path = self.path(snapshot.sequence);
+        let tmp_path = path.with_extension("json.tmp");
+        {
+            let mut file = File::create(&tmp_path)?;
+            serde_json::to_writer(&mut file, snapshot)?;
+            file.flush()?;
+            file.sync_all()?;
+        }
+        std::fs::rename(&tmp_path, &path)?;
+        Ok(path)
+    }
+
+    /// Load the snapshot for a sequence number
+    pub fn load(&self, sequence: u64) -> Result<Snapshot, SnapshotError> {
+        let file = File::open(self.path(sequence))?;
+        Ok(serde_json::from_reader(io::BufReader::new(file))?)
+    }
+
+    /// Sequence numbers of the stored snapshots, oldest first
+    pub fn sequences(&self) -> Result<Vec<u64>, SnapshotError> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut sequences: Vec<u64> = entries
+            .filter_map(|entry| {
+                let name = entry.ok()?.file_name();
+                name.to_str()?
+                    .strip_prefix("snapshot-")?
+                    .strip_suffix(".json")?
+                    .parse()
+                    .ok()
+            })
+            .collect();
+        sequences.sort_unstable();
+        Ok(sequences)
+    }
+
+    /// Delete all but the newest `keep` snapshots
+    ///
+    /// Returns the sequence numbers of the snapshots left, oldest first.
+    pub fn prune(&self, keep: usize) -> Result<Vec<u64>, SnapshotError> {
+        let mut sequences = self.sequences()?;
+        let excess = sequences.len().saturating_sub(keep.max(1));
+        for sequence in sequences.drain(..excess) {
+            std::fs::remove_file(self.path(sequence))?;
+        }
+        Ok(sequences)
+    }
+
+    fn path(&self, sequence: u64) -> PathBuf {
+        self.dir.join(format!("snapshot-{}.json", sequence))
+    }
+}
+
+/// Snapshot the state, then compact the WAL
+///
+/// The snapshot covers every entry up to the WAL's current sequence, and a
+/// `SnapshotTaken` marker is appended after it. Only the newest `keep`
+/// snapshots are kept, and the WAL keeps every entry after the oldest of
+/// them so that any remaining snapshot can still be replayed from.
+pub fn checkpoint(
+    wal: &mut Wal,
+    state: &MaterializedState,
+    store: &SnapshotStore,
+    keep: usize,
+) -> Result<u64, SnapshotError> {
+    let sequence = wal.sequence();
+    store.save(&Snapshot {
+        sequence,
+        state: state.clone(),
+    })?;
+    wal.append(&Operation::SnapshotTaken { sequence })?;
+
+    let remaining = store.prune(keep)?;
+    if let Some(oldest) = remaining.first() {
+        wal.compact(*oldest)?;
+    }
+    Ok(sequence)
+}
+
+#[cfg(test)]
+#[path = "snapshot_tests.rs"]
+mod tests;
@@ -0,0 +1,168 @@
+// SPDX-License-Identifier: BUSL-1.1
+// Copyright (c) 2026 Alfred Jean LLC
+
+use super::*;
+use std::collections::HashMap;
+
+fn delete(id: &str) -> Operation {
+    Operation::PipelineDelete { id: id.to_string() }
+}
+
+fn sample_state() -> MaterializedState {
+    let mut state = MaterializedState::default();
+    for op in [
+        Operation::PipelineCreate {
+            id: "pipe-1".to_string(),
+            kind: "build".to_string(),
+            name: "auth".to_string(),
+            inputs: HashMap::new(),
+            initial_phase: "plan".to_string(),
+            parent: None,
+        },
+        Operation::LockAcquire {
+            name: "main".to_string(),
+            holder: "pipe-1".to_string(),
+        },
+        Operation::QueuePush {
+            name: "bugs".to_string(),
+            item_id: "bug-1".to_string(),
+            data: HashMap::new(),
+            priority: 3,
+        },
+        Operation::QueueClaim {
+            name: "bugs".to_string(),
+            item_id: "bug-1".to_string(),
+            holder: "pipe-1".to_string(),
+        },
+        Operation::WorkerStart {
+            name: "fixers".to_string(),
+        },
+    ] {
+        state.apply(&op);
+    }
+    state
+}
+
+#[test]
+fn snapshot_roundtrip() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SnapshotStore::new(dir.path().join("snapshots"));
+    let state = sample_state();
+
+    store
+        .save(&Snapshot {
+            sequence: 5,
+            state: state.clone(),
+        })
+        .unwrap();
+    let loaded = store.load(5).unwrap();
+
+    assert_eq!(loaded.sequence, 5);
+    let pipeline = &loaded.state.pipelines["pipe-1"];
+    assert_eq!(pipeline.phase, "plan");
+    assert_eq!(
+        pipeline.created_at_ms,
+        state.pipelines["pipe-1"].created_at_ms
+    );
+    assert!(loaded.state.locks["main"].is_held_by("pipe-1"));
+    let item = loaded.state.queues["bugs"].get("bug-1").unwrap();
+    assert_eq!(item.holder(), Some("pipe-1"));
+    assert_eq!(item.priority, 3);
+    assert!(loaded.state.workers["fixers"].is_running());
+}
+
+#[test]
+fn prune_keeps_newest_snapshots() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SnapshotStore::new(dir.path());
+    assert!(store.sequences().unwrap().is_empty());
+
+    for sequence in [30, 4, 12] {
+        store
+            .save(&Snapshot {
+                sequence,
+                state: MaterializedState::default(),
+            })
+            .unwrap();
+    }
+    assert_eq!(store.sequences().unwrap(), vec![4, 12, 30]);
+    assert_eq!(store.prune(2).unwrap(), vec![12, 30]);
+    assert!(store.load(4).is_err());
+}
+
+#[test]
+fn checkpoint_compacts_wal_behind_oldest_snapshot() {
+    let dir = tempfile::tempdir().unwrap();
+    let wal_path = dir.path().join("events.wal");
+    let store = SnapshotStore::new(dir.path().join("snapshots"));
+    let mut wal = Wal::open(&wal_path).unwrap();
+    let state = MaterializedState::default();
+
+    for i in 0..3 {
+        wal.append(&delete(&format!("a{}", i))).unwrap();
+    }
+    assert_eq!(checkpoint(&mut wal, &state, &store, 2).unwrap(), 3);
+    wal.append(&delete("b")).unwrap();
+    assert_eq!(checkpoint(&mut wal, &state, &store, 2).unwrap(), 5);
+    wal.append(&delete("c")).unwrap();
+
+    // Entries after the oldest kept snapshot remain
+    assert_eq!(store.sequences().unwrap(), vec![3, 5]);
+    assert_eq!(
+        Wal::replay(&wal_path).unwrap(),
+        vec![
+            Operation::SnapshotTaken { sequence: 3 },
+            delete("b"),
+            Operation::SnapshotTaken { sequence: 5 },
+            delete("c"),
+        ]
+    );
+    let after: Vec<_> = Wal::replay_after(&wal_path, 5)
+        .unwrap()
+        .into_iter()
+        .map(|record| record.operation)
+        .collect();
+    assert_eq!(
+        after,
+        vec![Operation::SnapshotTaken { sequence: 5 }, delete("c")]
+    );
+    assert_eq!(Wal::replay_after(&wal_path, 3).unwrap().len(), 4);
+
+    // The sequence carries on after reopening the compacted log
+    drop(wal);
+    let mut wal = Wal::open(&wal_path).unwrap();
+    assert_eq!(wal.sequence(), 7);
+    assert_eq!(checkpoint(&mut wal, &state, &store, 2).unwrap(), 7);
+    assert_eq!(store.sequences().unwrap(), vec![5, 7]);
+    assert_eq!(Wal::replay(&wal_path).unwrap().len(), 3);
+}
+
+#[test]
+fn replay_after_requires_wal_to_continue_from_snapshot() {
+    let dir = tempfile::tempdir().unwrap();
+    let wal_path = dir.path().join("events.wal");
+    let store = SnapshotStore::new(dir.path().join("snapshots"));
+    let mut wal = Wal::open(&wal_path).unwrap();
+    for i in 0..4 {
+        wal.append(&delete(&format!("a{}", i))).unwrap();
+    }
+    checkpoint(&mut wal, &MaterializedState::default(), &store, 1).unwrap();
+
+    // Without the snapshot, entries 1-4 are missing
+    assert!(matches!(
+        Wal::replay_after(&wal_path, 0),
+        Err(WalError::SequenceGap {
+            expected: 1,
+            found: 5,
+            ..
+        })
+    ));
+    // A snapshot newer than the log means the log lost entries
+    assert!(matches!(
+        Wal::replay_after(&wal_path, 9),
+        Err(WalError::BehindSnapshot {
+            wal: 5,
+            snapshot: 9
+        })
+    ));
+}
@@ -3,19 +3,26 @@
 
 //! Materialized state from WAL replay
 
-use oj_core::{Operation, Pipeline, Worker};
+use oj_core::{
+    ActionRecord, ChainProgress, Clock, Cron, FixedClock, Lock, LockConfig, Operation, Pipeline,
+    Queue, QueueConfig, Semaphore, SemaphoreConfig, StrategyState, Worker,
+};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
 /// Session record
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Session {
     pub id: String,
     pub pipeline_id: String,
+    /// When the session was created, in Unix epoch milliseconds
+    #[serde(default)]
+    pub created_at_ms: u64,
 }
 
 /// Workspace record
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Workspace {
     pub id: String,
     pub path: PathBuf,
@@ -23,12 +30,21 @@ pub struct Workspace {
 }
 
 /// Materialized state built from WAL operations
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct MaterializedState {
     pub pipelines: HashMap<String, Pipeline>,
     pub sessions: HashMap<String, Session>,
     pub workspaces: HashMap<String, Workspace>,
     pub workers: HashMap<String, Worker>,
+    pub locks: HashMap<String, Lock>,
+    pub semaphores: HashMap<String, Semaphore>,
+    pub queues: HashMap<String, Queue>,
+    pub crons: HashMap<String, Cron>,
+    /// Response chain progress per monitor, keyed by item id
+    pub monitors: HashMap<String, HashMap<String, ChainProgress>>,
+    /// Named action history per action, keyed by target
+    pub actions: HashMap<String, HashMap<String, ActionRecord>>,
 }
 
 impl MaterializedState {
@@ -54,8 +70,40 @@ impl MaterializedState {
         }
     }
 
-    /// Apply an operation to update the state
+    /// Get the pipelines started by a pipeline's phases, oldest first
+    pub fn child_pipelines(&self, parent_id: &str) -> Vec<&Pipeline> {
+        let mut children: Vec<_> = self
+            .pipelines
+            .values()
+            .filter(|p| {
+                p.parent
+                    .as_ref()
+                    .is_some_and(|parent| parent.id == parent_id)
+            })
+            .collect();
+        children.sort_by(|a, b| (a.created_at_ms, &a.id).cmp(&(b.created_at_ms, &b.id)));
+        children
+    }
+
+    /// Apply an operation to update the state, as of now
     pub fn apply(&mut self, op: &Operation) {
+        self.apply_with(op, &oj_core::SystemClock);
+    }
+
+    /// Apply an operation as of when it was recorded, in Unix epoch milliseconds
+    ///
+    /// Replaying with the recorded time keeps creation times, heartbeats and
+    /// other timestamps accurate across restarts.
+    pub fn apply_at(&mut self, op: &Operation, epoch_ms: u64) {
+        self.apply_with(op, &FixedClock::at_epoch_ms(epoch_ms));
+    }
+
+    /// Apply an operation as of the current time of `clock`
+    pub fn apply_with(&mut self, op: &Operation, clock: &impl Clock) {
+        let epoch_ms = clock.epoch_ms();
+        if let Some(pipeline) = op.pipeline_id().and_then(|id| self.pipelines.get_mut(id)) {
+            pipeline.updated_at_ms = epoch_ms;
+        }
         match op {
             Operation::PipelineCreate {
                 id,
@@ -63,22 +111,37 @@ impl MaterializedState {
                 name,
                 inputs,
                 initial_phase,
+                parent,
             } => {
-                let pipeline = Pipeline::new(
+                let mut pipeline = Pipeline::new(
                     id.clone(),
                     name.clone(),
                     kind.clone(),
                     inputs.clone(),
                     initial_phase.clone(),
-                    &oj_core::SystemClock,
+                    clock,
                 );
+                // A child pipeline works in its parent's workspace
+                if let Some(parent) = parent {
+                    pipeline.workspace_path = self
+                        .pipelines
+                        .get(&parent.id)
+                        .and_then(|p| p.workspace_path.clone())
+                        .or_else(|| self.workspaces.get(&parent.id).map(|w| w.path.clone()));
+                    pipeline.parent = Some(parent.clone());
+                }
                 self.pipelines.insert(id.clone(), pipeline);
             }
 
-            Operation::PipelineTransition { id, phase } => {
+            Operation::PipelineTransition {
+                id,
+                phase,
+                trigger,
+                error,
+                outputs,
+            } => {
                 if let Some(pipeline) = self.pipelines.get_mut(id) {
-                    pipeline.phase = phase.clone();
-                    pipeline.phase_status = oj_core::PhaseStatus::Pending;
+                    pipeline.enter_phase(phase, *trigger, error.as_deref(), outputs, epoch_ms);
                 }
             }
 
@@ -87,7 +150,16 @@ impl MaterializedState {
                 status,
             } => {
                 if let Some(pipeline) = self.pipelines.get_mut(pipeline_id) {
-                    pipeline.phase_status = *status;
+                    pipeline.set_phase_status(*status);
+                }
+            }
+
+            Operation::PhaseExited {
+                pipeline_id,
+                exit_code,
+            } => {
+                if let Some(pipeline) = self.pipelines.get_mut(pipeline_id) {
+                    pipeline.record_exit(*exit_code);
                 }
             }
 
@@ -101,12 +173,22 @@ impl MaterializedState {
                     Session {
                         id: id.clone(),
                         pipeline_id: pipeline_id.clone(),
+                        created_at_ms: epoch_ms,
                     },
                 );
+                if let Some(pipeline) = self.pipelines.get_mut(pipeline_id) {
+                    pipeline.session_id = Some(id.clone());
+                }
             }
 
             Operation::SessionDelete { id } => {
-                self.sessions.remove(id);
+                if let Some(session) = self.sessions.remove(id) {
+                    if let Some(pipeline) = self.pipelines.get_mut(&session.pipeline_id) {
+                        if pipeline.session_id.as_ref() == Some(id) {
+                            pipeline.session_id = None;
+                        }
+                    }
+                }
             }
 
             Operation::WorkspaceCreate { id, path, branch } => {
@@ -127,6 +209,231 @@ impl MaterializedState {
             Operation::WorkspaceDelete { id } => {
                 self.workspaces.remove(id);
             }
+
+            Operation::LockAcquire { name, holder } => {
+                self.locks
+                    .entry(name.clone())
+                    .or_insert_with(|| Lock::new(name.clone(), LockConfig::default()))
+                    .grant(holder, clock.now());
+            }
+
+            Operation::LockRelease { name, holder } => {
+                if let Some(lock) = self.locks.get_mut(name) {
+                    lock.release(holder);
+                }
+            }
+
+            Operation::LockHeartbeat { name, holder } => {
+                if let Some(lock) = self.locks.get_mut(name) {
+                    lock.heartbeat(holder, clock);
+                }
+            }
+
+            Operation::SemaphoreAcquire {
+                name,
+                holder,
+                slots,
+            } => {
+                self.semaphores
+                    .entry(name.clone())
+                    .or_insert_with(|| Semaphore::new(name.clone(), SemaphoreConfig::default()))
+                    .grant(holder, *slots, clock.now());
+            }
+
+            Operation::SemaphoreRelease { name, holder } => {
+                if let Some(semaphore) = self.semaphores.get_mut(name) {
+                    semaphore.release(holder);
+                }
+            }
+
+            Operation::SemaphoreHeartbeat { name, holder } => {
+                if let Some(semaphore) = self.semaphores.get_mut(name) {
+                    semaphore.heartbeat(holder, clock);
+                }
+            }
+
+            Operation::StrategyAttempt {
+                pipeline_id,
+                phase,
+                attempt,
+                checkpoint,
+            } => {
+                if let Some(pipeline) = self.pipelines.get_mut(pipeline_id) {
+                    pipeline.strategy = Some(StrategyState {
+                        phase: phase.clone(),
+                        attempt: *attempt,
+                        checkpoint: checkpoint.clone(),
+                    });
+                }
+            }
+
+            Operation::RecoveryStep {
+                pipeline_id,
+                trigger,
+                progress,
+            } => {
+                if let Some(pipeline) = self.pipelines.get_mut(pipeline_id) {
+                    pipeline.recovery.insert(trigger.clone(), *progress);
+                }
+            }
+
+            Operation::RecoveryReset { pipeline_id } => {
+                if let Some(pipeline) = self.pipelines.get_mut(pipeline_id) {
+                    pipeline.recovery.clear();
+                }
+            }
+
+            Operation::QueuePush {
+                name,
+                item_id,
+                data,
+                priority,
+            } => {
+                self.queues
+                    .entry(name.clone())
+                    .or_insert_with(|| Queue::new(name.clone(), QueueConfig::default()))
+                    .push(item_id, data.clone(), *priority, clock.now());
+            }
+
+            Operation::QueueClaim {
+                name,
+                item_id,
+                holder,
+            } => {
+                if let Some(queue) = self.queues.get_mut(name) {
+                    queue.claim(item_id, holder, clock.now());
+                }
+            }
+
+            Operation::QueueExtend {
+                name,
+                item_id,
+                holder,
+            } => {
+                if let Some(queue) = self.queues.get_mut(name) {
+                    queue.extend(item_id, holder, clock.now());
+                }
+            }
+
+            Operation::QueueComplete { name, item_id } => {
+                if let Some(queue) = self.queues.get_mut(name) {
+                    queue.complete(item_id);
+                }
+            }
+
+            Operation::QueueFail {
+                name,
+                item_id,
+                reason,
+            } => {
+                if let Some(queue) = self.queues.get_mut(name) {
+                    queue.requeue(item_id, reason);
+                }
+            }
+
+            Operation::QueueRelease { name, item_id } => {
+                if let Some(queue) = self.queues.get_mut(name) {
+                    queue.release(item_id);
+                }
+            }
+
+            Operation::QueueDead {
+                name,
+                item_id,
+                reason,
+            } => {
+                if let Some(queue) = self.queues.get_mut(name) {
+                    queue.bury(item_id, reason, clock.now());
+                }
+            }
+
+            Operation::QueueRemove { name, item_id } => {
+                if let Some(queue) = self.queues.get_mut(name) {
+                    queue.remove(item_id);
+                }
+            }
+
+            Operation::WorkerStart { name } => {
+                self.workers
+                    .entry(name.clone())
+                    .or_insert_with(|| Worker::new(name.clone(), clock))
+                    .start(clock);
+            }
+
+            Operation::WorkerStop { name } => {
+                if let Some(worker) = self.workers.get_mut(name) {
+                    worker.stop();
+                }
+            }
+
+            Operation::WorkerAssign {
+                name,
+                pipeline_id,
+                item_id,
+            } => {
+                if let Some(worker) = self.workers.get_mut(name) {
+                    worker.begin_processing(pipeline_id.clone(), item_id.clone(), clock);
+                }
+            }
+
+            Operation::WorkerRelease { name, pipeline_id } => {
+                if let Some(worker) = self.workers.get_mut(name) {
+                    worker.finish_processing(pipeline_id, clock);
+                }
+            }
+
+            Operation::CronEnable { name } => {
+                self.crons
+                    .entry(name.clone())
+                    .or_insert_with(|| Cron::new(name.clone()))
+                    .enabled = true;
+            }
+
+            Operation::CronDisable { name } => {
+                if let Some(cron) = self.crons.get_mut(name) {
+                    cron.enabled = false;
+                }
+            }
+
+            Operation::MonitorRespond {
+                name,
+                item_id,
+                progress,
+            } => {
+                self.monitors
+                    .entry(name.clone())
+                    .or_default()
+                    .insert(item_id.clone(), *progress);
+            }
+
+            Operation::MonitorReset { name, item_id } => {
+                if let Some(items) = self.monitors.get_mut(name) {
+                    items.remove(item_id);
+                    if items.is_empty() {
+                        self.monitors.remove(name);
+                    }
+                }
+            }
+
+            Operation::ActionFire { name, target } => {
+                let now = clock.now();
+                self.actions
+                    .entry(name.clone())
+                    .or_default()
+                    .entry(target.clone())
+                    .and_modify(|record| record.fire(now))
+                    .or_insert_with(|| ActionRecord::new(now));
+            }
+
+            Operation::ActionReset { target } => {
+                self.actions.retain(|_, targets| {
+                    targets.remove(target);
+                    !targets.is_empty()
+                });
+            }
+
+            // A marker only; the snapshot itself lives outside the WAL
+            Operation::SnapshotTaken { .. } => {}
         }
     }
 }
@@ -2,6 +2,7 @@
 // Copyright (c) 2026 Alfred Jean LLC
 
 use super::*;
+use oj_core::{PhaseStatus, PhaseTrigger, PipelineParent, WorkerStatus};
 
 #[test]
 fn apply_pipeline_create() {
@@ -12,11 +13,270 @@ fn apply_pipeline_create() {
         name: "test".to_string(),
         inputs: HashMap::new(),
         initial_phase: "init".to_string(),
+        parent: None,
     });
 
     assert!(state.pipelines.contains_key("pipe-1"));
 }
 
+#[test]
+fn apply_child_pipeline_create_links_parent() {
+    let mut state = MaterializedState::default();
+    state.apply(&Operation::WorkspaceCreate {
+        id: "pipe-1".to_string(),
+        path: PathBuf::from("/tmp/ws/test"),
+        branch: "feature/test".to_string(),
+    });
+    let create = |id: &str, parent: Option<PipelineParent>| Operation::PipelineCreate {
+        id: id.to_string(),
+        kind: "build".to_string(),
+        name: id.to_string(),
+        inputs: HashMap::new(),
+        initial_phase: "init".to_string(),
+        parent,
+    };
+    state.apply(&create("pipe-1", None));
+    let parent = PipelineParent {
+        id: "pipe-1".to_string(),
+        phase: "test".to_string(),
+    };
+    state.apply(&create("pipe-2", Some(parent.clone())));
+    state.apply(&create("pipe-3", None));
+
+    let child = &state.pipelines["pipe-2"];
+    assert_eq!(child.parent, Some(parent));
+    assert_eq!(child.workspace_path, Some(PathBuf::from("/tmp/ws/test")));
+    let children: Vec<_> = state
+        .child_pipelines("pipe-1")
+        .iter()
+        .map(|p| p.id.as_str())
+        .collect();
+    assert_eq!(children, ["pipe-2"]);
+}
+
+#[test]
+fn apply_session_links_pipeline() {
+    let mut state = MaterializedState::default();
+    state.apply(&Operation::PipelineCreate {
+        id: "pipe-1".to_string(),
+        kind: "build".to_string(),
+        name: "test".to_string(),
+        inputs: HashMap::new(),
+        initial_phase: "init".to_string(),
+        parent: None,
+    });
+    state.apply(&Operation::SessionCreate {
+        id: "oj-pipe-1".to_string(),
+        pipeline_id: "pipe-1".to_string(),
+    });
+    assert_eq!(
+        state.pipelines["pipe-1"].session_id.as_deref(),
+        Some("oj-pipe-1")
+    );
+
+    state.apply(&Operation::SessionDelete {
+        id: "oj-pipe-1".to_string(),
+    });
+    assert!(state.sessions.is_empty());
+    assert_eq!(state.pipelines["pipe-1"].session_id, None);
+}
+
+#[test]
+fn apply_transition_records_outputs() {
+    let mut state = MaterializedState::default();
+    state.apply(&Operation::PipelineCreate {
+        id: "pipe-1".to_string(),
+        kind: "build".to_string(),
+        name: "test".to_string(),
+        inputs: HashMap::new(),
+        initial_phase: "commit".to_string(),
+        parent: None,
+    });
+    let transition = |phase: &str, sha: &str| Operation::PipelineTransition {
+        id: "pipe-1".to_string(),
+        phase: phase.to_string(),
+        trigger: PhaseTrigger::Next,
+        error: None,
+        outputs: [("sha".to_string(), sha.to_string())].into_iter().collect(),
+    };
+    state.apply(&transition("amend", "abc123"));
+    state.apply(&transition("push", "def456"));
+
+    let outputs = &state.pipelines["pipe-1"].outputs;
+    assert_eq!(outputs.len(), 1);
+    assert_eq!(outputs["sha"], "def456");
+}
+
+#[test]
+fn apply_at_keeps_recorded_times() {
+    let created = oj_core::SystemClock.epoch_ms() - 3_600_000;
+    let mut state = MaterializedState::default();
+    state.apply_at(
+        &Operation::PipelineCreate {
+            id: "pipe-1".to_string(),
+            kind: "build".to_string(),
+            name: "test".to_string(),
+            inputs: HashMap::new(),
+            initial_phase: "init".to_string(),
+            parent: None,
+        },
+        created,
+    );
+    state.apply_at(
+        &Operation::PipelineTransition {
+            id: "pipe-1".to_string(),
+            phase: "plan".to_string(),
+            trigger: PhaseTrigger::Next,
+            error: None,
+            outputs: HashMap::new(),
+        },
+        created + 60_000,
+    );
+    state.apply_at(
+        &Operation::PhaseStatusUpdate {
+            pipeline_id: "pipe-1".to_string(),
+            status: oj_core::PhaseStatus::Running,
+        },
+        created + 90_000,
+    );
+    state.apply_at(
+        &Operation::SessionCreate {
+            id: "sess-1".to_string(),
+            pipeline_id: "pipe-1".to_string(),
+        },
+        created + 90_000,
+    );
+
+    let pipeline = &state.pipelines["pipe-1"];
+    assert_eq!(pipeline.created_at_ms, created);
+    assert_eq!(pipeline.phase_started_at_ms, created + 60_000);
+    assert_eq!(pipeline.updated_at_ms, created + 90_000);
+    assert_eq!(state.sessions["sess-1"].created_at_ms, created + 90_000);
+}
+
+#[test]
+fn apply_at_before_boot_keeps_elapsed_time() {
+    // 1970: older than any monotonic clock reading
+    let long_ago = 1_000;
+    let mut state = MaterializedState::default();
+    state.apply_at(
+        &Operation::QueuePush {
+            name: "bugs".to_string(),
+            item_id: "bug-1".to_string(),
+            data: HashMap::new(),
+            priority: 0,
+        },
+        long_ago,
+    );
+    state.apply_at(
+        &Operation::QueueClaim {
+            name: "bugs".to_string(),
+            item_id: "bug-1".to_string(),
+            holder: "worker-1".to_string(),
+        },
+        long_ago,
+    );
+    state.apply_at(
+        &Operation::PipelineCreate {
+            id: "pipe-1".to_string(),
+            kind: "build".to_string(),
+            name: "test".to_string(),
+            inputs: HashMap::new(),
+            initial_phase: "init".to_string(),
+            parent: None,
+        },
+        long_ago,
+    );
+
+    // The claim is long overdue rather than freshly made
+    let queue = &state.queues["bugs"];
+    assert_eq!(
+        queue.expired(&oj_core::SystemClock),
+        vec!["bug-1".to_string()]
+    );
+    let pipeline = &state.pipelines["pipe-1"];
+    assert_eq!(pipeline.created_at_ms, long_ago);
+    assert_eq!(pipeline.phase_started_at_ms, long_ago);
+}
+
+#[test]
+fn apply_records_phase_history() {
+    let mut state = MaterializedState::default();
+    let ops = [
+        Operation::PipelineCreate {
+            id: "pipe-1".to_string(),
+            kind: "build".to_string(),
+            name: "test".to_string(),
+            inputs: HashMap::new(),
+            initial_phase: "test".to_string(),
+            parent: None,
+        },
+        Operation::PhaseStatusUpdate {
+            pipeline_id: "pipe-1".to_string(),
+            status: PhaseStatus::Running,
+        },
+        Operation::PhaseExited {
+            pipeline_id: "pipe-1".to_string(),
+            exit_code: 1,
+        },
+        Operation::PipelineTransition {
+            id: "pipe-1".to_string(),
+            phase: "fix".to_string(),
+            trigger: PhaseTrigger::OnFail,
+            error: Some("shell exited with code 1".to_string()),
+            outputs: HashMap::new(),
+        },
+        Operation::PhaseStatusUpdate {
+            pipeline_id: "pipe-1".to_string(),
+            status: PhaseStatus::Running,
+        },
+        Operation::PipelineTransition {
+            id: "pipe-1".to_string(),
+            phase: "done".to_string(),
+            trigger: PhaseTrigger::Next,
+            error: None,
+            outputs: HashMap::new(),
+        },
+    ];
+    for (at, op) in ops.iter().enumerate() {
+        state.apply_at(op, 1_000 * at as u64);
+    }
+
+    let history = &state.pipelines["pipe-1"].history;
+    let summary: Vec<_> = history
+        .iter()
+        .map(|r| (r.phase.as_str(), r.status, r.trigger, r.ended_at_ms))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (
+                "test",
+                PhaseStatus::Failed,
+                PhaseTrigger::Start,
+                Some(3_000)
+            ),
+            (
+                "fix",
+                PhaseStatus::Completed,
+                PhaseTrigger::OnFail,
+                Some(5_000)
+            ),
+            ("done", PhaseStatus::Pending, PhaseTrigger::Next, None),
+        ]
+    );
+    assert_eq!(history[0].exit_code, Some(1));
+    assert_eq!(
+        history[0].error.as_deref(),
+        Some("shell exited with code 1")
+    );
+    assert_eq!(history[1].started_at_ms, 3_000);
+    assert_eq!(
+        state.pipelines["pipe-1"].error.as_deref(),
+        Some("shell exited with code 1")
+    );
+}
+
 #[test]
 fn apply_pipeline_delete() {
     let mut state = MaterializedState::default();
@@ -26,6 +286,7 @@ fn apply_pipeline_delete() {
         name: "test".to_string(),
         inputs: HashMap::new(),
         initial_phase: "init".to_string(),
+        parent: None,
     });
     state.apply(&Operation::PipelineDelete {
         id: "pipe-1".to_string(),
@@ -51,3 +312,274 @@ fn apply_workspace_lifecycle() {
     });
     assert!(!state.workspaces.contains_key("ws-1"));
 }
+
+#[test]
+fn apply_lock_lifecycle() {
+    let mut state = MaterializedState::default();
+    state.apply(&Operation::LockAcquire {
+        name: "main_branch".to_string(),
+        holder: "pipe-1".to_string(),
+    });
+    assert!(state.locks["main_branch"].is_held_by("pipe-1"));
+
+    // Releasing with the wrong holder is a no-op
+    state.apply(&Operation::LockRelease {
+        name: "main_branch".to_string(),
+        holder: "pipe-2".to_string(),
+    });
+    assert!(state.locks["main_branch"].is_held_by("pipe-1"));
+
+    // A replayed reclaim hands the lock to the new holder
+    state.apply(&Operation::LockAcquire {
+        name: "main_branch".to_string(),
+        holder: "pipe-2".to_string(),
+    });
+    assert!(state.locks["main_branch"].is_held_by("pipe-2"));
+
+    state.apply(&Operation::LockRelease {
+        name: "main_branch".to_string(),
+        holder: "pipe-2".to_string(),
+    });
+    assert_eq!(state.locks["main_branch"].holder(), None);
+}
+
+#[test]
+fn apply_semaphore_holders() {
+    let mut state = MaterializedState::default();
+    for holder in ["pipe-1", "pipe-2"] {
+        state.apply(&Operation::SemaphoreAcquire {
+            name: "agents".to_string(),
+            holder: holder.to_string(),
+            slots: 1,
+        });
+    }
+    assert_eq!(state.semaphores["agents"].used(), 2);
+
+    state.apply(&Operation::SemaphoreRelease {
+        name: "agents".to_string(),
+        holder: "pipe-1".to_string(),
+    });
+    assert!(!state.semaphores["agents"].is_held_by("pipe-1"));
+    assert!(state.semaphores["agents"].is_held_by("pipe-2"));
+}
+
+#[test]
+fn apply_strategy_attempt_until_transition() {
+    let mut state = MaterializedState::default();
+    state.apply(&Operation::PipelineCreate {
+        id: "pipe-1".to_string(),
+        kind: "merge".to_string(),
+        name: "test".to_string(),
+        inputs: HashMap::new(),
+        initial_phase: "merge".to_string(),
+        parent: None,
+    });
+    state.apply(&Operation::StrategyAttempt {
+        pipeline_id: "pipe-1".to_string(),
+        phase: "merge".to_string(),
+        attempt: 1,
+        checkpoint: Some("abc123".to_string()),
+    });
+
+    let strategy = state.pipelines["pipe-1"].strategy.clone().unwrap();
+    assert_eq!(strategy.attempt, 1);
+    assert_eq!(strategy.checkpoint.as_deref(), Some("abc123"));
+
+    state.apply(&Operation::PipelineTransition {
+        id: "pipe-1".to_string(),
+        phase: "done".to_string(),
+        trigger: PhaseTrigger::Next,
+        error: None,
+        outputs: HashMap::new(),
+    });
+    assert_eq!(state.pipelines["pipe-1"].strategy, None);
+}
+
+#[test]
+fn apply_recovery_progress_until_transition() {
+    let mut state = MaterializedState::default();
+    state.apply(&Operation::PipelineCreate {
+        id: "pipe-1".to_string(),
+        kind: "build".to_string(),
+        name: "test".to_string(),
+        inputs: HashMap::new(),
+        initial_phase: "plan".to_string(),
+        parent: None,
+    });
+    let progress = ChainProgress {
+        step: 1,
+        attempts: 1,
+    };
+    state.apply(&Operation::RecoveryStep {
+        pipeline_id: "pipe-1".to_string(),
+        trigger: "idle".to_string(),
+        progress,
+    });
+    assert_eq!(state.pipelines["pipe-1"].recovery["idle"], progress);
+
+    state.apply(&Operation::RecoveryReset {
+        pipeline_id: "pipe-1".to_string(),
+    });
+    assert!(state.pipelines["pipe-1"].recovery.is_empty());
+
+    state.apply(&Operation::RecoveryStep {
+        pipeline_id: "pipe-1".to_string(),
+        trigger: "exit".to_string(),
+        progress,
+    });
+    state.apply(&Operation::PipelineTransition {
+        id: "pipe-1".to_string(),
+        phase: "execute".to_string(),
+        trigger: PhaseTrigger::Next,
+        error: None,
+        outputs: HashMap::new(),
+    });
+    assert!(state.pipelines["pipe-1"].recovery.is_empty());
+}
+
+#[test]
+fn apply_queue_item_lifecycle() {
+    let mut state = MaterializedState::default();
+    for item_id in ["bug-1", "bug-2"] {
+        state.apply(&Operation::QueuePush {
+            name: "bugs".to_string(),
+            item_id: item_id.to_string(),
+            data: HashMap::new(),
+            priority: 0,
+        });
+    }
+    state.apply(&Operation::QueueClaim {
+        name: "bugs".to_string(),
+        item_id: "bug-1".to_string(),
+        holder: "worker-1".to_string(),
+    });
+    assert_eq!(
+        state.queues["bugs"].get("bug-1").unwrap().holder(),
+        Some("worker-1")
+    );
+    state.apply(&Operation::QueueExtend {
+        name: "bugs".to_string(),
+        item_id: "bug-1".to_string(),
+        holder: "worker-1".to_string(),
+    });
+    assert_eq!(
+        state.queues["bugs"].get("bug-1").unwrap().holder(),
+        Some("worker-1")
+    );
+
+    state.apply(&Operation::QueueFail {
+        name: "bugs".to_string(),
+        item_id: "bug-1".to_string(),
+        reason: "tests failed".to_string(),
+    });
+    assert!(state.queues["bugs"].get("bug-1").unwrap().is_pending());
+    assert_eq!(state.queues["bugs"].get("bug-1").unwrap().attempts, 1);
+
+    state.apply(&Operation::QueueDead {
+        name: "bugs".to_string(),
+        item_id: "bug-1".to_string(),
+        reason: "exhausted".to_string(),
+    });
+    state.apply(&Operation::QueueClaim {
+        name: "bugs".to_string(),
+        item_id: "bug-2".to_string(),
+        holder: "worker-1".to_string(),
+    });
+    state.apply(&Operation::QueueComplete {
+        name: "bugs".to_string(),
+        item_id: "bug-2".to_string(),
+    });
+
+    let queue = &state.queues["bugs"];
+    assert_eq!(queue.items.len(), 1);
+    assert!(queue.get("bug-1").unwrap().is_dead());
+}
+
+#[test]
+fn apply_worker_assignments() {
+    let mut state = MaterializedState::default();
+    state.apply(&Operation::WorkerStart {
+        name: "fixers".to_string(),
+    });
+    state.apply(&Operation::WorkerAssign {
+        name: "fixers".to_string(),
+        pipeline_id: "pipe-1".to_string(),
+        item_id: "bug-1".to_string(),
+    });
+    assert_eq!(state.workers["fixers"].status, WorkerStatus::Processing);
+
+    // Stopping keeps the in-flight pipeline so its item is settled later
+    state.apply(&Operation::WorkerStop {
+        name: "fixers".to_string(),
+    });
+    assert_eq!(state.workers["fixers"].pipelines.len(), 1);
+
+    state.apply(&Operation::WorkerRelease {
+        name: "fixers".to_string(),
+        pipeline_id: "pipe-1".to_string(),
+    });
+    assert!(state.workers["fixers"].pipelines.is_empty());
+    assert_eq!(state.workers["fixers"].status, WorkerStatus::Stopped);
+}
+
+#[test]
+fn apply_cron_enable_disable() {
+    let mut state = MaterializedState::default();
+    state.apply(&Operation::CronDisable {
+        name: "watchdog".to_string(),
+    });
+    assert!(state.crons.is_empty());
+
+    state.apply(&Operation::CronEnable {
+        name: "watchdog".to_string(),
+    });
+    assert!(state.crons["watchdog"].enabled);
+
+    state.apply(&Operation::CronDisable {
+        name: "watchdog".to_string(),
+    });
+    assert!(!state.crons["watchdog"].enabled);
+}
+
+#[test]
+fn apply_monitor_progress() {
+    let mut state = MaterializedState::default();
+    let progress = ChainProgress {
+        step: 1,
+        attempts: 2,
+    };
+    state.apply(&Operation::MonitorRespond {
+        name: "agent_idle".to_string(),
+        item_id: "pipe-1".to_string(),
+        progress,
+    });
+    assert_eq!(state.monitors["agent_idle"]["pipe-1"], progress);
+
+    state.apply(&Operation::MonitorReset {
+        name: "agent_idle".to_string(),
+        item_id: "pipe-1".to_string(),
+    });
+    assert!(state.monitors.is_empty());
+}
+
+#[test]
+fn apply_action_fire_and_reset() {
+    let mut state = MaterializedState::default();
+    for target in ["pipe-1", "pipe-1", "pipe-2"] {
+        state.apply(&Operation::ActionFire {
+            name: "restart".to_string(),
+            target: target.to_string(),
+        });
+    }
+    assert_eq!(state.actions["restart"]["pipe-1"].attempts, 2);
+    assert_eq!(state.actions["restart"]["pipe-2"].attempts, 1);
+
+    state.apply(&Operation::ActionReset {
+        target: "pipe-1".to_string(),
+    });
+    assert!(!state.actions["restart"].contains_key("pipe-1"));
+    state.apply(&Operation::ActionReset {
+        target: "pipe-2".to_string(),
+    });
+    assert!(state.actions.is_empty());
+}
@@ -2,11 +2,19 @@
 // Copyright (c) 2026 Alfred Jean LLC
 
 //! Write-ahead log for durable storage
+//!
+//! Each entry is one line of JSON carrying a sequence number, a wall-clock
+//! timestamp, the operation, and a CRC32 of the operation JSON. Entries
+//! written before checksums existed (`{"seq":..,"op":..}`) are still read;
+//! having no timestamp, they are treated as written when they are read.
 
-use oj_core::Operation;
+use oj_core::{Clock, Operation, SystemClock};
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
 use std::fs::{File, OpenOptions};
-use std::io::{self, BufRead, BufReader, Write};
-use std::path::Path;
+use std::io::{self, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 /// Errors that can occur in WAL operations
@@ -16,40 +24,94 @@ pub enum WalError {
     Io(#[from] io::Error),
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
+    #[error("corrupt WAL entry at line {line}: {reason}")]
+    Corrupt { line: usize, reason: String },
+    #[error("WAL sequence gap at line {line}: expected {expected}, found {found}")]
+    SequenceGap {
+        line: usize,
+        expected: u64,
+        found: u64,
+    },
+    #[error("WAL ends at sequence {wal} but the snapshot covers up to {snapshot}")]
+    BehindSnapshot { wal: u64, snapshot: u64 },
+}
+
+/// A torn final entry dropped when the WAL was opened
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TruncatedEntry {
+    /// 1-based line number of the dropped entry
+    pub line: usize,
+    /// Why the entry could not be read
+    pub reason: String,
+}
+
+/// An operation read back from the log
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WalRecord {
+    pub sequence: u64,
+    /// When the operation was written, in Unix epoch milliseconds
+    pub epoch_ms: u64,
+    pub operation: Operation,
 }
 
 /// Write-ahead log for durable operation storage
 pub struct Wal {
+    path: PathBuf,
     file: File,
     sequence: u64,
+    truncated: Option<TruncatedEntry>,
 }
 
 impl Wal {
     /// Open or create a WAL at the given path
+    ///
+    /// A final entry left unreadable by an interrupted write is truncated
+    /// away (see [`Wal::truncated`]). Corruption anywhere else, or a gap in
+    /// sequence numbers, is an error that needs manual intervention.
     pub fn open(path: &Path) -> Result<Self, WalError> {
-        let file = OpenOptions::new()
+        let mut file = OpenOptions::new()
             .create(true)
             .append(true)
             .read(true)
             .open(path)?;
 
-        // Count existing entries to set sequence number
-        let reader = BufReader::new(File::open(path)?);
-        let sequence = reader.lines().count() as u64;
+        let scan = scan(&read(path)?)?;
+        if let Some(torn) = &scan.torn {
+            file.set_len(torn.offset)?;
+            file.sync_all()?;
+        } else if scan.missing_newline {
+            // The last entry made it to disk but its newline did not
+            writeln!(file)?;
+            file.sync_all()?;
+        }
 
-        Ok(Self { file, sequence })
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+            sequence: scan.entries.last().map_or(0, |entry| entry.sequence),
+            truncated: scan.torn.map(|torn| torn.entry),
+        })
     }
 
-    /// Append an operation to the log
+    /// Append an operation to the log, stamped with the current time
     pub fn append(&mut self, op: &Operation) -> Result<u64, WalError> {
-        self.sequence += 1;
+        self.append_at(op, SystemClock.epoch_ms())
+    }
+
+    /// Append an operation to the log, stamped with `epoch_ms`
+    pub fn append_at(&mut self, op: &Operation, epoch_ms: u64) -> Result<u64, WalError> {
+        let operation = RawValue::from_string(serde_json::to_string(op)?)?;
         let entry = WalEntry {
-            seq: self.sequence,
-            op: op.clone(),
+            sequence: self.sequence + 1,
+            timestamp_micros: epoch_ms * 1000,
+            machine_id: None,
+            checksum: crc32fast::hash(operation.get().as_bytes()),
+            operation: &operation,
         };
         let line = serde_json::to_string(&entry)?;
         writeln!(self.file, "{}", line)?;
         self.file.sync_all()?;
+        self.sequence += 1;
         Ok(self.sequence)
     }
 
@@ -58,36 +120,223 @@ impl Wal {
         self.sequence
     }
 
+    /// The torn final entry dropped when the WAL was opened, if any
+    pub fn truncated(&self) -> Option<&TruncatedEntry> {
+        self.truncated.as_ref()
+    }
+
     /// Replay all operations from the log
+    ///
+    /// An unreadable final entry is skipped; `open` is what truncates it.
     pub fn replay(path: &Path) -> Result<Vec<Operation>, WalError> {
-        let file = match File::open(path) {
-            Ok(f) => f,
-            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
-            Err(e) => return Err(e.into()),
-        };
+        Ok(scan(&read(path)?)?
+            .entries
+            .into_iter()
+            .map(|entry| entry.operation)
+            .collect())
+    }
+
+    /// Replay the operations after a snapshot taken at `sequence`, with the
+    /// times they were written
+    ///
+    /// Fails if the log does not continue on from the snapshot, either
+    /// because entries after it are missing or because the log ends before it.
+    pub fn replay_after(path: &Path, sequence: u64) -> Result<Vec<WalRecord>, WalError> {
+        let entries = scan(&read(path)?)?.entries;
+        let last = entries.last().map_or(0, |entry| entry.sequence);
+        if last < sequence {
+            return Err(WalError::BehindSnapshot {
+                wal: last,
+                snapshot: sequence,
+            });
+        }
+        if let Some(first) = entries.first().filter(|e| e.sequence > sequence + 1) {
+            return Err(WalError::SequenceGap {
+                line: first.line,
+                expected: sequence + 1,
+                found: first.sequence,
+            });
+        }
+        Ok(entries
+            .into_iter()
+            .filter(|entry| entry.sequence > sequence)
+            .map(|entry| WalRecord {
+                sequence: entry.sequence,
+                epoch_ms: entry.epoch_ms,
+                operation: entry.operation,
+            })
+            .collect())
+    }
 
-        let reader = BufReader::new(file);
-        let mut ops = Vec::new();
+    /// Rewrite the log keeping only entries after `sequence`
+    ///
+    /// The rewrite goes to a temporary file that replaces the log once
+    /// synced, so a crash leaves either the old or the new log. Returns the
+    /// number of entries dropped.
+    pub fn compact(&mut self, sequence: u64) -> Result<usize, WalError> {
+        let bytes = read(&self.path)?;
+        let entries = scan(&bytes)?.entries;
 
-        for line in reader.lines() {
-            let line = line?;
-            if line.is_empty() {
-                continue;
+        let mut kept = Vec::new();
+        let mut dropped = 0;
+        for entry in &entries {
+            if entry.sequence > sequence {
+                kept.extend_from_slice(&bytes[entry.span.clone()]);
+                kept.push(b'\n');
+            } else {
+                dropped += 1;
             }
-            let entry: WalEntry = serde_json::from_str(&line)?;
-            ops.push(entry.op);
         }
 
-        Ok(ops)
+        let tmp_path = self.path.with_extension("wal.tmp");
+        {
+            let mut tmp = File::create(&tmp_path)?;
+            tmp.write_all(&kept)?;
+            tmp.sync_all()?;
+        }
+        std::fs::rename(&tmp_path, &self.path)?;
+        self.file = OpenOptions::new()
+            .append(true)
+            .read(true)
+            .open(&self.path)?;
+        Ok(dropped)
     }
 }
 
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
-struct WalEntry {
+#[derive(Debug, Serialize, Deserialize)]
+struct WalEntry<'a> {
+    sequence: u64,
+    timestamp_micros: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    machine_id: Option<String>,
+    #[serde(borrow)]
+    operation: &'a RawValue,
+    checksum: u32,
+}
+
+/// Entry format used before checksums and timestamps
+#[derive(Debug, Deserialize)]
+struct LegacyEntry {
     seq: u64,
     op: Operation,
 }
 
+struct TornWrite {
+    entry: TruncatedEntry,
+    /// Byte offset where the torn entry starts
+    offset: u64,
+}
+
+struct ScannedEntry {
+    /// 1-based line number
+    line: usize,
+    sequence: u64,
+    epoch_ms: u64,
+    operation: Operation,
+    /// Byte range of the line, without its newline
+    span: Range<usize>,
+}
+
+struct Scan {
+    entries: Vec<ScannedEntry>,
+    torn: Option<TornWrite>,
+    missing_newline: bool,
+}
+
+/// Read the log, treating a missing file as empty
+fn read(path: &Path) -> Result<Vec<u8>, WalError> {
+    match std::fs::read(path) {
+        Ok(bytes) => Ok(bytes),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Verify every entry in the log
+fn scan(bytes: &[u8]) -> Result<Scan, WalError> {
+    let lines: Vec<&[u8]> = bytes.split(|b| *b == b'\n').collect();
+    let last = lines.iter().rposition(|line| !line.is_empty());
+    let mut entries: Vec<ScannedEntry> = Vec::new();
+    let mut torn = None;
+    let mut offset = 0;
+    let read_at = SystemClock.epoch_ms();
+
+    for (index, line) in lines.iter().enumerate() {
+        let start = offset;
+        offset += line.len() as u64 + 1;
+        if line.is_empty() {
+            continue;
+        }
+        let number = index + 1;
+        match decode(line) {
+            Ok((sequence, timestamp_micros, operation)) => {
+                if let Some(previous) = entries.last() {
+                    if sequence != previous.sequence + 1 {
+                        return Err(WalError::SequenceGap {
+                            line: number,
+                            expected: previous.sequence + 1,
+                            found: sequence,
+                        });
+                    }
+                }
+                let start = start as usize;
+                entries.push(ScannedEntry {
+                    line: number,
+                    sequence,
+                    epoch_ms: timestamp_micros.map_or(read_at, |micros| micros / 1000),
+                    operation,
+                    span: start..start + line.len(),
+                });
+            }
+            Err(reason) if Some(index) == last => {
+                torn = Some(TornWrite {
+                    entry: TruncatedEntry {
+                        line: number,
+                        reason,
+                    },
+                    offset: start,
+                });
+            }
+            Err(reason) => {
+                return Err(WalError::Corrupt {
+                    line: number,
+                    reason,
+                })
+            }
+        }
+    }
+
+    Ok(Scan {
+        entries,
+        missing_newline: torn.is_none() && bytes.last().is_some_and(|b| *b != b'\n'),
+        torn,
+    })
+}
+
+/// Decode and verify one line, returning its sequence number, timestamp
+/// (absent for legacy entries) and operation
+fn decode(line: &[u8]) -> Result<(u64, Option<u64>, Operation), String> {
+    let text = std::str::from_utf8(line).map_err(|e| e.to_string())?;
+    let entry: WalEntry = match serde_json::from_str(text) {
+        Ok(entry) => entry,
+        Err(e) => {
+            return serde_json::from_str::<LegacyEntry>(text)
+                .map(|legacy| (legacy.seq, None, legacy.op))
+                .map_err(|_| e.to_string());
+        }
+    };
+
+    let checksum = crc32fast::hash(entry.operation.get().as_bytes());
+    if checksum != entry.checksum {
+        return Err(format!(
+            "checksum mismatch (stored {}, computed {})",
+            entry.checksum, checksum
+        ));
+    }
+    let op = serde_json::from_str(entry.operation.get()).map_err(|e| e.to_string())?;
+    Ok((entry.sequence, Some(entry.timestamp_micros), op))
+}
+
 #[cfg(test)]
 #[path = "wal_tests.rs"]
 mod tests;
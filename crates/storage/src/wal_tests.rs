@@ -2,6 +2,7 @@
 // Copyright (c) 2026 Alfred Jean LLC
 
 use super::*;
+use oj_core::PhaseTrigger;
 use std::collections::HashMap;
 
 #[test]
@@ -18,11 +19,15 @@ fn wal_roundtrip() {
             name: "test".to_string(),
             inputs: HashMap::new(),
             initial_phase: "init".to_string(),
+            parent: None,
         })
         .unwrap();
         wal.append(&Operation::PipelineTransition {
             id: "pipe-1".to_string(),
             phase: "plan".to_string(),
+            trigger: PhaseTrigger::Next,
+            error: None,
+            outputs: HashMap::new(),
         })
         .unwrap();
     }
@@ -63,3 +68,179 @@ fn wal_replay_nonexistent() {
     let ops = Wal::replay(path).unwrap();
     assert!(ops.is_empty());
 }
+
+fn delete(id: &str) -> Operation {
+    Operation::PipelineDelete { id: id.to_string() }
+}
+
+/// Write `count` entries to a fresh WAL and return its path
+fn wal_with_entries(dir: &tempfile::TempDir, count: usize) -> std::path::PathBuf {
+    let path = dir.path().join("test.wal");
+    let mut wal = Wal::open(&path).unwrap();
+    for i in 0..count {
+        wal.append(&delete(&format!("p{}", i))).unwrap();
+    }
+    path
+}
+
+fn lines(path: &Path) -> Vec<String> {
+    std::fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .map(String::from)
+        .collect()
+}
+
+#[test]
+fn wal_entries_carry_checksum_and_timestamp() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = wal_with_entries(&dir, 1);
+
+    let line = &lines(&path)[0];
+    let entry: serde_json::Value = serde_json::from_str(line).unwrap();
+    assert_eq!(entry["sequence"], 1);
+    let micros = entry["timestamp_micros"].as_u64().unwrap();
+    assert!(micros > 0);
+    assert!(entry["checksum"].is_u64());
+    assert_eq!(entry["operation"]["PipelineDelete"]["id"], "p0");
+    assert_eq!(
+        decode(line.as_bytes()).unwrap(),
+        (1, Some(micros), delete("p0"))
+    );
+}
+
+#[test]
+fn wal_replay_after_returns_write_times() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("test.wal");
+    let mut wal = Wal::open(&path).unwrap();
+    wal.append_at(&delete("p0"), 1_000).unwrap();
+    wal.append_at(&delete("p1"), 2_000).unwrap();
+
+    let records = Wal::replay_after(&path, 0).unwrap();
+    assert_eq!(
+        records,
+        vec![
+            WalRecord {
+                sequence: 1,
+                epoch_ms: 1_000,
+                operation: delete("p0"),
+            },
+            WalRecord {
+                sequence: 2,
+                epoch_ms: 2_000,
+                operation: delete("p1"),
+            },
+        ]
+    );
+}
+
+#[test]
+fn wal_reads_legacy_entries() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("test.wal");
+    let legacy: Vec<String> = (1..=2)
+        .map(|seq| {
+            serde_json::json!({ "seq": seq, "op": delete(&format!("old{}", seq)) }).to_string()
+        })
+        .collect();
+    std::fs::write(&path, format!("{}\n", legacy.join("\n"))).unwrap();
+
+    let mut wal = Wal::open(&path).unwrap();
+    assert_eq!(wal.sequence(), 2);
+    assert_eq!(wal.append(&delete("new")).unwrap(), 3);
+
+    assert_eq!(
+        Wal::replay(&path).unwrap(),
+        vec![delete("old1"), delete("old2"), delete("new")]
+    );
+}
+
+#[test]
+fn wal_truncates_torn_final_write() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = wal_with_entries(&dir, 2);
+    let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+    write!(file, r#"{{"sequence":3,"timestamp_mic"#).unwrap();
+
+    // Replay alone skips the torn entry without touching the file
+    assert_eq!(Wal::replay(&path).unwrap().len(), 2);
+
+    let mut wal = Wal::open(&path).unwrap();
+    assert_eq!(wal.truncated().unwrap().line, 3);
+    assert_eq!(wal.sequence(), 2);
+    assert_eq!(wal.append(&delete("p2")).unwrap(), 3);
+    drop(wal);
+
+    let wal = Wal::open(&path).unwrap();
+    assert!(wal.truncated().is_none());
+    assert_eq!(lines(&path).len(), 3);
+    assert_eq!(Wal::replay(&path).unwrap()[2], delete("p2"));
+}
+
+#[test]
+fn wal_truncates_final_entry_with_bad_checksum() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = wal_with_entries(&dir, 2);
+    let content = std::fs::read_to_string(&path).unwrap();
+    std::fs::write(&path, content.replace(r#""id":"p1""#, r#""id":"p9""#)).unwrap();
+
+    let wal = Wal::open(&path).unwrap();
+    let truncated = wal.truncated().unwrap();
+    assert_eq!(truncated.line, 2);
+    assert!(
+        truncated.reason.contains("checksum"),
+        "{}",
+        truncated.reason
+    );
+    assert_eq!(Wal::replay(&path).unwrap(), vec![delete("p0")]);
+}
+
+#[test]
+fn wal_restores_missing_final_newline() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = wal_with_entries(&dir, 2);
+    let content = std::fs::read_to_string(&path).unwrap();
+    std::fs::write(&path, content.trim_end()).unwrap();
+
+    let mut wal = Wal::open(&path).unwrap();
+    assert!(wal.truncated().is_none());
+    wal.append(&delete("p2")).unwrap();
+    assert_eq!(Wal::replay(&path).unwrap().len(), 3);
+}
+
+#[test]
+fn wal_corruption_before_final_entry_is_an_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = wal_with_entries(&dir, 3);
+    let mut content = lines(&path);
+    content[1] = content[1].replace(r#""id":"p1""#, r#""id":"p9""#);
+    std::fs::write(&path, format!("{}\n", content.join("\n"))).unwrap();
+
+    assert!(matches!(
+        Wal::open(&path),
+        Err(WalError::Corrupt { line: 2, .. })
+    ));
+    assert!(matches!(
+        Wal::replay(&path),
+        Err(WalError::Corrupt { line: 2, .. })
+    ));
+}
+
+#[test]
+fn wal_sequence_gap_is_an_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = wal_with_entries(&dir, 3);
+    let mut content = lines(&path);
+    content.remove(1);
+    std::fs::write(&path, format!("{}\n", content.join("\n"))).unwrap();
+
+    assert!(matches!(
+        Wal::open(&path),
+        Err(WalError::SequenceGap {
+            line: 2,
+            expected: 2,
+            found: 3
+        })
+    ));
+}